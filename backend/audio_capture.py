@@ -6,13 +6,55 @@ Handles microphone and system audio recording using sounddevice (WASAPI)
 import logging
 import numpy as np
 import sounddevice as sd
+from math import ceil, gcd
+from scipy import signal
 from typing import List, Dict, Optional, Callable
+from pathlib import Path
+from collections import deque
 import queue
+import threading
+import time
 import wave
 import io
 
 logger = logging.getLogger(__name__)
 
+# Seconds without audio callbacks after which a recording's microphone counts as gone
+DEVICE_TIMEOUT = 2.0
+
+
+def refresh_devices():
+    """Rescan the audio devices; PortAudio only lists the ones present when it was initialized"""
+    sd._terminate()
+    sd._initialize()
+
+
+class StreamResampler:
+    """Resamples audio that arrives block by block, without seams between the blocks"""
+
+    def __init__(self, rate_in: int, rate_out: int):
+        common = gcd(rate_in, rate_out)
+        self.up = rate_out // common
+        self.down = rate_in // common
+        # Anti-aliasing low-pass at the lower of the two Nyquist frequencies, scaled for the zero-stuffing
+        self.taps = signal.firwin(20 * max(self.up, self.down) + 1, 1.0 / max(self.up, self.down),
+                                  window=("kaiser", 5.0)) * self.up
+        # Input the filter needs from before each block, in whole `down` periods so every call
+        # starts on the same filter phase
+        self.history = ceil(ceil(len(self.taps) / self.up) / self.down) * self.down
+        self.buffer = np.zeros(self.history, dtype=np.float32)
+
+    def process(self, samples: np.ndarray) -> np.ndarray:
+        """Resample the next mono samples; input short of a whole period is held for the next call"""
+        self.buffer = np.concatenate([self.buffer, samples.astype(np.float32)])
+        usable = (len(self.buffer) - self.history) // self.down * self.down
+        if usable <= 0:
+            return np.zeros(0, dtype=np.float32)
+        resampled = signal.upfirdn(self.taps, self.buffer[:self.history + usable], self.up, self.down)
+        start = self.history * self.up // self.down
+        self.buffer = self.buffer[usable:]
+        return resampled[start:start + usable * self.up // self.down].astype(np.float32)
+
 class AudioDevice:
     """Represents an audio device"""
     def __init__(self, index: int, name: str, channels: int, sample_rate: float, is_default: bool, device_type: str):
@@ -37,16 +79,141 @@ class AudioDevice:
 class AudioCapture:
     """Handles audio recording and device management"""
     
-    def __init__(self, sample_rate: int = 16000, channels: int = 1):
+    def __init__(self, sample_rate: int = 16000, channels: int = 1, channel_mode: str = "mono", channel: int = 1,
+                 capture_rate: int = 16000, buffer_ms: int = 0, wasapi: bool = False, exclusive: bool = False):
         self.sample_rate = sample_rate
         self.channels = channels
+        # How the device is opened: at `capture_rate` (resampled to `sample_rate` here), with
+        # `buffer_ms` blocks at low latency (0 = PortAudio's default), and through the default
+        # audio API or event-driven WASAPI, shared or exclusive
+        self.capture_rate = capture_rate
+        self.buffer_ms = buffer_ms
+        self.wasapi = wasapi
+        self.exclusive = exclusive
+        self.format: Optional[Dict] = None  # What the device was actually opened with, for diagnostics
+        self._resampler: Optional[StreamResampler] = None
+        # Which input channels make up the recording, for multi-channel interfaces:
+        # "mono" = the device's own mono input (usually its first channel), "mix" = all channels
+        # averaged, "channel" = only `channel` (1-based)
+        self.channel_mode = channel_mode
+        self.channel = channel
         self._is_recording = False
+        self._is_paused = False
         self.audio_queue = queue.Queue()
         self.stream = None
+        self.peak_level = 0.0  # Loudest sample since recording started; stays 0 on a muted mic
+        self.input_level = 0.0  # RMS of the latest block, for the overlay's level meter
+        self._spool = None  # Unbuffered file the recording is copied to as it arrives, for crash recovery
+        self._spool_path: Optional[Path] = None
+        self._last_callback = 0.0  # time.monotonic() of the latest audio block, paused or not
+        self._stream_ended = False  # The stream stopped on its own, e.g. the device was unplugged
+        self.device_index: Optional[int] = None  # The device the stream is open on
+        # Pre-buffer: while the stream is open without recording, the latest `_ring_seconds` of audio
+        # are kept here (in memory only) and become the start of the next recording
+        self._ring: Optional[deque] = None
+        self._ring_seconds = 0.0
+        self._ring_lock = threading.Lock()  # The callback thread appends while a recording takes the ring
     
     def is_recording(self) -> bool:
         """Check if currently recording"""
         return self._is_recording
+
+    def is_paused(self) -> bool:
+        """Check if recording is paused"""
+        return self._is_paused
+
+    def is_prebuffering(self) -> bool:
+        """Check if the microphone is open into the pre-buffer"""
+        return self._ring is not None
+
+    def pause(self):
+        """Pause recording - the stream stays open but incoming audio is dropped"""
+        self._is_paused = True
+        logger.info("⏸️ Recording paused")
+
+    def resume(self):
+        """Resume a paused recording"""
+        self._is_paused = False
+        logger.info("▶️ Recording resumed")
+
+    def _open_channels(self, device_index: Optional[int]) -> int:
+        """Number of channels to open the device with: all of them unless its mono input is used"""
+        if self.channel_mode == "mono":
+            return self.channels
+        count = int(sd.query_devices(device_index, 'input')['max_input_channels'])
+        if self.channel_mode == "channel" and self.channel > count:
+            logger.warning(f"⚠️ The device has {count} channel(s), not {self.channel}; mixing them instead")
+        logger.info(f"🎚️ Opening {count} input channel(s) ({self.channel_mode})")
+        return max(count, 1)
+
+    def _wasapi_device(self, device_index: Optional[int]) -> Optional[int]:
+        """The WASAPI entry of a device; sounddevice lists each device once per Windows audio API"""
+        api = next((api for api in sd.query_hostapis() if "WASAPI" in api["name"]), None)
+        if api is None:
+            logger.warning("⚠️ WASAPI isn't available, using the default audio API")
+            return device_index
+        if device_index is None:
+            return api["default_input_device"]
+        name = sd.query_devices(device_index)["name"]
+        for index in api["devices"]:
+            candidate = sd.query_devices(index)
+            # MME cuts names off at 31 characters
+            same = candidate["name"] == name or (len(name) >= 31 and candidate["name"].startswith(name))
+            if same and candidate["max_input_channels"] > 0:
+                return index
+        logger.warning(f"⚠️ {name} has no WASAPI input, using it through the default audio API")
+        return device_index
+
+    def _stream_options(self, device_index: Optional[int]) -> Dict:
+        """sounddevice stream arguments for the configured rate, buffer, and audio API; sets up resampling"""
+        device = self._wasapi_device(device_index) if self.wasapi else device_index
+        rate = self.capture_rate
+        if self.wasapi and not self.exclusive:
+            # Shared mode runs at the rate Windows mixes the device at
+            rate = int(sd.query_devices(device, "input")["default_samplerate"])
+        options = {"device": device, "channels": self._open_channels(device), "samplerate": rate, "dtype": np.float32}
+        if self.buffer_ms:
+            options["blocksize"] = rate * self.buffer_ms // 1000
+            options["latency"] = "low"
+        if self.wasapi:
+            options["extra_settings"] = sd.WasapiSettings(exclusive=self.exclusive)
+        self._resampler = StreamResampler(rate, self.sample_rate) if rate != self.sample_rate else None
+        return options
+
+    def _describe(self, stream, options: Dict) -> Dict:
+        """The format the device was opened with, next to what was asked for"""
+        device = sd.query_devices(stream.device)
+        return {
+            "device": device["name"],
+            "host_api": sd.query_hostapis(device["hostapi"])["name"],
+            "exclusive": self.wasapi and self.exclusive,
+            "sample_rate": int(stream.samplerate),
+            "requested_rate": options["samplerate"],
+            "channels": stream.channels,
+            "blocksize": stream.blocksize,  # 0 = varies per callback
+            "requested_blocksize": options.get("blocksize", 0),
+            "latency_ms": round(stream.latency * 1000, 1),
+        }
+
+    def _to_mono(self, data: np.ndarray) -> np.ndarray:
+        """The selected channel, or all channels mixed, as (frames, 1)"""
+        if data.shape[1] == 1:
+            return data
+        if self.channel_mode == "channel" and self.channel <= data.shape[1]:
+            return data[:, self.channel - 1:self.channel]
+        return data.mean(axis=1, keepdims=True, dtype=np.float32)
+
+    @property
+    def device_lost(self) -> bool:
+        """The microphone stopped delivering audio mid-recording or pre-buffering (unplugged, Bluetooth dropped)"""
+        if self.stream is None:
+            return False
+        return self._stream_ended or time.monotonic() - self._last_callback > DEVICE_TIMEOUT
+
+    def _stream_finished(self):
+        """Called by sounddevice when the stream stops, including when its device goes away"""
+        if self.stream is not None:
+            self._stream_ended = True
         
     def get_devices(self) -> Dict[str, List[AudioDevice]]:
         """Get all available audio devices"""
@@ -98,42 +265,64 @@ class AudioCapture:
     
     def _audio_callback(self, indata, frames, time_info, status):
         """Callback for audio stream"""
+        self._last_callback = time.monotonic()
         if status:
             logger.warning(f"⚠️ Audio callback status: {status}")
         
+        # Paused audio is never transcribed
+        if self._is_paused:
+            return
+
         try:
-            # Put audio data in queue
-            self.audio_queue.put(indata.copy())
+            indata = self._to_mono(indata)
+            if self._resampler is not None:
+                indata = self._resampler.process(indata[:, 0])[:, np.newaxis]
+                if len(indata) == 0:
+                    return
+            with self._ring_lock:
+                if self._ring is not None:
+                    self._ring.append(indata.copy())
+                    self._trim_ring()
+                    return
+                # Put audio data in queue
+                self.audio_queue.put(indata.copy())
+                if self._spool is not None:
+                    self._spool.write(indata.tobytes())
+            self.peak_level = max(self.peak_level, float(np.abs(indata).max()))
+            self.input_level = float(np.sqrt(np.mean(indata ** 2)))
             # Log occasionally to confirm audio is flowing
             if self.audio_queue.qsize() % 10 == 0:
                 logger.info(f"📊 Audio queue size: {self.audio_queue.qsize()}")
         except Exception as e:
             logger.error(f"❌ Audio callback error: {e}")
     
-    def start_recording(self, device_index: Optional[int] = None, duration: Optional[float] = None):
+    def start_recording(self, device_index: Optional[int] = None, duration: Optional[float] = None,
+                        spool_path: Optional[Path] = None):
         """
         Start recording audio
         
         Args:
             device_index: Index of the device to use (None = default)
             duration: Recording duration in seconds (None = infinite)
+            spool_path: File to write the raw float32 samples to while recording (deleted on stop),
+                so a crash doesn't lose the recording
         """
         if self._is_recording:
             logger.warning("Already recording!")
             return False
         
         try:
-            logger.info(f"Starting recording on device {device_index or 'default'}...")
-            
-            self.stream = sd.InputStream(
-                device=device_index,
-                channels=self.channels,
-                samplerate=self.sample_rate,
-                callback=self._audio_callback,
-                dtype=np.float32
-            )
-            
-            self.stream.start()
+            self._is_paused = False
+            self.peak_level = 0.0
+            self.input_level = 0.0
+            if spool_path is not None:
+                self._open_spool(spool_path)
+            if self.is_prebuffering():
+                # The stream is already open: the pre-buffered audio starts the recording
+                self._take_ring()
+            else:
+                logger.info(f"Starting recording on device {device_index or 'default'}...")
+                self._open_stream(device_index)
             self._is_recording = True
             logger.info("✅ Recording started")
             return True
@@ -141,7 +330,92 @@ class AudioCapture:
         except Exception as e:
             logger.error(f"Error starting recording: {e}")
             self._is_recording = False
+            if self.stream:
+                self.stream.close(ignore_errors=True)
+                self.stream = None
+            self._close_spool()
+            return False
+
+    def _open_stream(self, device_index: Optional[int]):
+        """Open and start the input stream"""
+        options = self._stream_options(device_index)
+        self.stream = sd.InputStream(
+            **options,
+            callback=self._audio_callback,
+            finished_callback=self._stream_finished
+        )
+        self.format = self._describe(self.stream, options)
+        logger.info(f"🎚️ Capture format: {self.format}")
+        self.device_index = device_index
+        self._last_callback = time.monotonic()
+        self._stream_ended = False
+        self.stream.start()
+
+    def start_prebuffer(self, device_index: Optional[int], seconds: float) -> bool:
+        """Open the microphone and keep its latest `seconds` of audio in memory until a recording starts"""
+        if self.stream is not None:
+            logger.warning("The microphone is already open!")
+            return False
+        try:
+            logger.info(f"⏪ Pre-buffering {seconds:g}s from device {device_index if device_index is not None else 'default'}")
+            self._ring_seconds = seconds
+            self._ring = deque()
+            self._open_stream(device_index)
+            return True
+        except Exception as e:
+            logger.error(f"Error starting the pre-buffer: {e}")
+            self.stop_prebuffer()
             return False
+
+    def stop_prebuffer(self):
+        """Close the microphone and drop the pre-buffered audio"""
+        with self._ring_lock:
+            self._ring = None
+        if self.stream is not None:
+            self.stream.close(ignore_errors=True)
+            self.stream = None
+
+    def _trim_ring(self):
+        """Drop pre-buffered blocks older than the pre-buffer's length (called with the lock held)"""
+        frames = sum(len(block) for block in self._ring)
+        while self._ring and frames - len(self._ring[0]) >= self._ring_seconds * self.sample_rate:
+            frames -= len(self._ring.popleft())
+
+    def _take_ring(self):
+        """Start the recording with the pre-buffered audio and stop pre-buffering"""
+        with self._ring_lock:
+            blocks = list(self._ring)
+            self._ring = None
+            for block in blocks:
+                self.audio_queue.put(block)
+                if self._spool is not None:
+                    self._spool.write(block.tobytes())
+        if blocks:
+            audio = np.concatenate(blocks)
+            self.peak_level = float(np.abs(audio).max())
+            logger.info(f"⏪ Recording starts with {len(audio) / self.sample_rate:.2f}s of pre-buffered audio")
+
+    def _open_spool(self, path: Path):
+        """Start copying the recording to `path`; recording goes on without it if the file can't be created"""
+        try:
+            path.parent.mkdir(parents=True, exist_ok=True)
+            # Unbuffered, so everything recorded so far is on disk even if the process is killed
+            self._spool = open(path, "wb", buffering=0)
+            self._spool_path = path
+        except OSError as e:
+            logger.warning(f"⚠️ Recording won't survive a crash, could not create {path}: {e}")
+
+    def _close_spool(self):
+        """Stop copying the recording and delete the copy"""
+        if self._spool is None:
+            return
+        try:
+            self._spool.close()
+            self._spool_path.unlink(missing_ok=True)
+        except OSError as e:
+            logger.warning(f"⚠️ Could not delete {self._spool_path}: {e}")
+        self._spool = None
+        self._spool_path = None
     
     def clear_queue(self):
         """Clear the audio queue"""
@@ -201,18 +475,22 @@ class AudioCapture:
         try:
             logger.info("Stopping recording...")
             
+            self._is_recording = False
             if self.stream:
-                self.stream.stop()
-                self.stream.close()
+                try:
+                    self.stream.stop()
+                    self.stream.close()
+                except Exception as e:
+                    # A device that went away can't be stopped cleanly; what it recorded is still queued
+                    logger.warning(f"⚠️ Could not close the audio stream: {e}")
                 self.stream = None
+            self._close_spool()
             
             # Get all audio chunks from queue
             audio_chunks = []
             while not self.audio_queue.empty():
                 audio_chunks.append(self.audio_queue.get())
             
-            self._is_recording = False
-            
             if audio_chunks:
                 audio_data = np.concatenate(audio_chunks, axis=0)
                 logger.info(f"✅ Recorded {len(audio_data) / self.sample_rate:.2f} seconds of audio")
@@ -224,6 +502,7 @@ class AudioCapture:
         except Exception as e:
             logger.error(f"Error stopping recording: {e}")
             self._is_recording = False
+            self._close_spool()
             return None
     
     def save_wav(self, audio_data: np.ndarray, filename: str):
@@ -290,16 +569,16 @@ class AudioCapture:
         try:
             # Record audio
             logger.info("Starting recording...")
-            recording = sd.rec(
-                int(duration * self.sample_rate),
-                samplerate=self.sample_rate,
-                channels=self.channels,
-                device=device_index,
-                dtype=np.float32
-            )
+            options = self._stream_options(device_index)
+            recording = sd.rec(int(duration * options["samplerate"]), **options)
+            self.format = self._describe(sd.get_stream(), options)
+            logger.info(f"🎚️ Capture format: {self.format}")
             
             logger.info(f"Recording in progress for {duration} seconds...")
             sd.wait()  # Wait until recording is finished
+            recording = self._to_mono(recording)
+            if self._resampler is not None:
+                recording = self._resampler.process(recording[:, 0])[:, np.newaxis]
             
             # Check if we got any audio
             logger.info(f"Recording complete. Shape: {recording.shape}, dtype: {recording.dtype}")
@@ -0,0 +1,198 @@
+"""
+Audio Pre-processing Module
+Optional clean-up applied to captured audio before transcription:
+a high-pass filter for rumble, a spectral noise gate for steady background noise, and auto gain
+"""
+
+import io
+import logging
+import wave
+import numpy as np
+from dataclasses import dataclass
+from typing import Dict, Optional
+
+logger = logging.getLogger(__name__)
+
+# STFT frames with 50% overlap; sqrt-Hann windows on both sides add back up to the input
+FRAME = 512
+HOP = FRAME // 2
+# Frames processed at once, which keeps memory flat on hour-long recordings
+BLOCK_FRAMES = 2048
+
+# High-pass: bins below the stop frequency are removed, with a smooth ramp up to the pass frequency
+HIGH_PASS_STOP_HZ = 60
+HIGH_PASS_PASS_HZ = 100
+
+# Noise gate: the noise floor of each frequency is a low percentile of its magnitude over time
+NOISE_PERCENTILE = 15
+NOISE_ESTIMATE_FRAMES = 4000  # Frames sampled for the estimate on long recordings
+OVER_SUBTRACTION = 2.0
+MIN_GAIN = 0.1  # -20 dB; removing the noise entirely leaves "musical" artifacts
+
+# Auto gain: bring the loud (speech) parts to about -20 dBFS
+GAIN_FRAME_SECONDS = 0.02
+SPEECH_PERCENTILE = 95
+TARGET_RMS = 0.1
+MAX_GAIN = 10.0  # +20 dB
+MIN_AUTO_GAIN = 0.5
+LIMIT = 0.95
+
+# Level check: samples this close to full scale are clipped, and a small share of them already
+# garbles words; speech below QUIET_DBFS is too quiet to be transcribed reliably
+CLIP_LEVEL = 0.99
+CLIP_FRACTION = 0.001
+QUIET_DBFS = -40.0
+
+
+@dataclass
+class Options:
+    denoise: bool = False
+    high_pass: bool = False
+    auto_gain: bool = False
+
+    @property
+    def any(self) -> bool:
+        return self.denoise or self.high_pass or self.auto_gain
+
+
+def preprocess(audio: np.ndarray, options: Options, sample_rate: int = 16000) -> np.ndarray:
+    """Return the audio with the selected processing applied (the input is left untouched)"""
+    audio = audio.flatten().astype(np.float32)
+    if not options.any or len(audio) == 0:
+        return audio
+
+    if options.high_pass or options.denoise:
+        gains = _static_gains(options.high_pass, sample_rate)
+        noise = _noise_floor(audio) if options.denoise else None
+        audio = _filter(audio, gains, noise)
+
+    if options.auto_gain:
+        audio = _auto_gain(audio, sample_rate)
+
+    return audio
+
+
+def _window() -> np.ndarray:
+    n = np.arange(FRAME)
+    return np.sqrt(0.5 - 0.5 * np.cos(2 * np.pi * n / FRAME)).astype(np.float32)
+
+
+def _static_gains(high_pass: bool, sample_rate: int) -> np.ndarray:
+    """Per-bin gain that doesn't depend on the signal (the high-pass ramp)"""
+    gains = np.ones(FRAME // 2 + 1, dtype=np.float32)
+    if high_pass:
+        freqs = np.fft.rfftfreq(FRAME, 1 / sample_rate)
+        ramp = np.clip((freqs - HIGH_PASS_STOP_HZ) / (HIGH_PASS_PASS_HZ - HIGH_PASS_STOP_HZ), 0, 1)
+        gains *= (0.5 - 0.5 * np.cos(np.pi * ramp)).astype(np.float32)
+    return gains
+
+
+def _frames(padded: np.ndarray, indices: np.ndarray, window: np.ndarray) -> np.ndarray:
+    return padded[indices[:, None] * HOP + np.arange(FRAME)] * window
+
+
+def _pad(audio: np.ndarray) -> np.ndarray:
+    # Leading padding so the first samples get full overlap; trailing padding completes the last frame
+    tail = FRAME + (-len(audio)) % HOP
+    return np.concatenate([np.zeros(HOP, dtype=np.float32), audio, np.zeros(tail, dtype=np.float32)])
+
+
+def _frame_count(padded: np.ndarray) -> int:
+    return (len(padded) - FRAME) // HOP + 1
+
+
+def _noise_floor(audio: np.ndarray) -> np.ndarray:
+    """Magnitude of the steady background noise per frequency bin"""
+    padded = _pad(audio)
+    total = _frame_count(padded)
+    indices = np.linspace(0, total - 1, min(total, NOISE_ESTIMATE_FRAMES)).astype(np.int64)
+    magnitudes = np.abs(np.fft.rfft(_frames(padded, indices, _window()), axis=1))
+    return np.percentile(magnitudes, NOISE_PERCENTILE, axis=0).astype(np.float32)
+
+
+def _filter(audio: np.ndarray, gains: np.ndarray, noise) -> np.ndarray:
+    """Apply per-bin gains (and the noise gate, if a noise floor is given) with STFT overlap-add"""
+    window = _window()
+    padded = _pad(audio)
+    total = _frame_count(padded)
+    output = np.zeros(len(padded), dtype=np.float32)
+
+    for start in range(0, total, BLOCK_FRAMES):
+        # Even block sizes keep each block's even frames on global even indices
+        indices = np.arange(start, min(start + BLOCK_FRAMES, total))
+        spectrum = np.fft.rfft(_frames(padded, indices, window), axis=1)
+        frame_gains = np.broadcast_to(gains, spectrum.shape)
+        if noise is not None:
+            power = np.abs(spectrum) ** 2 + 1e-12
+            gate = np.clip(1 - OVER_SUBTRACTION * noise ** 2 / power, MIN_GAIN ** 2, 1) ** 0.5
+            # Smoothing across neighbouring bins softens the gate's artifacts
+            gate = (gate + np.roll(gate, 1, axis=1) + np.roll(gate, -1, axis=1)) / 3
+            frame_gains = frame_gains * gate
+        frames = np.fft.irfft(spectrum * frame_gains, n=FRAME, axis=1).astype(np.float32) * window
+
+        # With 50% overlap, even frames tile the signal without overlapping each other, as do odd ones
+        for parity in (0, 1):
+            selected = frames[(indices % 2) == parity]
+            if len(selected) == 0:
+                continue
+            first = indices[(indices % 2) == parity][0] * HOP
+            output[first:first + len(selected) * FRAME] += selected.reshape(-1)
+
+    return output[HOP:HOP + len(audio)]
+
+
+def _speech_level(audio: np.ndarray, sample_rate: int) -> Optional[float]:
+    """RMS of the loud (speech) parts, or None if the clip is shorter than a frame"""
+    frame = int(sample_rate * GAIN_FRAME_SECONDS)
+    usable = len(audio) - len(audio) % frame
+    if usable == 0:
+        return None
+    rms = np.sqrt(np.mean(audio[:usable].reshape(-1, frame) ** 2, axis=1))
+    return float(np.percentile(rms, SPEECH_PERCENTILE))
+
+
+def analyze_levels(audio: np.ndarray, sample_rate: int = 16000) -> Dict:
+    """How loud a recording was, flagged "clipping", "quiet", or "ok" (the raw capture, before pre-processing)"""
+    audio = audio.flatten()
+    if len(audio) == 0:
+        return {"flag": "ok", "peak": 0.0, "speech_dbfs": None, "clipped": 0.0}
+    magnitude = np.abs(audio)
+    clipped = float(np.mean(magnitude >= CLIP_LEVEL))
+    level = _speech_level(audio, sample_rate)
+    speech_dbfs = round(20 * np.log10(max(level, 1e-10)), 1) if level is not None else None
+    if clipped >= CLIP_FRACTION:
+        flag = "clipping"
+    elif speech_dbfs is not None and speech_dbfs < QUIET_DBFS:
+        flag = "quiet"
+    else:
+        flag = "ok"
+    return {"flag": flag, "peak": float(magnitude.max()), "speech_dbfs": speech_dbfs, "clipped": clipped}
+
+
+def _auto_gain(audio: np.ndarray, sample_rate: int) -> np.ndarray:
+    """Scale the whole clip so speech sits near TARGET_RMS, softly limiting peaks"""
+    level = _speech_level(audio, sample_rate)
+    if level is None:
+        return audio
+    if level < 1e-5:
+        return audio  # Silence; amplifying it would only raise the noise
+
+    gain = float(np.clip(TARGET_RMS / level, MIN_AUTO_GAIN, MAX_GAIN))
+    logger.info(f"🔊 Auto gain: {20 * np.log10(gain):+.1f} dB")
+    audio = audio * gain
+    # Peaks above the limit are squashed with tanh instead of clipping hard
+    over = np.abs(audio) > LIMIT
+    audio[over] = np.sign(audio[over]) * (LIMIT + (1 - LIMIT) * np.tanh((np.abs(audio[over]) - LIMIT) / (1 - LIMIT)))
+    return audio.astype(np.float32)
+
+
+def to_wav_bytes(audio: np.ndarray, sample_rate: int = 16000) -> bytes:
+    """16-bit mono WAV of float audio, e.g. for playback in the UI"""
+    samples = (np.clip(audio.flatten(), -1.0, 1.0) * 32767).astype(np.int16)
+    buffer = io.BytesIO()
+    with wave.open(buffer, "wb") as wf:
+        wf.setnchannels(1)
+        wf.setsampwidth(2)
+        wf.setframerate(sample_rate)
+        wf.writeframes(samples.tobytes())
+    return buffer.getvalue()
@@ -0,0 +1,174 @@
+"""
+Benchmark: transcribe a sample clip with every downloaded model on CPU and GPU,
+and recommend the most accurate setup that is still fast enough for dictation
+"""
+
+import difflib
+import logging
+import os
+import re
+import subprocess
+import time
+import wave
+from pathlib import Path
+from typing import Dict, List, Optional
+
+import numpy as np
+
+from gpu_manager import get_data_dir
+from whisper_engine import WhisperEngine, list_downloaded_models
+
+logger = logging.getLogger(__name__)
+
+# The passage the sample clip reads; also the reference the transcriptions are scored against
+SAMPLE_TEXT = (
+    "Thanks for getting back to me so quickly. I looked over the draft this morning and I think "
+    "the second section needs a little more detail, especially around the budget and the timeline. "
+    "Could we set up a call on Thursday afternoon to go through it together? If that doesn't work, "
+    "send me a few times that suit you next week and I'll make one of them happen. "
+    "In the meantime, I'll share my notes in the document so you can see where I'm coming from."
+)
+
+# Largest first, so ties in the recommendation go to the more accurate model
+MODEL_ORDER = [
+    "large-v3", "large-v3-turbo", "distil-large-v3", "medium", "distil-medium.en", "small", "distil-small.en", "base", "tiny"
+]
+
+# A setup is fast enough for dictation if it transcribes at least this many seconds of audio per second
+MIN_SPEED = 3.0
+SAMPLE_RATE = 16000
+
+
+def get_sample_path() -> Path:
+    return get_data_dir() / "benchmark_sample.wav"
+
+
+def synthesize_sample(path: Path):
+    """Read SAMPLE_TEXT with the built-in Windows voice into a 16 kHz mono WAV"""
+    path.parent.mkdir(parents=True, exist_ok=True)
+    script = (
+        "Add-Type -AssemblyName System.Speech; "
+        "$voice = New-Object System.Speech.Synthesis.SpeechSynthesizer; "
+        "$format = New-Object System.Speech.AudioFormat.SpeechAudioFormatInfo(16000, "
+        "[System.Speech.AudioFormat.AudioBitsPerSample]::Sixteen, [System.Speech.AudioFormat.AudioChannel]::Mono); "
+        "$voice.SetOutputToWaveFile($env:BENCHMARK_WAV, $format); "
+        "$voice.Speak($env:BENCHMARK_TEXT); "
+        "$voice.Dispose()"
+    )
+    env = {**os.environ, "BENCHMARK_WAV": str(path), "BENCHMARK_TEXT": SAMPLE_TEXT}
+    subprocess.run(
+        ["powershell", "-NoProfile", "-NonInteractive", "-Command", script],
+        env=env,
+        capture_output=True,
+        timeout=60,
+        check=True,
+        creationflags=getattr(subprocess, "CREATE_NO_WINDOW", 0)
+    )
+
+
+def load_sample() -> np.ndarray:
+    """The sample clip as float32 samples, synthesized on first use"""
+    path = get_sample_path()
+    if not path.exists():
+        logger.info("🗣️ Creating the benchmark sample clip...")
+        synthesize_sample(path)
+
+    with wave.open(str(path), "rb") as wav:
+        if wav.getframerate() != SAMPLE_RATE or wav.getnchannels() != 1 or wav.getsampwidth() != 2:
+            raise ValueError("Benchmark sample must be 16 kHz mono 16-bit")
+        frames = wav.readframes(wav.getnframes())
+    return np.frombuffer(frames, dtype=np.int16).astype(np.float32) / 32768.0
+
+
+def words(text: str) -> List[str]:
+    return re.findall(r"[a-z0-9']+", text.lower())
+
+
+def accuracy(text: str) -> float:
+    """Share of the reference words transcribed correctly (0 to 1)"""
+    reference = words(SAMPLE_TEXT)
+    matcher = difflib.SequenceMatcher(a=reference, b=words(text), autojunk=False)
+    matched = sum(block.size for block in matcher.get_matching_blocks())
+    return matched / len(reference)
+
+
+def available_devices() -> List[str]:
+    devices = ["cpu"]
+    try:
+        import ctranslate2
+        if ctranslate2.get_cuda_device_count() > 0:
+            devices.append("cuda")
+    except Exception as e:
+        logger.info(f"💻 No GPU for the benchmark: {e}")
+    return devices
+
+
+def benchmark_one(model: str, device: str, audio: np.ndarray) -> Dict:
+    """Load `model` on `device` and transcribe the sample once"""
+    result = {"model": model, "device": device}
+    engine = WhisperEngine(model_size=model, device=device)
+    try:
+        start = time.time()
+        if not engine.load_model():
+            return {**result, "error": "Failed to load the model"}
+        result["load_time"] = time.time() - start
+        if engine.device != device:
+            return {**result, "error": "The GPU couldn't be used"}
+
+        # A short warm-up, so one-time initialization doesn't count against the model
+        engine.transcribe_audio(audio[:SAMPLE_RATE], language="en")
+        start = time.time()
+        transcription = engine.transcribe_audio(audio, language="en")
+        elapsed = time.time() - start
+        if not transcription.get("success"):
+            return {**result, "error": transcription.get("error", "Transcription failed")}
+
+        duration = len(audio) / SAMPLE_RATE
+        return {
+            **result,
+            "transcription_time": elapsed,
+            "realtime_factor": elapsed / duration,
+            "accuracy": accuracy(transcription["text"])
+        }
+    finally:
+        # Free the model (and GPU memory) before loading the next one
+        engine.unload_model()
+
+
+def recommend(results: List[Dict]) -> Optional[Dict]:
+    """The most accurate setup fast enough for dictation, otherwise the fastest one"""
+    working = [r for r in results if "error" not in r]
+    if not working:
+        return None
+
+    fast = [r for r in working if r["realtime_factor"] <= 1 / MIN_SPEED]
+    if fast:
+        # Accuracy within a point counts as a tie, which the faster setup wins
+        best = max(round(r["accuracy"], 2) for r in fast)
+        candidates = [r for r in fast if round(r["accuracy"], 2) >= best - 0.01]
+        choice = min(candidates, key=lambda r: r["realtime_factor"])
+    else:
+        choice = min(working, key=lambda r: r["realtime_factor"])
+    return {"model": choice["model"], "device": choice["device"]}
+
+
+def run(models: Optional[List[str]] = None) -> Dict:
+    """Benchmark the given (or all downloaded) models on every device"""
+    audio = load_sample()
+    downloaded = list_downloaded_models()
+    models = [m for m in (models or downloaded) if m in downloaded]
+    models.sort(key=lambda m: MODEL_ORDER.index(m) if m in MODEL_ORDER else len(MODEL_ORDER))
+
+    results = []
+    for device in available_devices():
+        for model in models:
+            logger.info(f"⏱️ Benchmarking {model} on {device}...")
+            result = benchmark_one(model, device, audio)
+            logger.info(f"⏱️ {result}")
+            results.append(result)
+
+    return {
+        "sample_duration": len(audio) / SAMPLE_RATE,
+        "results": results,
+        "recommendation": recommend(results)
+    }
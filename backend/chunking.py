@@ -0,0 +1,64 @@
+"""
+Audio Chunking Module
+Splits long recordings into chunks at quiet points so they can be transcribed incrementally
+"""
+
+import logging
+import numpy as np
+from typing import List
+
+logger = logging.getLogger(__name__)
+
+# Recordings longer than this are transcribed chunk by chunk
+LONG_RECORDING_SECONDS = 120
+CHUNK_SECONDS = 30
+# How far around each nominal boundary to look for a pause
+BOUNDARY_SEARCH_SECONDS = 3
+FRAME_SECONDS = 0.1
+
+
+def is_long_recording(audio_data: np.ndarray, sample_rate: int = 16000) -> bool:
+    """Whether a recording should take the chunked path"""
+    return len(audio_data) / sample_rate > LONG_RECORDING_SECONDS
+
+
+def _quietest_frame_start(audio: np.ndarray, start: int, end: int, frame: int) -> int:
+    """Sample offset of the lowest-energy frame in audio[start:end]"""
+    best_offset = start
+    best_energy = None
+    for offset in range(start, max(start + 1, end - frame), frame):
+        window = audio[offset:offset + frame]
+        energy = float(np.mean(window * window)) if len(window) else 0.0
+        if best_energy is None or energy < best_energy:
+            best_energy = energy
+            best_offset = offset
+    return best_offset + frame // 2
+
+
+def split_audio(audio_data: np.ndarray, sample_rate: int = 16000) -> List[np.ndarray]:
+    """
+    Split audio into ~CHUNK_SECONDS pieces, cutting at the quietest point near each boundary
+    so words aren't split in half
+
+    Args:
+        audio_data: Audio data (float32, mono, 16kHz), 1D or (N, 1)
+
+    Returns:
+        List of 1D audio chunks covering the whole recording in order
+    """
+    audio = audio_data.flatten() if len(audio_data.shape) > 1 else audio_data
+    chunk = CHUNK_SECONDS * sample_rate
+    search = BOUNDARY_SEARCH_SECONDS * sample_rate
+    frame = int(FRAME_SECONDS * sample_rate)
+
+    chunks = []
+    position = 0
+    while len(audio) - position > chunk + search:
+        target = position + chunk
+        cut = _quietest_frame_start(audio, target - search, target + search, frame)
+        chunks.append(audio[position:cut])
+        position = cut
+
+    chunks.append(audio[position:])
+    logger.info(f"✂️ Split {len(audio) / sample_rate:.1f}s of audio into {len(chunks)} chunks")
+    return chunks
@@ -0,0 +1,84 @@
+"""
+Speaker Diarization Module
+Labels transcription segments with "Speaker 1", "Speaker 2", ... using pyannote.audio (optional dependency)
+"""
+
+import logging
+import os
+import numpy as np
+from typing import Dict, List
+
+logger = logging.getLogger(__name__)
+
+PIPELINE_NAME = "pyannote/speaker-diarization-3.1"
+
+# pyannote is large and gated on Hugging Face, so it is not bundled - diarization is off unless installed
+try:
+    import torch
+    from pyannote.audio import Pipeline
+    DIARIZATION_AVAILABLE = True
+    logger.info("✅ pyannote.audio is available (speaker diarization enabled)")
+except ImportError as e:
+    DIARIZATION_AVAILABLE = False
+    Pipeline = None
+    logger.info(f"ℹ️ Speaker diarization not available: {e}")
+
+_pipeline = None
+
+
+def _load_pipeline():
+    """Load the diarization pipeline once (needs a Hugging Face token with access to the model)"""
+    global _pipeline
+    if _pipeline is None:
+        token = os.getenv("HF_TOKEN") or os.getenv("HUGGINGFACE_TOKEN")
+        logger.info(f"📥 Loading diarization pipeline: {PIPELINE_NAME}")
+        _pipeline = Pipeline.from_pretrained(PIPELINE_NAME, use_auth_token=token)
+        if _pipeline is None:
+            raise RuntimeError(f"Could not load {PIPELINE_NAME} (set HF_TOKEN and accept the model's terms)")
+    return _pipeline
+
+
+def speaker_turns(audio_data: np.ndarray, sample_rate: int = 16000) -> List[Dict]:
+    """Who spoke when: [{"start", "end", "speaker"}], speakers numbered in order of first appearance"""
+    pipeline = _load_pipeline()
+    waveform = torch.from_numpy(audio_data.astype(np.float32).flatten()).unsqueeze(0)
+    annotation = pipeline({"waveform": waveform, "sample_rate": sample_rate})
+
+    labels = {}
+    turns = []
+    for turn, _, label in annotation.itertracks(yield_label=True):
+        speaker = labels.setdefault(label, f"Speaker {len(labels) + 1}")
+        turns.append({"start": turn.start, "end": turn.end, "speaker": speaker})
+    return turns
+
+
+def assign_speakers(segments: List[Dict], turns: List[Dict]) -> List[Dict]:
+    """Give each transcription segment the speaker it overlaps most (None if no turn overlaps it)"""
+    labeled = []
+    for segment in segments:
+        overlaps = {}
+        for turn in turns:
+            overlap = min(segment["end"], turn["end"]) - max(segment["start"], turn["start"])
+            if overlap > 0:
+                overlaps[turn["speaker"]] = overlaps.get(turn["speaker"], 0) + overlap
+        speaker = max(overlaps, key=overlaps.get) if overlaps else None
+        labeled.append(dict(segment, speaker=speaker))
+    return labeled
+
+
+def labeled_text(segments: List[Dict]) -> str:
+    """Transcript with a "Speaker N:" line prefix whenever the speaker changes"""
+    lines = []
+    current = None
+    for segment in segments:
+        text = segment["text"].strip()
+        if not text:
+            continue
+        if segment.get("speaker") and segment["speaker"] != current:
+            current = segment["speaker"]
+            lines.append(f"{current}: {text}")
+        elif lines:
+            lines[-1] += " " + text
+        else:
+            lines.append(text)
+    return "\n".join(lines)
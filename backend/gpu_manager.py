@@ -25,12 +25,20 @@ CUDA_PACKAGES = {
 }
 
 
+def get_data_dir() -> Path:
+    """Get the app data directory (next to the app in portable mode, otherwise AppData)"""
+    portable_dir = os.getenv('WHISPER4WINDOWS_DATA_DIR')
+    if portable_dir:
+        return Path(portable_dir)
+    appdata = Path(os.getenv('APPDATA') or os.path.expanduser('~'))
+    return appdata / 'Whisper4Windows'
+
+
 def get_gpu_libs_dir() -> Path:
     """Get the directory where GPU libraries are stored"""
     if getattr(sys, 'frozen', False):
-        # Running as bundled executable - use AppData
-        appdata = Path(os.getenv('APPDATA') or os.path.expanduser('~'))
-        gpu_dir = appdata / 'Whisper4Windows' / 'gpu_libs'
+        # Running as bundled executable - use the app data directory
+        gpu_dir = get_data_dir() / 'gpu_libs'
     else:
         # Running from source - use local directory
         gpu_dir = Path("gpu_libs")
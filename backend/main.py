@@ -5,8 +5,12 @@ FastAPI server for local speech-to-text processing
 
 import logging
 import asyncio
-from contextlib import asynccontextmanager
-from typing import Optional, Dict, List
+import getpass
+import os
+import functools
+import time
+from contextlib import asynccontextmanager, contextmanager
+from typing import Optional, Dict, List, Tuple
 import numpy as np
 
 from fastapi import FastAPI, HTTPException
@@ -14,9 +18,17 @@ from fastapi.middleware.cors import CORSMiddleware
 from pydantic import BaseModel
 
 # Import our modules
-from audio_capture import AudioCapture
-from whisper_engine import WhisperEngine
+from audio_capture import AudioCapture, refresh_devices
+from whisper_engine import (
+    WhisperEngine, DecodingOptions, list_downloaded_models, is_downloaded, model_catalog, validate_custom_model,
+    available_memory_gb, MODEL_MEMORY_GB
+)
 import gpu_manager
+import chunking
+import diarization
+import audio_processing
+import media
+import benchmark
 
 # Configure logging
 logging.basicConfig(
@@ -25,24 +37,180 @@ logging.basicConfig(
 )
 logger = logging.getLogger(__name__)
 
+# Set by the app: several users (or sessions) on one machine each run a backend of their own
+PORT = int(os.getenv('WHISPER4WINDOWS_PORT') or 8000)
+SESSION = os.getenv('WHISPER4WINDOWS_SESSION', '')
+
 # Global instances
 audio_capture: Optional[AudioCapture] = None
 whisper_engine: Optional[WhisperEngine] = None
+# Other model slots' engines kept loaded for a quick switch, by (model_size, device)
+warm_engines: Dict[Tuple[str, str], WhisperEngine] = {}
+warm_keys: List[Tuple[str, str]] = []  # Which engines may be kept warm, from the last /start
 is_recording = False
 transcription_task: Optional[asyncio.Task] = None
 last_transcribed_text = ""
+last_audio: Optional[np.ndarray] = None  # Audio of the last recording, kept for re-transcription
+last_audio_time: float = 0.0  # When last_audio was recorded (epoch seconds), for the retention policy
+# Continuous dictation: background segmenter task and the segments it produced ({"id", "text"})
+continuous_task: Optional[asyncio.Task] = None
+continuous_segments: List[Dict] = []
+continuous_audio: List[np.ndarray] = []  # Everything captured this recording, for /retranscribe
+continuous_buffer = np.zeros(0, dtype=np.float32)  # Audio not yet assigned to a segment
+continuous_language: Optional[str] = "en"
+recording_language: Optional[str] = "en"  # Language from /start, used by /stop unless it names another
+# Multilingual mode: detect the language of each segment (only among multilingual_languages, if any)
+multilingual = False
+multilingual_languages: List[str] = []
+diarize_recording = False  # Label speakers when the current recording is stopped
+retain_audio = True  # Keep the current recording in memory for /retranscribe (off in privacy mode)
+preprocessing = audio_processing.Options()  # Clean-up applied before transcribing the current/last recording
+# Progress of a chunked (long-recording) transcription, polled via /progress
+transcription_progress = {"active": False, "done": 0, "total": 0, "partial_text": ""}
+benchmark_running = False  # Recording is refused while models are being benchmarked
+# Model load /start kicks off so it runs while the user speaks; resolves to the load time (None if it failed)
+model_load_task: Optional[asyncio.Future] = None
+# Memory policy: "keep" the model loaded, unload it after model_idle_minutes "idle", or "on_demand" after each use
+model_policy = "keep"
+model_idle_minutes = 15
+model_users = 0  # Requests using the model right now; it is never unloaded under them
+last_model_use = 0.0  # When a request last finished with the model (epoch seconds)
+# Pre-buffer: the microphone kept open between recordings so a recording starts with the audio
+# from just before it (see POST /prebuffer); the settings, with the candidate microphones by name
+# since device indices change when devices are rescanned
+prebuffer_capture: Optional[AudioCapture] = None
+prebuffer_request: Optional["PrebufferRequest"] = None
+prebuffer_devices: List[Optional[str]] = []  # None = the default microphone
 
 
 # Pydantic models
-class StartRequest(BaseModel):
+class WarmModel(BaseModel):
+    model_size: str
+    device: str = "auto"
+
+
+class CaptureRequest(BaseModel):
+    """How the microphone is opened (see AudioCapture)"""
+    channel_mode: str = "mono"  # Input channels to record: mono, mix, or channel
+    channel: int = 1  # With channel_mode "channel", 1-based
+    capture_rate: int = 16000  # Rate the device is opened at; anything else is resampled to 16 kHz
+    buffer_ms: int = 0  # Block size at low latency (0 = PortAudio's default)
+    wasapi: bool = False  # Event-driven WASAPI instead of the default audio API
+    exclusive: bool = False  # WASAPI exclusive mode
+
+    def capture(self) -> AudioCapture:
+        return AudioCapture(channel_mode=self.channel_mode, channel=self.channel, capture_rate=self.capture_rate,
+                            buffer_ms=self.buffer_ms, wasapi=self.wasapi, exclusive=self.exclusive)
+
+    def capture_settings(self) -> Dict:
+        return self.model_dump(include=set(CaptureRequest.model_fields))
+
+
+class AdvancedRequest(BaseModel):
+    """Advanced settings: precision the model is loaded with, and decoding parameters (see DecodingOptions)"""
+    compute_type: str = "auto"  # auto, int8, int8_float16, float16, float32
+    beam_size: int = 1
+    temperature: float = 0.0
+    condition_on_previous_text: bool = False
+    no_speech_threshold: float = 0.6
+
+    def decoding(self) -> DecodingOptions:
+        return DecodingOptions(
+            beam_size=max(1, self.beam_size),
+            temperature=max(0.0, self.temperature),
+            condition_on_previous_text=self.condition_on_previous_text,
+            no_speech_threshold=self.no_speech_threshold
+        )
+
+
+class StartRequest(AdvancedRequest, CaptureRequest):
     model_size: str = "small"  # tiny, base, small, medium, large-v3
     language: Optional[str] = "en"
     device: str = "auto"  # auto, cpu, cuda
     device_index: Optional[int] = None  # Microphone device index (None = default)
+    device_indices: List[int] = []  # Preferred microphones, tried in order before device_index
+    continuous: bool = False  # Transcribe each pause-delimited segment while recording continues
+    silence_ms: int = 700  # Pause length that ends a segment in continuous mode
+    diarize: bool = False  # Label speakers ("Speaker 1", ...) in the final transcript
+    retain_audio: bool = True  # Keep the audio for /retranscribe after stopping
+    multilingual: bool = False  # Detect the language per segment instead of using `language`
+    languages: List[str] = []  # Languages multilingual detection may pick (empty = any)
+    denoise: bool = False  # Pre-processing applied before transcription (see audio_processing)
+    high_pass: bool = False
+    auto_gain: bool = False
+    warm_models: List[WarmModel] = []  # Other models to keep loaded next to this one if memory allows
 
 
 class StopRequest(BaseModel):
-    pass
+    language: Optional[str] = None  # One-off override of the /start language (null = auto-detect); omit to keep it
+    multilingual: Optional[bool] = None  # Override of the /start multilingual mode; omit to keep it
+
+
+class RetranscribeRequest(AdvancedRequest):
+    model_size: Optional[str] = None  # None = keep the current engine
+    device: Optional[str] = None
+    language: Optional[str] = "en"
+    diarize: bool = False
+    multilingual: bool = False
+    languages: List[str] = []
+    compute_type: Optional[str] = None  # None = keep the current engine's
+
+
+class TranscribeFileRequest(RetranscribeRequest):
+    path: str
+    audio_track: int = 0  # Which of the file's audio tracks (see /file_tracks)
+
+
+class FileTracksRequest(BaseModel):
+    path: str
+
+
+class PrebufferRequest(CaptureRequest):
+    enabled: bool = False
+    seconds: float = 2.0  # Audio kept from before a recording starts
+    device_index: Optional[int] = None
+    device_indices: List[int] = []  # Preferred microphones, as in /start
+
+
+class PurgeRequest(BaseModel):
+    older_than_secs: Optional[float] = None
+
+
+class SelfTestModelRequest(BaseModel):
+    model_size: str = "small"
+    device: str = "auto"
+
+
+class ModelPolicyRequest(BaseModel):
+    policy: str = "keep"  # keep, idle, on_demand
+    idle_minutes: int = 15
+
+
+class PreloadRequest(BaseModel):
+    model_size: str = "small"
+    device: str = "auto"
+    compute_type: str = "auto"
+
+
+class SelfTestMicRequest(CaptureRequest):
+    device_index: Optional[int] = None
+    seconds: float = 1.0
+
+
+class BenchmarkRequest(BaseModel):
+    models: List[str] = []  # Empty = every downloaded model
+
+
+class ValidateModelRequest(BaseModel):
+    source: str  # Local folder or Hugging Face ID of a CTranslate2 Whisper model
+
+
+class MicTestRequest(CaptureRequest):
+    device_index: Optional[int] = None
+    seconds: float = 4.0
+    denoise: bool = False
+    high_pass: bool = False
+    auto_gain: bool = False
 
 
 class TranscriptionResponse(BaseModel):
@@ -57,29 +225,436 @@ class HealthResponse(BaseModel):
     backend: str
     model: str
     recording: bool
+    session: str  # Windows session of the app that started this backend
+
+
+def transcribe_chunk(audio: np.ndarray, language: Optional[str]) -> Dict:
+    """Transcribe one piece of audio, detecting the language per segment in multilingual mode"""
+    if multilingual:
+        return whisper_engine.transcribe_multilingual(audio, multilingual_languages)
+    return whisper_engine.transcribe_audio(audio, language)
+
+
+async def transcribe_with_progress(audio_data: np.ndarray, language: Optional[str]) -> Dict:
+    """Transcribe audio, going chunk by chunk (with progress) for long recordings"""
+    loop = asyncio.get_event_loop()
+
+    if not chunking.is_long_recording(audio_data):
+        return await loop.run_in_executor(None, transcribe_chunk, audio_data, language)
+
+    chunks = chunking.split_audio(audio_data)
+    texts = []
+    segments = []
+    words = []
+    chunk_offset = 0.0  # Start of the current chunk within the recording, in seconds
+    detected_language = language
+    transcription_progress.update(active=True, done=0, total=len(chunks), partial_text="")
+
+    try:
+        for index, chunk in enumerate(chunks):
+            logger.info(f"🧩 Transcribing chunk {index + 1}/{len(chunks)} ({len(chunk) / 16000:.1f}s)")
+            result = await loop.run_in_executor(None, transcribe_chunk, chunk, language)
+            if not result["success"]:
+                return result
+
+            if result["text"].strip():
+                texts.append(result["text"].strip())
+            segments.extend(offset_timestamps(segment, chunk_offset) for segment in result.get("segments", []))
+            words.extend(offset_timestamps(word, chunk_offset) for word in result.get("words", []))
+            chunk_offset += len(chunk) / 16000
+            detected_language = detected_language or result.get("language")
+            transcription_progress.update(done=index + 1, partial_text=" ".join(texts))
+
+        return {
+            "success": True,
+            "text": " ".join(texts),
+            "segments": segments,
+            "words": words,
+            "language": detected_language or "en",
+            "chunks": len(chunks)
+        }
+    finally:
+        transcription_progress["active"] = False
+
+
+async def apply_diarization(audio_data: np.ndarray, result: Dict) -> None:
+    """Label result["segments"] with speakers and prefix the text with them (left unchanged on failure)"""
+    if not diarization.DIARIZATION_AVAILABLE:
+        result["diarization_error"] = "Speaker diarization requires pyannote.audio"
+        logger.warning("⚠️ Diarization requested but pyannote.audio is not installed")
+        return
+
+    try:
+        loop = asyncio.get_event_loop()
+        logger.info("🗣️ Identifying speakers...")
+        turns = await loop.run_in_executor(None, diarization.speaker_turns, audio_data)
+        result["segments"] = diarization.assign_speakers(result.get("segments", []), turns)
+        result["text"] = diarization.labeled_text(result["segments"])
+        result["speakers"] = len({turn["speaker"] for turn in turns})
+        logger.info(f"✅ Found {result['speakers']} speaker(s)")
+    except Exception as e:
+        result["diarization_error"] = str(e)
+        logger.error(f"❌ Diarization failed: {e}")
+
+
+def offset_timestamps(item: Dict, offset: float) -> Dict:
+    """Shift a segment's (and its words') start/end from chunk time to recording time"""
+    shifted = dict(item, start=item["start"] + offset, end=item["end"] + offset)
+    if "words" in item:
+        shifted["words"] = [offset_timestamps(word, offset) for word in item["words"]]
+    return shifted
+
+
+# Continuous dictation tuning
+SEGMENT_POLL_SECONDS = 0.2
+SILENCE_RMS_THRESHOLD = 0.01
+MIN_SPEECH_SECONDS = 0.5
+MAX_SEGMENT_SECONDS = 20
+
+
+async def transcribe_segment(audio: np.ndarray, language: Optional[str]):
+    """Transcribe one dictation segment and append it to continuous_segments"""
+    loop = asyncio.get_event_loop()
+    if preprocessing.any:
+        audio = await loop.run_in_executor(None, audio_processing.preprocess, audio, preprocessing)
+    result = await loop.run_in_executor(None, transcribe_chunk, audio, language)
+    text = result["text"].strip() if result["success"] else ""
+    if text:
+        continuous_segments.append({"id": len(continuous_segments) + 1, "text": text})
+        logger.info(f"🗣️ Segment {len(continuous_segments)}: {text}")
+    elif not result["success"]:
+        logger.error(f"Segment transcription failed: {result.get('error')}")
+
+
+async def run_continuous_segmenter(language: Optional[str], silence_ms: int):
+    """
+    Pull audio while recording, cut a segment at each pause, and transcribe it right away.
+    On cancellation the remaining audio is left in the buffer for /stop to flush.
+    """
+    global continuous_buffer
+    loop = asyncio.get_event_loop()
+    sample_rate = 16000
+    silence_samples = int(sample_rate * silence_ms / 1000)
+
+    if not whisper_engine.is_loaded:
+        await loop.run_in_executor(None, whisper_engine.load_model)
+
+    while is_recording:
+        await asyncio.sleep(SEGMENT_POLL_SECONDS)
+
+        chunk = audio_capture.get_audio_chunk(min_duration=0)
+        if chunk is None:
+            continue
+        chunk = chunk.flatten()
+        continuous_audio.append(chunk)
+        continuous_buffer = np.concatenate([continuous_buffer, chunk])
+
+        # Segment ends after enough speech followed by a long enough pause (or when it gets too long)
+        buffered = len(continuous_buffer)
+        if buffered < MIN_SPEECH_SECONDS * sample_rate + silence_samples:
+            continue
+        tail = continuous_buffer[-silence_samples:]
+        tail_is_silent = float(np.sqrt(np.mean(tail * tail))) < SILENCE_RMS_THRESHOLD
+        if not tail_is_silent and buffered < MAX_SEGMENT_SECONDS * sample_rate:
+            continue
+
+        head = continuous_buffer[:-silence_samples] if tail_is_silent else continuous_buffer
+        continuous_buffer = np.zeros(0, dtype=np.float32)
+        if float(np.sqrt(np.mean(head * head))) < SILENCE_RMS_THRESHOLD:
+            continue  # Nothing but silence
+
+        await transcribe_segment(head, language)
+
+
+def get_user_dir():
+    """Where this user's recordings are kept: the data folder, or in portable mode, which everyone
+    using that copy shares, a folder per user inside it"""
+    data_dir = gpu_manager.get_data_dir()
+    if os.getenv('WHISPER4WINDOWS_DATA_DIR'):
+        data_dir = data_dir / 'users' / getpass.getuser()
+    data_dir.mkdir(parents=True, exist_ok=True)
+    return data_dir
+
+
+def session_suffix() -> str:
+    """Added to the names of files only one session may use at a time: "-2" in session 2"""
+    return f"-{SESSION}" if SESSION else ""
+
+
+def get_recovery_audio_path():
+    """Where /recovery/save keeps the last recording across a backend restart"""
+    return get_user_dir() / f"recovery_audio{session_suffix()}.npy"
+
+
+def get_spool_path():
+    """Where the recording in progress is copied as it is captured"""
+    return get_user_dir() / f"recording_spool{session_suffix()}.f32"
+
+
+def get_orphan_path():
+    """Where a recording cut off by a crash waits until the user transcribes or discards it"""
+    return get_user_dir() / "orphaned_recording.f32"
+
+
+def detect_orphaned_recording():
+    """A spool left over from the previous run means the app or backend died while recording. The
+    crashed run may have been in another session (before a reboot), so every session's spool is
+    looked at; one that another session is still writing can't be moved and is left alone."""
+    for spool in sorted(get_user_dir().glob("recording_spool*.f32")):
+        try:
+            # Anything under half a second is a stray keypress, not worth offering
+            if spool.stat().st_size < 16000 * 4 // 2:
+                spool.unlink(missing_ok=True)
+                continue
+            spool.replace(get_orphan_path())
+        except PermissionError:
+            continue  # Recording right now in another session
+        logger.info(f"🩹 Found a recording interrupted by a crash ({spool_duration(get_orphan_path()):.1f}s)")
+
+
+def spool_duration(path) -> float:
+    """Seconds of 16 kHz float32 audio in a spool file"""
+    return path.stat().st_size / 4 / 16000
+
+
+def load_recovery_audio():
+    """Pick up the recording saved before a restart so /retranscribe still works, then delete the file"""
+    global last_audio, last_audio_time
+    path = get_recovery_audio_path()
+    if not path.exists():
+        return
+    try:
+        last_audio = np.load(path)
+        last_audio_time = path.stat().st_mtime
+        logger.info(f"♻️ Restored {len(last_audio) / 16000:.1f}s of audio from before the restart")
+    except Exception as e:
+        logger.warning(f"⚠️ Could not restore recovery audio: {e}")
+    finally:
+        path.unlink(missing_ok=True)
+
+
+def device_name(device_index: Optional[int]) -> Optional[str]:
+    import sounddevice as sd
+    return None if device_index is None else sd.query_devices(device_index)["name"]
+
+
+def device_index_of(name: Optional[str]) -> Optional[int]:
+    """Index of the first input device called `name` (None for the default), or -1 if it isn't connected"""
+    import sounddevice as sd
+    if name is None:
+        return None
+    for i, dev in enumerate(sd.query_devices()):
+        if dev["name"] == name and dev["max_input_channels"] > 0:
+            return i
+    return -1
+
+
+def stop_prebuffer():
+    """Close the pre-buffer's microphone, dropping what it held"""
+    global prebuffer_capture
+    if prebuffer_capture is not None:
+        prebuffer_capture.stop_prebuffer()
+        prebuffer_capture = None
+
+
+def arm_prebuffer():
+    """Open the microphone into the pre-buffer, if it is on and nothing is recording"""
+    global prebuffer_capture
+    stop_prebuffer()
+    if prebuffer_request is None or not prebuffer_request.enabled or is_recording:
+        return
+    seconds = min(max(prebuffer_request.seconds, 0.5), 3.0)
+    for name in dict.fromkeys(prebuffer_devices):
+        device_index = device_index_of(name)
+        if device_index == -1:
+            continue
+        capture = prebuffer_request.capture()
+        if capture.start_prebuffer(device_index, seconds):
+            prebuffer_capture = capture
+            return
+    logger.warning("⚠️ Pre-buffer: no microphone could be opened")
+
+
+@contextmanager
+def prebuffer_closed():
+    """Free the microphone for a test recording, which may need it exclusively"""
+    stop_prebuffer()
+    try:
+        yield
+    finally:
+        arm_prebuffer()
+
+
+def uses_model(endpoint):
+    """Keep the model loaded while the endpoint runs; under "on_demand" it is unloaded afterwards"""
+    @functools.wraps(endpoint)
+    async def wrapper(*args, **kwargs):
+        global model_users, last_model_use
+        model_users += 1
+        try:
+            return await endpoint(*args, **kwargs)
+        finally:
+            model_users -= 1
+            last_model_use = time.time()
+            release_idle_model(after_use=True)
+    return wrapper
+
+
+# Spare memory (GB) to leave free when keeping another model loaded
+WARM_MEMORY_MARGIN_GB = 1.0
+
+
+def load_timed(engine: WhisperEngine) -> Optional[float]:
+    """Load the engine's model; returns how long it took, or None if it failed"""
+    start = time.time()
+    return time.time() - start if engine.load_model() else None
+
+
+def start_model_load():
+    """Load the recording's model in the background, so /stop doesn't wait for a cold start"""
+    global model_load_task
+    model_load_task = None
+    if whisper_engine.is_loaded:
+        return
+    logger.info("📥 Loading Whisper model while recording...")
+    model_load_task = asyncio.get_event_loop().run_in_executor(None, load_timed, whisper_engine)
+
+
+async def ensure_model_loaded() -> Tuple[bool, float]:
+    """
+    Wait for the load /start began (or load the model now if there was none); returns whether the
+    model is loaded and how long the load took (0 if it was already loaded)
+    """
+    global model_load_task
+    task, model_load_task = model_load_task, None
+    load_time = await task if task is not None else 0.0
+    if not whisper_engine.is_loaded:
+        load_time = await asyncio.get_event_loop().run_in_executor(None, load_timed, whisper_engine)
+    return load_time is not None and whisper_engine.is_loaded, load_time or 0.0
+
+
+def use_engine(model_size: str, device: str, compute_type: str = "auto"):
+    """
+    Make the engine for model_size/device/compute_type the current one. The one it replaces is kept
+    warm if it is in warm_keys and the new model still fits in free memory next to it, otherwise unloaded.
+    """
+    global whisper_engine
+
+    key = (model_size, device)
+    if whisper_engine is not None and (whisper_engine.model_size, whisper_engine._original_device) == key:
+        if whisper_engine._original_compute_type == compute_type:
+            return
+        # Same model in another precision: it has to be loaded again
+        whisper_engine.unload_model()
+        whisper_engine = None
+    previous = whisper_engine
+    whisper_engine = warm_engines.pop(key, None)
+    if whisper_engine is not None and whisper_engine._original_compute_type != compute_type:
+        whisper_engine.unload_model()
+        whisper_engine = None
+    if whisper_engine is not None:
+        logger.info(f"♨️ Switching to warm engine: {model_size} ({whisper_engine.device})")
+    else:
+        whisper_engine = WhisperEngine(model_size=model_size, device=device, compute_type=compute_type)
+        logger.info(f"✓ Whisper engine created (device: {whisper_engine.device}, compute type: {whisper_engine.compute_type})")
+
+    if previous is not None and previous.is_loaded:
+        previous_key = (previous.model_size, previous._original_device)
+        needed = 0.0 if whisper_engine.is_loaded else MODEL_MEMORY_GB.get(model_size, 2.0) + WARM_MEMORY_MARGIN_GB
+        free = available_memory_gb()
+        if previous_key in warm_keys and (free is None or free >= needed):
+            warm_engines[previous_key] = previous
+            logger.info(f"♨️ Keeping {previous.model_size} warm")
+        else:
+            previous.unload_model()
+
+
+def release_warm_engines(keep: Optional[List[Tuple[str, str]]] = None):
+    """Unload the warm engines, except those in `keep`"""
+    for key in list(warm_engines):
+        if keep is None or key not in keep:
+            warm_engines.pop(key).unload_model()
+
+
+def release_idle_model(after_use: bool = False):
+    """
+    Unload the model if the policy allows it and nothing is using it. Under "on_demand" that is
+    only right after a use, so a preloaded model waits for the recording it was loaded for.
+    """
+    if whisper_engine is None or not whisper_engine.is_loaded:
+        return
+    if model_users > 0 or is_recording or benchmark_running:
+        return
+    if model_policy == "keep" or (model_policy == "on_demand" and not after_use):
+        return
+    if model_policy == "idle" and time.time() - last_model_use < model_idle_minutes * 60:
+        return
+    whisper_engine.unload_model()
+    release_warm_engines()
+
+
+async def watch_idle_model():
+    """Unload the model once it has been idle longer than the policy allows"""
+    while True:
+        await asyncio.sleep(30)
+        try:
+            release_idle_model()
+        except Exception as e:
+            logger.error(f"❌ Failed to unload idle model: {e}")
+
+
+async def load_selected_model(model_size: str, device: str, compute_type: str = "auto") -> Dict:
+    """Load the given model (replacing the current one if it differs); never downloads one"""
+    global whisper_engine, last_model_use
+
+    if is_recording:
+        return {"status": "error", "message": "Recording in progress"}
+    if not is_downloaded(model_size):
+        return {"status": "error", "message": f"Model '{model_size}' is not downloaded yet"}
+
+    use_engine(model_size, device, compute_type)
+
+    loop = asyncio.get_event_loop()
+    start = time.time()
+    if not await loop.run_in_executor(None, whisper_engine.load_model):
+        return {"status": "error", "message": "Failed to load Whisper model"}
+    # Counts as a use, so an idle policy doesn't unload it straight away
+    last_model_use = time.time()
+    return {
+        "status": "success",
+        "model": model_size,
+        "device": whisper_engine.device,
+        "load_time": time.time() - start
+    }
 
 
 @asynccontextmanager
 async def lifespan(app: FastAPI):
     """Lifecycle manager for the FastAPI app"""
     logger.info("=" * 60)
-    logger.info("🚀 Whisper4Windows Backend Starting...")
+    logger.info(f"🚀 Whisper4Windows Backend Starting on port {PORT} (session {SESSION or 'unknown'})...")
     logger.info("=" * 60)
-    logger.info(f"Server: http://127.0.0.1:8000")
-    logger.info(f"API Docs: http://127.0.0.1:8000/docs")
-    logger.info(f"Health Check: http://127.0.0.1:8000/health")
+    logger.info(f"Server: http://127.0.0.1:{PORT}")
+    logger.info(f"API Docs: http://127.0.0.1:{PORT}/docs")
+    logger.info(f"Health Check: http://127.0.0.1:{PORT}/health")
     logger.info("=" * 60)
+
+    load_recovery_audio()
+    detect_orphaned_recording()
+    idle_watcher = asyncio.create_task(watch_idle_model())
     
     yield
     
     # Cleanup on shutdown
     logger.info("Shutting down...")
+    idle_watcher.cancel()
     global audio_capture
     if is_recording and audio_capture:
         try:
             audio_capture.stop_recording()
         except:
             pass
+    stop_prebuffer()
 
 
 # Create FastAPI app
@@ -127,50 +702,89 @@ async def health_check():
         status="ok",
         backend=backend,
         model=model,
-        recording=is_recording
+        recording=is_recording,
+        session=SESSION
     )
 
 
 @app.post("/start")
 async def start_recording(request: StartRequest):
-    """Start recording audio (no transcription until stop)"""
-    global audio_capture, whisper_engine, is_recording
+    """Start recording audio (no transcription until stop, unless continuous)"""
+    global audio_capture, whisper_engine, is_recording, warm_keys, prebuffer_capture
+    global continuous_task, continuous_segments, continuous_audio, continuous_buffer, continuous_language
+    global diarize_recording, retain_audio, preprocessing, recording_language, multilingual, multilingual_languages
     
     try:
         if is_recording:
             return {"status": "error", "message": "Already recording"}
+        if benchmark_running:
+            return {"status": "error", "message": "A benchmark is running"}
         
         logger.info(f"🎙️ Starting recording (will transcribe on STOP)")
         logger.info(f"📋 Requested device: {request.device}")
 
-        # Reuse existing engine if model/device match, otherwise create new one
-        if whisper_engine is not None and \
-           whisper_engine.model_size == request.model_size and \
-           whisper_engine._original_device == request.device:
-            logger.info(f"♻️ Reusing existing Whisper engine (device: {whisper_engine.device})")
-        else:
-            # Initialize new Whisper engine (model will load on transcription)
-            whisper_engine = WhisperEngine(
-                model_size=request.model_size,
-                device=request.device
-            )
-            logger.info(f"✓ Whisper engine created (device: {whisper_engine.device})")
+        # Reuse the current or a warm engine if model/device match, otherwise create one (model loads on transcription)
+        warm_keys = [(m.model_size, m.device) for m in request.warm_models]
+        release_warm_engines(keep=warm_keys)
+        use_engine(request.model_size, request.device, request.compute_type)
+        whisper_engine.decoding = request.decoding()
+        if whisper_engine.decoding != DecodingOptions():
+            logger.info(f"🎛️ Decoding: {whisper_engine.decoding}")
         
-        # Initialize audio capture
-        audio_capture = AudioCapture()
+        # Start audio stream with the first microphone that opens: the preferred ones, then the selected one
+        candidates = list(dict.fromkeys(request.device_indices + [request.device_index]))
+        # The pre-buffer's stream is taken over if it's open the same way on the first of them; otherwise
+        # the microphone is reopened
+        if (prebuffer_capture is not None and prebuffer_capture.device_index == candidates[0]
+                and prebuffer_request.capture_settings() == request.capture_settings()):
+            audio_capture = prebuffer_capture
+            prebuffer_capture = None
+            candidates = candidates[:1]
+        else:
+            stop_prebuffer()
+            audio_capture = request.capture()
         audio_capture.clear_queue()
 
-        # Start audio stream with selected device
-        device_index = request.device_index if request.device_index is not None else None
+        for device_index in candidates:
+            if audio_capture.start_recording(
+                device_index=device_index,
+                spool_path=get_spool_path() if request.retain_audio else None
+            ):
+                break
+            logger.warning(f"⚠️ Microphone {device_index if device_index is not None else 'default'} is unavailable")
+        else:
+            arm_prebuffer()
+            return {"status": "error", "message": "No microphone could be opened"}
         if device_index is not None:
             logger.info(f"🎤 Using microphone device index: {device_index}")
         else:
             logger.info(f"🎤 Using default microphone device")
-
-        audio_capture.start_recording(device_index=device_index)
+        start_model_load()
         await asyncio.sleep(0.1)
         
         is_recording = True
+        diarize_recording = request.diarize
+        recording_language = request.language
+        multilingual = request.multilingual
+        multilingual_languages = request.languages
+        if multilingual:
+            logger.info(f"🌍 Multilingual mode ({', '.join(multilingual_languages) or 'any language'})")
+        retain_audio = request.retain_audio
+        preprocessing = audio_processing.Options(
+            denoise=request.denoise,
+            high_pass=request.high_pass,
+            auto_gain=request.auto_gain
+        )
+        if preprocessing.any:
+            logger.info(f"🎚️ Pre-processing: {preprocessing}")
+
+        if request.continuous:
+            continuous_segments = []
+            continuous_audio = []
+            continuous_buffer = np.zeros(0, dtype=np.float32)
+            continuous_language = request.language
+            continuous_task = asyncio.create_task(run_continuous_segmenter(request.language, request.silence_ms))
+            logger.info("🗣️ Continuous dictation enabled")
         
         logger.info("✅ Recording started! Speak now...")
         
@@ -178,7 +792,9 @@ async def start_recording(request: StartRequest):
             "status": "started",
             "message": "Recording... Press Alt+T when done",
             "model": request.model_size,
-            "device": whisper_engine.device
+            "device": whisper_engine.device,
+            "device_index": device_index,
+            "capture_format": audio_capture.format
         }
         
     except Exception as e:
@@ -192,14 +808,16 @@ async def start_recording(request: StartRequest):
                 audio_capture.stop_recording()
             except:
                 pass
+        arm_prebuffer()
         
         return {"status": "error", "message": str(e)}
 
 
 @app.post("/stop")
-async def stop_recording():
+@uses_model
+async def stop_recording(request: Optional[StopRequest] = None):
     """Stop recording and transcribe everything"""
-    global is_recording, audio_capture, whisper_engine
+    global is_recording, audio_capture, whisper_engine, last_audio, last_audio_time, multilingual
     
     try:
         if not is_recording:
@@ -209,10 +827,25 @@ async def stop_recording():
         
         # Stop recording flag first
         is_recording = False
+        stop_start = time.time()
         
         # Stop audio capture and get ALL audio
         loop = asyncio.get_event_loop()
+
+        if continuous_task is not None:
+            return await finish_continuous_recording(loop)
+
+        # The model finishes loading (if it still is) while the audio is finalized
+        model_ready = asyncio.ensure_future(ensure_model_loaded())
         audio_data = await loop.run_in_executor(None, audio_capture.stop_recording)
+        arm_prebuffer()
+
+        language = recording_language
+        if request is not None and "language" in request.model_fields_set:
+            language = request.language
+            logger.info(f"🌐 Transcribing this recording as: {language or 'auto-detect'}")
+        if request is not None and request.multilingual is not None:
+            multilingual = request.multilingual
         
         if audio_data is None or len(audio_data) == 0:
             logger.warning("No audio captured")
@@ -223,27 +856,32 @@ async def stop_recording():
             }
         
         logger.info(f"📼 Captured {len(audio_data) / 16000:.1f} seconds of audio")
-        
-        # Load model if not loaded
-        if not whisper_engine.is_loaded:
-            logger.info("📥 Loading Whisper model...")
-            success = whisper_engine.load_model()
-            if not success:
-                return {"status": "error", "message": "Failed to load Whisper model"}
+        quality = audio_processing.analyze_levels(audio_data)
+        if quality["flag"] != "ok":
+            logger.warning(f"⚠️ Recording level: {quality}")
+        last_audio = audio_data if retain_audio else None
+        last_audio_time = time.time()
+        # The raw audio is kept, so a retry can process it again
+        if preprocessing.any:
+            audio_data = await loop.run_in_executor(None, audio_processing.preprocess, audio_data, preprocessing)
+        # Seconds after the stop spent on each stage, returned with the result
+        timings = {"finalize": time.time() - stop_start}
+
+        wait_start = time.time()
+        loaded, load_time = await model_ready
+        timings["model_wait"] = time.time() - wait_start
+        timings["model_load"] = load_time
+        if not loaded:
+            return {"status": "error", "message": "Failed to load Whisper model"}
         
         # Transcribe ALL audio at once with timing
-        import time
         logger.info("🎙️ Transcribing full recording...")
         transcription_start = time.time()
         
-        result = await loop.run_in_executor(
-            None,
-            whisper_engine.transcribe_audio,
-            audio_data,
-            "en"
-        )
+        result = await transcribe_with_progress(audio_data, language)
         
         transcription_time = time.time() - transcription_start
+        timings["transcription"] = transcription_time
         logger.info(f"⏱️ Transcription took: {transcription_time:.2f} seconds")
         
         if not result["success"]:
@@ -252,6 +890,13 @@ async def stop_recording():
                 "status": "error",
                 "message": result.get('error', 'Transcription failed')
             }
+
+        if diarize_recording:
+            diarization_start = time.time()
+            await apply_diarization(audio_data, result)
+            timings["diarization"] = time.time() - diarization_start
+        timings["total"] = time.time() - stop_start
+        logger.info("⏱️ Stages: " + ", ".join(f"{stage} {secs:.2f}s" for stage, secs in timings.items()))
         
         final_text = result["text"].strip()
         logger.info(f"✅ Transcription complete!")
@@ -260,11 +905,17 @@ async def stop_recording():
         return {
             "status": "success",
             "text": final_text,
-            "language": result.get("language", "en"),
+            "segments": result.get("segments", []),
+            "words": result.get("words", []),
+            "language": result.get("language", language),
             "duration": len(audio_data) / 16000,
             "transcription_time": transcription_time,
             "model": whisper_engine.model_size,
-            "device": whisper_engine.device  # Return actual device used
+            "device": whisper_engine.device,  # Return actual device used
+            "speakers": result.get("speakers"),
+            "diarization_error": result.get("diarization_error"),
+            "timings": timings,
+            "quality": quality
         }
         
     except Exception as e:
@@ -274,10 +925,268 @@ async def stop_recording():
         return {"status": "error", "message": str(e)}
 
 
+@app.get("/progress")
+async def get_progress():
+    """Progress of the current chunked transcription (chunks done / total, text so far)"""
+    return transcription_progress
+
+
+@app.post("/pause")
+async def pause_recording():
+    """Pause recording (audio captured while paused is discarded)"""
+    if not is_recording or audio_capture is None:
+        return {"status": "error", "message": "Not recording"}
+
+    audio_capture.pause()
+    return {"status": "paused"}
+
+
+@app.post("/resume")
+async def resume_recording():
+    """Resume a paused recording"""
+    if not is_recording or audio_capture is None:
+        return {"status": "error", "message": "Not recording"}
+
+    audio_capture.resume()
+    return {"status": "recording"}
+
+
+@app.post("/purge")
+async def purge_retained_audio(request: Optional[PurgeRequest] = None):
+    """Forget the audio kept for /retranscribe (only if older than older_than_secs, when given)"""
+    global last_audio
+    if request is not None and request.older_than_secs is not None:
+        cutoff = time.time() - request.older_than_secs
+        orphan = get_orphan_path()
+        if orphan.exists() and orphan.stat().st_mtime < cutoff:
+            orphan.unlink(missing_ok=True)
+            logger.info("🧹 Old interrupted recording purged")
+        if last_audio is None or last_audio_time >= cutoff:
+            return {"status": "success", "purged": False}
+    else:
+        get_orphan_path().unlink(missing_ok=True)
+    last_audio = None
+    get_recovery_audio_path().unlink(missing_ok=True)
+    logger.info("🧹 Retained audio purged")
+    return {"status": "success"}
+
+
+@app.get("/orphan")
+async def get_orphaned_recording():
+    """The recording interrupted by the last crash, if there is one"""
+    path = get_orphan_path()
+    if not path.exists():
+        return {"status": "success", "found": False}
+    return {
+        "status": "success",
+        "found": True,
+        "duration": spool_duration(path),
+        "recorded_at": path.stat().st_mtime
+    }
+
+
+@app.post("/orphan/restore")
+async def restore_orphaned_recording():
+    """Make the interrupted recording the one /retranscribe works on"""
+    global last_audio, last_audio_time
+    path = get_orphan_path()
+    if not path.exists():
+        return {"status": "error", "message": "No interrupted recording"}
+    if is_recording:
+        return {"status": "error", "message": "Recording in progress"}
+
+    last_audio = np.fromfile(path, dtype=np.float32)
+    last_audio_time = path.stat().st_mtime
+    path.unlink(missing_ok=True)
+    logger.info(f"🩹 Restored {len(last_audio) / 16000:.1f}s of interrupted recording")
+    return {"status": "success", "duration": len(last_audio) / 16000}
+
+
+@app.post("/orphan/discard")
+async def discard_orphaned_recording():
+    """Delete the interrupted recording"""
+    get_orphan_path().unlink(missing_ok=True)
+    logger.info("🗑️ Interrupted recording discarded")
+    return {"status": "success"}
+
+
+@app.post("/recovery/save")
+async def save_recovery_audio():
+    """Write the retained audio to disk before the app restarts a stuck backend (loaded again on startup)"""
+    if last_audio is None or len(last_audio) == 0:
+        return {"status": "success", "saved": False}
+
+    path = get_recovery_audio_path()
+    path.parent.mkdir(parents=True, exist_ok=True)
+    np.save(path, last_audio)
+    logger.info(f"💾 Saved {len(last_audio) / 16000:.1f}s of audio for recovery")
+    return {"status": "success", "saved": True}
+
+
+@app.post("/retranscribe")
+@uses_model
+async def retranscribe_last(request: RetranscribeRequest):
+    """Transcribe the last recording again (optionally with a different model)"""
+    if is_recording:
+        return {"status": "error", "message": "Recording in progress"}
+
+    if last_audio is None or len(last_audio) == 0:
+        return {"status": "error", "message": "No previous recording"}
+
+    logger.info(f"🔁 Re-transcribing last recording ({len(last_audio) / 16000:.1f}s)...")
+    return await transcribe_request(request, last_audio)
+
+
+@app.post("/transcribe_file")
+@uses_model
+async def transcribe_file(request: TranscribeFileRequest):
+    """Transcribe an audio or video file (any format FFmpeg reads)"""
+    if is_recording:
+        return {"status": "error", "message": "Recording in progress"}
+
+    try:
+        loop = asyncio.get_event_loop()
+        audio = await loop.run_in_executor(None, functools.partial(media.decode_track, request.path, request.audio_track))
+    except Exception as e:
+        logger.error(f"❌ Failed to read {request.path}: {e}")
+        return {"status": "error", "message": f"Couldn't read the file: {e}"}
+
+    if len(audio) == 0:
+        return {"status": "error", "message": "The file has no audio"}
+
+    logger.info(f"📂 Transcribing {request.path}, track {request.audio_track + 1} ({len(audio) / 16000:.1f}s)...")
+    return await transcribe_request(request, audio)
+
+
+@app.post("/file_tracks")
+async def file_tracks(request: FileTracksRequest):
+    """List the audio tracks of an audio or video file, so one can be picked for /transcribe_file"""
+    try:
+        loop = asyncio.get_event_loop()
+        tracks = await loop.run_in_executor(None, media.audio_tracks, request.path)
+    except Exception as e:
+        logger.error(f"❌ Failed to read {request.path}: {e}")
+        return {"status": "error", "message": f"Couldn't read the file: {e}"}
+    return {"status": "success", "tracks": tracks}
+
+
+async def transcribe_request(request: RetranscribeRequest, audio: np.ndarray):
+    """Transcribe audio that isn't being recorded, with the request's model and settings"""
+    global whisper_engine, multilingual, multilingual_languages
+
+    try:
+        multilingual = request.multilingual
+        multilingual_languages = request.languages
+        model_size = request.model_size or (whisper_engine.model_size if whisper_engine else "small")
+        device = request.device or (whisper_engine._original_device if whisper_engine else "auto")
+        compute_type = request.compute_type or (whisper_engine._original_compute_type if whisper_engine else "auto")
+
+        use_engine(model_size, device, compute_type)
+        whisper_engine.decoding = request.decoding()
+
+        if not whisper_engine.is_loaded:
+            logger.info("📥 Loading Whisper model...")
+            if not whisper_engine.load_model():
+                return {"status": "error", "message": "Failed to load Whisper model"}
+
+        transcription_start = time.time()
+
+        audio_data = audio
+        if preprocessing.any:
+            loop = asyncio.get_event_loop()
+            audio_data = await loop.run_in_executor(None, audio_processing.preprocess, audio_data, preprocessing)
+        result = await transcribe_with_progress(audio_data, request.language)
+
+        transcription_time = time.time() - transcription_start
+        logger.info(f"⏱️ Transcription took: {transcription_time:.2f} seconds")
+
+        if not result["success"]:
+            logger.error(f"Transcription failed: {result.get('error')}")
+            return {
+                "status": "error",
+                "message": result.get('error', 'Transcription failed')
+            }
+
+        if request.diarize:
+            await apply_diarization(audio_data, result)
+
+        final_text = result["text"].strip()
+        logger.info(f"📝 Transcribed text: {final_text[:100]}")
+
+        return {
+            "status": "success",
+            "text": final_text,
+            "segments": result.get("segments", []),
+            "words": result.get("words", []),
+            "language": result.get("language", request.language),
+            "duration": len(audio) / 16000,
+            "transcription_time": transcription_time,
+            "model": whisper_engine.model_size,
+            "device": whisper_engine.device,
+            "speakers": result.get("speakers"),
+            "diarization_error": result.get("diarization_error")
+        }
+
+    except Exception as e:
+        logger.error(f"❌ Failed to transcribe: {e}")
+        import traceback
+        logger.error(traceback.format_exc())
+        return {"status": "error", "message": str(e)}
+
+
+async def finish_continuous_recording(loop):
+    """Let the segmenter finish, transcribe whatever is left, and return every segment"""
+    global continuous_task, continuous_buffer, last_audio, last_audio_time
+
+    # The segmenter exits on its own once is_recording is False (after any in-flight segment)
+    await continuous_task
+    continuous_task = None
+
+    remaining = await loop.run_in_executor(None, audio_capture.stop_recording)
+    arm_prebuffer()
+    if remaining is not None and len(remaining) > 0:
+        remaining = remaining.flatten()
+        continuous_audio.append(remaining)
+        continuous_buffer = np.concatenate([continuous_buffer, remaining])
+
+    if len(continuous_buffer) > 0 and float(np.sqrt(np.mean(continuous_buffer * continuous_buffer))) >= SILENCE_RMS_THRESHOLD:
+        await transcribe_segment(continuous_buffer, continuous_language)
+    continuous_buffer = np.zeros(0, dtype=np.float32)
+
+    recorded = np.concatenate(continuous_audio) if continuous_audio else None
+    quality = audio_processing.analyze_levels(recorded) if recorded is not None else None
+    if quality is not None and quality["flag"] != "ok":
+        logger.warning(f"⚠️ Recording level: {quality}")
+    last_audio = recorded if retain_audio else None
+    last_audio_time = time.time()
+    continuous_audio.clear()
+
+    logger.info(f"✅ Continuous dictation finished ({len(continuous_segments)} segments)")
+    return {
+        "status": "success",
+        "text": " ".join(segment["text"] for segment in continuous_segments),
+        "segments": continuous_segments,
+        "continuous": True,
+        "duration": len(recorded) / 16000 if recorded is not None else 0,
+        "model": whisper_engine.model_size,
+        "device": whisper_engine.device,
+        "quality": quality
+    }
+
+
+@app.get("/segments")
+async def get_segments(after: int = 0):
+    """Continuous dictation segments with id > after"""
+    return {
+        "segments": [segment for segment in continuous_segments if segment["id"] > after],
+        "recording": is_recording
+    }
+
+
 @app.post("/cancel")
 async def cancel_recording():
     """Cancel recording without transcribing"""
-    global is_recording, audio_capture
+    global is_recording, audio_capture, continuous_task
 
     try:
         if not is_recording:
@@ -288,10 +1197,16 @@ async def cancel_recording():
         # Stop recording flag
         is_recording = False
 
+        # Drop continuous dictation segments still in progress
+        if continuous_task is not None:
+            continuous_task.cancel()
+            continuous_task = None
+
         # Stop audio capture without transcribing
         if audio_capture:
             loop = asyncio.get_event_loop()
             await loop.run_in_executor(None, audio_capture.stop_recording)
+        arm_prebuffer()
 
         logger.info("✅ Recording canceled")
 
@@ -305,53 +1220,174 @@ async def cancel_recording():
         return {"status": "error", "message": str(e)}
 
 
-@app.get("/audio_level")
-async def get_audio_level():
-    """Get current audio input level (0.0 to 1.0)"""
-    global is_recording, audio_capture
+@app.post("/mic_test")
+async def mic_test(request: MicTestRequest):
+    """Record a few seconds and return it as WAV before and after pre-processing (base64)"""
+    import base64
 
     try:
-        if not is_recording or not audio_capture:
-            return {"level": 0.0, "recording": False}
+        if is_recording:
+            return {"status": "error", "message": "Recording in progress"}
 
-        # Peek at recent audio without removing from queue
-        if audio_capture.audio_queue.empty():
-            return {"level": 0.0, "recording": True}
+        seconds = min(max(request.seconds, 1.0), 15.0)
+        loop = asyncio.get_event_loop()
+        capture = request.capture()
+        with prebuffer_closed():
+            recording = await loop.run_in_executor(None, capture.record_for_duration, seconds, request.device_index)
+        if recording is None or len(recording) == 0:
+            return {"status": "error", "message": "No audio recorded"}
+
+        options = audio_processing.Options(
+            denoise=request.denoise,
+            high_pass=request.high_pass,
+            auto_gain=request.auto_gain
+        )
+        processed = await loop.run_in_executor(None, audio_processing.preprocess, recording, options)
+        logger.info(f"🎧 Mic test recorded ({seconds:.1f}s, {options})")
 
-        # Get queue size to know how much audio we have
-        queue_size = audio_capture.audio_queue.qsize()
+        return {
+            "status": "success",
+            "original": base64.b64encode(audio_processing.to_wav_bytes(recording)).decode("ascii"),
+            "processed": base64.b64encode(audio_processing.to_wav_bytes(processed)).decode("ascii")
+        }
 
-        # Sample a few recent chunks to calculate level
-        chunks = []
-        temp_chunks = []
+    except Exception as e:
+        logger.error(f"❌ Mic test failed: {e}")
+        return {"status": "error", "message": str(e)}
 
-        # Get up to 5 most recent chunks
-        for _ in range(min(5, queue_size)):
-            try:
-                chunk = audio_capture.audio_queue.get_nowait()
-                temp_chunks.append(chunk)
-                chunks.append(chunk)
-            except:
-                break
 
-        # Put them back in the queue
-        for chunk in temp_chunks:
-            audio_capture.audio_queue.put(chunk)
+@app.post("/self_test/model")
+@uses_model
+async def self_test_model(request: SelfTestModelRequest):
+    """Load the selected model (kept loaded for the next recording); never downloads one"""
+    try:
+        return await load_selected_model(request.model_size, request.device)
+
+    except Exception as e:
+        logger.error(f"❌ Model self-test failed: {e}")
+        return {"status": "error", "message": str(e)}
+
+
+@app.post("/model_policy")
+async def set_model_policy(request: ModelPolicyRequest):
+    """Set when the model is unloaded to free memory"""
+    global model_policy, model_idle_minutes
+
+    if request.policy not in ("keep", "idle", "on_demand"):
+        return {"status": "error", "message": f"Unknown policy '{request.policy}'"}
+    model_policy = request.policy
+    model_idle_minutes = max(request.idle_minutes, 1)
+    logger.info(f"💾 Model memory policy: {model_policy}" + (f" ({model_idle_minutes} min)" if model_policy == "idle" else ""))
+    release_idle_model()
+    return {"status": "success"}
+
+
+@app.post("/prebuffer")
+async def set_prebuffer(request: PrebufferRequest):
+    """Turn the pre-buffer on or off; while on, the microphone stays open between recordings"""
+    global prebuffer_request, prebuffer_devices
+
+    try:
+        prebuffer_request = request
+        prebuffer_devices = [device_name(i) for i in dict.fromkeys(request.device_indices + [request.device_index])]
+        if request.enabled:
+            logger.info(f"⏪ Pre-buffer on ({request.seconds:g}s)")
+        elif prebuffer_capture is not None:
+            logger.info("⏪ Pre-buffer off")
+        arm_prebuffer()
+        return {"status": "success", "active": prebuffer_capture is not None}
+    except Exception as e:
+        logger.error(f"❌ Failed to set the pre-buffer: {e}")
+        return {"status": "error", "message": str(e)}
+
+
+@app.post("/preload")
+async def preload_model(request: PreloadRequest):
+    """Load the model now so the next recording doesn't wait for it"""
+    try:
+        logger.info(f"🔥 Preloading {request.model_size} ({request.device})")
+        return await load_selected_model(request.model_size, request.device, request.compute_type)
+
+    except Exception as e:
+        logger.error(f"❌ Preload failed: {e}")
+        return {"status": "error", "message": str(e)}
+
+
+@app.post("/self_test/mic")
+async def self_test_mic(request: SelfTestMicRequest):
+    """Record briefly and report how loud it was"""
+    try:
+        if is_recording:
+            return {"status": "error", "message": "Recording in progress"}
+
+        seconds = min(max(request.seconds, 0.5), 5.0)
+        loop = asyncio.get_event_loop()
+        capture = request.capture()
+        with prebuffer_closed():
+            recording = await loop.run_in_executor(None, capture.record_for_duration, seconds, request.device_index)
+        if recording is None or len(recording) == 0:
+            return {"status": "error", "message": "No audio recorded"}
+
+        return {
+            "status": "success",
+            "duration": len(recording) / 16000,
+            "peak": float(np.abs(recording).max()),
+            "format": capture.format
+        }
+
+    except Exception as e:
+        logger.error(f"❌ Microphone self-test failed: {e}")
+        return {"status": "error", "message": str(e)}
+
 
-        if not chunks:
-            return {"level": 0.0, "recording": True}
+@app.post("/benchmark")
+async def run_benchmark(request: BenchmarkRequest):
+    """Time each downloaded model on CPU and GPU with the sample clip and recommend one"""
+    global whisper_engine, benchmark_running
+
+    try:
+        if is_recording:
+            return {"status": "error", "message": "Recording in progress"}
+        if benchmark_running:
+            return {"status": "error", "message": "A benchmark is already running"}
+        if not list_downloaded_models():
+            return {"status": "error", "message": "Download a model first"}
+
+        benchmark_running = True
+        # Unload the current models so they don't compete for memory; the next recording reloads them
+        whisper_engine = None
+        release_warm_engines()
+        loop = asyncio.get_event_loop()
+        result = await loop.run_in_executor(None, benchmark.run, request.models or None)
+        return {"status": "success", **result}
+
+    except Exception as e:
+        logger.error(f"❌ Benchmark failed: {e}")
+        return {"status": "error", "message": str(e)}
+
+    finally:
+        benchmark_running = False
 
-        # Calculate RMS level
-        audio_data = np.concatenate(chunks, axis=0)
-        rms = np.sqrt(np.mean(audio_data ** 2))
+
+@app.get("/audio_level")
+async def get_audio_level():
+    """Get current audio input level (0.0 to 1.0)"""
+    global is_recording, audio_capture
+
+    try:
+        if not is_recording or not audio_capture:
+            return {"level": 0.0, "recording": False}
+
+        # Read from the capture callback rather than the queue, which the transcription owns
+        if audio_capture.is_paused():
+            return {"level": 0.0, "recording": True, "paused": True}
 
         # Normalize to 0-1 range (typical speech is around 0.1-0.3 RMS)
-        normalized_level = min(1.0, rms * 3.0)
+        normalized_level = min(1.0, audio_capture.input_level * 3.0)
 
         return {
             "level": float(normalized_level),
-            "recording": True,
-            "queue_size": queue_size
+            "recording": True
         }
 
     except Exception as e:
@@ -359,6 +1395,14 @@ async def get_audio_level():
         return {"level": 0.0, "recording": False, "error": str(e)}
 
 
+@app.get("/mic_status")
+async def get_mic_status():
+    """Loudest input sample since recording started, to tell a muted or dead mic from a quiet room"""
+    if not is_recording or not audio_capture:
+        return {"recording": False, "peak": 0.0, "device_lost": False}
+    return {"recording": True, "peak": audio_capture.peak_level, "device_lost": audio_capture.device_lost}
+
+
 # Removed /get_live_chunk endpoint - using simple record/stop flow now
 
 
@@ -367,6 +1411,12 @@ async def list_devices():
     """List available audio devices"""
     try:
         import sounddevice as sd
+        # Pick up microphones connected or disconnected since the backend started (not while one is
+        # open, unless it's the pre-buffer's and it went away)
+        if not is_recording and (prebuffer_capture is None or prebuffer_capture.device_lost):
+            stop_prebuffer()
+            refresh_devices()
+            arm_prebuffer()
         devices = sd.query_devices()
 
         input_devices = []
@@ -381,7 +1431,7 @@ async def list_devices():
             }
 
             if dev["max_input_channels"] > 0:
-                input_devices.append(device_info)
+                input_devices.append({**device_info, "default": i == sd.default.device[0]})
             if dev["max_output_channels"] > 0:
                 output_devices.append(device_info)
 
@@ -401,6 +1451,32 @@ async def list_devices():
         }
 
 
+@app.get("/models")
+async def list_models():
+    """List downloaded Whisper models, and every model on offer with its download details"""
+    try:
+        return {"success": True, "models": list_downloaded_models(), "catalog": model_catalog()}
+    except Exception as e:
+        logger.error(f"Error listing models: {e}")
+        return {"success": False, "error": str(e), "models": [], "catalog": []}
+
+
+@app.post("/models/validate")
+async def validate_model(request: ValidateModelRequest):
+    """Check a custom model before it is added; only its vocabulary is downloaded"""
+    source = request.source.strip()
+    try:
+        loop = asyncio.get_event_loop()
+        info = await loop.run_in_executor(None, validate_custom_model, source)
+        logger.info(f"🧩 Custom model {source} is valid (English-only: {info['english_only']})")
+        return {"status": "success", "source": source, **info}
+    except ValueError as e:
+        return {"status": "error", "message": str(e)}
+    except Exception as e:
+        logger.error(f"❌ Failed to validate custom model {source}: {e}")
+        return {"status": "error", "message": str(e)}
+
+
 @app.get("/gpu/info")
 async def get_gpu_info():
     """Get GPU and library installation status"""
@@ -484,6 +1560,6 @@ if __name__ == "__main__":
     uvicorn.run(
         app,
         host="127.0.0.1",
-        port=8000,
+        port=PORT,
         log_level="info"
     )
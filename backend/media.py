@@ -0,0 +1,60 @@
+"""
+Media Files Module
+Reading the audio out of audio and video files (anything FFmpeg reads) with PyAV, the FFmpeg
+bindings faster-whisper depends on, so no separate FFmpeg install is needed.
+Unlike faster-whisper's decode_audio, any audio track can be picked, not only the first.
+"""
+
+import logging
+import numpy as np
+
+import av
+
+logger = logging.getLogger(__name__)
+
+
+def audio_tracks(path: str) -> list:
+    """The file's audio tracks, in order, with what the container says about them"""
+    with av.open(path, mode="r", metadata_errors="ignore") as container:
+        return [
+            {
+                "index": index,
+                "codec": stream.codec_context.name,
+                "channels": stream.channels,
+                "language": stream.metadata.get("language"),
+                "title": stream.metadata.get("title") or stream.metadata.get("handler_name"),
+            }
+            for index, stream in enumerate(container.streams.audio)
+        ]
+
+
+def decode_track(path: str, track: int = 0, sampling_rate: int = 16000) -> np.ndarray:
+    """Audio track `track` (0 = first) as mono float32 samples at `sampling_rate`"""
+    resampler = av.audio.resampler.AudioResampler(format="s16", layout="mono", rate=sampling_rate)
+    chunks = []
+
+    with av.open(path, mode="r", metadata_errors="ignore") as container:
+        streams = container.streams.audio
+        if track >= len(streams):
+            raise ValueError(f"The file has {len(streams)} audio track(s), not {track + 1}")
+
+        frames = container.decode(streams[track])
+        while True:
+            try:
+                frame = next(frames)
+            except StopIteration:
+                break
+            except av.error.InvalidDataError:
+                # A damaged packet; skip it like FFmpeg itself would
+                continue
+            for resampled in resampler.resample(frame):
+                chunks.append(resampled.to_ndarray())
+
+        # Samples the resampler still holds
+        for resampled in resampler.resample(None):
+            chunks.append(resampled.to_ndarray())
+
+    if not chunks:
+        return np.zeros(0, dtype=np.float32)
+    audio = np.concatenate(chunks, axis=1).reshape(-1)
+    return audio.astype(np.float32) / 32768.0
@@ -3,15 +3,25 @@ Whisper Speech-to-Text Engine
 Handles model loading and transcription using faster-whisper
 """
 
+import gc
+import json
 import logging
 import os
+import re
 import sys
-from typing import Optional, Dict, List
+import threading
+from dataclasses import dataclass
+from typing import Optional, Dict, List, Tuple
 import numpy as np
 from pathlib import Path
 
+from gpu_manager import get_data_dir
+
 logger = logging.getLogger(__name__)
 
+# In multilingual mode, segments shorter than this keep the recording's language (detection on them is unreliable)
+MIN_DETECTION_SECONDS = 1.0
+
 # Add CUDA library paths for bundled executables
 def setup_cuda_paths():
     """Add CUDA library paths to PATH for all environments"""
@@ -42,9 +52,8 @@ def setup_cuda_paths():
             Path(sys._MEIPASS) / "nvidia" / "cusparse" / "bin",
         ])
 
-    # Add downloaded GPU libraries from AppData (for optional GPU install)
-    appdata = Path(os.getenv('APPDATA') or os.path.expanduser('~'))
-    gpu_libs_dir = appdata / 'Whisper4Windows' / 'gpu_libs'
+    # Add downloaded GPU libraries from the app data directory (for optional GPU install)
+    gpu_libs_dir = get_data_dir() / 'gpu_libs'
     if gpu_libs_dir.exists():
         logger.info(f"   Found downloaded GPU libraries: {gpu_libs_dir}")
         cuda_paths.extend([
@@ -82,7 +91,7 @@ def setup_cuda_paths():
         # Skip system cuDNN to avoid version conflicts with bundled libraries
         bundled_cudnn_available = any(
             (Path(sys._MEIPASS) / "nvidia" / "cudnn" / "bin").exists() if getattr(sys, 'frozen', False) else False,
-            (appdata / 'Whisper4Windows' / 'gpu_libs' / 'nvidia' / 'cudnn' / 'bin').exists()
+            (gpu_libs_dir / 'nvidia' / 'cudnn' / 'bin').exists()
         )
         
         if not bundled_cudnn_available:
@@ -150,11 +159,14 @@ setup_cuda_paths()
 
 # Get the appropriate models directory
 def get_models_dir() -> Path:
-    """Get the models directory, using AppData for bundled apps"""
+    """Get the models directory, using the app data directory for bundled apps"""
+    # Set by the app when an administrator's policy points at shared models (possibly read-only)
+    policy_dir = os.environ.get('WHISPER4WINDOWS_MODELS_DIR')
+    if policy_dir:
+        return Path(policy_dir)
     if getattr(sys, 'frozen', False):
-        # Running as bundled executable
-        appdata = Path(os.getenv('APPDATA') or os.path.expanduser('~'))
-        models_dir = appdata / 'Whisper4Windows' / 'models'
+        # Running as bundled executable (AppData, or next to the app in portable mode)
+        models_dir = get_data_dir() / 'models'
     else:
         # Running from source
         models_dir = Path("models")
@@ -162,6 +174,155 @@ def get_models_dir() -> Path:
     models_dir.mkdir(parents=True, exist_ok=True)
     return models_dir
 
+# Models the app offers: Hugging Face repo (CTranslate2 weights), download size, and whether it
+# only transcribes English. Distil-Whisper models are distilled from Whisper on English speech.
+MODEL_CATALOG = {
+    "tiny": {"repo": "Systran/faster-whisper-tiny", "size_mb": 75, "english_only": False},
+    "base": {"repo": "Systran/faster-whisper-base", "size_mb": 145, "english_only": False},
+    "small": {"repo": "Systran/faster-whisper-small", "size_mb": 484, "english_only": False},
+    "medium": {"repo": "Systran/faster-whisper-medium", "size_mb": 1530, "english_only": False},
+    "large-v3": {"repo": "Systran/faster-whisper-large-v3", "size_mb": 3090, "english_only": False},
+    "large-v3-turbo": {"repo": "mobiuslabsgmbh/faster-whisper-large-v3-turbo", "size_mb": 1620, "english_only": False},
+    "distil-large-v3": {"repo": "Systran/faster-distil-whisper-large-v3", "size_mb": 1510, "english_only": True},
+    "distil-medium.en": {"repo": "Systran/faster-distil-whisper-medium.en", "size_mb": 789, "english_only": True},
+    "distil-small.en": {"repo": "Systran/faster-distil-whisper-small.en", "size_mb": 336, "english_only": True}
+}
+
+def is_custom_model(model_size: str) -> bool:
+    """Custom models are given as a local folder or a Hugging Face ID ("org/model") instead of a name"""
+    return model_size not in MODEL_CATALOG and ("/" in model_size or "\\" in model_size)
+
+def model_repo(model_size: str) -> str:
+    """
+    What faster-whisper loads: the Hugging Face repo for offered models, the folder or ID itself for
+    custom ones (unknown names use the Systran naming)
+    """
+    entry = MODEL_CATALOG.get(model_size)
+    if entry:
+        return entry["repo"]
+    return model_size if is_custom_model(model_size) else f"Systran/faster-whisper-{model_size}"
+
+def is_english_only(model_size: str) -> bool:
+    return MODEL_CATALOG.get(model_size, {}).get("english_only", model_size.endswith(".en"))
+
+def model_cache_dir(model_size: str) -> Path:
+    """Where the Hugging Face cache keeps the model's files"""
+    return get_models_dir() / f"models--{model_repo(model_size).replace('/', '--')}"
+
+def is_downloaded(model_size: str) -> bool:
+    if is_custom_model(model_size) and Path(model_size).is_dir():
+        return (Path(model_size) / "model.bin").exists()
+    # A snapshot may hold only the vocabulary fetched while validating a custom model
+    snapshot_dir = model_cache_dir(model_size) / "snapshots"
+    return snapshot_dir.exists() and any(snapshot_dir.glob("*/model.bin"))
+
+def list_downloaded_models() -> List[str]:
+    """Models with a downloaded snapshot in the models directory"""
+    downloaded = {name for name in MODEL_CATALOG if is_downloaded(name)}
+    # Other Systran models downloaded by name (e.g. by an older version)
+    prefix = "models--Systran--faster-whisper-"
+    for model_path in get_models_dir().glob(f"{prefix}*"):
+        snapshot_dir = model_path / "snapshots"
+        if snapshot_dir.exists() and any(snapshot_dir.iterdir()):
+            downloaded.add(model_path.name[len(prefix):])
+    return sorted(downloaded)
+
+# Whisper vocabularies with language tokens have at least this many tokens (English-only ones have fewer)
+MULTILINGUAL_VOCAB_SIZE = 51865
+VOCABULARY_FILES = ("vocabulary.json", "vocabulary.txt", "tokenizer.json")
+
+def vocabulary_size(path: Path) -> int:
+    """Tokens in a CTranslate2 vocabulary file or a Hugging Face tokenizer.json"""
+    if path.suffix == ".txt":
+        return len(path.read_text(encoding="utf-8").splitlines())
+    data = json.loads(path.read_text(encoding="utf-8"))
+    if path.name == "tokenizer.json":
+        ids = set(data["model"]["vocab"].values()) | {token["id"] for token in data.get("added_tokens", [])}
+        return len(ids)
+    return len(data)
+
+def validate_custom_model(source: str) -> Dict:
+    """
+    Check that a local folder or Hugging Face ID holds a CTranslate2 Whisper model, without
+    downloading the weights; raises ValueError with a message for the user otherwise
+    """
+    path = Path(source)
+    if path.is_dir():
+        if not (path / "model.bin").exists():
+            raise ValueError("The folder has no model.bin; convert the model to CTranslate2 with ct2-transformers-converter")
+        vocabulary = next((path / name for name in VOCABULARY_FILES if (path / name).exists()), None)
+    elif re.fullmatch(r"[\w.-]+/[\w.-]+", source):
+        from huggingface_hub import hf_hub_download, list_repo_files
+        try:
+            files = list_repo_files(source)
+        except Exception as e:
+            raise ValueError(f"Couldn't find '{source}' on Hugging Face: {e}")
+        if "model.bin" not in files:
+            raise ValueError(f"'{source}' has no model.bin; it must be a CTranslate2 (faster-whisper) model")
+        name = next((name for name in VOCABULARY_FILES if name in files), None)
+        vocabulary = Path(hf_hub_download(source, name, cache_dir=str(get_models_dir()))) if name else None
+    else:
+        raise ValueError("Enter a folder that exists or a Hugging Face model ID like org/model")
+
+    if vocabulary is None:
+        raise ValueError("The model has no vocabulary (vocabulary.json or tokenizer.json)")
+    return {
+        "english_only": vocabulary_size(vocabulary) < MULTILINGUAL_VOCAB_SIZE,
+        "downloaded": is_downloaded(source)
+    }
+
+def model_catalog() -> List[Dict]:
+    """Every offered model, with its download URL and whether it is downloaded"""
+    return [
+        {
+            "id": name,
+            "repo": entry["repo"],
+            "url": f"https://huggingface.co/{entry['repo']}",
+            "size_mb": entry["size_mb"],
+            "english_only": entry["english_only"],
+            "downloaded": is_downloaded(name)
+        }
+        for name, entry in MODEL_CATALOG.items()
+    ]
+
+# Rough memory a loaded model takes (GB), to tell whether another one fits next to it
+MODEL_MEMORY_GB = {
+    "tiny": 0.3,
+    "base": 0.5,
+    "small": 1.0,
+    "medium": 2.5,
+    "large-v3": 4.5,
+    "large-v3-turbo": 2.5,
+    "distil-large-v3": 2.5,
+    "distil-medium.en": 1.3,
+    "distil-small.en": 0.6
+}
+
+def available_memory_gb() -> Optional[float]:
+    """Free physical memory in GB (None if it can't be read)"""
+    if sys.platform != "win32":
+        return None
+    import ctypes
+
+    class MEMORYSTATUSEX(ctypes.Structure):
+        _fields_ = [
+            ("dwLength", ctypes.c_ulong),
+            ("dwMemoryLoad", ctypes.c_ulong),
+            ("ullTotalPhys", ctypes.c_ulonglong),
+            ("ullAvailPhys", ctypes.c_ulonglong),
+            ("ullTotalPageFile", ctypes.c_ulonglong),
+            ("ullAvailPageFile", ctypes.c_ulonglong),
+            ("ullTotalVirtual", ctypes.c_ulonglong),
+            ("ullAvailVirtual", ctypes.c_ulonglong),
+            ("ullAvailExtendedVirtual", ctypes.c_ulonglong)
+        ]
+
+    status = MEMORYSTATUSEX()
+    status.dwLength = ctypes.sizeof(MEMORYSTATUSEX)
+    if not ctypes.windll.kernel32.GlobalMemoryStatusEx(ctypes.byref(status)):
+        return None
+    return status.ullAvailPhys / 1024 ** 3
+
 # Try to import faster-whisper
 try:
     from faster_whisper import WhisperModel
@@ -173,6 +334,15 @@ except ImportError as e:
     WhisperModel = None
 
 
+@dataclass
+class DecodingOptions:
+    """Decoding parameters for faster-whisper; the defaults favour speed for dictation"""
+    beam_size: int = 1  # 1 = greedy decoding
+    temperature: float = 0.0  # 0 = deterministic
+    condition_on_previous_text: bool = False  # Feed earlier text to the model as context (can loop on long audio)
+    no_speech_threshold: float = 0.6  # Segments more likely than this to be silence are skipped
+
+
 class WhisperEngine:
     """Whisper speech-to-text engine"""
     
@@ -193,6 +363,10 @@ class WhisperEngine:
         self.model_size = model_size
         self.device = device
         self.compute_type = compute_type
+        self._original_compute_type = compute_type  # Requested compute type, before detection and fallbacks
+        self.decoding = DecodingOptions()
+        # A load started with the recording may still be running when transcription needs the model
+        self._load_lock = threading.Lock()
         self.model = None
         self.is_loaded = False
         self._cuda_detected = False
@@ -250,26 +424,25 @@ class WhisperEngine:
         if model_size is None:
             model_size = self.model_size
             
-        models_dir = get_models_dir()
-        model_path = models_dir / f"models--Systran--faster-whisper-{model_size}"
-        
-        # Check if model directory exists and has required files
-        if model_path.exists():
-            snapshot_dir = model_path / "snapshots"
-            if snapshot_dir.exists() and any(snapshot_dir.iterdir()):
-                logger.info(f"✅ Model '{model_size}' is already downloaded")
-                return True
+        if is_downloaded(model_size):
+            logger.info(f"✅ Model '{model_size}' is already downloaded")
+            return True
         
         logger.warning(f"⚠️ Model '{model_size}' is not downloaded")
         return False
     
     def load_model(self) -> bool:
         """
-        Load the Whisper model with automatic GPU compute type fallback, then CPU fallback
+        Load the Whisper model with automatic GPU compute type fallback, then CPU fallback.
+        Concurrent calls wait for the load in progress instead of loading the model twice.
 
         Returns:
             True if successful, False otherwise
         """
+        with self._load_lock:
+            return self._load_model()
+
+    def _load_model(self) -> bool:
         if not WHISPER_AVAILABLE:
             logger.error("❌ faster-whisper is not installed!")
             return False
@@ -291,14 +464,16 @@ class WhisperEngine:
                 compute_types_to_try = self._get_cuda_compute_type_fallbacks()
 
                 # If user specified a specific compute type, try that first
-                if self.compute_type != "auto" and self.compute_type not in compute_types_to_try:
-                    compute_types_to_try.insert(0, self.compute_type)
+                if self._original_compute_type != "auto":
+                    if self._original_compute_type in compute_types_to_try:
+                        compute_types_to_try.remove(self._original_compute_type)
+                    compute_types_to_try.insert(0, self._original_compute_type)
 
                 for compute_type in compute_types_to_try:
                     try:
                         logger.info(f"🔄 Trying CUDA with compute type: {compute_type}")
                         self.model = WhisperModel(
-                            self.model_size,
+                            model_repo(self.model_size),
                             device=self.device,
                             compute_type=compute_type,
                             download_root=str(models_dir)
@@ -324,7 +499,7 @@ class WhisperEngine:
             # Try loading with current device/compute_type (either CPU from start, or CPU fallback)
             try:
                 self.model = WhisperModel(
-                    self.model_size,
+                    model_repo(self.model_size),
                     device=self.device,
                     compute_type=self.compute_type,
                     download_root=str(models_dir)
@@ -347,6 +522,21 @@ class WhisperEngine:
             logger.error(traceback.format_exc())
             return False
     
+    def english_only(self) -> bool:
+        """Whether the model only transcribes English (known once a custom model is loaded)"""
+        if self.model is None or self.model_size in MODEL_CATALOG:
+            return is_english_only(self.model_size)
+        return not self.model.model.is_multilingual
+
+    def unload_model(self):
+        """Free the model's memory; it is loaded again on next use"""
+        if not self.is_loaded:
+            return
+        self.model = None
+        self.is_loaded = False
+        gc.collect()
+        logger.info(f"💤 Model unloaded: {self.model_size}")
+
     def transcribe_audio(
         self,
         audio_data: np.ndarray,
@@ -373,6 +563,11 @@ class WhisperEngine:
                     "text": ""
                 }
 
+        if language != "en" and self.english_only():
+            # English-only models can't detect or transcribe other languages
+            logger.info(f"🇬🇧 {self.model_size} is English-only; transcribing as English")
+            language = "en"
+
         try:
             logger.info(f"🎙️ Transcribing audio...")
             logger.info(f"   Audio shape: {audio_data.shape}")
@@ -391,27 +586,41 @@ class WhisperEngine:
                 audio_data,
                 language=language,
                 task=task,
-                beam_size=1,  # Greedy decoding for speed (was 5)
+                beam_size=self.decoding.beam_size,
                 best_of=1,  # Single pass for speed
-                temperature=0.0,  # Deterministic
+                temperature=self.decoding.temperature,
                 vad_filter=False,  # DISABLED - was removing all speech
                 # vad_parameters=dict(
                 #     min_silence_duration_ms=300
                 # ),
-                condition_on_previous_text=False  # Don't wait for context
+                condition_on_previous_text=self.decoding.condition_on_previous_text,
+                no_speech_threshold=self.decoding.no_speech_threshold,
+                word_timestamps=True  # Per-word timing and confidence
             )
             
             # Collect segments
             transcription_segments = []
+            transcription_words = []
             full_text = ""
             
             for segment in segments:
+                segment_words = [
+                    {
+                        "word": word.word.strip(),
+                        "start": word.start,
+                        "end": word.end,
+                        "probability": word.probability
+                    }
+                    for word in (segment.words or [])
+                ]
                 segment_dict = {
                     "start": segment.start,
                     "end": segment.end,
-                    "text": segment.text.strip()
+                    "text": segment.text.strip(),
+                    "words": segment_words
                 }
                 transcription_segments.append(segment_dict)
+                transcription_words.extend(segment_words)
                 full_text += segment.text
             
             full_text = full_text.strip()
@@ -425,6 +634,7 @@ class WhisperEngine:
                 "success": True,
                 "text": full_text,
                 "segments": transcription_segments,
+                "words": transcription_words,
                 "language": info.language,
                 "language_probability": info.language_probability,
                 "duration": info.duration if hasattr(info, 'duration') else 0
@@ -465,6 +675,69 @@ class WhisperEngine:
                 "text": ""
             }
     
+    def detect_language(self, audio_data: np.ndarray, allowed: Optional[List[str]] = None) -> Tuple[str, float]:
+        """Most likely language of the audio and its probability, optionally only among `allowed`"""
+        # Segments are decoded lazily, so without iterating them only language detection runs
+        _, info = self.model.transcribe(audio_data, language=None, beam_size=1)
+        candidates = info.all_language_probs or [(info.language, info.language_probability)]
+        if allowed:
+            candidates = [c for c in candidates if c[0] in allowed] or [(allowed[0], 0.0)]
+        return max(candidates, key=lambda c: c[1])
+
+    def transcribe_multilingual(self, audio_data: np.ndarray, languages: Optional[List[str]] = None) -> Dict:
+        """
+        Transcribe speech that switches language: each segment is transcribed in its own detected
+        language (among `languages`, if given), so every part keeps its original script
+        """
+        if not self.is_loaded and not self.load_model():
+            return {"success": False, "error": "Failed to load model", "text": ""}
+        if self.english_only():
+            return self.transcribe_audio(audio_data, "en")
+
+        audio_data = audio_data.flatten().astype(np.float32)
+        sample_rate = 16000
+        overall, probability = self.detect_language(audio_data, languages)
+        logger.info(f"🌍 Multilingual: mostly {overall} ({probability:.0%})")
+
+        result = self.transcribe_audio(audio_data, overall)
+        if not result["success"]:
+            return result
+
+        segments = []
+        detected = []
+        for segment in result["segments"]:
+            piece = audio_data[int(segment["start"] * sample_rate):int(segment["end"] * sample_rate)]
+            language = overall
+            if len(piece) >= MIN_DETECTION_SECONDS * sample_rate:
+                language, _ = self.detect_language(piece, languages)
+
+            if language != overall:
+                redo = self.transcribe_audio(piece, language)
+                if redo["success"] and redo["text"].strip():
+                    offset = segment["start"]
+                    segment = {
+                        "start": segment["start"],
+                        "end": segment["end"],
+                        "text": redo["text"].strip(),
+                        "words": [{**word, "start": word["start"] + offset, "end": word["end"] + offset} for word in redo["words"]]
+                    }
+                    logger.info(f"🌍 Segment at {offset:.1f}s re-transcribed as {language}")
+                else:
+                    language = overall
+
+            segment["language"] = language
+            segments.append(segment)
+            detected.append(language)
+
+        return {
+            **result,
+            "text": " ".join(segment["text"] for segment in segments if segment["text"]),
+            "segments": segments,
+            "words": [word for segment in segments for word in segment["words"]],
+            "language": overall,
+            "languages": sorted(set(detected))
+        }
+
     def transcribe_file(self, audio_file: str, language: Optional[str] = None) -> Dict:
         """
         Transcribe an audio file
@@ -0,0 +1,186 @@
+use serde::{Deserialize, Serialize};
+use std::sync::mpsc::{channel, Sender};
+use std::sync::{Mutex, OnceLock};
+use tauri::{AppHandle, Manager};
+use windows::core::{w, BSTR, HSTRING};
+use windows::Win32::Media::Speech::{ISpVoice, SpVoice, SPF_ASYNC, SPF_IS_NOT_XML, SPF_PURGEBEFORESPEAK};
+use windows::Win32::System::Com::{CoCreateInstance, CoInitializeEx, CLSCTX_ALL, COINIT_APARTMENTTHREADED};
+use windows::Win32::UI::Accessibility::{
+    UiaHostProviderFromHwnd, UiaRaiseNotificationEvent, NotificationKind_Other, NotificationProcessing_ImportantMostRecent,
+};
+use windows::Win32::UI::WindowsAndMessaging::{
+    FindWindowW, SystemParametersInfoW, SPI_GETSCREENREADER, SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS,
+};
+
+use crate::AppState;
+
+// Spoken feedback for people who can't see the overlay: recording started, transcribing, text
+// inserted, and errors are announced through the screen reader or a SAPI voice. Transcripts can
+// also be read aloud by the SAPI voice, before or after they're inserted.
+
+pub const CONFIG_FILE: &str = "accessibility.json";
+
+// Activity ID screen readers use to tell our notifications apart (a newer one replaces an older one)
+const ACTIVITY_ID: &str = "Whisper4Windows.Status";
+// Longest a readback before insertion holds the text back
+const READBACK_WAIT_LIMIT_MS: u32 = 30_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum AnnounceMethod {
+    Off,
+    #[default]
+    ScreenReader,  // UI Automation notifications, read by Narrator, NVDA, and JAWS; silent without one
+    Speech,  // The Windows text-to-speech voice, for screen readers that skip background apps
+}
+
+// When transcripts are read aloud by the SAPI voice
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum Readback {
+    #[default]
+    Off,
+    Before,  // Read first, then insert; for checking the text before it lands
+    After,  // Insert, then read it (replaces the "Text inserted" announcement)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct AccessibilitySettings {
+    pub announce: AnnounceMethod,
+    pub readback: Readback,  // Not in privacy mode; the replay hotkey still works there
+    pub speech_rate: i32,  // -10 (slowest) to 10 (fastest), for the SAPI voice
+}
+
+impl AccessibilitySettings {
+    async fn reads_back(&self, state: &AppState, timing: Readback, text: &str) -> bool {
+        self.readback == timing && !text.trim().is_empty() && !crate::privacy::is_enabled(state).await
+    }
+}
+
+// Announce `message` in the background with the current settings
+pub fn announce(app: &AppHandle, message: impl Into<String>) {
+    let app = app.clone();
+    let message = message.into();
+    tauri::async_runtime::spawn(async move {
+        let state: tauri::State<AppState> = app.state();
+        let settings = state.accessibility.lock().await.clone();
+        deliver(&settings, message);
+    });
+}
+
+// Read `text` aloud before it's delivered, if the settings ask for that; returns once it's been read
+pub async fn read_before_delivery(state: &AppState, text: &str) {
+    let settings = state.accessibility.lock().await.clone();
+    if settings.reads_back(state, Readback::Before, text).await {
+        read_aloud(text, settings.speech_rate).await;
+    }
+}
+
+// Announce that text was delivered, reading it back if the settings ask for that
+pub async fn announce_delivery(state: &AppState, outcome: &str, text: &str) {
+    let settings = state.accessibility.lock().await.clone();
+    if text.trim().is_empty() {
+        deliver(&settings, crate::locale::tr("announce.nothing"));
+    } else if settings.reads_back(state, Readback::After, text).await {
+        speak(format!("{}: {}", outcome, text), settings.speech_rate, None);
+    } else {
+        deliver(&settings, outcome.to_string());
+    }
+}
+
+// Read `text` with the SAPI voice, whatever the announcement method; returns once it's been read
+pub async fn read_aloud(text: &str, rate: i32) {
+    let (done, finished) = tokio::sync::oneshot::channel();
+    speak(text.to_string(), rate, Some(done));
+    let _ = finished.await;
+}
+
+// Announce `message` with these settings (also used by the settings window's Test button)
+pub fn deliver(settings: &AccessibilitySettings, message: String) {
+    match settings.announce {
+        AnnounceMethod::Off => {}
+        AnnounceMethod::ScreenReader => {
+            // UI Automation calls want a thread of their own rather than a runtime thread
+            std::thread::spawn(move || {
+                if let Err(e) = unsafe { notify_screen_reader(&message) } {
+                    log::warn!("⚠️ Screen reader announcement failed: {}", e);
+                }
+            });
+        }
+        AnnounceMethod::Speech => speak(message, settings.speech_rate, None),
+    }
+}
+
+// Whether a screen reader is running (they set this system flag while active)
+pub fn screen_reader_running() -> bool {
+    let mut running = windows::Win32::Foundation::BOOL(0);
+    let result = unsafe {
+        SystemParametersInfoW(
+            SPI_GETSCREENREADER,
+            0,
+            Some(&mut running as *mut _ as *mut std::ffi::c_void),
+            SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS(0),
+        )
+    };
+    result.is_ok() && running.as_bool()
+}
+
+// Raise the notification from the settings window, which exists (hidden) for the app's lifetime
+unsafe fn notify_screen_reader(message: &str) -> windows::core::Result<()> {
+    let window = FindWindowW(None, w!("Whisper4Windows - Settings"));
+    if window.0 == 0 {
+        return Err(windows::core::Error::from_win32());
+    }
+    let provider = UiaHostProviderFromHwnd(window)?;
+    UiaRaiseNotificationEvent(
+        &provider,
+        NotificationKind_Other,
+        NotificationProcessing_ImportantMostRecent,
+        &BSTR::from(message),
+        &BSTR::from(ACTIVITY_ID),
+    )
+}
+
+struct Utterance {
+    text: String,
+    rate: i32,
+    done: Option<tokio::sync::oneshot::Sender<()>>,  // Told when it's finished (or cut off by the wait limit)
+}
+
+// One voice on its own thread, so a new announcement cuts the previous one off instead of overlapping
+fn speak(text: String, rate: i32, done: Option<tokio::sync::oneshot::Sender<()>>) {
+    static VOICE: OnceLock<Mutex<Sender<Utterance>>> = OnceLock::new();
+    let sender = VOICE.get_or_init(|| {
+        let (sender, receiver) = channel::<Utterance>();
+        std::thread::spawn(move || {
+            let voice: ISpVoice = match unsafe {
+                let _ = CoInitializeEx(None, COINIT_APARTMENTTHREADED);
+                CoCreateInstance(&SpVoice, None, CLSCTX_ALL)
+            } {
+                Ok(voice) => voice,
+                Err(e) => {
+                    log::warn!("⚠️ No text-to-speech voice: {}", e);
+                    return;
+                }
+            };
+            for utterance in receiver {
+                let flags = (SPF_ASYNC.0 | SPF_PURGEBEFORESPEAK.0 | SPF_IS_NOT_XML.0) as u32;
+                let spoken = unsafe {
+                    let _ = voice.SetRate(utterance.rate.clamp(-10, 10));
+                    voice.Speak(&HSTRING::from(utterance.text), flags, None)
+                };
+                if let Err(e) = spoken {
+                    log::warn!("⚠️ Speech failed: {}", e);
+                } else if utterance.done.is_some() {
+                    let _ = unsafe { voice.WaitUntilDone(READBACK_WAIT_LIMIT_MS) };
+                }
+                if let Some(done) = utterance.done {
+                    let _ = done.send(());
+                }
+            }
+        });
+        Mutex::new(sender)
+    });
+    let _ = sender.lock().unwrap().send(Utterance { text, rate, done });
+}
@@ -0,0 +1,232 @@
+use rubato::{FftFixedIn, Resampler};
+use std::fs::File;
+use std::io::{BufWriter, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use symphonia::core::audio::SampleBuffer;
+use symphonia::core::codecs::{DecoderOptions, CODEC_TYPE_NULL};
+use symphonia::core::errors::Error as SymphoniaError;
+use symphonia::core::formats::FormatOptions;
+use symphonia::core::io::MediaSourceStream;
+use symphonia::core::meta::MetadataOptions;
+use symphonia::core::probe::Hint;
+
+use crate::file_transcription::AudioFile;
+
+// Decoding files for transcription here instead of in the backend: symphonia reads the audio
+// track, which is mixed down to mono, resampled to 16 kHz with rubato and streamed into a
+// temporary WAV, so the backend only ever gets the format Whisper wants. Anything symphonia can't
+// decode (Opus, WMA, ...) still goes to the backend as it is, where FFmpeg reads it.
+
+const SAMPLE_RATE: u32 = 16000;
+
+// Input frames the resampler takes at a time
+const CHUNK_FRAMES: usize = 1024;
+
+// Tells apart the temporary files of conversions running at the same time
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+// A converted copy of a file, deleted when dropped
+pub struct ConvertedFile {
+    pub path: PathBuf,
+}
+
+impl Drop for ConvertedFile {
+    fn drop(&mut self) {
+        match std::fs::remove_file(&self.path) {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => log::warn!("⚠️ Failed to delete {}: {}", self.path.display(), e),
+        }
+    }
+}
+
+// Convert `file` to 16 kHz mono WAV, or None to leave it to the backend
+pub async fn convert(file: &AudioFile) -> Option<ConvertedFile> {
+    let source = PathBuf::from(&file.path);
+    let track = file.track;
+    let id = NEXT_ID.fetch_add(1, Ordering::SeqCst);
+    let target = std::env::temp_dir().join(format!("whisper4windows-{}-{}.wav", std::process::id(), id));
+
+    let started = std::time::Instant::now();
+    let result = {
+        let target = target.clone();
+        tokio::task::spawn_blocking(move || decode(&source, track, &target)).await.unwrap_or_else(|e| Err(e.to_string()))
+    };
+    // Made before checking the result, so a half-written file is deleted too
+    let converted = ConvertedFile { path: target };
+
+    match result {
+        Ok(seconds) => {
+            log::info!("🎼 Decoded {} ({:.1}s of audio in {}ms)", file.path, seconds, started.elapsed().as_millis());
+            Some(converted)
+        }
+        Err(e) => {
+            log::warn!("⚠️ Can't decode {} here ({}), leaving it to the backend", file.path, e);
+            None
+        }
+    }
+}
+
+// Decode audio track `track` (0 = first) of `source` into a WAV at `target`; returns its length in seconds
+fn decode(source: &Path, track: usize, target: &Path) -> Result<f64, String> {
+    let stream = MediaSourceStream::new(Box::new(File::open(source).map_err(|e| e.to_string())?), Default::default());
+    let mut hint = Hint::new();
+    if let Some(extension) = source.extension().and_then(|ext| ext.to_str()) {
+        hint.with_extension(extension);
+    }
+    let probed = symphonia::default::get_probe()
+        .format(&hint, stream, &FormatOptions::default(), &MetadataOptions::default())
+        .map_err(|e| format!("unsupported format: {}", e))?;
+    let mut format = probed.format;
+
+    // Audio tracks numbered like the backend numbers them, skipping video and subtitles
+    let audio_tracks: Vec<_> = format
+        .tracks()
+        .iter()
+        .filter(|t| t.codec_params.codec != CODEC_TYPE_NULL && t.codec_params.sample_rate.is_some())
+        .collect();
+    let selected = audio_tracks
+        .get(track)
+        .ok_or_else(|| format!("the file has {} audio track(s), not {}", audio_tracks.len(), track + 1))?;
+    let track_id = selected.id;
+    let rate = selected.codec_params.sample_rate.unwrap_or(SAMPLE_RATE);
+    let mut decoder = symphonia::default::get_codecs()
+        .make(&selected.codec_params, &DecoderOptions::default())
+        .map_err(|e| format!("unsupported codec: {}", e))?;
+
+    let mut output = Output::new(target, rate)?;
+    let mut buffer: Option<SampleBuffer<f32>> = None;
+    let mut mono = Vec::new();
+    loop {
+        let packet = match format.next_packet() {
+            Ok(packet) => packet,
+            Err(SymphoniaError::IoError(e)) if e.kind() == std::io::ErrorKind::UnexpectedEof => break,
+            Err(e) => return Err(e.to_string()),
+        };
+        if packet.track_id() != track_id {
+            continue;
+        }
+
+        let decoded = match decoder.decode(&packet) {
+            Ok(decoded) => decoded,
+            // A damaged packet; skip it like FFmpeg would
+            Err(SymphoniaError::DecodeError(e)) => {
+                log::debug!("Skipping a damaged packet: {}", e);
+                continue;
+            }
+            Err(e) => return Err(e.to_string()),
+        };
+        let spec = *decoded.spec();
+        let channels = spec.channels.count().max(1);
+        let buffer = match &mut buffer {
+            Some(buffer) if buffer.capacity() >= decoded.capacity() * channels => buffer,
+            slot => slot.insert(SampleBuffer::new(decoded.capacity() as u64, spec)),
+        };
+        buffer.copy_interleaved_ref(decoded);
+
+        mono.clear();
+        mono.extend(buffer.samples().chunks(channels).map(|frame| frame.iter().sum::<f32>() / channels as f32));
+        output.push(&mono)?;
+    }
+
+    output.finish()
+}
+
+// Resamples mono audio to 16 kHz and writes it out as 16-bit PCM
+struct Output {
+    file: BufWriter<File>,
+    rate: u32,
+    resampler: Option<FftFixedIn<f32>>,  // None when the audio is 16 kHz already
+    pending: Vec<f32>,  // Input not yet resampled
+    frames_in: u64,
+    frames_out: u64,
+    delay: usize,  // Output frames still to drop for the resampler's delay
+}
+
+impl Output {
+    fn new(path: &Path, rate: u32) -> Result<Self, String> {
+        let resampler = if rate == SAMPLE_RATE {
+            None
+        } else {
+            Some(FftFixedIn::new(rate as usize, SAMPLE_RATE as usize, CHUNK_FRAMES, 2, 1).map_err(|e| e.to_string())?)
+        };
+        let delay = resampler.as_ref().map_or(0, |resampler| resampler.output_delay());
+
+        let mut file = BufWriter::new(File::create(path).map_err(|e| e.to_string())?);
+        // Sizes are filled in by finish()
+        file.write_all(&wav_header(0)).map_err(|e| e.to_string())?;
+        Ok(Self { file, rate, resampler, pending: Vec::new(), frames_in: 0, frames_out: 0, delay })
+    }
+
+    fn push(&mut self, samples: &[f32]) -> Result<(), String> {
+        self.frames_in += samples.len() as u64;
+        let Some(resampler) = self.resampler.as_mut() else {
+            return self.write(samples);
+        };
+
+        self.pending.extend_from_slice(samples);
+        let mut used = 0;
+        let mut resampled = Vec::new();
+        while self.pending.len() - used >= resampler.input_frames_next() {
+            let frames = resampler.input_frames_next();
+            let chunk = resampler.process(&[&self.pending[used..used + frames]], None).map_err(|e| e.to_string())?;
+            resampled.extend(chunk.into_iter().flatten());
+            used += frames;
+        }
+        self.pending.drain(..used);
+
+        // The resampler's delay is dropped from the front of its output
+        let skip = self.delay.min(resampled.len());
+        self.delay -= skip;
+        self.write(&resampled[skip..])
+    }
+
+    fn write(&mut self, samples: &[f32]) -> Result<(), String> {
+        for sample in samples {
+            let value = (sample.clamp(-1.0, 1.0) * i16::MAX as f32) as i16;
+            self.file.write_all(&value.to_le_bytes()).map_err(|e| e.to_string())?;
+        }
+        self.frames_out += samples.len() as u64;
+        Ok(())
+    }
+
+    // Flush what the resampler holds, cut the output to the input's length and fill in the header
+    fn finish(mut self) -> Result<f64, String> {
+        if let Some(mut resampler) = self.resampler.take() {
+            let rest = resampler.process_partial(Some(&[&self.pending[..]]), None).map_err(|e| e.to_string())?;
+            let tail = resampler.process_partial(None::<&[&[f32]]>, None).map_err(|e| e.to_string())?;
+            let expected = self.frames_in * SAMPLE_RATE as u64 / self.rate as u64;
+            for resampled in [rest, tail].into_iter().filter_map(|channels| channels.into_iter().next()) {
+                let skip = self.delay.min(resampled.len());
+                self.delay -= skip;
+                let room = expected.saturating_sub(self.frames_out) as usize;
+                let end = resampled.len().min(skip + room);
+                self.write(&resampled[skip..end])?;
+            }
+        }
+
+        let data_len = u32::try_from(self.frames_out * 2).map_err(|_| "too long for a WAV file".to_string())?;
+        self.file.seek(SeekFrom::Start(0)).map_err(|e| e.to_string())?;
+        self.file.write_all(&wav_header(data_len)).map_err(|e| e.to_string())?;
+        self.file.flush().map_err(|e| e.to_string())?;
+        Ok(self.frames_out as f64 / SAMPLE_RATE as f64)
+    }
+}
+
+fn wav_header(data_len: u32) -> Vec<u8> {
+    let mut header = Vec::with_capacity(44);
+    header.extend_from_slice(b"RIFF");
+    header.extend_from_slice(&(36 + data_len).to_le_bytes());
+    header.extend_from_slice(b"WAVEfmt ");
+    header.extend_from_slice(&16u32.to_le_bytes());
+    header.extend_from_slice(&1u16.to_le_bytes());  // PCM
+    header.extend_from_slice(&1u16.to_le_bytes());  // Mono
+    header.extend_from_slice(&SAMPLE_RATE.to_le_bytes());
+    header.extend_from_slice(&(SAMPLE_RATE * 2).to_le_bytes());  // Bytes per second
+    header.extend_from_slice(&2u16.to_le_bytes());  // Block align
+    header.extend_from_slice(&16u16.to_le_bytes());  // Bits per sample
+    header.extend_from_slice(b"data");
+    header.extend_from_slice(&data_len.to_le_bytes());
+    header
+}
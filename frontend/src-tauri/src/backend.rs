@@ -0,0 +1,243 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+use tauri::async_runtime::Receiver;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_shell::process::CommandEvent;
+use windows::core::PCWSTR;
+use windows::Win32::Foundation::{CloseHandle, HANDLE};
+use windows::Win32::System::JobObjects::{
+    AssignProcessToJobObject, CreateJobObjectW, JobObjectExtendedLimitInformation, SetInformationJobObject,
+    JOBOBJECT_EXTENDED_LIMIT_INFORMATION, JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE,
+};
+use windows::Win32::System::RemoteDesktop::ProcessIdToSessionId;
+use windows::Win32::System::Threading::{GetCurrentProcessId, OpenProcess, PROCESS_SET_QUOTA, PROCESS_TERMINATE};
+
+use crate::AppState;
+
+// Every backend call fails instead of hanging forever if the backend stops answering
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(3);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+// Polled twice a second during a transcription, so it must give up quickly
+pub const PROGRESS_TIMEOUT: Duration = Duration::from_secs(2);
+// Transcriptions only get a hard ceiling; the stall watchdog in `send_with_progress` catches hangs much sooner
+pub const TRANSCRIPTION_TIMEOUT: Duration = Duration::from_secs(60 * 60);
+// How long a transcription may go without any progress before it is considered stuck
+pub const STALL_TIMEOUT: Duration = Duration::from_secs(3 * 60);
+const RECOVERY_SAVE_TIMEOUT: Duration = Duration::from_secs(5);
+
+// The backend's usual port; taken when it's free, which it is unless another user on the same
+// machine (a terminal server) already runs Whisper4Windows
+const DEFAULT_PORT: u16 = 8000;
+// How many times a backend whose port was taken before it could bind is moved to another one
+const BIND_ATTEMPTS: u32 = 5;
+// What the backend prints when binding its port fails
+const BIND_ERROR: &str = "error while attempting to bind";
+
+// Why a transcription request was abandoned before the backend answered
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Interrupt {
+    Cancel,  // The user cancelled; the result is discarded
+    Recover,  // Stuck: restart the backend, keeping the audio for Retry
+}
+
+#[derive(Debug)]
+pub enum TranscriptionError {
+    Request(reqwest::Error),
+    Interrupted(Interrupt),
+}
+
+impl std::fmt::Display for TranscriptionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TranscriptionError::Request(e) => write!(f, "{}", e),
+            TranscriptionError::Interrupted(Interrupt::Cancel) => write!(f, "Transcription cancelled"),
+            TranscriptionError::Interrupted(Interrupt::Recover) => write!(f, "Transcription was stuck, backend restarted"),
+        }
+    }
+}
+
+// Job the backend runs in. Windows closes our handle to it when this process exits, even after a
+// crash, and the job then kills the backend and anything it started instead of leaving it holding the port.
+static BACKEND_JOB: OnceLock<Option<HANDLE>> = OnceLock::new();
+
+fn backend_job() -> Option<HANDLE> {
+    *BACKEND_JOB.get_or_init(|| unsafe {
+        let job = CreateJobObjectW(None, PCWSTR::null())
+            .map_err(|e| log::warn!("⚠️ Failed to create the backend job: {}", e))
+            .ok()?;
+        let mut limits = JOBOBJECT_EXTENDED_LIMIT_INFORMATION::default();
+        limits.BasicLimitInformation.LimitFlags = JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE;
+        SetInformationJobObject(
+            job,
+            JobObjectExtendedLimitInformation,
+            &limits as *const _ as *const std::ffi::c_void,
+            std::mem::size_of::<JOBOBJECT_EXTENDED_LIMIT_INFORMATION>() as u32,
+        )
+        .map_err(|e| log::warn!("⚠️ Failed to configure the backend job: {}", e))
+        .ok()?;
+        Some(job)
+    })
+}
+
+// Make the backend process exit together with the app
+pub fn tie_to_app(pid: u32) -> anyhow::Result<()> {
+    let job = backend_job().ok_or_else(|| anyhow::anyhow!("No backend job"))?;
+    unsafe {
+        let process = OpenProcess(PROCESS_SET_QUOTA | PROCESS_TERMINATE, false, pid)?;
+        let result = AssignProcessToJobObject(job, process);
+        let _ = CloseHandle(process);
+        result?;
+    }
+    Ok(())
+}
+
+// Port this instance's backend listens on: the default if it's free, or else whatever Windows hands
+// out, so every user and session gets a backend of its own. Picked on first use, and again if
+// another process takes it before the backend binds it.
+static PORT: Mutex<u16> = Mutex::new(0);
+static BIND_RETRIES: AtomicU32 = AtomicU32::new(0);
+
+pub fn port() -> u16 {
+    let mut port = PORT.lock().unwrap();
+    if *port == 0 {
+        *port = pick_port(DEFAULT_PORT);
+    }
+    *port
+}
+
+// `preferred` if it's free (0 for any), else a free one
+fn pick_port(preferred: u16) -> u16 {
+    let free = |port| std::net::TcpListener::bind(("127.0.0.1", port)).and_then(|listener| listener.local_addr());
+    match free(preferred).or_else(|_| free(0)) {
+        Ok(address) => address.port(),
+        Err(e) => {
+            log::warn!("⚠️ No free port found, trying {}: {}", DEFAULT_PORT, e);
+            DEFAULT_PORT
+        }
+    }
+}
+
+// Follow a just-started backend's output; if it exits because its port was taken between picking
+// and binding it (another process won the race), start it again on a new port
+pub fn watch_bind(app: AppHandle, mut events: Receiver<CommandEvent>) {
+    tauri::async_runtime::spawn(async move {
+        let mut bind_failed = false;
+        while let Some(event) = events.recv().await {
+            match event {
+                CommandEvent::Stdout(line) | CommandEvent::Stderr(line) => {
+                    bind_failed |= String::from_utf8_lossy(&line).contains(BIND_ERROR);
+                }
+                CommandEvent::Terminated(_) => break,
+                _ => {}
+            }
+        }
+        if !bind_failed {
+            return;
+        }
+        if BIND_RETRIES.fetch_add(1, Ordering::SeqCst) >= BIND_ATTEMPTS {
+            log::error!("❌ Backend couldn't bind a port after {} attempts", BIND_ATTEMPTS);
+            return;
+        }
+
+        let port = {
+            let mut port = PORT.lock().unwrap();
+            *port = pick_port(0);
+            *port
+        };
+        log::warn!("⚠️ Backend port was taken, moving it to {}", port);
+        restart(&app).await;
+        // The windows call the backend directly too
+        let script = format!("if (window.setBackendUrl) setBackendUrl('http://127.0.0.1:{}')", port);
+        for win in app.webview_windows().values() {
+            let _ = win.eval(&script);
+        }
+    });
+}
+
+// URL of a backend endpoint, e.g. url("health")
+pub fn url(path: &str) -> String {
+    format!("http://127.0.0.1:{}/{}", port(), path)
+}
+
+// Windows session this instance runs in, for diagnostics
+pub fn session_id() -> Option<u32> {
+    let mut session = 0;
+    unsafe { ProcessIdToSessionId(GetCurrentProcessId(), &mut session) }.ok()?;
+    Some(session)
+}
+
+// "port 8000, session 2", for the log and diagnostics
+pub fn describe() -> String {
+    let session = session_id().map(|id| id.to_string()).unwrap_or_else(|| "unknown".to_string());
+    format!("port {}, session {}", port(), session)
+}
+
+// HTTP client for talking to the backend
+pub fn client() -> reqwest::Client {
+    reqwest::Client::builder()
+        .connect_timeout(CONNECT_TIMEOUT)
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+        .unwrap_or_default()
+}
+
+// Abandon the in-flight transcription request, if any
+pub async fn interrupt(state: &AppState, interrupt: Interrupt) -> bool {
+    match state.transcription_interrupt.lock().await.take() {
+        Some(sender) => sender.send(interrupt).is_ok(),
+        None => false,
+    }
+}
+
+// Kill the backend process (if any) and start a fresh one
+pub async fn restart(app: &AppHandle) {
+    let state: tauri::State<AppState> = app.state();
+    let mut backend = state.backend_child.lock().await;
+    if let Some(child) = backend.take() {
+        let _ = child.kill();
+    }
+    match crate::spawn_backend(app) {
+        Ok(child) => {
+            *backend = Some(child);
+            log::info!("✅ Backend restarted");
+            // The new backend starts with the default memory policy and no pre-buffer
+            crate::model_memory::spawn_apply(app.clone());
+            crate::prebuffer::spawn_apply(app.clone());
+        }
+        Err(e) => log::error!("❌ Failed to restart backend: {}", e),
+    }
+}
+
+// Get out of a stuck transcription: keep the audio if the backend still answers, restart it, reset the overlay
+pub async fn recover(app: &AppHandle) {
+    log::warn!("🩺 Recovering from a stuck transcription");
+
+    // The restarted backend loads the saved audio again, so Retry works afterwards
+    let saved = match client()
+        .post(url("recovery/save"))
+        .timeout(RECOVERY_SAVE_TIMEOUT)
+        .send()
+        .await
+    {
+        Ok(resp) => resp
+            .json::<serde_json::Value>()
+            .await
+            .ok()
+            .and_then(|data| data.get("saved").and_then(|s| s.as_bool()))
+            .unwrap_or(false),
+        Err(e) => {
+            log::warn!("⚠️ Could not save the recording before restarting the backend: {}", e);
+            false
+        }
+    };
+
+    restart(app).await;
+
+    let _ = crate::recording_state::transition(app, crate::recording_state::RecordingState::Idle).await;
+    if let Some(win) = app.get_webview_window("recording") {
+        let _ = win.hide();
+    }
+    let message = crate::locale::tr(if saved { "toast.backend_recovered_saved" } else { "toast.backend_recovered_lost" });
+    crate::show_toast(app, &crate::locale::tr("toast.backend_recovered"), &message);
+}
@@ -0,0 +1,64 @@
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use crate::backend;
+
+// Times every downloaded model on the CPU and the GPU with a sample clip, so the user can pick
+// the most accurate setup their machine still runs fast enough for dictation
+
+// Loading and running each model in turn, largest first, can take many minutes on CPU
+const BENCHMARK_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Measurement {
+    pub model: String,
+    pub device: String,
+    pub load_time: Option<f64>,
+    pub transcription_time: Option<f64>,
+    pub realtime_factor: Option<f64>,  // Seconds spent per second of audio; below 1 is faster than realtime
+    pub accuracy: Option<f64>,  // Share of the sample's words transcribed correctly (0 to 1)
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Recommendation {
+    pub model: String,
+    pub device: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Report {
+    pub sample_duration: f64,
+    pub results: Vec<Measurement>,
+    pub recommendation: Option<Recommendation>,
+}
+
+pub async fn run(models: Vec<String>) -> Result<Report, String> {
+    let resp = backend::client()
+        .post(backend::url("benchmark"))
+        .timeout(BENCHMARK_TIMEOUT)
+        .json(&serde_json::json!({ "models": models }))
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {}", e))?;
+    let data = resp.json::<serde_json::Value>().await.map_err(|e| format!("Invalid response: {}", e))?;
+    if data.get("status").and_then(|s| s.as_str()) != Some("success") {
+        let message = data.get("message").and_then(|m| m.as_str()).unwrap_or("Benchmark failed");
+        return Err(message.to_string());
+    }
+
+    let report: Report = serde_json::from_value(data).map_err(|e| format!("Invalid response: {}", e))?;
+    for r in &report.results {
+        match (&r.error, r.realtime_factor, r.accuracy) {
+            (Some(error), _, _) => log::warn!("⏱️ {} on {}: {}", r.model, r.device, error),
+            (None, Some(rtf), Some(accuracy)) => {
+                log::info!("⏱️ {} on {}: {:.2}x realtime, {:.0}% accurate", r.model, r.device, rtf, accuracy * 100.0)
+            }
+            _ => {}
+        }
+    }
+    if let Some(rec) = &report.recommendation {
+        log::info!("⏱️ Recommended: {} on {}", rec.model, rec.device);
+    }
+    Ok(report)
+}
@@ -0,0 +1,321 @@
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use std::io::{Error, ErrorKind};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::tcp::OwnedReadHalf;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{broadcast, mpsc};
+use windows::Win32::Security::Cryptography::{BCryptHash, BCRYPT_SHA1_ALG_HANDLE};
+
+use crate::recording_state::RecordingState;
+use crate::AppState;
+
+pub const CONFIG_FILE: &str = "caption_server.json";
+
+// Appended to the client's key before hashing, as RFC 6455 requires
+const WEBSOCKET_GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+// Captions buffered for a slow client before it starts skipping some
+const CHANNEL_CAPACITY: usize = 64;
+// Clients only send pings and close frames; anything bigger is not a caption overlay
+const MAX_CLIENT_FRAME: u64 = 64 * 1024;
+// Limits on the request a client opens with, so it can't make us buffer without end
+const MAX_HEADER_LINE: u64 = 8 * 1024;
+const MAX_HEADER_LINES: usize = 64;
+
+const OPCODE_TEXT: u8 = 0x1;
+const OPCODE_CLOSE: u8 = 0x8;
+const OPCODE_PING: u8 = 0x9;
+const OPCODE_PONG: u8 = 0xA;
+
+// Built-in caption overlay, served to plain HTTP requests (e.g. an OBS browser source)
+const OVERLAY_PAGE: &str = include_str!("caption_overlay.html");
+
+// Local WebSocket server broadcasting captions to streaming overlays
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CaptionServerSettings {
+    pub enabled: bool,
+    pub port: u16,
+    pub token: String,  // Clients must connect with ?token=<token>; generated when the server is enabled without one
+}
+
+impl Default for CaptionServerSettings {
+    fn default() -> Self {
+        Self { enabled: false, port: 8765, token: String::new() }
+    }
+}
+
+// One message to every client, sent as a JSON text frame ({"type": "final", "text": "..."})
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Caption {
+    State { state: RecordingState },  // Overlays clear their text when a recording starts
+    Partial { text: String },  // Transcript so far of a long recording; replaces the previous partial
+    Segment { id: u64, text: String },  // A continuous-dictation segment as delivered
+    Final { text: String },  // The delivered transcription of a recording
+}
+
+#[derive(Debug)]
+pub struct CaptionServer {
+    pub settings: CaptionServerSettings,
+    sender: broadcast::Sender<String>,
+    task: Option<tauri::async_runtime::JoinHandle<()>>,  // Accept loop (None while stopped)
+}
+
+impl Default for CaptionServer {
+    fn default() -> Self {
+        Self { settings: CaptionServerSettings::default(), sender: broadcast::channel(CHANNEL_CAPACITY).0, task: None }
+    }
+}
+
+// Send a caption to every connected client (a no-op while the server is off)
+pub async fn publish(state: &AppState, caption: Caption) {
+    let server = state.caption_server.lock().await;
+    if server.task.is_none() {
+        return;
+    }
+    if let Ok(json) = serde_json::to_string(&caption) {
+        let _ = server.sender.send(json);  // Fails only when nobody is connected
+    }
+}
+
+// Give enabled settings a random token if they have none (there is no unauthenticated mode, since
+// any web page open in a browser could otherwise read the captions); returns whether it did, so the
+// caller saves it
+pub fn ensure_token(settings: &mut CaptionServerSettings) -> Result<bool, String> {
+    if !settings.enabled || !settings.token.trim().is_empty() {
+        return Ok(false);
+    }
+    settings.token = crate::elevation::random_token().map_err(|e| format!("Couldn't generate a token: {}", e))?;
+    log::info!("📡 Generated a caption server token");
+    Ok(true)
+}
+
+// Start, restart, or stop the server to match `settings`
+pub async fn apply(state: &AppState, settings: CaptionServerSettings) -> Result<(), String> {
+    let mut server = state.caption_server.lock().await;
+    if let Some(task) = server.task.take() {
+        task.abort();
+        log::info!("📡 Caption server stopped");
+    }
+    // A fresh channel disconnects the clients of the previous server
+    server.sender = broadcast::channel(CHANNEL_CAPACITY).0;
+    server.settings = settings.clone();
+    if !settings.enabled {
+        return Ok(());
+    }
+    if crate::policy::get().allow_caption_server == Some(false) {
+        return Err("The caption server is turned off by your administrator".to_string());
+    }
+    if settings.token.trim().is_empty() {
+        return Err("The caption server needs a token".to_string());
+    }
+
+    let listener = TcpListener::bind(("127.0.0.1", settings.port))
+        .await
+        .map_err(|e| format!("Can't listen on port {}: {}", settings.port, e))?;
+    log::info!("📡 Caption server listening on ws://127.0.0.1:{}", settings.port);
+    server.task = Some(tauri::async_runtime::spawn(accept_loop(listener, server.sender.clone(), settings)));
+    Ok(())
+}
+
+async fn accept_loop(listener: TcpListener, sender: broadcast::Sender<String>, settings: CaptionServerSettings) {
+    loop {
+        let stream = match listener.accept().await {
+            Ok((stream, _)) => stream,
+            Err(e) => {
+                log::warn!("⚠️ Caption server accept failed: {}", e);
+                continue;
+            }
+        };
+        let receiver = sender.subscribe();
+        let settings = settings.clone();
+        tokio::spawn(async move {
+            if let Err(e) = serve_client(stream, receiver, &settings).await {
+                log::info!("📡 Caption client disconnected: {}", e);
+            }
+        });
+    }
+}
+
+// Handshake, then forward captions until either side closes. Plain HTTP requests get the overlay page.
+async fn serve_client(
+    stream: TcpStream,
+    mut captions: broadcast::Receiver<String>,
+    settings: &CaptionServerSettings,
+) -> std::io::Result<()> {
+    let (reader, mut writer) = stream.into_split();
+    let mut reader = BufReader::new(reader);
+
+    let Some(request_line) = read_header_line(&mut reader).await? else { return Ok(()) };
+    let mut key = None;
+    let mut origin = None;
+    let mut lines = 0;
+    loop {
+        let Some(line) = read_header_line(&mut reader).await? else { return Ok(()) };
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        lines += 1;
+        if lines > MAX_HEADER_LINES {
+            return Err(Error::new(ErrorKind::InvalidData, "Too many header lines"));
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            let name = name.trim();
+            if name.eq_ignore_ascii_case("sec-websocket-key") {
+                key = Some(value.trim().to_string());
+            } else if name.eq_ignore_ascii_case("origin") {
+                origin = Some(value.trim().to_string());
+            }
+        }
+    }
+
+    // An empty token never means "no token"; `apply` doesn't start without one
+    let token = settings.token.trim();
+    if token.is_empty() || query_param(&request_line, "token") != Some(token) {
+        writer.write_all(b"HTTP/1.1 401 Unauthorized\r\nConnection: close\r\nContent-Length: 0\r\n\r\n").await?;
+        return Ok(());
+    }
+    let Some(key) = key else {
+        let response = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: text/html; charset=utf-8\r\nConnection: close\r\nContent-Length: {}\r\n\r\n{}",
+            OVERLAY_PAGE.len(),
+            OVERLAY_PAGE
+        );
+        writer.write_all(response.as_bytes()).await?;
+        return Ok(());
+    };
+
+    // Browsers always send their page's origin; only the overlay page this server hands out (or a
+    // local file or OBS, which send "null" or nothing) may connect, not any site the user has open
+    if !origin_allowed(origin.as_deref(), settings.port) {
+        writer.write_all(b"HTTP/1.1 403 Forbidden\r\nConnection: close\r\nContent-Length: 0\r\n\r\n").await?;
+        log::warn!("⚠️ Caption client from {} refused", origin.unwrap_or_default());
+        return Ok(());
+    }
+
+    let response = format!(
+        "HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\nConnection: Upgrade\r\nSec-WebSocket-Accept: {}\r\n\r\n",
+        accept_key(&key)?
+    );
+    writer.write_all(response.as_bytes()).await?;
+    log::info!("📡 Caption client connected");
+
+    // Frames are read on their own task, since a half-read frame can't be abandoned for a caption;
+    // it forwards ping payloads and closes the channel when the client goes away
+    let (pings_tx, mut pings) = mpsc::channel::<Vec<u8>>(4);
+    let read_task = tokio::spawn(read_control_frames(reader, pings_tx));
+    let result = loop {
+        tokio::select! {
+            caption = captions.recv() => match caption {
+                Ok(json) => writer.write_all(&frame(OPCODE_TEXT, json.as_bytes())).await?,
+                Err(broadcast::error::RecvError::Lagged(skipped)) => log::warn!("⚠️ Caption client skipped {} messages", skipped),
+                Err(broadcast::error::RecvError::Closed) => break writer.write_all(&frame(OPCODE_CLOSE, &[])).await,
+            },
+            ping = pings.recv() => match ping {
+                Some(payload) => writer.write_all(&frame(OPCODE_PONG, &payload)).await?,
+                None => break writer.write_all(&frame(OPCODE_CLOSE, &[])).await,
+            },
+        }
+    };
+    read_task.abort();
+    result
+}
+
+// One line of the request, or None at the end of the stream
+async fn read_header_line(reader: &mut BufReader<OwnedReadHalf>) -> std::io::Result<Option<String>> {
+    let mut line = String::new();
+    if (&mut *reader).take(MAX_HEADER_LINE).read_line(&mut line).await? == 0 {
+        return Ok(None);
+    }
+    if !line.ends_with('\n') {
+        return Err(Error::new(ErrorKind::InvalidData, "Header line too long"));
+    }
+    Ok(Some(line))
+}
+
+// Whether a WebSocket upgrade's Origin header is absent, "null", or this server itself
+fn origin_allowed(origin: Option<&str>, port: u16) -> bool {
+    match origin {
+        None | Some("null") => true,
+        Some(origin) => {
+            let origin = origin.trim_end_matches('/');
+            [format!("http://127.0.0.1:{}", port), format!("http://localhost:{}", port)]
+                .iter()
+                .any(|own| origin.eq_ignore_ascii_case(own))
+        }
+    }
+}
+
+async fn read_control_frames(mut reader: BufReader<OwnedReadHalf>, pings: mpsc::Sender<Vec<u8>>) {
+    while let Ok((opcode, payload)) = read_frame(&mut reader).await {
+        match opcode {
+            OPCODE_CLOSE => return,
+            OPCODE_PING if pings.send(payload).await.is_err() => return,
+            _ => {}  // Pings are answered above; clients have nothing else to tell us
+        }
+    }
+}
+
+async fn read_frame(reader: &mut BufReader<OwnedReadHalf>) -> std::io::Result<(u8, Vec<u8>)> {
+    let mut header = [0u8; 2];
+    reader.read_exact(&mut header).await?;
+    let opcode = header[0] & 0x0f;
+    let masked = header[1] & 0x80 != 0;
+    let len = match header[1] & 0x7f {
+        126 => reader.read_u16().await? as u64,
+        127 => reader.read_u64().await?,
+        len => len as u64,
+    };
+    if len > MAX_CLIENT_FRAME {
+        return Err(Error::new(ErrorKind::InvalidData, format!("Frame of {} bytes is too large", len)));
+    }
+
+    let mut mask = [0u8; 4];
+    if masked {
+        reader.read_exact(&mut mask).await?;
+    }
+    let mut payload = vec![0u8; len as usize];
+    reader.read_exact(&mut payload).await?;
+    if masked {
+        for (i, byte) in payload.iter_mut().enumerate() {
+            *byte ^= mask[i % 4];
+        }
+    }
+    Ok((opcode, payload))
+}
+
+// A single unmasked, unfragmented frame (servers never mask)
+fn frame(opcode: u8, payload: &[u8]) -> Vec<u8> {
+    let mut frame = vec![0x80 | opcode];
+    match payload.len() {
+        len if len < 126 => frame.push(len as u8),
+        len if len <= u16::MAX as usize => {
+            frame.push(126);
+            frame.extend((len as u16).to_be_bytes());
+        }
+        len => {
+            frame.push(127);
+            frame.extend((len as u64).to_be_bytes());
+        }
+    }
+    frame.extend_from_slice(payload);
+    frame
+}
+
+// Sec-WebSocket-Accept: base64(SHA-1(key + GUID))
+fn accept_key(key: &str) -> std::io::Result<String> {
+    let mut digest = [0u8; 20];
+    unsafe { BCryptHash(BCRYPT_SHA1_ALG_HANDLE, None, format!("{}{}", key, WEBSOCKET_GUID).as_bytes(), &mut digest) }
+        .ok()
+        .map_err(|e| Error::other(format!("SHA-1 failed: {}", e)))?;
+    Ok(base64::engine::general_purpose::STANDARD.encode(digest))
+}
+
+// Value of `name` in the query string of an HTTP request line ("GET /?token=abc HTTP/1.1")
+fn query_param<'a>(request_line: &'a str, name: &str) -> Option<&'a str> {
+    let target = request_line.split_whitespace().nth(1)?;
+    let (_, query) = target.split_once('?')?;
+    query.split('&').find_map(|pair| pair.strip_prefix(name)?.strip_prefix('='))
+}
@@ -0,0 +1,187 @@
+use serde::{Deserialize, Serialize};
+
+// Spoken casing commands for dictating identifiers: "camel case order item count" becomes
+// orderItemCount. A command applies to the words after it, up to punctuation, a line break, the end
+// phrase ("end case"), or the next command. The phrases are configurable; code mode always uses them,
+// other dictation only when turned on.
+
+pub const CONFIG_FILE: &str = "casing.json";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CasingStyle {
+    Camel,  // orderItemCount
+    Pascal,  // OrderItemCount
+    Snake,  // order_item_count
+    Kebab,  // order-item-count
+    Constant,  // ORDER_ITEM_COUNT
+    Smash,  // orderitemcount
+    AllCaps,  // ORDER ITEM COUNT
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CasingTrigger {
+    pub phrase: String,  // What to say, e.g. "camel case"; "next" may follow it
+    pub style: CasingStyle,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CasingSettings {
+    pub enabled: bool,  // Outside code mode
+    pub triggers: Vec<CasingTrigger>,
+    pub end_phrase: String,  // Ends a command's words early, e.g. "camel case user name end case is empty"
+}
+
+impl Default for CasingSettings {
+    fn default() -> Self {
+        let trigger = |phrase: &str, style| CasingTrigger { phrase: phrase.to_string(), style };
+        Self {
+            enabled: false,
+            triggers: vec![
+                trigger("camel case", CasingStyle::Camel),
+                trigger("pascal case", CasingStyle::Pascal),
+                trigger("snake case", CasingStyle::Snake),
+                trigger("kebab case", CasingStyle::Kebab),
+                trigger("constant case", CasingStyle::Constant),
+                trigger("screaming snake case", CasingStyle::Constant),
+                trigger("no space", CasingStyle::Smash),
+                trigger("all caps", CasingStyle::AllCaps),
+            ],
+            end_phrase: "end case".to_string(),
+        }
+    }
+}
+
+// Reject phrases that can't be matched or that mean two things
+pub fn validate(settings: &CasingSettings) -> Result<(), String> {
+    let mut seen: Vec<Vec<String>> = Vec::new();
+    for phrase in settings.triggers.iter().map(|t| t.phrase.as_str()).chain(std::iter::once(settings.end_phrase.as_str())) {
+        let words = words(phrase);
+        if words.is_empty() {
+            return Err("Casing phrases must contain at least one word".to_string());
+        }
+        if seen.contains(&words) {
+            return Err(format!("\"{}\" is used twice", phrase.trim()));
+        }
+        seen.push(words);
+    }
+    Ok(())
+}
+
+// Lowercase word without surrounding punctuation, for phrase matching
+pub fn normalize(word: &str) -> String {
+    word.trim_matches(|c: char| !c.is_alphanumeric()).to_lowercase()
+}
+
+fn words(phrase: &str) -> Vec<String> {
+    phrase.split_whitespace().map(normalize).filter(|w| !w.is_empty()).collect()
+}
+
+// Number of words `phrase` takes up at `at` in `normalized`, if it's there
+fn phrase_at(normalized: &[String], at: usize, phrase: &str) -> Option<usize> {
+    let phrase = words(phrase);
+    let matched = !phrase.is_empty() && normalized.len() >= at + phrase.len() && phrase.iter().zip(&normalized[at..]).all(|(p, w)| p == w);
+    matched.then_some(phrase.len())
+}
+
+// The casing command at `at`, with the number of words it takes up (longest phrase first)
+pub fn command_at(normalized: &[String], at: usize, triggers: &[CasingTrigger]) -> Option<(usize, CasingStyle)> {
+    let (len, style) = triggers
+        .iter()
+        .filter_map(|trigger| Some((phrase_at(normalized, at, &trigger.phrase)?, trigger.style)))
+        .max_by_key(|(len, _)| *len)?;
+    let len = if normalized.get(at + len).is_some_and(|w| w == "next") { len + 1 } else { len };
+    Some((len, style))
+}
+
+// Whether the end phrase is at `at`, and how many words it takes up
+pub fn end_at(normalized: &[String], at: usize, settings: &CasingSettings) -> Option<usize> {
+    phrase_at(normalized, at, &settings.end_phrase)
+}
+
+// Join lowercase words in `style`
+pub fn format(words: &[String], style: CasingStyle) -> String {
+    let capitalized = |word: &String| -> String {
+        let mut chars = word.chars();
+        chars.next().map(|first| first.to_uppercase().chain(chars).collect()).unwrap_or_default()
+    };
+    match style {
+        CasingStyle::Camel => words.iter().enumerate().map(|(i, w)| if i == 0 { w.clone() } else { capitalized(w) }).collect(),
+        CasingStyle::Pascal => words.iter().map(capitalized).collect(),
+        CasingStyle::Snake => words.join("_"),
+        CasingStyle::Kebab => words.join("-"),
+        CasingStyle::Constant => words.join("_").to_uppercase(),
+        CasingStyle::Smash => words.concat(),
+        CasingStyle::AllCaps => words.join(" ").to_uppercase(),
+    }
+}
+
+// Apply the casing commands in a formatted transcription, keeping its spacing, line breaks, and
+// the punctuation that ends a command's words
+pub fn apply(text: &str, settings: &CasingSettings) -> String {
+    if !settings.enabled {
+        return text.to_string();
+    }
+    // Byte ranges of the words
+    let mut spans = Vec::new();
+    let mut start = None;
+    for (i, c) in text.char_indices() {
+        match (c.is_whitespace(), start) {
+            (false, None) => start = Some(i),
+            (true, Some(s)) => {
+                spans.push((s, i));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(s) = start {
+        spans.push((s, text.len()));
+    }
+    let normalized: Vec<String> = spans.iter().map(|&(s, e)| normalize(&text[s..e])).collect();
+
+    let mut result = String::with_capacity(text.len());
+    let mut copied = 0;  // Text before this is in `result`
+    let mut i = 0;
+    while i < spans.len() {
+        let Some((len, style)) = command_at(&normalized, i, &settings.triggers) else {
+            i += 1;
+            continue;
+        };
+
+        // The command keeps the spacing before it and replaces itself and its words
+        result.push_str(&text[copied..spans[i].0]);
+        i += len;
+        let mut run = Vec::new();
+        let mut tail = "";
+        while i < spans.len() && command_at(&normalized, i, &settings.triggers).is_none() {
+            if !run.is_empty() && text[spans[i - 1].1..spans[i].0].contains('\n') {
+                break;
+            }
+            if let Some(end_len) = end_at(&normalized, i, settings) {
+                i += end_len;
+                break;
+            }
+            let word = &text[spans[i].0..spans[i].1];
+            run.push(normalized[i].clone());
+            i += 1;
+            // Punctuation after a word ends the identifier and stays after it
+            let core_len = word.trim_end_matches(|c: char| !c.is_alphanumeric()).len();
+            if core_len < word.len() {
+                tail = &word[core_len..];
+                break;
+            }
+        }
+        result.push_str(&format(&run, style));
+        result.push_str(tail);
+        copied = spans.get(i - 1).map_or(text.len(), |&(_, end)| end);
+        // Nothing was said after the command: drop the space that was before it
+        if run.is_empty() && tail.is_empty() {
+            let trimmed = result.trim_end_matches(' ').len();
+            result.truncate(trimmed);
+        }
+    }
+    result.push_str(&text[copied..]);
+    result
+}
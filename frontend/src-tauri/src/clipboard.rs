@@ -0,0 +1,283 @@
+use anyhow::Result;
+use std::fmt;
+use windows::Win32::{
+    Foundation::{GlobalFree, HANDLE, HGLOBAL, HWND},
+    System::DataExchange::{
+        CloseClipboard, EmptyClipboard, EnumClipboardFormats, GetClipboardData, GetOpenClipboardWindow,
+        OpenClipboard, SetClipboardData,
+    },
+    System::Memory::{GlobalAlloc, GlobalLock, GlobalSize, GlobalUnlock, GMEM_MOVEABLE},
+    UI::Shell::{DragQueryFileW, HDROP},
+};
+
+const CF_UNICODETEXT: u32 = 13;
+const CF_HDROP: u32 = 15;
+
+// Retry schedule for OpenClipboard: ~10 attempts, backing off from 10ms up to 100ms (~0.6s total)
+const OPEN_ATTEMPTS: u32 = 10;
+const OPEN_INITIAL_BACKOFF_MS: u64 = 10;
+const OPEN_MAX_BACKOFF_MS: u64 = 100;
+
+// Why the clipboard could not be opened after all retries
+#[derive(Debug)]
+pub enum ClipboardError {
+    // Another process kept the clipboard open (usually a clipboard manager)
+    Busy { holder: Option<String> },
+    // Nobody holds it but Windows still refused (secure desktop, UIPI, ...)
+    Denied(String),
+}
+
+impl fmt::Display for ClipboardError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClipboardError::Busy { holder: Some(name) } => write!(f, "Clipboard is busy (held by {})", name),
+            ClipboardError::Busy { holder: None } => write!(f, "Clipboard is busy (held by another application)"),
+            ClipboardError::Denied(reason) => write!(f, "Clipboard access denied: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for ClipboardError {}
+
+// Open the clipboard, retrying with backoff while another app holds it
+fn open_clipboard() -> std::result::Result<(), ClipboardError> {
+    let mut backoff = OPEN_INITIAL_BACKOFF_MS;
+    let mut last_error = String::new();
+
+    for attempt in 1..=OPEN_ATTEMPTS {
+        match unsafe { OpenClipboard(HWND::default()) } {
+            Ok(()) => {
+                if attempt > 1 {
+                    log::info!("📋 Clipboard opened after {} attempts", attempt);
+                }
+                return Ok(());
+            }
+            Err(e) => last_error = e.to_string(),
+        }
+
+        if attempt < OPEN_ATTEMPTS {
+            std::thread::sleep(std::time::Duration::from_millis(backoff));
+            backoff = (backoff * 2).min(OPEN_MAX_BACKOFF_MS);
+        }
+    }
+
+    let holder_window = unsafe { GetOpenClipboardWindow() };
+    if holder_window.0 != 0 {
+        let holder = crate::window_info::process_name_for_window(holder_window);
+        log::warn!("⚠️ Clipboard still held by {:?} after {} attempts", holder, OPEN_ATTEMPTS);
+        Err(ClipboardError::Busy { holder })
+    } else {
+        log::warn!("⚠️ Clipboard access denied after {} attempts: {}", OPEN_ATTEMPTS, last_error);
+        Err(ClipboardError::Denied(last_error))
+    }
+}
+
+// Formats whose data is a GDI handle or owner-drawn rather than an HGLOBAL.
+// These can't be copied byte-for-byte; Windows re-synthesizes CF_BITMAP/CF_ENHMETAFILE
+// from CF_DIB/CF_METAFILEPICT equivalents, so skipping them keeps images intact.
+const CF_BITMAP: u32 = 2;
+const CF_METAFILEPICT: u32 = 3;
+const CF_PALETTE: u32 = 9;
+const CF_ENHMETAFILE: u32 = 14;
+const CF_OWNERDISPLAY: u32 = 0x0080;
+const CF_DSPBITMAP: u32 = 0x0082;
+const CF_DSPMETAFILEPICT: u32 = 0x0083;
+const CF_DSPENHMETAFILE: u32 = 0x008E;
+const CF_PRIVATEFIRST: u32 = 0x0200;
+const CF_GDIOBJLAST: u32 = 0x03FF;
+
+fn is_hglobal_format(format: u32) -> bool {
+    !matches!(
+        format,
+        CF_BITMAP
+            | CF_METAFILEPICT
+            | CF_PALETTE
+            | CF_ENHMETAFILE
+            | CF_OWNERDISPLAY
+            | CF_DSPBITMAP
+            | CF_DSPMETAFILEPICT
+            | CF_DSPENHMETAFILE
+    ) && !(CF_PRIVATEFIRST..=CF_GDIOBJLAST).contains(&format)
+}
+
+// A copy of every HGLOBAL-backed clipboard format (text, HTML, files, DIB, PNG, ...)
+#[derive(Debug, Default)]
+pub struct ClipboardSnapshot {
+    formats: Vec<(u32, Vec<u8>)>,
+}
+
+impl ClipboardSnapshot {
+    pub fn is_empty(&self) -> bool {
+        self.formats.is_empty()
+    }
+}
+
+// Read the bytes behind a clipboard handle (clipboard must be open)
+unsafe fn read_global(handle: HANDLE) -> Option<Vec<u8>> {
+    let hglobal = HGLOBAL(handle.0 as _);
+
+    let locked = GlobalLock(hglobal);
+    if locked.is_null() {
+        return None;
+    }
+
+    let size = GlobalSize(hglobal);
+    let mut data = vec![0u8; size];
+    std::ptr::copy_nonoverlapping(locked as *const u8, data.as_mut_ptr(), size);
+    let _ = GlobalUnlock(hglobal);
+
+    Some(data)
+}
+
+// Hand a copy of `data` to the clipboard under `format` (clipboard must be open and owned)
+unsafe fn write_global(format: u32, data: &[u8]) -> Result<()> {
+    let hmem = GlobalAlloc(GMEM_MOVEABLE, data.len().max(1))
+        .map_err(|e| anyhow::anyhow!("Failed to allocate memory: {}", e))?;
+
+    let locked = GlobalLock(hmem);
+    if locked.is_null() {
+        let _ = GlobalFree(hmem);
+        return Err(anyhow::anyhow!("Failed to lock memory"));
+    }
+
+    std::ptr::copy_nonoverlapping(data.as_ptr(), locked as *mut u8, data.len());
+    let _ = GlobalUnlock(hmem);
+
+    // On success the clipboard owns the memory; on failure we still do
+    if let Err(e) = SetClipboardData(format, HANDLE(hmem.0 as _)) {
+        let _ = GlobalFree(hmem);
+        return Err(anyhow::anyhow!("Failed to set clipboard data (format {}): {}", format, e));
+    }
+
+    Ok(())
+}
+
+// Capture all copyable formats currently on the clipboard
+pub fn snapshot() -> std::result::Result<ClipboardSnapshot, ClipboardError> {
+    open_clipboard()?;
+
+    unsafe {
+        let mut snapshot = ClipboardSnapshot::default();
+        let mut format = EnumClipboardFormats(0);
+        while format != 0 {
+            if is_hglobal_format(format) {
+                if let Ok(handle) = GetClipboardData(format) {
+                    if !handle.is_invalid() {
+                        if let Some(data) = read_global(handle) {
+                            snapshot.formats.push((format, data));
+                        }
+                    }
+                }
+            }
+            format = EnumClipboardFormats(format);
+        }
+
+        let _ = CloseClipboard();
+
+        log::info!("📋 Clipboard snapshot: {} format(s)", snapshot.formats.len());
+        Ok(snapshot)
+    }
+}
+
+// Put a previously captured snapshot back on the clipboard (an empty snapshot clears it)
+pub fn restore(snapshot: &ClipboardSnapshot) -> Result<()> {
+    open_clipboard()?;
+
+    unsafe {
+        if let Err(e) = EmptyClipboard() {
+            let _ = CloseClipboard();
+            return Err(anyhow::anyhow!("Failed to empty clipboard: {}", e));
+        }
+
+        // Restore as many formats as possible; one bad format shouldn't lose the rest
+        for (format, data) in &snapshot.formats {
+            if let Err(e) = write_global(*format, data) {
+                log::warn!("⚠️ {}", e);
+            }
+        }
+
+        let _ = CloseClipboard();
+        Ok(())
+    }
+}
+
+// Set clipboard text (UTF-16, null-terminated)
+pub fn set_text(text_utf16: &[u16]) -> Result<()> {
+    open_clipboard()?;
+
+    unsafe {
+        if let Err(e) = EmptyClipboard() {
+            let _ = CloseClipboard();
+            return Err(anyhow::anyhow!("Failed to empty clipboard: {}", e));
+        }
+
+        let bytes = std::slice::from_raw_parts(
+            text_utf16.as_ptr() as *const u8,
+            std::mem::size_of_val(text_utf16),
+        );
+        let result = write_global(CF_UNICODETEXT, bytes);
+
+        let _ = CloseClipboard();
+        result
+    }
+}
+
+// Read clipboard text (None if the clipboard holds no text)
+pub fn get_text() -> Result<Option<String>> {
+    open_clipboard()?;
+
+    unsafe {
+        let text = match GetClipboardData(CF_UNICODETEXT) {
+            Ok(handle) if !handle.is_invalid() => read_global(handle).map(|bytes| {
+                let utf16: Vec<u16> = bytes.chunks_exact(2).map(|b| u16::from_le_bytes([b[0], b[1]])).collect();
+                let len = utf16.iter().position(|&c| c == 0).unwrap_or(utf16.len());
+                String::from_utf16_lossy(&utf16[..len])
+            }),
+            _ => None,
+        };
+
+        let _ = CloseClipboard();
+        Ok(text)
+    }
+}
+
+// Files on the clipboard, as copied in Explorer or a chat app (empty if it holds no files)
+pub fn get_files() -> Result<Vec<std::path::PathBuf>> {
+    open_clipboard()?;
+
+    unsafe {
+        let mut files = Vec::new();
+        if let Ok(handle) = GetClipboardData(CF_HDROP) {
+            if !handle.is_invalid() {
+                let drop = HDROP(handle.0);
+                let count = DragQueryFileW(drop, u32::MAX, None);
+                for index in 0..count {
+                    let len = DragQueryFileW(drop, index, None) as usize;
+                    let mut name = vec![0u16; len + 1];
+                    let copied = DragQueryFileW(drop, index, Some(&mut name)) as usize;
+                    files.push(std::path::PathBuf::from(String::from_utf16_lossy(&name[..copied])));
+                }
+            }
+        }
+
+        let _ = CloseClipboard();
+        Ok(files)
+    }
+}
+
+// Poll until the clipboard reads back as `text`, or give up after `timeout`. Setting it bumps the
+// sequence number at once, but clipboard sync tools and remote clients can hold it open or replace
+// it for a while after that.
+pub fn wait_for_text(text: &str, timeout: std::time::Duration) -> bool {
+    let deadline = std::time::Instant::now() + timeout;
+    loop {
+        if get_text().is_ok_and(|current| current.as_deref() == Some(text)) {
+            return true;
+        }
+        if std::time::Instant::now() >= deadline {
+            return false;
+        }
+        // Not too often: every read opens the clipboard, which locks everyone else out
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+}
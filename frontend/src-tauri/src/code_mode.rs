@@ -0,0 +1,212 @@
+use serde::{Deserialize, Serialize};
+
+use crate::casing::{self, CasingSettings};
+use crate::{AppState, InjectionSettings};
+use Joining::{Break, Call, Close, Infix, Open, Operator, Quote, Trailing};
+
+// Code mode, for dictating into code editors: a profile (model slot) can turn it on so its
+// recordings skip the text formatting, turn spoken symbols and casing commands ("open paren",
+// "underscore", "camel case user name") into code, and are typed instead of pasted, so editors
+// don't reformat pasted text. The casing commands are the configurable ones from casing.rs.
+
+pub const CONFIG_FILE: &str = "code_mode.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct CodeModeSettings {
+    pub profiles: Vec<String>,  // Model slots that dictate code
+}
+
+// How a symbol joins the surrounding words
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Joining {
+    Open,  // Glued to the next word: "{x", "#include"
+    Call,  // Like Open, and glued to a name before it: "f(x)", "items[i]", but "= (a"
+    Close,  // Glued to the previous word: "x)"
+    Infix,  // Glued to both: "a.b", "a_b"
+    Operator,  // Spaced on both sides: "a = b"
+    Trailing,  // Glued to the previous word, spaced from the next: "a, b"
+    Quote,  // Opens or closes, alternately
+    Break,  // Line breaks and tabs: no spaces on either side
+}
+
+// (phrase, output, joining); phrases are matched case-insensitively as whole words
+const SYMBOLS: &[(&str, &str, Joining)] = &[
+    ("open paren", "(", Call),
+    ("open parenthesis", "(", Call),
+    ("close paren", ")", Close),
+    ("close parenthesis", ")", Close),
+    ("open bracket", "[", Call),
+    ("close bracket", "]", Close),
+    ("open brace", "{", Open),
+    ("open curly", "{", Open),
+    ("close brace", "}", Close),
+    ("close curly", "}", Close),
+    ("open angle", "<", Open),
+    ("close angle", ">", Close),
+    ("dot", ".", Infix),
+    ("underscore", "_", Infix),
+    ("slash", "/", Infix),
+    ("backslash", "\\", Infix),
+    ("double colon", "::", Infix),
+    ("arrow", "->", Infix),
+    ("equals", "=", Operator),
+    ("double equals", "==", Operator),
+    ("triple equals", "===", Operator),
+    ("not equals", "!=", Operator),
+    ("plus equals", "+=", Operator),
+    ("minus equals", "-=", Operator),
+    ("less than", "<", Operator),
+    ("greater than", ">", Operator),
+    ("less or equal", "<=", Operator),
+    ("greater or equal", ">=", Operator),
+    ("plus", "+", Operator),
+    ("minus", "-", Operator),
+    ("times", "*", Operator),
+    ("divided by", "/", Operator),
+    ("modulo", "%", Operator),
+    ("and and", "&&", Operator),
+    ("or or", "||", Operator),
+    ("fat arrow", "=>", Operator),
+    ("comma", ",", Trailing),
+    ("colon", ":", Trailing),
+    ("semicolon", ";", Trailing),
+    ("quote", "\"", Quote),
+    ("single quote", "'", Quote),
+    ("backtick", "`", Quote),
+    ("hash", "#", Open),
+    ("at sign", "@", Open),
+    ("dollar", "$", Open),
+    ("bang", "!", Open),
+    ("ampersand", "&", Open),
+    ("star", "*", Open),
+    ("pipe", "|", Operator),
+    ("tilde", "~", Open),
+    ("caret", "^", Operator),
+    ("percent", "%", Open),
+    ("question mark", "?", Trailing),
+    ("new line", "\n", Break),
+    ("tab", "\t", Break),
+];
+
+// Whether recordings in the current profile dictate code
+pub async fn active(state: &AppState) -> bool {
+    let slot = state.recording_slot.lock().await.clone();
+    state.code_mode.lock().await.profiles.contains(&slot)
+}
+
+// Injection settings for the current profile: code is typed, never pasted
+pub async fn injection(state: &AppState, injection: &InjectionSettings) -> InjectionSettings {
+    let mut injection = injection.clone();
+    if active(state).await {
+        injection.typed = true;
+    }
+    injection
+}
+
+enum Token<'a> {
+    Word(&'a str),
+    Symbol(&'a str, Joining),
+    Cased(String),
+}
+
+// Turn a transcription into code: model punctuation and sentence capitals are dropped, spoken
+// symbols become characters, and casing commands join the words up to the next symbol, command, or
+// end phrase
+pub fn apply(text: &str, casing: &CasingSettings) -> String {
+    let stripped = crate::spoken_punctuation::strip_model_punctuation(text);
+    let words: Vec<&str> = stripped.split_whitespace().collect();
+    let normalized: Vec<String> = words.iter().map(|w| casing::normalize(w)).collect();
+    let matches = |phrase: &str, at: usize| -> Option<usize> {
+        let phrase: Vec<&str> = phrase.split(' ').collect();
+        (normalized.len() >= at + phrase.len() && phrase.iter().zip(&normalized[at..]).all(|(p, w)| p == w)).then_some(phrase.len())
+    };
+    // Longest phrases first so "double equals" wins over "equals"
+    let symbol_at = |at: usize| {
+        SYMBOLS.iter().filter_map(|(phrase, output, joining)| Some((matches(phrase, at)?, *output, *joining))).max_by_key(|(len, _, _)| *len)
+    };
+    let casing_at = |at: usize| casing::command_at(&normalized, at, &casing.triggers);
+
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < words.len() {
+        if let Some((len, style)) = casing_at(i) {
+            i += len;
+            let start = i;
+            while i < words.len() && symbol_at(i).is_none() && casing_at(i).is_none() && casing::end_at(&normalized, i, casing).is_none() {
+                i += 1;
+            }
+            tokens.push(Token::Cased(casing::format(&normalized[start..i], style)));
+            if let Some(end_len) = casing::end_at(&normalized, i, casing) {
+                i += end_len;
+            }
+        } else if let Some((len, output, joining)) = symbol_at(i) {
+            tokens.push(Token::Symbol(output, joining));
+            i += len;
+        } else {
+            tokens.push(Token::Word(words[i]));
+            i += 1;
+        }
+    }
+    join(&tokens)
+}
+
+fn join(tokens: &[Token]) -> String {
+    let mut result = String::new();
+    let mut glue_next = true;  // No space before the next word (start of text, after "(" or a line break)
+    let mut quote_open = false;
+
+    for token in tokens {
+        let (output, joining) = match token {
+            Token::Word(word) => (uncapitalize(word), None),
+            Token::Cased(word) => (word.clone(), None),
+            Token::Symbol(output, joining) => (output.to_string(), Some(*joining)),
+        };
+        let joining = match joining {
+            Some(Quote) => {
+                quote_open = !quote_open;
+                Some(if quote_open { Open } else { Close })
+            }
+            joining => joining,
+        };
+
+        match joining {
+            None | Some(Open) | Some(Call) => {
+                let call = joining == Some(Call) && result.ends_with(|c: char| c.is_alphanumeric() || matches!(c, '_' | ')' | ']'));
+                if !glue_next && !call && !output.is_empty() {
+                    result.push(' ');
+                }
+                result.push_str(&output);
+                glue_next = joining.is_some();
+            }
+            // Quotes were resolved to Open or Close above
+            Some(Close) | Some(Trailing) | Some(Quote) => {
+                result.push_str(&output);
+                glue_next = false;
+            }
+            Some(Infix) | Some(Break) => {
+                let trimmed_len = result.trim_end_matches(' ').len();
+                result.truncate(trimmed_len);
+                result.push_str(&output);
+                glue_next = true;
+            }
+            Some(Operator) => {
+                if !result.is_empty() && !glue_next {
+                    result.push(' ');
+                }
+                result.push_str(&output);
+                glue_next = false;
+            }
+        }
+    }
+    result
+}
+
+// "Print" -> "print" (the model capitalizes sentences); "HTTP" and "MyClass" stay as they are
+fn uncapitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) if first.is_uppercase() && chars.all(|c| !c.is_uppercase()) => first.to_lowercase().chain(word.chars().skip(1)).collect(),
+        _ => word.to_string(),
+    }
+}
@@ -0,0 +1,91 @@
+use serde::Serialize;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+use crate::{backend, AppState};
+
+// The backend copies each recording to disk while it is captured and deletes the copy once the
+// recording stops. A copy still there when it starts means the app or backend died mid-recording;
+// the backend keeps it aside and the user is offered to transcribe or discard it.
+
+// How long to wait for the backend to answer after startup
+const BACKEND_ATTEMPTS: u32 = 30;
+const BACKEND_RETRY: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Clone, Serialize)]
+pub struct OrphanedRecording {
+    pub duration_secs: f64,
+    pub recorded_at: i64,  // Unix time in milliseconds of the last audio written
+}
+
+// Ask the backend for an interrupted recording once it is up, and tell the user about it
+pub fn spawn_check(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        for attempt in 0..BACKEND_ATTEMPTS {
+            if attempt > 0 {
+                tokio::time::sleep(BACKEND_RETRY).await;
+            }
+            let Ok(resp) = backend::client().get(backend::url("orphan")).send().await else { continue };
+            let Ok(data) = resp.json::<serde_json::Value>().await else { return };
+            if data.get("found").and_then(|f| f.as_bool()) != Some(true) {
+                return;
+            }
+
+            let recording = OrphanedRecording {
+                duration_secs: data.get("duration").and_then(|d| d.as_f64()).unwrap_or_default(),
+                recorded_at: data.get("recorded_at").and_then(|t| t.as_f64()).map(|t| (t * 1000.0) as i64).unwrap_or_default(),
+            };
+            log::info!("🩹 Interrupted recording found ({:.1}s)", recording.duration_secs);
+            let state: tauri::State<AppState> = app.state();
+            *state.orphaned_recording.lock().await = Some(recording.clone());
+
+            if let Some(win) = app.get_webview_window("main") {
+                if let Ok(json) = serde_json::to_string(&recording) {
+                    let _ = win.eval(format!("showOrphanedRecording({})", json));
+                }
+            }
+            crate::show_toast(
+                &app,
+                &crate::locale::tr("toast.orphan"),
+                &crate::locale::tr_with("toast.orphan_body", &[("duration", &format_duration(recording.duration_secs))]),
+            );
+            return;
+        }
+        log::warn!("⚠️ Backend didn't answer; not checking for an interrupted recording");
+    });
+}
+
+fn format_duration(secs: f64) -> String {
+    let secs = secs.round() as u64;
+    if secs < 60 {
+        format!("{}s", secs)
+    } else {
+        format!("{}m {}s", secs / 60, secs % 60)
+    }
+}
+
+// Hand the interrupted recording to the backend as the one to re-transcribe
+pub async fn restore(state: &AppState) -> Result<(), String> {
+    let resp = backend::client()
+        .post(backend::url("orphan/restore"))
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {}", e))?;
+    let data = resp.json::<serde_json::Value>().await.map_err(|e| format!("Invalid response: {}", e))?;
+    if data.get("status").and_then(|s| s.as_str()) != Some("success") {
+        let message = data.get("message").and_then(|m| m.as_str()).unwrap_or("Could not restore the recording");
+        return Err(message.to_string());
+    }
+    *state.orphaned_recording.lock().await = None;
+    Ok(())
+}
+
+pub async fn discard(state: &AppState) -> Result<(), String> {
+    backend::client()
+        .post(backend::url("orphan/discard"))
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {}", e))?;
+    *state.orphaned_recording.lock().await = None;
+    Ok(())
+}
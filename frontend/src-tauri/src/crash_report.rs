@@ -0,0 +1,195 @@
+use anyhow::Result;
+use serde::Serialize;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
+use std::time::Instant;
+use tauri::{AppHandle, Manager};
+use windows::core::{HSTRING, PCWSTR};
+use windows::Win32::Foundation::CloseHandle;
+use windows::Win32::Storage::FileSystem::{CreateFileW, CREATE_ALWAYS, FILE_ATTRIBUTE_NORMAL, FILE_GENERIC_WRITE, FILE_SHARE_MODE};
+use windows::Win32::System::Diagnostics::Debug::{
+    MiniDumpWithIndirectlyReferencedMemory, MiniDumpWithThreadInfo, MiniDumpWriteDump, SetUnhandledExceptionFilter,
+    EXCEPTION_POINTERS, MINIDUMP_EXCEPTION_INFORMATION,
+};
+use windows::Win32::System::Threading::{GetCurrentProcess, GetCurrentProcessId, GetCurrentThreadId};
+
+use crate::{backend, storage, zip::ZipWriter, AppState};
+
+// On a panic or a native crash, a crash log (what happened, what the app was doing, a backtrace)
+// and a minidump are written next to the app's logs. The next launch offers to bundle them with
+// the logs for a bug report.
+
+pub const FILE_PREFIX: &str = "crash-";
+// Holds the name of the last crash report until the user exports or dismisses it
+const PENDING_FILE: &str = "crash-pending";
+// Let Windows carry on with its default handling (Windows Error Reporting) after ours
+const EXCEPTION_CONTINUE_SEARCH: i32 = 0;
+
+struct Reporter {
+    app: AppHandle,
+    dir: PathBuf,
+    started: Instant,
+}
+
+static REPORTER: OnceLock<Reporter> = OnceLock::new();
+// Only the first crash is reported; a panic while unwinding from another would otherwise overwrite it
+static REPORTED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CrashReport {
+    pub name: String,
+    pub time: i64,  // Unix time in milliseconds
+    pub reason: String,
+}
+
+// Install the panic hook and the unhandled exception filter (call once logging is set up)
+pub fn install(app: &AppHandle) {
+    let dir = match storage::log_dir(app) {
+        Ok(dir) => dir,
+        Err(e) => {
+            log::warn!("⚠️ Crash reports disabled: {}", e);
+            return;
+        }
+    };
+    if REPORTER.set(Reporter { app: app.clone(), dir, started: Instant::now() }).is_err() {
+        return;
+    }
+
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        write_report(&format!("Panic: {}", info), true, None);
+        default_hook(info);
+    }));
+    unsafe { SetUnhandledExceptionFilter(Some(on_unhandled_exception)) };
+}
+
+unsafe extern "system" fn on_unhandled_exception(info: *const EXCEPTION_POINTERS) -> i32 {
+    let code = (*(*info).ExceptionRecord).ExceptionCode.0;
+    // A backtrace isn't safe to take here; the minidump has the stacks
+    write_report(&format!("Unhandled exception 0x{:08X}", code as u32), false, Some(info));
+    EXCEPTION_CONTINUE_SEARCH
+}
+
+fn write_report(reason: &str, backtrace: bool, exception: Option<*const EXCEPTION_POINTERS>) {
+    let Some(reporter) = REPORTER.get() else { return };
+    if REPORTED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+
+    let name = format!("{}{}", FILE_PREFIX, chrono::Local::now().format("%Y%m%d-%H%M%S"));
+    let _ = std::fs::create_dir_all(&reporter.dir);
+    write_minidump(&reporter.dir.join(format!("{}.dmp", name)), exception);
+
+    let mut text = summary(reporter, reason);
+    if backtrace {
+        let _ = write!(text, "\nBacktrace:\n{}\n", std::backtrace::Backtrace::force_capture());
+    }
+    let _ = std::fs::write(reporter.dir.join(format!("{}.log", name)), text);
+    let _ = std::fs::write(reporter.dir.join(PENDING_FILE), &name);
+    log::error!("💥 {} (crash report {})", reason, name);
+}
+
+// What the app was doing; state that is locked right now is skipped rather than waited for
+fn summary(reporter: &Reporter, reason: &str) -> String {
+    let state: tauri::State<AppState> = reporter.app.state();
+    let locked = |value: Option<String>| value.unwrap_or_else(|| "(busy)".to_string());
+    let thread = std::thread::current();
+
+    let mut text = String::new();
+    let _ = writeln!(text, "Whisper4Windows {}", reporter.app.package_info().version);
+    let _ = writeln!(text, "Time: {}", chrono::Local::now().format("%Y-%m-%d %H:%M:%S"));
+    let _ = writeln!(text, "Uptime: {}s", reporter.started.elapsed().as_secs());
+    let _ = writeln!(text, "Reason: {}", reason);
+    let _ = writeln!(text, "Thread: {}", thread.name().unwrap_or("unnamed"));
+    let _ = writeln!(text, "Backend: {}", backend::describe());
+    let _ = writeln!(text, "Recording state: {}", locked(state.recording_state.try_lock().ok().map(|s| format!("{:?}", *s))));
+    let _ = writeln!(text, "Paused: {}", locked(state.is_paused.try_lock().ok().map(|p| p.to_string())));
+    let _ = writeln!(text, "Model: {}", locked(state.selected_model.try_lock().ok().map(|m| m.clone())));
+    let _ = writeln!(text, "Device: {}", locked(state.selected_device.try_lock().ok().map(|d| d.clone())));
+    let _ = writeln!(text, "Language: {}", locked(state.selected_language.try_lock().ok().map(|l| l.clone())));
+    let _ = writeln!(text, "Output mode: {}", locked(state.output_mode.try_lock().ok().map(|o| format!("{:?}", *o))));
+    text
+}
+
+fn write_minidump(path: &Path, exception: Option<*const EXCEPTION_POINTERS>) {
+    let path = HSTRING::from(path.to_string_lossy().as_ref());
+    unsafe {
+        let Ok(file) = CreateFileW(
+            PCWSTR(path.as_ptr()),
+            FILE_GENERIC_WRITE.0,
+            FILE_SHARE_MODE(0),
+            None,
+            CREATE_ALWAYS,
+            FILE_ATTRIBUTE_NORMAL,
+            None,
+        ) else {
+            return;
+        };
+        let exception_info = exception.map(|pointers| MINIDUMP_EXCEPTION_INFORMATION {
+            ThreadId: GetCurrentThreadId(),
+            ExceptionPointers: pointers as *mut _,
+            ClientPointers: false.into(),
+        });
+        let _ = MiniDumpWriteDump(
+            GetCurrentProcess(),
+            GetCurrentProcessId(),
+            file,
+            MiniDumpWithThreadInfo | MiniDumpWithIndirectlyReferencedMemory,
+            exception_info.as_ref().map(|info| info as *const _),
+            None,
+            None,
+        );
+        let _ = CloseHandle(file);
+    }
+}
+
+// The crash report the user hasn't exported or dismissed yet
+pub fn pending(app: &AppHandle) -> Option<CrashReport> {
+    let dir = storage::log_dir(app).ok()?;
+    let name = std::fs::read_to_string(dir.join(PENDING_FILE)).ok()?.trim().to_string();
+    let log = dir.join(format!("{}.log", name));
+    let text = std::fs::read_to_string(&log).unwrap_or_default();
+    let time = std::fs::metadata(&log)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or_default();
+    let reason = text.lines().find_map(|line| line.strip_prefix("Reason: ")).unwrap_or("Unknown").to_string();
+    Some(CrashReport { name, time, reason })
+}
+
+pub fn dismiss(app: &AppHandle) -> Result<()> {
+    match std::fs::remove_file(storage::log_dir(app)?.join(PENDING_FILE)) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+        _ => Ok(()),
+    }
+}
+
+// A ZIP of the crash log, the minidump, and the app logs from around the crash
+pub fn diagnostics(app: &AppHandle, report: &CrashReport) -> Result<Vec<u8>> {
+    let dir = storage::log_dir(app)?;
+    let mut files = vec![format!("{}.log", report.name), format!("{}.dmp", report.name), "app.log".to_string()];
+    // The session that crashed may have been rotated out of app.log
+    let newest_rotated = std::fs::read_dir(&dir)?
+        .flatten()
+        .filter(|entry| {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            name.starts_with("app_") && name.ends_with(".log")
+        })
+        .max_by_key(|entry| entry.metadata().and_then(|m| m.modified()).ok());
+    if let Some(entry) = newest_rotated {
+        files.push(entry.file_name().to_string_lossy().into_owned());
+    }
+
+    let mut zip = ZipWriter::default();
+    for file in files {
+        // Missing pieces (a dump that couldn't be written, deleted logs) are left out
+        if let Ok(contents) = std::fs::read(dir.join(&file)) {
+            zip.add(&file, &contents);
+        }
+    }
+    Ok(zip.finish())
+}
@@ -0,0 +1,51 @@
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use crate::{backend, quick_switch};
+
+// Fine-tuned or otherwise custom Whisper models, given as a local folder or a Hugging Face ID.
+// The source itself is the model's identifier: it's what gets selected, stored in slots, and
+// sent to the backend as `model_size`, which hands it to faster-whisper unchanged.
+
+pub const CONFIG_FILE: &str = "custom_models.json";
+
+// Validating a Hugging Face model fetches its file list and vocabulary
+const VALIDATE_TIMEOUT: Duration = Duration::from_secs(2 * 60);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomModel {
+    pub name: String,  // Shown in the model lists
+    pub source: String,  // Local folder or Hugging Face ID
+    pub english_only: bool,  // Detected from the vocabulary when the model was added
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct CustomModelStore {
+    pub models: Vec<CustomModel>,
+}
+
+// Ask the backend whether `source` holds a CTranslate2 Whisper model; returns whether it's English-only
+pub async fn validate(source: &str) -> Result<bool, String> {
+    let resp = backend::client()
+        .post(backend::url("models/validate"))
+        .timeout(VALIDATE_TIMEOUT)
+        .json(&serde_json::json!({ "source": source }))
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {}", e))?;
+    let data = resp.json::<serde_json::Value>().await.map_err(|e| format!("Invalid response: {}", e))?;
+    if data.get("status").and_then(|s| s.as_str()) != Some("success") {
+        let message = data.get("message").and_then(|m| m.as_str()).unwrap_or("Not a valid Whisper model");
+        return Err(message.to_string());
+    }
+    Ok(data.get("english_only").and_then(|e| e.as_bool()).unwrap_or(false))
+}
+
+// English-only check that also knows the custom models
+pub fn is_english_only(custom: &[CustomModel], model: &str) -> bool {
+    match custom.iter().find(|m| m.source == model) {
+        Some(m) => m.english_only,
+        None => quick_switch::is_english_only(model),
+    }
+}
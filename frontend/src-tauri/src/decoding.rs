@@ -0,0 +1,54 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::AppState;
+
+// Advanced backend settings: the precision the model is loaded with and how it decodes. The
+// defaults favour speed for dictation; a model slot can override them, e.g. a wider beam for the
+// accurate model.
+
+pub const CONFIG_FILE: &str = "decoding.json";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DecodingSettings {
+    pub compute_type: String,  // auto, int8, int8_float16, float16, float32
+    pub beam_size: u32,  // 1 = greedy decoding, the fastest
+    pub temperature: f32,  // 0 = deterministic
+    pub condition_on_previous_text: bool,  // Use earlier text as context; more consistent, but can repeat itself
+    pub no_speech_threshold: f32,  // Segments more likely than this to be silence are skipped
+}
+
+impl Default for DecodingSettings {
+    fn default() -> Self {
+        Self {
+            compute_type: "auto".to_string(),
+            beam_size: 1,
+            temperature: 0.0,
+            condition_on_previous_text: false,
+            no_speech_threshold: 0.6,
+        }
+    }
+}
+
+impl DecodingSettings {
+    // Add the settings to a /start or /retranscribe request body
+    pub fn add_to_request(&self, body: &mut serde_json::Value) {
+        if let (Some(body), Ok(serde_json::Value::Object(fields))) = (body.as_object_mut(), serde_json::to_value(self)) {
+            body.extend(fields);
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AdvancedSettings {
+    pub defaults: DecodingSettings,
+    pub overrides: HashMap<String, DecodingSettings>,  // Model slot -> settings used instead of the defaults
+}
+
+// The settings a recording in `slot` uses
+pub async fn resolve(state: &AppState, slot: &str) -> DecodingSettings {
+    let advanced = state.advanced.lock().await;
+    advanced.overrides.get(slot).unwrap_or(&advanced.defaults).clone()
+}
@@ -0,0 +1,110 @@
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::{AppState, OutputMode};
+
+// How often new segments are fetched while dictating
+const SEGMENT_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(300);
+
+// What has already been delivered during the current continuous dictation
+#[derive(Debug, Default)]
+pub struct DictationProgress {
+    pub last_segment_id: u64,  // Segments with ids up to this one were injected already
+    pub delivered: String,  // Everything injected so far (formatted), becomes the last transcription
+}
+
+// A transcribed segment as returned by the backend
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct Segment {
+    pub id: u64,
+    pub text: String,
+}
+
+// Poll the backend for new segments and inject each one while the recording `session` lasts
+pub fn spawn_segment_poller(app: AppHandle, session: u64) {
+    tauri::async_runtime::spawn(async move {
+        let client = crate::backend::client();
+
+        loop {
+            tokio::time::sleep(SEGMENT_POLL_INTERVAL).await;
+
+            let state: tauri::State<AppState> = app.state();
+            if *state.recording_session.lock().await != session || !crate::recording_state::is_recording(&state).await {
+                return;
+            }
+
+            let after = state.dictation.lock().await.last_segment_id;
+            let segments = match client
+                .get(crate::backend::url(&format!("segments?after={}", after)))
+                .send()
+                .await
+            {
+                Ok(resp) => match resp.json::<serde_json::Value>().await {
+                    Ok(data) => parse_segments(&data),
+                    Err(_) => continue,
+                },
+                Err(_) => continue,
+            };
+
+            if !segments.is_empty() {
+                deliver_segments(&app, &segments).await;
+            }
+        }
+    });
+}
+
+pub fn parse_segments(data: &serde_json::Value) -> Vec<Segment> {
+    data.get("segments")
+        .cloned()
+        .and_then(|segments| serde_json::from_value(segments).ok())
+        .unwrap_or_default()
+}
+
+// Inject segments not delivered yet, in order, separated from what came before.
+// Holding the progress lock for the whole delivery keeps the poller and the final flush on stop
+// from injecting the same segment twice or out of order.
+pub async fn deliver_segments(app: &AppHandle, segments: &[Segment]) {
+    let state: tauri::State<AppState> = app.state();
+    let mut progress = state.dictation.lock().await;
+
+    let injection = state.injection.lock().await.clone();
+    let injection = crate::profile_injection(&state, &injection).await;
+    let output_mode = crate::delivery_output_mode(&state).await;
+    let language = crate::selected_language_code(&state).await;
+
+    for segment in segments {
+        if segment.id <= progress.last_segment_id {
+            continue;
+        }
+        let mut text = crate::process_transcript(&state, &segment.text, language.as_deref()).await;
+        progress.last_segment_id = segment.id;
+        if text.is_empty() {
+            continue;
+        }
+        if !progress.delivered.is_empty() && !progress.delivered.ends_with(char::is_whitespace) {
+            text.insert(0, ' ');
+        }
+
+        log::info!("🗣️ Segment {}: {}", segment.id, text);
+        let result = if output_mode == OutputMode::Scratchpad {
+            crate::scratchpad::append(app, &state, &text, progress.delivered.is_empty()).await;
+            Ok(())
+        } else if output_mode == OutputMode::ClipboardOnly {
+            // Keep the whole dictation so far on the clipboard
+            crate::copy_text_to_clipboard(&format!("{}{}", progress.delivered, text))
+        } else {
+            crate::inject_text(&text, output_mode == OutputMode::Both, &injection)
+        };
+
+        match result {
+            Ok(()) => {
+                progress.delivered.push_str(&text);
+                crate::caption_server::publish(&state, crate::caption_server::Caption::Segment { id: segment.id, text }).await;
+            }
+            Err(e) => {
+                log::error!("❌ Segment injection failed: {}", e);
+                crate::show_toast(app, &crate::locale::tr("toast.paste_failed"), &e.to_string());
+                let _ = app.emit("injection-error", e.to_string());
+            }
+        }
+    }
+}
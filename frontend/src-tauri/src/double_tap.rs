@@ -0,0 +1,166 @@
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, AtomicU16, AtomicU32, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::AppHandle;
+use windows::Win32::{
+    Foundation::{LPARAM, LRESULT, WPARAM},
+    UI::Input::KeyboardAndMouse::{VIRTUAL_KEY, VK_LCONTROL, VK_LMENU, VK_LSHIFT, VK_RCONTROL, VK_RMENU, VK_RSHIFT},
+    UI::WindowsAndMessaging::{
+        CallNextHookEx, HC_ACTION, HHOOK, KBDLLHOOKSTRUCT, LLKHF_INJECTED, WH_KEYBOARD_LL, WM_KEYDOWN, WM_KEYUP,
+        WM_SYSKEYDOWN, WM_SYSKEYUP,
+    },
+};
+
+pub const CONFIG_FILE: &str = "double_tap.json";
+
+// Modifier key whose double-tap toggles recording
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum DoubleTapKey {
+    #[default]
+    RightCtrl,
+    LeftCtrl,
+    RightAlt,
+    LeftAlt,
+    RightShift,
+    LeftShift,
+}
+
+impl DoubleTapKey {
+    fn virtual_key(self) -> VIRTUAL_KEY {
+        match self {
+            DoubleTapKey::RightCtrl => VK_RCONTROL,
+            DoubleTapKey::LeftCtrl => VK_LCONTROL,
+            DoubleTapKey::RightAlt => VK_RMENU,
+            DoubleTapKey::LeftAlt => VK_LMENU,
+            DoubleTapKey::RightShift => VK_RSHIFT,
+            DoubleTapKey::LeftShift => VK_LSHIFT,
+        }
+    }
+}
+
+// Double-tap trigger config (the keyboard hook is only installed while enabled)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DoubleTapSettings {
+    pub enabled: bool,
+    pub key: DoubleTapKey,
+    pub interval_ms: u64,  // Max time between the first release and the second press
+}
+
+impl Default for DoubleTapSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            key: DoubleTapKey::RightCtrl,
+            interval_ms: 300,
+        }
+    }
+}
+
+// A press held longer than this is a modifier being used, not a tap
+const MAX_TAP_HOLD: Duration = Duration::from_millis(250);
+
+static APP: Mutex<Option<AppHandle>> = Mutex::new(None);
+static TARGET_KEY: AtomicU16 = AtomicU16::new(0);
+static INTERVAL_MS: AtomicU32 = AtomicU32::new(300);
+// Thread running the hook's message loop (0 = hook not installed)
+static HOOK_THREAD: AtomicU32 = AtomicU32::new(0);
+// Set while global hotkeys are released for a blocklisted app
+static SUSPENDED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Default)]
+struct TapState {
+    pressed_at: Option<Instant>,  // Target key is currently held since this time
+    last_tap_at: Option<Instant>,  // Release time of the previous clean tap
+}
+
+static TAP_STATE: Mutex<TapState> = Mutex::new(TapState { pressed_at: None, last_tap_at: None });
+
+// Install, reconfigure, or remove the keyboard hook to match `settings`
+pub fn apply(app: &AppHandle, settings: &DoubleTapSettings) -> Result<(), String> {
+    *APP.lock().unwrap() = Some(app.clone());
+    TARGET_KEY.store(settings.key.virtual_key().0, Ordering::SeqCst);
+    INTERVAL_MS.store(settings.interval_ms.min(u32::MAX as u64) as u32, Ordering::SeqCst);
+    *TAP_STATE.lock().unwrap() = TapState::default();
+
+    let running = HOOK_THREAD.load(Ordering::SeqCst);
+    if settings.enabled && running == 0 {
+        let thread_id = crate::ll_hook::install(WH_KEYBOARD_LL, Some(keyboard_proc))?;
+        HOOK_THREAD.store(thread_id, Ordering::SeqCst);
+        log::info!("⌨️ Double-tap hook installed ({:?} within {}ms)", settings.key, settings.interval_ms);
+    } else if !settings.enabled && running != 0 {
+        HOOK_THREAD.store(0, Ordering::SeqCst);
+        crate::ll_hook::uninstall(running);
+        log::info!("⌨️ Double-tap hook removed");
+    }
+    Ok(())
+}
+
+// Ignore double-taps without touching the hook (used by the per-app blocklist)
+pub fn set_suspended(suspended: bool) {
+    SUSPENDED.store(suspended, Ordering::SeqCst);
+}
+
+// Feed one key event into the tap detector; returns true when a double-tap completes
+fn on_key(vk: u16, pressed: bool) -> bool {
+    let Ok(mut state) = TAP_STATE.try_lock() else { return false };
+    let now = Instant::now();
+
+    if vk != TARGET_KEY.load(Ordering::SeqCst) {
+        // Any other key in between (including Ctrl+C style chords) breaks the sequence
+        *state = TapState::default();
+        return false;
+    }
+
+    if pressed {
+        // Ignore auto-repeat while the key is held
+        if state.pressed_at.is_none() {
+            state.pressed_at = Some(now);
+        }
+        return false;
+    }
+
+    let Some(pressed_at) = state.pressed_at.take() else { return false };
+    if now.duration_since(pressed_at) > MAX_TAP_HOLD {
+        state.last_tap_at = None;
+        return false;
+    }
+
+    let interval = Duration::from_millis(INTERVAL_MS.load(Ordering::SeqCst) as u64);
+    match state.last_tap_at {
+        Some(last) if pressed_at.duration_since(last) <= interval => {
+            state.last_tap_at = None;
+            true
+        }
+        _ => {
+            state.last_tap_at = Some(now);
+            false
+        }
+    }
+}
+
+unsafe extern "system" fn keyboard_proc(code: i32, wparam: WPARAM, lparam: LPARAM) -> LRESULT {
+    if code == HC_ACTION as i32 {
+        let info = &*(lparam.0 as *const KBDLLHOOKSTRUCT);
+        let pressed = match wparam.0 as u32 {
+            WM_KEYDOWN | WM_SYSKEYDOWN => Some(true),
+            WM_KEYUP | WM_SYSKEYUP => Some(false),
+            _ => None,
+        };
+
+        // Injected keys include our own Ctrl+V, which must not count as taps
+        if let Some(pressed) = pressed.filter(|_| info.flags.0 & LLKHF_INJECTED.0 == 0) {
+            if on_key(info.vkCode as u16, pressed) && !SUSPENDED.load(Ordering::SeqCst) {
+                if let Some(app) = APP.lock().unwrap().clone() {
+                    log::info!("⌨️ Double-tap detected");
+                    crate::run_hotkey_action(app, "toggle".to_string());
+                }
+            }
+        }
+    }
+
+    // Never swallow the modifier itself - it still has to work as a normal key
+    CallNextHookEx(HHOOK::default(), code, wparam, lparam)
+}
@@ -0,0 +1,66 @@
+use anyhow::{Context, Result};
+use std::path::Path;
+use windows::Win32::{
+    Foundation::{LocalFree, HLOCAL},
+    Security::Cryptography::{CryptProtectData, CryptUnprotectData, CRYPTPROTECT_UI_FORBIDDEN, CRYPT_INTEGER_BLOB},
+};
+
+// Marks files written by `write_file`, so plaintext files from older versions can be told apart
+const MAGIC: &[u8] = b"W4WDPAPI1\n";
+
+// Encrypt for the current Windows user (only this user on this machine can decrypt)
+pub fn protect(data: &[u8]) -> Result<Vec<u8>> {
+    let input = blob(data);
+    let mut output = CRYPT_INTEGER_BLOB::default();
+    unsafe {
+        CryptProtectData(&input, None, None, None, None, CRYPTPROTECT_UI_FORBIDDEN, &mut output)
+            .context("CryptProtectData failed")?;
+        Ok(take(output))
+    }
+}
+
+pub fn unprotect(data: &[u8]) -> Result<Vec<u8>> {
+    let input = blob(data);
+    let mut output = CRYPT_INTEGER_BLOB::default();
+    unsafe {
+        CryptUnprotectData(&input, None, None, None, None, CRYPTPROTECT_UI_FORBIDDEN, &mut output)
+            .context("CryptUnprotectData failed (file written by another user or machine?)")?;
+        Ok(take(output))
+    }
+}
+
+fn blob(data: &[u8]) -> CRYPT_INTEGER_BLOB {
+    CRYPT_INTEGER_BLOB { cbData: data.len() as u32, pbData: data.as_ptr() as *mut u8 }
+}
+
+// Copy a DPAPI output buffer and release it
+unsafe fn take(output: CRYPT_INTEGER_BLOB) -> Vec<u8> {
+    let data = std::slice::from_raw_parts(output.pbData, output.cbData as usize).to_vec();
+    let _ = LocalFree(HLOCAL(output.pbData as *mut _));
+    data
+}
+
+// Contents of a file and whether it was encrypted (older versions wrote plaintext; None if missing)
+pub fn read_file(path: &Path) -> Result<Option<(Vec<u8>, bool)>> {
+    let contents = match std::fs::read(path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e).with_context(|| format!("Failed to read {}", path.display())),
+    };
+
+    match contents.strip_prefix(MAGIC) {
+        Some(encrypted) => Ok(Some((unprotect(encrypted)?, true))),
+        None => Ok(Some((contents, false))),
+    }
+}
+
+// Encrypt and write a file (via a temp file so a crash can't truncate it)
+pub fn write_file(path: &Path, data: &[u8]) -> Result<()> {
+    let mut contents = MAGIC.to_vec();
+    contents.extend(protect(data)?);
+
+    let tmp_path = path.with_extension("tmp");
+    std::fs::write(&tmp_path, contents)?;
+    std::fs::rename(&tmp_path, path)?;
+    Ok(())
+}
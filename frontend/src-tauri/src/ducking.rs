@@ -0,0 +1,127 @@
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use windows::core::{ComInterface, PWSTR};
+use windows::Win32::Foundation::S_OK;
+use windows::Win32::Media::Audio::{
+    eRender, IAudioSessionControl2, IAudioSessionManager2, IMMDeviceEnumerator, ISimpleAudioVolume, MMDeviceEnumerator,
+    DEVICE_STATE_ACTIVE,
+};
+use windows::Win32::System::Com::{CoCreateInstance, CoInitializeEx, CoTaskMemFree, CLSCTX_ALL, COINIT_MULTITHREADED};
+
+pub const CONFIG_FILE: &str = "ducking.json";
+
+// Lowering other apps' volume while recording, so speaker audio doesn't bleed into the mic
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DuckingSettings {
+    pub enabled: bool,
+    pub percent: u8,  // How much to lower the volume by (100 = mute)
+}
+
+impl Default for DuckingSettings {
+    fn default() -> Self {
+        Self { enabled: false, percent: 80 }
+    }
+}
+
+// A session we lowered: its volume before, and the volume we set (to notice the user changing it)
+struct Ducked {
+    id: String,
+    original: f32,
+    ducked: f32,
+}
+
+static DUCKED: Mutex<Vec<Ducked>> = Mutex::new(Vec::new());
+
+// Lower every other app's audio session on the playback devices
+pub async fn duck(settings: &DuckingSettings) {
+    if !settings.enabled || settings.percent == 0 || !DUCKED.lock().unwrap().is_empty() {
+        return;
+    }
+    let factor = 1.0 - f32::from(settings.percent.min(100)) / 100.0;
+
+    let result = tauri::async_runtime::spawn_blocking(move || {
+        let mut ducked = Vec::new();
+        let result = for_each_session(|id, volume| unsafe {
+            let Ok(original) = volume.GetMasterVolume() else { return };
+            let lowered = original * factor;
+            if volume.SetMasterVolume(lowered, std::ptr::null()).is_ok() {
+                ducked.push(Ducked { id, original, ducked: lowered });
+            }
+        });
+        (ducked, result)
+    })
+    .await;
+
+    match result {
+        Ok((ducked, result)) => {
+            if let Err(e) = result {
+                log::warn!("⚠️ Ducking incomplete: {}", e);
+            }
+            log::info!("🔉 Ducked {} audio sessions by {}%", ducked.len(), settings.percent);
+            *DUCKED.lock().unwrap() = ducked;
+        }
+        Err(e) => log::error!("❌ Ducking failed: {}", e),
+    }
+}
+
+// Put ducked sessions back to their previous volume, unless the user changed it meanwhile
+pub async fn restore() {
+    let ducked = std::mem::take(&mut *DUCKED.lock().unwrap());
+    if ducked.is_empty() {
+        return;
+    }
+
+    let result = tauri::async_runtime::spawn_blocking(move || {
+        for_each_session(|id, volume| unsafe {
+            let Some(session) = ducked.iter().find(|d| d.id == id) else { return };
+            if volume.GetMasterVolume().is_ok_and(|current| (current - session.ducked).abs() < 0.01) {
+                let _ = volume.SetMasterVolume(session.original, std::ptr::null());
+            }
+        })
+    })
+    .await;
+
+    match result {
+        Ok(Ok(())) => log::info!("🔊 Restored audio volume"),
+        Ok(Err(e)) => log::error!("❌ Failed to restore audio volume: {}", e),
+        Err(e) => log::error!("❌ Failed to restore audio volume: {}", e),
+    }
+}
+
+// Call `visit` with the instance ID and volume of each audio session on the active playback devices,
+// skipping our own sounds and the system sounds
+fn for_each_session(mut visit: impl FnMut(String, &ISimpleAudioVolume)) -> windows::core::Result<()> {
+    unsafe {
+        // Runs on a blocking pool thread, which may already be initialized
+        let _ = CoInitializeEx(None, COINIT_MULTITHREADED);
+        let enumerator: IMMDeviceEnumerator = CoCreateInstance(&MMDeviceEnumerator, None, CLSCTX_ALL)?;
+        let devices = enumerator.EnumAudioEndpoints(eRender, DEVICE_STATE_ACTIVE)?;
+
+        for device_index in 0..devices.GetCount()? {
+            let manager: IAudioSessionManager2 = devices.Item(device_index)?.Activate(CLSCTX_ALL, None)?;
+            let sessions = manager.GetSessionEnumerator()?;
+            for session_index in 0..sessions.GetCount()? {
+                let Ok(control) = sessions.GetSession(session_index).and_then(|s| s.cast::<IAudioSessionControl2>()) else {
+                    continue;
+                };
+                if control.IsSystemSoundsSession() == S_OK || control.GetProcessId().ok() == Some(std::process::id()) {
+                    continue;
+                }
+                let (Some(id), Ok(volume)) = (session_id(&control), control.cast::<ISimpleAudioVolume>()) else {
+                    continue;
+                };
+                visit(id, &volume);
+            }
+        }
+    }
+    Ok(())
+}
+
+// Unique per session, and stable while it lives, unlike the position in the enumeration
+unsafe fn session_id(control: &IAudioSessionControl2) -> Option<String> {
+    let id: PWSTR = control.GetSessionInstanceIdentifier().ok()?;
+    let text = id.to_string().ok();
+    CoTaskMemFree(Some(id.0 as *const _));
+    text
+}
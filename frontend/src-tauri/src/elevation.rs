@@ -0,0 +1,216 @@
+use anyhow::{Context, Result};
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use windows::core::{w, HSTRING, PCWSTR};
+use windows::Win32::Foundation::{CloseHandle, HANDLE, HWND};
+use windows::Win32::Security::Cryptography::{BCryptGenRandom, BCRYPT_USE_SYSTEM_PREFERRED_RNG};
+use windows::Win32::Security::{GetSidSubAuthority, GetSidSubAuthorityCount, GetTokenInformation, TokenIntegrityLevel, TOKEN_MANDATORY_LABEL, TOKEN_QUERY};
+use windows::Win32::System::Threading::{GetCurrentProcess, OpenProcess, OpenProcessToken, PROCESS_QUERY_LIMITED_INFORMATION};
+use windows::Win32::UI::Shell::{ShellExecuteExW, SHELLEXECUTEINFOW};
+use windows::Win32::UI::WindowsAndMessaging::{GetWindowThreadProcessId, SW_HIDE};
+
+use crate::InjectionSettings;
+
+// Windows (UIPI) drops keystrokes sent to a window of a higher integrity level, so pasting into an
+// app running as administrator fails silently. The optional helper is a second copy of this exe,
+// started elevated after a UAC prompt, that injects on our behalf. It connects back to a port we
+// listen on and proves itself with a token from its command line, so it never accepts commands
+// from other processes, and it exits when the app closes the connection.
+
+const HELPER_ARG: &str = "--injection-helper";
+// Covers the user reading and answering the UAC prompt
+const HELPER_START_TIMEOUT: Duration = Duration::from_secs(60);
+// Injection itself sleeps for the configured delays; this only catches a helper that hangs
+const HELPER_REPLY_TIMEOUT: Duration = Duration::from_secs(10);
+// SECURITY_MANDATORY_HIGH_RID, the integrity level of elevated processes
+const HIGH_INTEGRITY: u32 = 0x3000;
+
+pub const ELEVATED_TARGET_ERROR: &str =
+    "The window is running as administrator, so Windows blocks pasting into it. The text is on the clipboard - press Ctrl+V, or turn on the administrator helper in Settings.";
+
+#[derive(serde::Serialize, serde::Deserialize)]
+struct HelperRequest {
+    text: String,
+    save_to_clipboard: bool,
+    settings: InjectionSettings,
+    press_enter: bool,  // Not part of the serialized settings, which only hold what's saved
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+struct HelperReply {
+    error: Option<String>,
+}
+
+struct Helper {
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+}
+
+static HELPER: Mutex<Option<Helper>> = Mutex::new(None);
+
+// Whether the window belongs to a process at a higher integrity level than ours
+pub fn is_elevated(window: HWND) -> bool {
+    unsafe {
+        let Some(own) = integrity_level(GetCurrentProcess()) else { return false };
+        let mut pid = 0u32;
+        GetWindowThreadProcessId(window, Some(&mut pid));
+        let Ok(process) = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid) else { return false };
+        let level = integrity_level(process);
+        let _ = CloseHandle(process);
+        match level {
+            Some(level) => level > own,
+            // Elevated processes may not let us read their token
+            None => own < HIGH_INTEGRITY,
+        }
+    }
+}
+
+unsafe fn integrity_level(process: HANDLE) -> Option<u32> {
+    let mut token = HANDLE::default();
+    OpenProcessToken(process, TOKEN_QUERY, &mut token).ok()?;
+    let mut size = 0u32;
+    let _ = GetTokenInformation(token, TokenIntegrityLevel, None, 0, &mut size);
+    // u64 storage keeps the label aligned
+    let mut buffer = vec![0u64; (size as usize).div_ceil(8)];
+    let result = GetTokenInformation(token, TokenIntegrityLevel, Some(buffer.as_mut_ptr() as *mut _), size, &mut size);
+    let _ = CloseHandle(token);
+    result.ok()?;
+
+    let sid = (*(buffer.as_ptr() as *const TOKEN_MANDATORY_LABEL)).Label.Sid;
+    let count = *GetSidSubAuthorityCount(sid);
+    Some(*GetSidSubAuthority(sid, u32::from(count.checked_sub(1)?)))
+}
+
+// Inject through the elevated helper, starting it first if needed
+pub fn inject(text: &str, save_to_clipboard: bool, settings: &InjectionSettings) -> Result<()> {
+    let request = HelperRequest { text: text.to_string(), save_to_clipboard, settings: settings.clone(), press_enter: settings.press_enter };
+    let mut helper = HELPER.lock().unwrap();
+    if helper.is_none() {
+        *helper = Some(start_helper()?);
+    }
+
+    let result = send(helper.as_mut().unwrap(), &request);
+    if result.is_err() {
+        // A dead connection means the helper exited; the next injection starts a new one
+        *helper = None;
+    }
+    result
+}
+
+fn send(helper: &mut Helper, request: &HelperRequest) -> Result<()> {
+    let mut line = serde_json::to_string(request)?;
+    line.push('\n');
+    helper.writer.write_all(line.as_bytes()).context("Administrator helper closed")?;
+
+    let mut reply = String::new();
+    if helper.reader.read_line(&mut reply).context("Administrator helper didn't answer")? == 0 {
+        return Err(anyhow::anyhow!("Administrator helper closed"));
+    }
+    match serde_json::from_str::<HelperReply>(&reply)?.error {
+        Some(error) => Err(anyhow::anyhow!(error)),
+        None => Ok(()),
+    }
+}
+
+// Start the helper if it isn't running (shows the UAC prompt)
+pub fn ensure_helper() -> Result<()> {
+    let mut helper = HELPER.lock().unwrap();
+    if helper.is_none() {
+        *helper = Some(start_helper()?);
+    }
+    Ok(())
+}
+
+// Close the connection, which makes the helper exit
+pub fn stop_helper() {
+    if HELPER.lock().unwrap().take().is_some() {
+        log::info!("🛡️ Administrator helper stopped");
+    }
+}
+
+fn start_helper() -> Result<Helper> {
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let port = listener.local_addr()?.port();
+    let token = random_token()?;
+    let exe = std::env::current_exe()?;
+
+    log::info!("🛡️ Starting administrator helper");
+    let parameters = HSTRING::from(format!("{} {} {}", HELPER_ARG, port, token));
+    let file = HSTRING::from(exe.to_string_lossy().as_ref());
+    let mut info = SHELLEXECUTEINFOW {
+        cbSize: std::mem::size_of::<SHELLEXECUTEINFOW>() as u32,
+        lpVerb: w!("runas"),
+        lpFile: PCWSTR(file.as_ptr()),
+        lpParameters: PCWSTR(parameters.as_ptr()),
+        nShow: SW_HIDE.0,
+        ..Default::default()
+    };
+    unsafe { ShellExecuteExW(&mut info) }.context("Administrator helper wasn't allowed to start")?;
+
+    // Wait for the helper to connect back with the token
+    listener.set_nonblocking(true)?;
+    let deadline = Instant::now() + HELPER_START_TIMEOUT;
+    loop {
+        match listener.accept() {
+            Ok((stream, _)) => {
+                stream.set_nonblocking(false)?;
+                stream.set_read_timeout(Some(HELPER_REPLY_TIMEOUT))?;
+                let mut reader = BufReader::new(stream.try_clone()?);
+                let mut line = String::new();
+                if reader.read_line(&mut line).is_ok() && line.trim() == token {
+                    log::info!("🛡️ Administrator helper connected");
+                    return Ok(Helper { reader, writer: stream });
+                }
+                log::warn!("⚠️ Rejected a connection without the helper token");
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                if Instant::now() > deadline {
+                    return Err(anyhow::anyhow!("Administrator helper didn't start"));
+                }
+                std::thread::sleep(Duration::from_millis(100));
+            }
+            Err(e) => return Err(e.into()),
+        }
+    }
+}
+
+// 128 random bits as hex, for shared secrets between local processes
+pub(crate) fn random_token() -> Result<String> {
+    let mut bytes = [0u8; 16];
+    unsafe { BCryptGenRandom(None, &mut bytes, BCRYPT_USE_SYSTEM_PREFERRED_RNG) }.ok()?;
+    Ok(bytes.iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+// When started as the helper, serve injection requests until the app disconnects and return true
+pub fn run_helper_if_requested() -> bool {
+    let args: Vec<String> = std::env::args().collect();
+    let [_, flag, port, token] = args.as_slice() else { return false };
+    if flag != HELPER_ARG {
+        return false;
+    }
+
+    let Ok(stream) = TcpStream::connect(("127.0.0.1", port.parse::<u16>().unwrap_or(0))) else { return true };
+    let Ok(mut writer) = stream.try_clone() else { return true };
+    if writeln!(writer, "{}", token).is_err() {
+        return true;
+    }
+
+    for line in BufReader::new(stream).lines() {
+        let Ok(line) = line else { break };
+        let error = match serde_json::from_str::<HelperRequest>(&line) {
+            // The helper never hands off to another helper
+            Ok(request) => {
+                let settings = InjectionSettings { elevated_helper: false, press_enter: request.press_enter, ..request.settings };
+                crate::inject_text(&request.text, request.save_to_clipboard, &settings).err().map(|e| e.to_string())
+            }
+            Err(e) => Some(e.to_string()),
+        };
+        let Ok(reply) = serde_json::to_string(&HelperReply { error }) else { break };
+        if writeln!(writer, "{}", reply).is_err() {
+            break;
+        }
+    }
+    true
+}
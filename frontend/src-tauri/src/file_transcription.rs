@@ -0,0 +1,347 @@
+use serde::{Deserialize, Serialize};
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager};
+use windows::core::{w, HSTRING, PCWSTR};
+use windows::Win32::Foundation::HWND;
+use windows::Win32::System::Com::{CoCreateInstance, CoInitializeEx, CoTaskMemFree, CLSCTX_INPROC_SERVER, COINIT_APARTMENTTHREADED};
+use windows::Win32::UI::Controls::{
+    TaskDialogIndirect, TASKDIALOGCONFIG, TASKDIALOG_BUTTON, TASKDIALOG_FLAGS, TDF_ALLOW_DIALOG_CANCELLATION, TDF_USE_COMMAND_LINKS,
+};
+use windows::Win32::UI::Shell::Common::COMDLG_FILTERSPEC;
+use windows::Win32::UI::Shell::{FileOpenDialog, IFileOpenDialog, SIGDN_FILESYSPATH};
+
+use crate::history::Segment;
+use crate::{backend, recording_state, scratchpad, AppState};
+
+// Transcribing an existing audio or video file, picked in a dialog (tray menu and jump list),
+// copied to the clipboard (tray menu and hotkey), or sent from Explorer's context menu. If it has
+// several audio tracks, the user is asked which one to use first. The transcript goes to the
+// scratchpad, since there's no text field to paste into, or for Explorer into a .txt next to the
+// file. A video also gets subtitles saved next to it.
+
+pub const CONFIG_FILE: &str = "file_transcription.json";
+
+const AUDIO_EXTENSIONS: [&str; 8] = ["wav", "mp3", "m4a", "flac", "ogg", "opus", "aac", "wma"];
+const VIDEO_EXTENSIONS: [&str; 5] = ["mp4", "mkv", "mov", "webm", "avi"];
+
+// Command link IDs for the tracks start here (the track's index is added)
+const TRACK_BUTTON: i32 = 100;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FileTranscriptionSettings {
+    pub video_subtitles: bool,  // Save <name>.srt next to a transcribed video
+    pub explorer_menu: bool,  // "Transcribe with Whisper4Windows" in Explorer's context menu
+}
+
+impl Default for FileTranscriptionSettings {
+    fn default() -> Self {
+        Self { video_subtitles: true, explorer_menu: false }
+    }
+}
+
+// Where a file's transcript goes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Destination {
+    Scratchpad,
+    NextToFile,  // <name>.txt next to the file
+}
+
+// A file to transcribe, and which of its audio tracks (0 = the first)
+pub struct AudioFile {
+    pub path: String,
+    pub track: usize,
+}
+
+// An audio track as the backend describes it (fields the container doesn't have are missing)
+#[derive(Debug, Clone, Deserialize)]
+struct AudioTrack {
+    index: usize,
+    codec: Option<String>,
+    channels: Option<u32>,
+    language: Option<String>,
+    title: Option<String>,
+}
+
+// Pick a file and transcribe it in the background
+pub fn start(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let state: tauri::State<AppState> = app.state();
+        if recording_state::is_recording(&state).await {
+            crate::show_toast(&app, &crate::locale::tr("toast.file_busy"), &crate::locale::tr("error.busy"));
+            return;
+        }
+
+        let Some(path) = pick_file().await else { return };
+        transcribe(&app, &state, &path, Destination::Scratchpad).await;
+    });
+}
+
+// Transcribe the audio or video file on the clipboard in the background
+pub fn start_from_clipboard(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let state: tauri::State<AppState> = app.state();
+        if recording_state::is_recording(&state).await {
+            crate::show_toast(&app, &crate::locale::tr("toast.file_busy"), &crate::locale::tr("error.busy"));
+            return;
+        }
+
+        let Some(path) = clipboard_file() else {
+            log::info!("📋 No audio or video file on the clipboard");
+            crate::show_toast(&app, &crate::locale::tr("toast.clipboard_no_file"), &crate::locale::tr("toast.clipboard_no_file_body"));
+            return;
+        };
+        log::info!("📋 Transcribing the copied file {}", path.display());
+        transcribe(&app, &state, &path, Destination::Scratchpad).await;
+    });
+}
+
+// The first audio or video file on the clipboard: copied as a file, or its path copied as text
+// (Explorer's "Copy as path" puts it in quotes)
+fn clipboard_file() -> Option<PathBuf> {
+    let files = crate::clipboard::get_files().unwrap_or_else(|e| {
+        log::warn!("⚠️ Failed to read files from the clipboard: {}", e);
+        Vec::new()
+    });
+    if let Some(path) = files.into_iter().find(|path| is_media(path)) {
+        return Some(path);
+    }
+
+    let text = crate::clipboard::get_text().ok().flatten()?;
+    let path = PathBuf::from(text.trim().trim_matches('"'));
+    (is_media(&path) && path.is_file()).then_some(path)
+}
+
+pub async fn transcribe(app: &AppHandle, state: &AppState, path: &Path, destination: Destination) {
+    let name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    let Some(track) = choose_track(path, &name).await else {
+        log::info!("📂 No track picked for {}", name);
+        return;
+    };
+    log::info!("📂 Transcribing file: {} (track {})", path.display(), track + 1);
+    crate::show_toast(app, &crate::locale::tr("toast.file_transcribing"), &name);
+
+    let file = AudioFile { path: path.to_string_lossy().to_string(), track };
+    match crate::transcribe_without_recording(app, state, Some(&file)).await {
+        Ok(Some((text, data))) => {
+            let body = match destination {
+                Destination::Scratchpad => {
+                    scratchpad::append(app, state, &text, true).await;
+                    crate::locale::tr_with("toast.file_ready_body", &[("name", &name)])
+                }
+                Destination::NextToFile => {
+                    let transcript = path.with_extension("txt");
+                    if let Err(e) = std::fs::write(&transcript, &text) {
+                        log::error!("❌ Failed to save {}: {}", transcript.display(), e);
+                        crate::show_toast(app, &crate::locale::tr("toast.transcript_failed"), &e.to_string());
+                        return;
+                    }
+                    log::info!("📂 Transcript saved to {}", transcript.display());
+                    let saved = transcript.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+                    crate::locale::tr_with("toast.file_saved_body", &[("name", &saved)])
+                }
+            };
+            if is_video(path) && state.file_transcription.lock().await.video_subtitles {
+                let subtitles = path.with_extension("srt");
+                match std::fs::write(&subtitles, srt(&data, &text)) {
+                    Ok(()) => log::info!("🎞️ Subtitles saved to {}", subtitles.display()),
+                    Err(e) => {
+                        log::error!("❌ Failed to save {}: {}", subtitles.display(), e);
+                        crate::show_toast(app, &crate::locale::tr("toast.subtitles_failed"), &e.to_string());
+                    }
+                }
+            }
+            crate::show_toast(app, &crate::locale::tr("toast.file_ready"), &body);
+        }
+        Ok(None) => log::info!("📂 Nothing transcribed from {}", name),
+        Err(e) => {
+            log::error!("❌ File transcription failed: {}", e);
+            crate::show_toast(app, &crate::locale::tr("toast.transcription_failed"), &e);
+        }
+    }
+}
+
+fn has_extension(path: &Path, extensions: &[&str]) -> bool {
+    let extension = path.extension().and_then(|ext| ext.to_str()).unwrap_or_default();
+    extensions.iter().any(|known| known.eq_ignore_ascii_case(extension))
+}
+
+fn is_video(path: &Path) -> bool {
+    has_extension(path, &VIDEO_EXTENSIONS)
+}
+
+// File extensions of the audio and video formats offered for transcription
+pub fn extensions() -> impl Iterator<Item = &'static str> {
+    AUDIO_EXTENSIONS.into_iter().chain(VIDEO_EXTENSIONS)
+}
+
+// Whether `path` looks like a file that can be transcribed
+pub fn is_media(path: &Path) -> bool {
+    has_extension(path, &AUDIO_EXTENSIONS) || is_video(path)
+}
+
+// The audio track to transcribe: the only one, or the one the user picks (None if cancelled)
+async fn choose_track(path: &Path, name: &str) -> Option<usize> {
+    let tracks = match audio_tracks(path).await {
+        Ok(tracks) => tracks,
+        Err(e) => {
+            // Transcribing reports what's wrong with the file
+            log::warn!("⚠️ Couldn't list the audio tracks of {}: {}", name, e);
+            return Some(0);
+        }
+    };
+    if tracks.len() <= 1 {
+        return Some(0);
+    }
+
+    let name = name.to_string();
+    let (sender, receiver) = tokio::sync::oneshot::channel();
+    // The dialog runs its own message loop, so it gets a thread of its own
+    std::thread::spawn(move || {
+        let _ = sender.send(show_track_dialog(&name, &tracks));
+    });
+    receiver.await.ok().flatten()
+}
+
+async fn audio_tracks(path: &Path) -> Result<Vec<AudioTrack>, String> {
+    let response = backend::client()
+        .post(backend::url("file_tracks"))
+        .json(&serde_json::json!({ "path": path.to_string_lossy() }))
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {}", e))?;
+    let data = response.json::<serde_json::Value>().await.map_err(|e| format!("Invalid response: {}", e))?;
+    if data.get("status").and_then(|s| s.as_str()) != Some("success") {
+        return Err(data.get("message").and_then(|m| m.as_str()).unwrap_or("Couldn't read the file").to_string());
+    }
+    serde_json::from_value(data["tracks"].clone()).map_err(|e| format!("Invalid response: {}", e))
+}
+
+// "Track 2 (English · Director's commentary · aac, 2 channels)"
+fn track_label(track: &AudioTrack) -> String {
+    let mut details: Vec<String> = [&track.language, &track.title].into_iter().flatten().cloned().collect();
+    let format = match (&track.codec, track.channels) {
+        (Some(codec), Some(channels)) => Some(format!("{}, {} channels", codec, channels)),
+        (Some(codec), None) => Some(codec.clone()),
+        (None, Some(channels)) => Some(format!("{} channels", channels)),
+        (None, None) => None,
+    };
+    details.extend(format);
+
+    let label = crate::locale::tr_with("file.track", &[("number", &(track.index + 1).to_string())]);
+    if details.is_empty() {
+        label
+    } else {
+        format!("{} ({})", label, details.join(" · "))
+    }
+}
+
+fn show_track_dialog(name: &str, tracks: &[AudioTrack]) -> Option<usize> {
+    let title = HSTRING::from("Transcribe a File");
+    let count = tracks.len().to_string();
+    let instruction = HSTRING::from(crate::locale::tr_with("file.tracks", &[("name", name), ("count", &count)]));
+    let content = HSTRING::from(crate::locale::tr("file.tracks_content"));
+    let labels: Vec<(i32, HSTRING)> =
+        tracks.iter().map(|track| (TRACK_BUTTON + track.index as i32, HSTRING::from(track_label(track)))).collect();
+    let buttons: Vec<TASKDIALOG_BUTTON> = labels
+        .iter()
+        .map(|(id, label)| TASKDIALOG_BUTTON { nButtonID: *id, pszButtonText: PCWSTR(label.as_ptr()) })
+        .collect();
+
+    let config = TASKDIALOGCONFIG {
+        cbSize: std::mem::size_of::<TASKDIALOGCONFIG>() as u32,
+        // Escape and the close button cancel the transcription
+        dwFlags: TASKDIALOG_FLAGS(TDF_USE_COMMAND_LINKS.0 | TDF_ALLOW_DIALOG_CANCELLATION.0),
+        pszWindowTitle: PCWSTR(title.as_ptr()),
+        pszMainInstruction: PCWSTR(instruction.as_ptr()),
+        pszContent: PCWSTR(content.as_ptr()),
+        cButtons: buttons.len() as u32,
+        pButtons: buttons.as_ptr(),
+        nDefaultButton: TRACK_BUTTON,
+        ..Default::default()
+    };
+
+    let mut pressed = 0;
+    if let Err(e) = unsafe { TaskDialogIndirect(&config, Some(&mut pressed), None, None) } {
+        log::error!("❌ Track dialog failed: {}", e);
+        return None;
+    }
+    let index = usize::try_from(pressed - TRACK_BUTTON).ok()?;
+    tracks.iter().any(|track| track.index == index).then_some(index)
+}
+
+// SubRip subtitles for a backend result, one cue per segment (the whole text as one cue if there
+// are no segments)
+pub fn srt(data: &serde_json::Value, text: &str) -> String {
+    let segments: Vec<Segment> =
+        data.get("segments").cloned().and_then(|segments| serde_json::from_value(segments).ok()).unwrap_or_default();
+    let cues: Vec<(f64, f64, String)> = if segments.is_empty() {
+        let duration = data.get("duration").and_then(|d| d.as_f64()).unwrap_or_default();
+        vec![(0.0, duration, text.to_string())]
+    } else {
+        segments
+            .iter()
+            .map(|segment| {
+                let line = match &segment.speaker {
+                    Some(speaker) => format!("{}: {}", speaker, segment.text.trim()),
+                    None => segment.text.trim().to_string(),
+                };
+                (segment.start, segment.end, line)
+            })
+            .collect()
+    };
+
+    let mut out = String::new();
+    for (index, (start, end, line)) in cues.iter().enumerate() {
+        let _ = writeln!(out, "{}\n{} --> {}\n{}\n", index + 1, timestamp(*start), timestamp(*end), line);
+    }
+    out
+}
+
+// 01:02:03,456
+fn timestamp(secs: f64) -> String {
+    let ms = (secs.max(0.0) * 1000.0).round() as u64;
+    format!("{:02}:{:02}:{:02},{:03}", ms / 3_600_000, ms / 60_000 % 60, ms / 1000 % 60, ms % 1000)
+}
+
+// Ask for an audio or video file; None if cancelled
+async fn pick_file() -> Option<PathBuf> {
+    let (sender, receiver) = tokio::sync::oneshot::channel();
+    // The dialog needs a single-threaded apartment with its own message loop
+    std::thread::spawn(move || {
+        let _ = sender.send(unsafe { show_dialog() });
+    });
+    match receiver.await {
+        Ok(Ok(path)) => path,
+        Ok(Err(e)) => {
+            log::error!("❌ File dialog failed: {}", e);
+            None
+        }
+        Err(_) => None,
+    }
+}
+
+unsafe fn show_dialog() -> windows::core::Result<Option<PathBuf>> {
+    let _ = CoInitializeEx(None, COINIT_APARTMENTTHREADED);
+    let dialog: IFileOpenDialog = CoCreateInstance(&FileOpenDialog, None, CLSCTX_INPROC_SERVER)?;
+    dialog.SetTitle(w!("Transcribe a File"))?;
+    dialog.SetFileTypes(&[
+        COMDLG_FILTERSPEC {
+            pszName: w!("Audio and video"),
+            pszSpec: w!("*.wav;*.mp3;*.m4a;*.flac;*.ogg;*.opus;*.aac;*.wma;*.mp4;*.mkv;*.mov;*.webm;*.avi"),
+        },
+        COMDLG_FILTERSPEC { pszName: w!("All files"), pszSpec: w!("*.*") },
+    ])?;
+
+    // Fails with ERROR_CANCELLED when the user closes the dialog
+    if dialog.Show(HWND::default()).is_err() {
+        return Ok(None);
+    }
+    let name = dialog.GetResult()?.GetDisplayName(SIGDN_FILESYSPATH)?;
+    let path = name.to_string();
+    CoTaskMemFree(Some(name.0 as *const _));
+    Ok(path.ok().map(PathBuf::from))
+}
@@ -0,0 +1,89 @@
+use serde::{Deserialize, Serialize};
+use std::ffi::c_void;
+use tauri::{AppHandle, Manager};
+use windows::Win32::UI::Shell::{
+    SHQueryUserNotificationState, QUNS_BUSY, QUNS_PRESENTATION_MODE, QUNS_QUIET_TIME, QUNS_RUNNING_D3D_FULL_SCREEN,
+};
+
+use crate::AppState;
+
+// Staying quiet while Windows Focus Assist (Do Not Disturb on Windows 11) is on. Recording works
+// as usual; only toasts, sounds, and optionally the overlay are held back.
+
+pub const CONFIG_FILE: &str = "focus_assist.json";
+
+// WNF state the shell publishes the active quiet-hours profile in (0 = off, 1 = priority only, 2 = alarms only)
+const WNF_SHEL_QUIETHOURS_ACTIVE_PROFILE_CHANGED: u64 = 0x0D83_063E_A3BF_1C75;
+
+#[link(name = "ntdll")]
+extern "system" {
+    fn NtQueryWnfStateData(
+        state_name: *const u64,
+        type_id: *const c_void,
+        explicit_scope: *const c_void,
+        change_stamp: *mut u32,
+        buffer: *mut c_void,
+        buffer_size: *mut u32,
+    ) -> i32;
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FocusAssistSettings {
+    pub respect: bool,  // Hold back toasts and sounds while Focus Assist is on (off = override it)
+    pub hide_overlay: bool,  // Also record without showing the overlay
+}
+
+impl Default for FocusAssistSettings {
+    fn default() -> Self {
+        Self { respect: true, hide_overlay: false }
+    }
+}
+
+impl FocusAssistSettings {
+    // Whether toasts and sounds should be held back right now
+    pub fn is_quiet(&self) -> bool {
+        self.respect && is_on()
+    }
+
+    // Whether the overlay should stay hidden right now
+    pub fn hides_overlay(&self) -> bool {
+        self.respect && self.hide_overlay && is_on()
+    }
+}
+
+// Focus Assist turned on by hand or by a schedule, or one of its automatic rules (games,
+// presentations, full-screen apps) holding notifications back
+pub fn is_on() -> bool {
+    quiet_hours_profile().is_some_and(|profile| profile != 0) || notifications_suppressed()
+}
+
+// Settings of the running app, for callers without the state at hand
+pub fn is_quiet(app: &AppHandle) -> bool {
+    let state: tauri::State<AppState> = app.state();
+    let settings = state.focus_assist.lock().unwrap().clone();
+    settings.is_quiet()
+}
+
+fn quiet_hours_profile() -> Option<u32> {
+    let mut profile = 0u32;
+    let mut size = std::mem::size_of::<u32>() as u32;
+    let mut change_stamp = 0u32;
+    let status = unsafe {
+        NtQueryWnfStateData(
+            &WNF_SHEL_QUIETHOURS_ACTIVE_PROFILE_CHANGED,
+            std::ptr::null(),
+            std::ptr::null(),
+            &mut change_stamp,
+            &mut profile as *mut u32 as *mut c_void,
+            &mut size,
+        )
+    };
+    (status >= 0 && size as usize == std::mem::size_of::<u32>()).then_some(profile)
+}
+
+fn notifications_suppressed() -> bool {
+    unsafe { SHQueryUserNotificationState() }.is_ok_and(|state| {
+        state == QUNS_BUSY || state == QUNS_RUNNING_D3D_FULL_SCREEN || state == QUNS_PRESENTATION_MODE || state == QUNS_QUIET_TIME
+    })
+}
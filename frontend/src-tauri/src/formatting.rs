@@ -0,0 +1,156 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::profanity::{self, ProfanityMode, ProfanityWords};
+use crate::spoken_punctuation;
+use crate::AppState;
+
+pub const CONFIG_FILE: &str = "formatting.json";
+
+// Filler words removed when `strip_fillers` is enabled (matched case-insensitively)
+const FILLER_WORDS: &[&str] = &["um", "umm", "uh", "uhh", "uhm", "er", "erm", "ah", "hmm", "mm"];
+
+// What to append after the formatted text
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum TrailingText {
+    #[default]
+    None,
+    Space,
+    Newline,
+}
+
+// Formatting applied to a transcription before it is injected
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct FormattingSettings {
+    pub capitalize_first: bool,
+    pub ensure_terminal_punctuation: bool,
+    pub trailing: TrailingText,
+    pub lowercase: bool,
+    pub strip_fillers: bool,
+    pub spoken_punctuation: bool,  // Ignore model punctuation and only use dictated "comma", "period"...
+    pub profanity: ProfanityMode,  // Censoring of the shared profanity words in this profile
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct FormattingProfiles {
+    pub profiles: HashMap<String, FormattingSettings>,  // Model slot -> formatting; slots without one aren't formatted
+    pub profanity_words: ProfanityWords,  // What profiles that censor profanity censor
+}
+
+impl FormattingProfiles {
+    pub fn for_profile(&self, profile: &str) -> FormattingSettings {
+        self.profiles.get(profile).cloned().unwrap_or_default()
+    }
+
+    pub fn select(&mut self, profile: &str, settings: FormattingSettings) {
+        if settings == FormattingSettings::default() {
+            self.profiles.remove(profile);
+        } else {
+            self.profiles.insert(profile.to_string(), settings);
+        }
+    }
+}
+
+// The formatting of the profile the current recording uses, and the shared profanity words
+pub async fn current(state: &AppState) -> (FormattingSettings, ProfanityWords) {
+    let profile = state.recording_slot.lock().await.clone();
+    let profiles = state.formatting.lock().await;
+    (profiles.for_profile(&profile), profiles.profanity_words.clone())
+}
+
+// Apply formatting in a fixed order:
+// spoken punctuation -> strip fillers -> profanity -> lowercase -> capitalize -> punctuation -> trailing.
+// `language` picks the spoken-command and profanity dictionaries (None = transcription language unknown).
+pub fn apply(text: &str, settings: &FormattingSettings, profanity_words: &ProfanityWords, language: Option<&str>) -> String {
+    let mut result = text.trim().to_string();
+
+    if settings.spoken_punctuation {
+        result = spoken_punctuation::apply(&result, language);
+    }
+
+    if settings.strip_fillers {
+        result = strip_filler_words(&result);
+    }
+
+    result = profanity::apply(&result, settings.profanity, profanity_words, language);
+
+    if settings.lowercase {
+        result = result.to_lowercase();
+    }
+
+    if settings.capitalize_first {
+        result = capitalize_first_letter(&result);
+    }
+
+    if settings.ensure_terminal_punctuation && !result.is_empty() && !ends_with_terminal_punctuation(&result) {
+        result.push('.');
+    }
+
+    if !result.is_empty() {
+        match settings.trailing {
+            TrailingText::None => {}
+            TrailingText::Space => result.push(' '),
+            TrailingText::Newline => result.push('\n'),
+        }
+    }
+
+    result
+}
+
+fn is_filler(word: &str) -> bool {
+    let core: String = word
+        .chars()
+        .filter(|c| c.is_alphabetic())
+        .collect::<String>()
+        .to_lowercase();
+    !core.is_empty() && FILLER_WORDS.contains(&core.as_str())
+}
+
+fn strip_filler_words(text: &str) -> String {
+    let mut kept: Vec<String> = Vec::new();
+
+    for word in text.split_whitespace() {
+        if !is_filler(word) {
+            kept.push(word.to_string());
+            continue;
+        }
+
+        // Keep sentence-ending punctuation that was attached to the filler ("...said, um.")
+        if let Some(last_char) = word.chars().last().filter(|c| matches!(c, '.' | '!' | '?')) {
+            if let Some(prev) = kept.last_mut() {
+                let prev_trimmed = prev.trim_end_matches([',', ';', ':']).to_string();
+                if !ends_with_terminal_punctuation(&prev_trimmed) {
+                    *prev = format!("{}{}", prev_trimmed, last_char);
+                }
+            }
+        }
+    }
+
+    kept.join(" ")
+}
+
+fn capitalize_first_letter(text: &str) -> String {
+    match text.char_indices().find(|(_, c)| c.is_alphabetic()) {
+        Some((idx, ch)) => {
+            let mut result = String::with_capacity(text.len());
+            result.push_str(&text[..idx]);
+            result.extend(ch.to_uppercase());
+            result.push_str(&text[idx + ch.len_utf8()..]);
+            result
+        }
+        None => text.to_string(),
+    }
+}
+
+fn ends_with_terminal_punctuation(text: &str) -> bool {
+    // Look past closing quotes/brackets so `"Done."` counts as punctuated
+    text.trim_end()
+        .trim_end_matches(['"', '\'', ')', ']', '”', '’'])
+        .chars()
+        .last()
+        .map(|c| matches!(c, '.' | '!' | '?' | '…' | ':' | ';' | '。' | '！' | '？'))
+        .unwrap_or(false)
+}
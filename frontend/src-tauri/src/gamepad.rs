@@ -0,0 +1,152 @@
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::AppHandle;
+use windows::Win32::UI::Input::XboxController::{
+    XInputGetState, XINPUT_GAMEPAD, XINPUT_GAMEPAD_A, XINPUT_GAMEPAD_B, XINPUT_GAMEPAD_BACK, XINPUT_GAMEPAD_BUTTON_FLAGS,
+    XINPUT_GAMEPAD_DPAD_DOWN, XINPUT_GAMEPAD_DPAD_LEFT, XINPUT_GAMEPAD_DPAD_RIGHT, XINPUT_GAMEPAD_DPAD_UP,
+    XINPUT_GAMEPAD_LEFT_SHOULDER, XINPUT_GAMEPAD_LEFT_THUMB, XINPUT_GAMEPAD_RIGHT_SHOULDER, XINPUT_GAMEPAD_RIGHT_THUMB,
+    XINPUT_GAMEPAD_START, XINPUT_GAMEPAD_TRIGGER_THRESHOLD, XINPUT_GAMEPAD_X, XINPUT_GAMEPAD_Y, XINPUT_STATE,
+    XUSER_MAX_COUNT,
+};
+
+pub const CONFIG_FILE: &str = "gamepad_bindings.json";
+
+// Fast enough that a quick tap on a button isn't missed
+const POLL_INTERVAL: Duration = Duration::from_millis(30);
+// XInputGetState is slow for empty slots, so those are only checked now and then
+const RECONNECT_INTERVAL: Duration = Duration::from_secs(2);
+
+// Xbox controller buttons; the analog triggers count as pressed past the usual threshold
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GamepadButton {
+    A,
+    B,
+    X,
+    Y,
+    LeftBumper,
+    RightBumper,
+    LeftTrigger,
+    RightTrigger,
+    LeftStick,
+    RightStick,
+    Back,
+    Start,
+    DpadUp,
+    DpadDown,
+    DpadLeft,
+    DpadRight,
+}
+
+impl GamepadButton {
+    fn is_pressed(self, pad: &XINPUT_GAMEPAD) -> bool {
+        let flag = |flag: XINPUT_GAMEPAD_BUTTON_FLAGS| pad.wButtons.0 & flag.0 != 0;
+        let threshold = XINPUT_GAMEPAD_TRIGGER_THRESHOLD.0 as u8;
+        match self {
+            GamepadButton::A => flag(XINPUT_GAMEPAD_A),
+            GamepadButton::B => flag(XINPUT_GAMEPAD_B),
+            GamepadButton::X => flag(XINPUT_GAMEPAD_X),
+            GamepadButton::Y => flag(XINPUT_GAMEPAD_Y),
+            GamepadButton::LeftBumper => flag(XINPUT_GAMEPAD_LEFT_SHOULDER),
+            GamepadButton::RightBumper => flag(XINPUT_GAMEPAD_RIGHT_SHOULDER),
+            GamepadButton::LeftTrigger => pad.bLeftTrigger > threshold,
+            GamepadButton::RightTrigger => pad.bRightTrigger > threshold,
+            GamepadButton::LeftStick => flag(XINPUT_GAMEPAD_LEFT_THUMB),
+            GamepadButton::RightStick => flag(XINPUT_GAMEPAD_RIGHT_THUMB),
+            GamepadButton::Back => flag(XINPUT_GAMEPAD_BACK),
+            GamepadButton::Start => flag(XINPUT_GAMEPAD_START),
+            GamepadButton::DpadUp => flag(XINPUT_GAMEPAD_DPAD_UP),
+            GamepadButton::DpadDown => flag(XINPUT_GAMEPAD_DPAD_DOWN),
+            GamepadButton::DpadLeft => flag(XINPUT_GAMEPAD_DPAD_LEFT),
+            GamepadButton::DpadRight => flag(XINPUT_GAMEPAD_DPAD_RIGHT),
+        }
+    }
+}
+
+// A button on any connected controller, and the action it triggers (see crate::BUTTON_ACTIONS)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GamepadBinding {
+    pub button: GamepadButton,
+    pub action: String,
+}
+
+// Bindings persisted in the config directory
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct GamepadBindings {
+    pub bindings: Vec<GamepadBinding>,
+}
+
+static BINDINGS: Mutex<Vec<GamepadBinding>> = Mutex::new(Vec::new());
+// Polling task, only running while something is bound
+static POLLER: Mutex<Option<tauri::async_runtime::JoinHandle<()>>> = Mutex::new(None);
+
+// Replace all bindings, starting or stopping the polling task to match
+pub fn set_bindings(app: &AppHandle, bindings: Vec<GamepadBinding>) {
+    let listen = !bindings.is_empty();
+    *BINDINGS.lock().unwrap() = bindings;
+
+    let mut poller = POLLER.lock().unwrap();
+    match (listen, poller.is_some()) {
+        (true, false) => {
+            *poller = Some(tauri::async_runtime::spawn(poll(app.clone())));
+            log::info!("🎮 Gamepad polling started");
+        }
+        (false, true) => {
+            if let Some(task) = poller.take() {
+                task.abort();
+            }
+            log::info!("🎮 Gamepad polling stopped");
+        }
+        _ => {}
+    }
+}
+
+pub fn bindings() -> Vec<GamepadBinding> {
+    BINDINGS.lock().unwrap().clone()
+}
+
+async fn poll(app: AppHandle) {
+    // Per controller slot: whether it answered last time, and when an empty slot was last checked
+    let mut connected = [false; XUSER_MAX_COUNT as usize];
+    let mut last_checked = [None::<Instant>; XUSER_MAX_COUNT as usize];
+    // Bound buttons currently held on any controller, so each press and release fires once
+    let mut held: Vec<GamepadButton> = Vec::new();
+
+    loop {
+        let mut pads = Vec::new();
+        for slot in 0..XUSER_MAX_COUNT as usize {
+            let due = connected[slot] || last_checked[slot].map_or(true, |at| at.elapsed() >= RECONNECT_INTERVAL);
+            if !due {
+                continue;
+            }
+            let mut state = XINPUT_STATE::default();
+            let found = unsafe { XInputGetState(slot as u32, &mut state) } == 0;
+            if found != connected[slot] {
+                log::info!("🎮 Controller {} {}", slot + 1, if found { "connected" } else { "disconnected" });
+            }
+            connected[slot] = found;
+            last_checked[slot] = Some(Instant::now());
+            if found {
+                pads.push(state.Gamepad);
+            }
+        }
+
+        for binding in bindings() {
+            let pressed = pads.iter().any(|pad| binding.button.is_pressed(pad));
+            let was_held = held.contains(&binding.button);
+            if pressed == was_held {
+                continue;
+            }
+            if pressed {
+                held.push(binding.button);
+            } else {
+                held.retain(|b| *b != binding.button);
+            }
+            crate::run_button_action(app.clone(), &binding.action, pressed);
+        }
+
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+}
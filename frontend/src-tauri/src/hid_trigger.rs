@@ -0,0 +1,274 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+use tauri::AppHandle;
+use tokio::sync::oneshot;
+use windows::core::w;
+use windows::Win32::{
+    Foundation::{HANDLE, HINSTANCE, HWND, LPARAM, LRESULT, WPARAM},
+    UI::Input::{
+        GetRawInputData, GetRawInputDeviceInfoW, GetRawInputDeviceList, RegisterRawInputDevices, HRAWINPUT, RAWINPUT,
+        RAWINPUTDEVICE, RAWINPUTDEVICELIST, RAWINPUTHEADER, RIDEV_DEVNOTIFY, RIDEV_INPUTSINK, RIDI_DEVICEINFO,
+        RID_DEVICE_INFO, RID_INPUT, RIM_TYPEHID,
+    },
+    UI::WindowsAndMessaging::{
+        CreateWindowExW, DefWindowProcW, DispatchMessageW, GetMessageW, RegisterClassW, TranslateMessage, GIDC_ARRIVAL,
+        HWND_MESSAGE, MSG, WINDOW_EX_STYLE, WM_INPUT, WM_INPUT_DEVICE_CHANGE, WNDCLASSW, WS_OVERLAPPED,
+    },
+};
+
+pub const CONFIG_FILE: &str = "hid_bindings.json";
+
+// How long the capture flow waits for a button press
+const CAPTURE_TIMEOUT: Duration = Duration::from_secs(10);
+
+// One button of a HID device (e.g. a USB foot pedal). Buttons are bits of the device's input
+// report, which works for pedals and button boxes without parsing their report descriptors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HidButton {
+    pub vendor_id: u16,
+    pub product_id: u16,
+    pub usage_page: u16,
+    pub usage: u16,
+    pub button: u32,  // Bit index in the input report
+}
+
+// A device button and the action it triggers (see crate::BUTTON_ACTIONS)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HidBinding {
+    pub button: HidButton,
+    pub action: String,
+}
+
+// Bindings persisted in the config directory
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct HidBindings {
+    pub bindings: Vec<HidBinding>,
+}
+
+// Read from the listener thread, which can't await the app state
+static BINDINGS: Mutex<Vec<HidBinding>> = Mutex::new(Vec::new());
+// Set while the capture flow waits for the next button press
+static CAPTURE: Mutex<Option<oneshot::Sender<HidButton>>> = Mutex::new(None);
+static APP: OnceLock<AppHandle> = OnceLock::new();
+
+// Device identity and previous report per raw input device handle (only touched on the listener thread)
+struct Device {
+    info: Option<(u16, u16, u16, u16)>,  // Vendor, product, usage page, usage
+    last_report: Vec<u8>,
+}
+
+thread_local! {
+    static DEVICES: std::cell::RefCell<HashMap<isize, Device>> = std::cell::RefCell::new(HashMap::new());
+}
+
+// Replace all bindings, starting the listener if any exist
+pub fn set_bindings(app: &AppHandle, bindings: Vec<HidBinding>) {
+    let listen = !bindings.is_empty();
+    *BINDINGS.lock().unwrap() = bindings;
+    if listen {
+        spawn_listener(app.clone());
+    }
+}
+
+pub fn bindings() -> Vec<HidBinding> {
+    BINDINGS.lock().unwrap().clone()
+}
+
+// Wait for the user to press a button on any HID device and return it
+pub async fn capture(app: &AppHandle) -> Result<HidButton, String> {
+    spawn_listener(app.clone());
+    let (tx, rx) = oneshot::channel();
+    *CAPTURE.lock().unwrap() = Some(tx);
+    log::info!("🦶 Waiting for a device button press");
+
+    let result = tokio::time::timeout(CAPTURE_TIMEOUT, rx).await;
+    CAPTURE.lock().unwrap().take();
+    match result {
+        Ok(Ok(button)) => {
+            log::info!("🦶 Captured {:?}", button);
+            Ok(button)
+        }
+        _ => Err("No device button was pressed".to_string()),
+    }
+}
+
+// Listen for HID input reports on a message-only window with its own message loop (started once)
+fn spawn_listener(app: AppHandle) {
+    if APP.set(app).is_err() {
+        return;
+    }
+
+    std::thread::spawn(|| unsafe {
+        let class = WNDCLASSW {
+            lpfnWndProc: Some(window_proc),
+            lpszClassName: w!("Whisper4WindowsHidInput"),
+            ..Default::default()
+        };
+        if RegisterClassW(&class) == 0 {
+            log::error!("❌ Failed to register HID input window class");
+            return;
+        }
+
+        let hwnd = CreateWindowExW(
+            WINDOW_EX_STYLE::default(),
+            w!("Whisper4WindowsHidInput"),
+            w!("Whisper4Windows HID input"),
+            WS_OVERLAPPED,
+            0,
+            0,
+            0,
+            0,
+            HWND_MESSAGE,
+            None,
+            HINSTANCE::default(),
+            None,
+        );
+        if hwnd.0 == 0 {
+            log::error!("❌ Failed to create HID input window");
+            return;
+        }
+        register_devices(hwnd);
+
+        let mut msg = MSG::default();
+        while GetMessageW(&mut msg, HWND::default(), 0, 0).as_bool() {
+            let _ = TranslateMessage(&msg);
+            DispatchMessageW(&msg);
+        }
+    });
+}
+
+// Ask for input from every kind of HID device that is plugged in, except keyboards and mice
+// (usage page 1, usages 2 and 6), which have their own hotkey handling
+unsafe fn register_devices(hwnd: HWND) {
+    let mut usages: Vec<(u16, u16)> = Vec::new();
+    for device in device_list() {
+        if device.dwType != RIM_TYPEHID {
+            continue;
+        }
+        let Some((_, _, page, usage)) = device_info(device.hDevice) else { continue };
+        let keyboard_or_mouse = page == 1 && matches!(usage, 2 | 6);
+        if !keyboard_or_mouse && !usages.contains(&(page, usage)) {
+            usages.push((page, usage));
+        }
+    }
+
+    let devices: Vec<RAWINPUTDEVICE> = usages
+        .iter()
+        .map(|&(page, usage)| RAWINPUTDEVICE {
+            usUsagePage: page,
+            usUsage: usage,
+            dwFlags: RIDEV_INPUTSINK | RIDEV_DEVNOTIFY,
+            hwndTarget: hwnd,
+        })
+        .collect();
+    if devices.is_empty() {
+        return;
+    }
+    match RegisterRawInputDevices(&devices, std::mem::size_of::<RAWINPUTDEVICE>() as u32) {
+        Ok(()) => log::info!("🦶 Listening to {} kinds of HID devices", devices.len()),
+        Err(e) => log::error!("❌ Failed to register for HID input: {}", e),
+    }
+}
+
+unsafe fn device_list() -> Vec<RAWINPUTDEVICELIST> {
+    let entry_size = std::mem::size_of::<RAWINPUTDEVICELIST>() as u32;
+    let mut count = 0u32;
+    if GetRawInputDeviceList(None, &mut count, entry_size) == u32::MAX || count == 0 {
+        return Vec::new();
+    }
+    let mut devices = vec![RAWINPUTDEVICELIST::default(); count as usize];
+    let found = GetRawInputDeviceList(Some(devices.as_mut_ptr()), &mut count, entry_size);
+    devices.truncate(if found == u32::MAX { 0 } else { found as usize });
+    devices
+}
+
+// Vendor, product, usage page, and usage of a HID device
+unsafe fn device_info(device: HANDLE) -> Option<(u16, u16, u16, u16)> {
+    let mut info = RID_DEVICE_INFO { cbSize: std::mem::size_of::<RID_DEVICE_INFO>() as u32, ..Default::default() };
+    let mut size = info.cbSize;
+    let read = GetRawInputDeviceInfoW(device, RIDI_DEVICEINFO, Some(&mut info as *mut _ as *mut _), &mut size);
+    if read == u32::MAX || info.dwType != RIM_TYPEHID {
+        return None;
+    }
+    let hid = info.Anonymous.hid;
+    Some((hid.dwVendorId as u16, hid.dwProductId as u16, hid.usUsagePage, hid.usUsage))
+}
+
+unsafe extern "system" fn window_proc(hwnd: HWND, msg: u32, wparam: WPARAM, lparam: LPARAM) -> LRESULT {
+    match msg {
+        WM_INPUT => handle_input(HRAWINPUT(lparam.0)),
+        // A newly plugged-in device may be a kind we haven't registered for yet
+        WM_INPUT_DEVICE_CHANGE if wparam.0 as u32 == GIDC_ARRIVAL => register_devices(hwnd),
+        _ => {}
+    }
+    DefWindowProcW(hwnd, msg, wparam, lparam)
+}
+
+unsafe fn handle_input(input: HRAWINPUT) {
+    let header_size = std::mem::size_of::<RAWINPUTHEADER>() as u32;
+    let mut size = 0u32;
+    GetRawInputData(input, RID_INPUT, None, &mut size, header_size);
+    // u64 storage keeps the RAWINPUT header aligned
+    let mut buffer = vec![0u64; (size as usize).div_ceil(8)];
+    if GetRawInputData(input, RID_INPUT, Some(buffer.as_mut_ptr() as *mut _), &mut size, header_size) == u32::MAX {
+        return;
+    }
+    let raw = &*(buffer.as_ptr() as *const RAWINPUT);
+    if raw.header.dwType != RIM_TYPEHID.0 {
+        return;
+    }
+
+    let report_size = raw.data.hid.dwSizeHid as usize;
+    let count = raw.data.hid.dwCount as usize;
+    let reports = std::slice::from_raw_parts(raw.data.hid.bRawData.as_ptr(), report_size * count);
+    let device = raw.header.hDevice;
+
+    let mut events = Vec::new();
+    DEVICES.with(|devices| {
+        let mut devices = devices.borrow_mut();
+        let state = devices
+            .entry(device.0)
+            .or_insert_with(|| Device { info: device_info(device), last_report: Vec::new() });
+        let Some((vendor_id, product_id, usage_page, usage)) = state.info else { return };
+
+        for report in reports.chunks(report_size.max(1)) {
+            // Without a previous report every set bit counts as a press
+            let previous = std::mem::replace(&mut state.last_report, report.to_vec());
+            for (index, &byte) in report.iter().enumerate() {
+                let changed = byte ^ previous.get(index).copied().unwrap_or(0);
+                for bit in (0..8).filter(|bit| changed & (1 << bit) != 0) {
+                    let button = HidButton { vendor_id, product_id, usage_page, usage, button: (index * 8 + bit) as u32 };
+                    events.push((button, byte & (1 << bit) != 0));
+                }
+            }
+        }
+    });
+
+    for (button, pressed) in events {
+        on_button(button, pressed);
+    }
+}
+
+fn on_button(button: HidButton, pressed: bool) {
+    // Capture takes the first button released, since a device's first report can't be told apart
+    // from a press (a report ID byte would look like a pressed button that never comes up)
+    {
+        let mut capture = CAPTURE.lock().unwrap();
+        if capture.is_some() {
+            if !pressed {
+                let _ = capture.take().unwrap().send(button);
+            }
+            return;
+        }
+    }
+
+    let Some(action) = BINDINGS.lock().unwrap().iter().find(|b| b.button == button).map(|b| b.action.clone()) else {
+        return;
+    };
+    if let Some(app) = APP.get() {
+        crate::run_button_action(app.clone(), &action, pressed);
+    }
+}
@@ -0,0 +1,583 @@
+use anyhow::{Context, Result};
+use rusqlite::{params, serialize::OwnedData, Connection, DatabaseName, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::ptr::NonNull;
+
+use crate::window_info::WindowTarget;
+use crate::{dpapi, AppState};
+
+pub const DB_FILE: &str = "history.db";
+
+// Entry columns added after the first release, created on open for older databases
+const ADDED_COLUMNS: &[(&str, &str)] =
+    &[("app", "TEXT"), ("window_title", "TEXT"), ("timings", "TEXT"), ("pinned", "INTEGER NOT NULL DEFAULT 0"), ("quality", "TEXT")];
+
+// Full-text index over entry texts, kept in step with `entries` by triggers
+const SEARCH_INDEX: &str = "CREATE VIRTUAL TABLE entries_fts USING fts5(
+        text, content = 'entries', content_rowid = 'id', tokenize = 'unicode61 remove_diacritics 2'
+    );
+    CREATE TRIGGER entries_fts_insert AFTER INSERT ON entries BEGIN
+        INSERT INTO entries_fts (rowid, text) VALUES (new.id, new.text);
+    END;
+    CREATE TRIGGER entries_fts_delete AFTER DELETE ON entries BEGIN
+        INSERT INTO entries_fts (entries_fts, rowid, text) VALUES ('delete', old.id, old.text);
+    END;
+    CREATE TRIGGER entries_fts_update AFTER UPDATE OF text ON entries BEGIN
+        INSERT INTO entries_fts (entries_fts, rowid, text) VALUES ('delete', old.id, old.text);
+        INSERT INTO entries_fts (rowid, text) VALUES (new.id, new.text);
+    END;
+    INSERT INTO entries_fts (entries_fts) VALUES ('rebuild');";
+
+// Search filters, numbered after the FTS query (?1) and before the limit (?7)
+const SEARCH_FILTERS: &str = "(?2 IS NULL OR e.created_at >= ?2) AND (?3 IS NULL OR e.created_at < ?3)
+    AND (?4 IS NULL OR e.language = ?4) AND (?5 IS NULL OR e.model = ?5) AND (?6 IS NULL OR e.app = ?6 COLLATE NOCASE)";
+
+// Marks around matched words in search snippets (split into parts before leaving the store)
+const MATCH_START: char = '\u{1}';
+const MATCH_END: char = '\u{2}';
+// Words of context in a search snippet, and characters of an entry shown when there are no keywords
+const SNIPPET_WORDS: u32 = 16;
+const PREVIEW_CHARS: usize = 160;
+
+// Entries with their word statistics, read by `entry_from_row`; callers add WHERE, GROUP BY, and ORDER BY
+const ENTRY_QUERY: &str = "SELECT e.id, e.created_at, e.text, e.language, e.model, e.device, e.duration_secs,
+        e.transcription_secs, e.speakers, COUNT(w.position), MIN(w.probability), e.app, e.window_title, e.timings,
+        e.pinned, e.quality
+     FROM entries e LEFT JOIN words w ON w.entry_id = e.id";
+
+// One recognized word with its position in the recording (seconds) and model confidence (0-1)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Word {
+    pub word: String,
+    pub start: f64,
+    pub end: f64,
+    pub probability: f64,
+}
+
+// A stretch of the transcript as the model segmented it, with the speaker when diarized
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Segment {
+    pub start: f64,
+    pub end: f64,
+    pub text: String,
+    #[serde(default)]
+    pub speaker: Option<String>,
+}
+
+// Seconds each stage took after the user pressed stop (stored as JSON; stages that didn't run are missing)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StageTimings {
+    pub finalize: Option<f64>,  // Stopping the capture and preparing the audio
+    pub model_wait: Option<f64>,  // Waiting for the model to finish loading
+    pub model_load: Option<f64>,  // The whole load, most of it while recording (0 if it was already loaded)
+    pub transcription: Option<f64>,  // Inference
+    pub diarization: Option<f64>,
+    pub injection: Option<f64>,  // Pasting or typing the text
+    pub total: Option<f64>,  // From pressing stop until the text was delivered
+}
+
+// What to search history for: keywords and/or filters (all optional; dates are Unix ms, end exclusive)
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct SearchQuery {
+    pub text: String,  // Every word must appear; the last one may be the start of a word
+    pub from: Option<i64>,
+    pub to: Option<i64>,
+    pub language: Option<String>,
+    pub model: Option<String>,
+    pub app: Option<String>,
+    pub limit: Option<u32>,
+}
+
+// A piece of a search snippet, matched words marked
+#[derive(Debug, Clone, Serialize)]
+pub struct SnippetPart {
+    pub text: String,
+    pub matched: bool,
+}
+
+// An entry found by a search, with the part of its text that matched
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchHit {
+    #[serde(flatten)]
+    pub entry: HistoryEntry,
+    pub snippet: Vec<SnippetPart>,
+}
+
+// Values the history can be filtered by, most used first
+#[derive(Debug, Clone, Serialize)]
+pub struct FilterValues {
+    pub apps: Vec<String>,
+    pub languages: Vec<String>,
+    pub models: Vec<String>,
+}
+
+// A delivered transcription (word timings and segments are fetched separately)
+#[derive(Debug, Clone, Serialize)]
+pub struct HistoryEntry {
+    pub id: i64,
+    pub created_at: i64,  // Unix time in milliseconds
+    pub text: String,  // Text as delivered (after formatting)
+    pub language: Option<String>,
+    pub model: Option<String>,
+    pub device: Option<String>,
+    pub duration_secs: Option<f64>,  // Length of the recording
+    pub transcription_secs: Option<f64>,  // Time the backend spent transcribing
+    pub speakers: Option<i64>,  // Number of speakers found (None if not diarized)
+    pub word_count: i64,  // Words with timing info stored for this entry
+    pub min_probability: Option<f64>,  // Lowest word confidence, to flag entries worth reviewing
+    pub app: Option<String>,  // Executable of the window the text was delivered to (e.g. "slack.exe")
+    pub window_title: Option<String>,
+    pub timings: Option<StageTimings>,
+    pub pinned: bool,  // Listed first, offered in the tray, and kept by retention
+    pub quality: Option<String>,  // Recording level: "clipping", "quiet", or "ok" (None before it was checked)
+}
+
+// Transcription history: a SQLite database held in memory and saved to the config directory
+// encrypted with DPAPI after every change, so transcripts never touch the disk in plaintext
+#[derive(Debug)]
+pub struct HistoryStore {
+    conn: Connection,
+    path: PathBuf,
+}
+
+impl HistoryStore {
+    // Load the database, encrypting it in place if an older version left it in plaintext
+    pub fn open(path: &Path) -> Result<Self> {
+        let mut conn = Connection::open_in_memory()?;
+        let mut migrate = false;
+        if let Some((contents, encrypted)) = dpapi::read_file(path)? {
+            if !contents.is_empty() {
+                conn.deserialize(DatabaseName::Main, owned_data(&contents)?, false)
+                    .with_context(|| format!("{} is not a valid history database", path.display()))?;
+            }
+            migrate = !encrypted;
+        }
+
+        conn.execute_batch(
+            "PRAGMA foreign_keys = ON;
+            CREATE TABLE IF NOT EXISTS entries (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                created_at INTEGER NOT NULL,
+                text TEXT NOT NULL,
+                language TEXT,
+                model TEXT,
+                device TEXT,
+                duration_secs REAL,
+                transcription_secs REAL,
+                speakers INTEGER,
+                app TEXT,
+                window_title TEXT,
+                timings TEXT,
+                pinned INTEGER NOT NULL DEFAULT 0,
+                quality TEXT
+            );
+            CREATE TABLE IF NOT EXISTS words (
+                entry_id INTEGER NOT NULL REFERENCES entries(id) ON DELETE CASCADE,
+                position INTEGER NOT NULL,
+                word TEXT NOT NULL,
+                start REAL NOT NULL,
+                end REAL NOT NULL,
+                probability REAL NOT NULL,
+                PRIMARY KEY (entry_id, position)
+            );
+            CREATE TABLE IF NOT EXISTS segments (
+                entry_id INTEGER NOT NULL REFERENCES entries(id) ON DELETE CASCADE,
+                position INTEGER NOT NULL,
+                start REAL NOT NULL,
+                end REAL NOT NULL,
+                text TEXT NOT NULL,
+                speaker TEXT,
+                PRIMARY KEY (entry_id, position)
+            );",
+        )?;
+        add_missing_columns(&conn)?;
+        let indexed = add_search_index(&conn)?;
+
+        let store = Self { conn, path: path.to_path_buf() };
+        if migrate || indexed {
+            store.save()?;
+        }
+        if migrate {
+            log::info!("🔐 Encrypted existing history database");
+        }
+        Ok(store)
+    }
+
+    fn save(&self) -> Result<()> {
+        let data = self.conn.serialize(DatabaseName::Main)?;
+        dpapi::write_file(&self.path, &data)
+    }
+
+    // Store a transcription from a backend result (`data`) along with its word timings and segments,
+    // and the window it was delivered to
+    pub fn add(&mut self, text: &str, data: &serde_json::Value, target: &WindowTarget) -> Result<i64> {
+        let words: Vec<Word> = parse_list(data, "words");
+        // Continuous dictation results carry untimed segments, which don't parse and are skipped
+        let segments: Vec<Segment> = parse_list(data, "segments");
+        let created_at = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_millis() as i64)
+            .unwrap_or_default();
+
+        let tx = self.conn.transaction()?;
+        tx.execute(
+            "INSERT INTO entries (created_at, text, language, model, device, duration_secs, transcription_secs, speakers, app, window_title, timings, quality)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+            params![
+                created_at,
+                text,
+                data.get("language").and_then(|l| l.as_str()),
+                data.get("model").and_then(|m| m.as_str()),
+                data.get("device").and_then(|d| d.as_str()),
+                data.get("duration").and_then(|d| d.as_f64()),
+                data.get("transcription_time").and_then(|t| t.as_f64()),
+                data.get("speakers").and_then(|s| s.as_i64()),
+                target.process_name,
+                target.title,
+                data.get("timings").map(|t| t.to_string()),
+                crate::mic_check::quality_flag(data),
+            ],
+        )?;
+        let id = tx.last_insert_rowid();
+
+        {
+            let mut insert = tx.prepare(
+                "INSERT INTO words (entry_id, position, word, start, end, probability) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            )?;
+            for (position, word) in words.iter().enumerate() {
+                insert.execute(params![id, position as i64, word.word, word.start, word.end, word.probability])?;
+            }
+
+            let mut insert = tx.prepare(
+                "INSERT INTO segments (entry_id, position, start, end, text, speaker) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            )?;
+            for (position, segment) in segments.iter().enumerate() {
+                insert.execute(params![id, position as i64, segment.start, segment.end, segment.text, segment.speaker])?;
+            }
+        }
+        tx.commit()?;
+        self.save()?;
+        Ok(id)
+    }
+
+    // Delete every entry (word timings and segments go with them)
+    pub fn clear(&mut self) -> Result<()> {
+        self.conn.execute("DELETE FROM entries", [])?;
+        self.conn.execute_batch("VACUUM")?;
+        self.save()
+    }
+
+    // Delete unpinned entries created before `cutoff` (Unix ms); returns how many went
+    pub fn delete_before(&mut self, cutoff: i64) -> Result<usize> {
+        let deleted = self.conn.execute("DELETE FROM entries WHERE created_at < ?1 AND pinned = 0", params![cutoff])?;
+        if deleted > 0 {
+            self.save()?;
+        }
+        Ok(deleted)
+    }
+
+    // Pin or unpin an entry; returns false if it doesn't exist
+    pub fn set_pinned(&mut self, id: i64, pinned: bool) -> Result<bool> {
+        let changed = self.conn.execute("UPDATE entries SET pinned = ?2 WHERE id = ?1", params![id, pinned])?;
+        if changed > 0 {
+            self.save()?;
+        }
+        Ok(changed > 0)
+    }
+
+    // Pinned entries first, then the most recent, optionally only those delivered to `app`
+    pub fn list(&self, limit: u32, app: Option<&str>) -> rusqlite::Result<Vec<HistoryEntry>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "{} WHERE ?2 IS NULL OR e.app = ?2 COLLATE NOCASE
+             GROUP BY e.id ORDER BY e.pinned DESC, e.created_at DESC, e.id DESC LIMIT ?1",
+            ENTRY_QUERY
+        ))?;
+        let entries = stmt.query_map(params![limit, app], entry_from_row)?.collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(entries)
+    }
+
+    // Oldest first: the given entries, or all created between `from` and `to` (Unix ms, end exclusive,
+    // either end open), for export
+    pub fn select(&self, ids: Option<&[i64]>, from: Option<i64>, to: Option<i64>) -> rusqlite::Result<Vec<HistoryEntry>> {
+        let ids = ids.map(|ids| serde_json::Value::from(ids.to_vec()).to_string());
+        let mut stmt = self.conn.prepare(&format!(
+            "{} WHERE (?1 IS NULL OR e.id IN (SELECT value FROM json_each(?1)))
+               AND (?2 IS NULL OR e.created_at >= ?2) AND (?3 IS NULL OR e.created_at < ?3)
+             GROUP BY e.id ORDER BY e.created_at, e.id",
+            ENTRY_QUERY
+        ))?;
+        let entries = stmt.query_map(params![ids, from, to], entry_from_row)?.collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(entries)
+    }
+
+    pub fn entry(&self, id: i64) -> rusqlite::Result<Option<HistoryEntry>> {
+        self.conn
+            .query_row(&format!("{} WHERE e.id = ?1 GROUP BY e.id", ENTRY_QUERY), params![id], entry_from_row)
+            .optional()
+    }
+
+    // The most recently created entry, pinned or not
+    pub fn latest(&self) -> rusqlite::Result<Option<HistoryEntry>> {
+        self.conn
+            .query_row(&format!("{} GROUP BY e.id ORDER BY e.created_at DESC, e.id DESC LIMIT 1", ENTRY_QUERY), [], entry_from_row)
+            .optional()
+    }
+
+    // Pinned entries, most recently created first
+    pub fn pinned(&self) -> rusqlite::Result<Vec<HistoryEntry>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "{} WHERE e.pinned = 1 GROUP BY e.id ORDER BY e.created_at DESC, e.id DESC",
+            ENTRY_QUERY
+        ))?;
+        let entries = stmt.query_map([], entry_from_row)?.collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(entries)
+    }
+
+    // Apps that received transcriptions, most used first (for filtering)
+    pub fn apps(&self) -> rusqlite::Result<Vec<String>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT app FROM entries WHERE app IS NOT NULL GROUP BY app COLLATE NOCASE ORDER BY COUNT(*) DESC",
+        )?;
+        let apps = stmt.query_map([], |row| row.get(0))?.collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(apps)
+    }
+
+    pub fn filter_values(&self) -> rusqlite::Result<FilterValues> {
+        let distinct = |column: &str| -> rusqlite::Result<Vec<String>> {
+            let mut stmt = self.conn.prepare(&format!(
+                "SELECT {0} FROM entries WHERE {0} IS NOT NULL GROUP BY {0} ORDER BY COUNT(*) DESC",
+                column
+            ))?;
+            let values = stmt.query_map([], |row| row.get(0))?.collect::<rusqlite::Result<Vec<_>>>()?;
+            Ok(values)
+        };
+        Ok(FilterValues { apps: self.apps()?, languages: distinct("language")?, models: distinct("model")? })
+    }
+
+    // Entries matching `query`: best keyword matches first, or newest first without keywords
+    pub fn search(&self, query: &SearchQuery) -> rusqlite::Result<Vec<SearchHit>> {
+        let limit = query.limit.unwrap_or(100);
+        let expression = match_expression(&query.text);
+        let filters = params![
+            expression,
+            query.from,
+            query.to,
+            query.language,
+            query.model,
+            query.app,
+            limit
+        ];
+
+        if expression.is_none() {
+            let mut stmt = self.conn.prepare(&format!(
+                "{} WHERE {} GROUP BY e.id ORDER BY e.created_at DESC, e.id DESC LIMIT ?7",
+                ENTRY_QUERY, SEARCH_FILTERS
+            ))?;
+            let entries = stmt.query_map(filters, entry_from_row)?.collect::<rusqlite::Result<Vec<_>>>()?;
+            return Ok(entries.into_iter().map(|entry| SearchHit { snippet: preview(&entry.text), entry }).collect());
+        }
+
+        let mut stmt = self.conn.prepare(&format!(
+            "WITH hits AS (SELECT rowid AS id, rank FROM entries_fts WHERE entries_fts MATCH ?1)
+             {} JOIN hits h ON h.id = e.id WHERE {}
+             GROUP BY e.id ORDER BY MIN(h.rank), e.created_at DESC LIMIT ?7",
+            ENTRY_QUERY, SEARCH_FILTERS
+        ))?;
+        let entries = stmt.query_map(filters, entry_from_row)?.collect::<rusqlite::Result<Vec<_>>>()?;
+
+        // Snippets only for the entries returned, rather than every match
+        let ids = serde_json::Value::from(entries.iter().map(|entry| entry.id).collect::<Vec<_>>()).to_string();
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT rowid, snippet(entries_fts, 0, char({}), char({}), '…', {}) FROM entries_fts
+             WHERE entries_fts MATCH ?1 AND rowid IN (SELECT value FROM json_each(?2))",
+            MATCH_START as u32, MATCH_END as u32, SNIPPET_WORDS
+        ))?;
+        let mut snippets = stmt
+            .query_map(params![expression, ids], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?)))?
+            .collect::<rusqlite::Result<std::collections::HashMap<_, _>>>()?;
+
+        Ok(entries
+            .into_iter()
+            .map(|entry| {
+                let snippet = snippets.remove(&entry.id).map(|s| snippet_parts(&s)).unwrap_or_else(|| preview(&entry.text));
+                SearchHit { entry, snippet }
+            })
+            .collect())
+    }
+
+    // Word timings for an entry, in spoken order (None if the entry doesn't exist)
+    pub fn words(&self, id: i64) -> rusqlite::Result<Option<Vec<Word>>> {
+        if !self.exists(id)? {
+            return Ok(None);
+        }
+
+        let mut stmt = self
+            .conn
+            .prepare("SELECT word, start, end, probability FROM words WHERE entry_id = ?1 ORDER BY position")?;
+        let words = stmt
+            .query_map(params![id], |row| {
+                Ok(Word {
+                    word: row.get(0)?,
+                    start: row.get(1)?,
+                    end: row.get(2)?,
+                    probability: row.get(3)?,
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(Some(words))
+    }
+
+    // Segments for an entry, in order (None if the entry doesn't exist)
+    pub fn segments(&self, id: i64) -> rusqlite::Result<Option<Vec<Segment>>> {
+        if !self.exists(id)? {
+            return Ok(None);
+        }
+
+        let mut stmt = self
+            .conn
+            .prepare("SELECT start, end, text, speaker FROM segments WHERE entry_id = ?1 ORDER BY position")?;
+        let segments = stmt
+            .query_map(params![id], |row| {
+                Ok(Segment {
+                    start: row.get(0)?,
+                    end: row.get(1)?,
+                    text: row.get(2)?,
+                    speaker: row.get(3)?,
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(Some(segments))
+    }
+
+    fn exists(&self, id: i64) -> rusqlite::Result<bool> {
+        let found = self
+            .conn
+            .query_row("SELECT 1 FROM entries WHERE id = ?1", params![id], |_| Ok(()))
+            .optional()?;
+        Ok(found.is_some())
+    }
+}
+
+fn entry_from_row(row: &rusqlite::Row) -> rusqlite::Result<HistoryEntry> {
+    Ok(HistoryEntry {
+        id: row.get(0)?,
+        created_at: row.get(1)?,
+        text: row.get(2)?,
+        language: row.get(3)?,
+        model: row.get(4)?,
+        device: row.get(5)?,
+        duration_secs: row.get(6)?,
+        transcription_secs: row.get(7)?,
+        speakers: row.get(8)?,
+        word_count: row.get(9)?,
+        min_probability: row.get(10)?,
+        app: row.get(11)?,
+        window_title: row.get(12)?,
+        timings: row.get::<_, Option<String>>(13)?.and_then(|json| serde_json::from_str(&json).ok()),
+        pinned: row.get(14)?,
+        quality: row.get(15)?,
+    })
+}
+
+// Copy a database image into a buffer SQLite can take ownership of
+fn owned_data(bytes: &[u8]) -> Result<OwnedData> {
+    unsafe {
+        let ptr = rusqlite::ffi::sqlite3_malloc64(bytes.len() as u64) as *mut u8;
+        let ptr = NonNull::new(ptr).context("Out of memory loading history")?;
+        std::ptr::copy_nonoverlapping(bytes.as_ptr(), ptr.as_ptr(), bytes.len());
+        Ok(OwnedData::from_raw_nonnull(ptr, bytes.len()))
+    }
+}
+
+// A list field of a backend result (empty if missing or malformed)
+fn parse_list<T: serde::de::DeserializeOwned>(data: &serde_json::Value, key: &str) -> Vec<T> {
+    data.get(key)
+        .cloned()
+        .and_then(|items| serde_json::from_value(items).ok())
+        .unwrap_or_default()
+}
+
+// Add a delivered transcription to history, unless privacy mode is on (failures are logged, never surfaced to the user)
+pub async fn record(state: &AppState, text: &str, data: &serde_json::Value, target: &WindowTarget) {
+    if text.is_empty() || crate::privacy::is_enabled(state).await {
+        return;
+    }
+    let mut history = state.history.lock().await;
+    let Some(store) = history.as_mut() else { return };
+
+    match store.add(text, data, target) {
+        Ok(id) => log::info!("🗂️ Saved transcription #{} to history", id),
+        Err(e) => log::error!("❌ Failed to save history entry: {}", e),
+    }
+}
+
+// Create the full-text index if this database doesn't have one yet; returns true if it did
+fn add_search_index(conn: &Connection) -> rusqlite::Result<bool> {
+    let exists = conn
+        .query_row("SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'entries_fts'", [], |_| Ok(()))
+        .optional()?
+        .is_some();
+    if !exists {
+        conn.execute_batch(SEARCH_INDEX)?;
+        log::info!("🔎 History search index built");
+    }
+    Ok(!exists)
+}
+
+// Typed keywords as an FTS5 query: each word quoted (so punctuation isn't read as syntax), all
+// required, and the last one matching word starts, so results show up while typing
+fn match_expression(text: &str) -> Option<String> {
+    let words: Vec<String> = text
+        .split_whitespace()
+        .map(|word| word.replace('"', ""))
+        .filter(|word| !word.is_empty())
+        .map(|word| format!("\"{}\"", word))
+        .collect();
+    if words.is_empty() {
+        return None;
+    }
+    Some(format!("{}*", words.join(" ")))
+}
+
+fn snippet_parts(snippet: &str) -> Vec<SnippetPart> {
+    let mut parts = Vec::new();
+    for (index, piece) in snippet.split(MATCH_START).enumerate() {
+        // Every piece after the first starts with a match, ended by MATCH_END
+        let (matched, rest) = match piece.split_once(MATCH_END) {
+            Some((matched, rest)) if index > 0 => (matched, rest),
+            _ => ("", piece),
+        };
+        if !matched.is_empty() {
+            parts.push(SnippetPart { text: matched.to_string(), matched: true });
+        }
+        if !rest.is_empty() {
+            parts.push(SnippetPart { text: rest.to_string(), matched: false });
+        }
+    }
+    parts
+}
+
+// The start of an entry, for results found by filters alone
+fn preview(text: &str) -> Vec<SnippetPart> {
+    let mut shown: String = text.chars().take(PREVIEW_CHARS).collect();
+    if text.chars().count() > PREVIEW_CHARS {
+        shown.push('…');
+    }
+    vec![SnippetPart { text: shown, matched: false }]
+}
+
+fn add_missing_columns(conn: &Connection) -> rusqlite::Result<()> {
+    let existing = conn
+        .prepare("SELECT name FROM pragma_table_info('entries')")?
+        .query_map([], |row| row.get::<_, String>(0))?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    for (name, column_type) in ADDED_COLUMNS {
+        if !existing.iter().any(|column| column == name) {
+            conn.execute(&format!("ALTER TABLE entries ADD COLUMN {} {}", name, column_type), [])?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,213 @@
+use chrono::{Local, TimeZone};
+use serde::{Deserialize, Serialize};
+use std::fmt::Write as _;
+
+use crate::history::HistoryEntry;
+use crate::zip::ZipWriter;
+
+pub const CONFIG_FILE: &str = "history_export.json";
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportFormat {
+    Markdown,
+    Csv,
+    Docx,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExportSettings {
+    // One entry; {text}, {app}, {window}, {language}, {model}, {duration}, and the entry's
+    // {date}, {time}, {datetime}, or custom formats like {date:%A}
+    pub markdown_template: String,
+}
+
+impl Default for ExportSettings {
+    fn default() -> Self {
+        Self { markdown_template: "## {datetime} · {app}\n\n{text}\n".to_string() }
+    }
+}
+
+pub fn export(format: ExportFormat, entries: &[HistoryEntry], settings: &ExportSettings) -> Vec<u8> {
+    match format {
+        ExportFormat::Markdown => markdown(entries, &settings.markdown_template).into_bytes(),
+        ExportFormat::Csv => csv(entries).into_bytes(),
+        ExportFormat::Docx => docx(entries),
+    }
+}
+
+// One session as a document: a title with its time span, then each entry under its time
+pub fn export_session(format: ExportFormat, entries: &[HistoryEntry]) -> Vec<u8> {
+    let (Some(first), Some(last)) = (entries.first(), entries.last()) else { return Vec::new() };
+    let title = format!(
+        "Session {} – {}",
+        local_time(first).format("%Y-%m-%d %H:%M"),
+        local_time(last).format("%H:%M")
+    );
+    let time = |entry: &HistoryEntry| local_time(entry).format("%H:%M:%S").to_string();
+
+    match format {
+        ExportFormat::Markdown => {
+            let mut document = format!("# {}
+", title);
+            for entry in entries {
+                let _ = write!(document, "\n**{}**", time(entry));
+                if let Some(app) = &entry.app {
+                    let _ = write!(document, " · {}", app);
+                }
+                let _ = write!(document, "\n\n{}\n", entry.text);
+            }
+            document.into_bytes()
+        }
+        ExportFormat::Csv => csv(entries).into_bytes(),
+        ExportFormat::Docx => {
+            let mut body = paragraph(&title, true);
+            body.push_str("<w:p/>");
+            for entry in entries {
+                body.push_str(&paragraph(&time(entry), true));
+                for line in entry.text.lines() {
+                    body.push_str(&paragraph(line, false));
+                }
+            }
+            docx_package(&body)
+        }
+    }
+}
+
+fn local_time(entry: &HistoryEntry) -> chrono::DateTime<Local> {
+    Local.timestamp_millis_opt(entry.created_at).single().unwrap_or_else(Local::now)
+}
+
+fn heading(entry: &HistoryEntry) -> String {
+    let time = local_time(entry).format("%Y-%m-%d %H:%M").to_string();
+    match &entry.app {
+        Some(app) => format!("{} · {}", time, app),
+        None => time,
+    }
+}
+
+// The template filled in per entry, with the transcription last so braces in it stay as spoken
+fn markdown(entries: &[HistoryEntry], template: &str) -> String {
+    let mut document = String::from("# Whisper4Windows history\n");
+    for entry in entries {
+        let rendered = crate::snippets::render_at(template, local_time(entry), &crate::regional_format::ISO, false)
+            .replace("{app}", entry.app.as_deref().unwrap_or("unknown app"))
+            .replace("{window}", entry.window_title.as_deref().unwrap_or_default())
+            .replace("{language}", entry.language.as_deref().unwrap_or_default())
+            .replace("{model}", entry.model.as_deref().unwrap_or_default())
+            .replace("{duration}", &entry.duration_secs.map(|secs| format!("{:.1}s", secs)).unwrap_or_default())
+            .replace("{text}", &entry.text);
+        document.push('\n');
+        document.push_str(&rendered);
+        if !rendered.ends_with('\n') {
+            document.push('\n');
+        }
+    }
+    document
+}
+
+// UTF-8 with a byte order mark, which Excel needs to read non-ASCII text correctly
+fn csv(entries: &[HistoryEntry]) -> String {
+    let mut document = String::from(
+        "\u{FEFF}id,time,app,window_title,language,model,device,duration_secs,transcription_secs,model_wait_secs,injection_secs,total_secs,speakers,min_confidence,text\r\n",
+    );
+    let number = |value: Option<f64>| value.map(|v| format!("{:.2}", v)).unwrap_or_default();
+    for entry in entries {
+        let timings = entry.timings.clone().unwrap_or_default();
+        let fields = [
+            entry.id.to_string(),
+            local_time(entry).format("%Y-%m-%d %H:%M:%S").to_string(),
+            csv_field(entry.app.as_deref().unwrap_or_default()),
+            csv_field(entry.window_title.as_deref().unwrap_or_default()),
+            csv_field(entry.language.as_deref().unwrap_or_default()),
+            csv_field(entry.model.as_deref().unwrap_or_default()),
+            csv_field(entry.device.as_deref().unwrap_or_default()),
+            number(entry.duration_secs),
+            number(entry.transcription_secs),
+            number(timings.model_wait),
+            number(timings.injection),
+            number(timings.total),
+            entry.speakers.map(|s| s.to_string()).unwrap_or_default(),
+            number(entry.min_probability),
+            csv_field(&entry.text),
+        ];
+        document.push_str(&fields.join(","));
+        document.push_str("\r\n");
+    }
+    document
+}
+
+// Quoted when needed; a leading = + - or @ gets an apostrophe so spreadsheets don't run it as a formula
+fn csv_field(value: &str) -> String {
+    let value = if value.starts_with(['=', '+', '-', '@']) { format!("'{}", value) } else { value.to_string() };
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
+    }
+}
+
+// A minimal Word document: a bold heading line and the text for each entry
+fn docx(entries: &[HistoryEntry]) -> Vec<u8> {
+    let mut body = String::new();
+    for entry in entries {
+        body.push_str(&paragraph(&heading(entry), true));
+        for line in entry.text.lines() {
+            body.push_str(&paragraph(line, false));
+        }
+        body.push_str("<w:p/>");
+    }
+    docx_package(&body)
+}
+
+fn paragraph(text: &str, bold: bool) -> String {
+    let style = if bold { "<w:rPr><w:b/></w:rPr>" } else { "" };
+    format!("<w:p><w:r>{}<w:t xml:space=\"preserve\">{}</w:t></w:r></w:p>", style, xml_escape(text))
+}
+
+// The document body wrapped into a .docx package
+fn docx_package(body: &str) -> Vec<u8> {
+    let document = format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\
+         <w:document xmlns:w=\"http://schemas.openxmlformats.org/wordprocessingml/2006/main\"><w:body>{}</w:body></w:document>",
+        body
+    );
+
+    let mut zip = ZipWriter::default();
+    zip.add(
+        "[Content_Types].xml",
+        b"<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\
+          <Types xmlns=\"http://schemas.openxmlformats.org/package/2006/content-types\">\
+          <Default Extension=\"rels\" ContentType=\"application/vnd.openxmlformats-package.relationships+xml\"/>\
+          <Default Extension=\"xml\" ContentType=\"application/xml\"/>\
+          <Override PartName=\"/word/document.xml\" ContentType=\"application/vnd.openxmlformats-officedocument.wordprocessingml.document.main+xml\"/>\
+          </Types>",
+    );
+    zip.add(
+        "_rels/.rels",
+        b"<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\
+          <Relationships xmlns=\"http://schemas.openxmlformats.org/package/2006/relationships\">\
+          <Relationship Id=\"rId1\" Type=\"http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument\" Target=\"word/document.xml\"/>\
+          </Relationships>",
+    );
+    zip.add("word/document.xml", document.as_bytes());
+    zip.finish()
+}
+
+// Escaped for XML text, dropping control characters XML doesn't allow
+fn xml_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\t' => escaped.push(c),
+            c if c.is_control() => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
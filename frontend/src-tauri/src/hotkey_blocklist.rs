@@ -0,0 +1,50 @@
+use tauri::{AppHandle, Manager};
+
+use crate::AppState;
+
+// The blocklisted process names, as a JSON list
+pub const CONFIG_FILE: &str = "hotkey_blocklist.json";
+
+// How often the foreground app is checked against the blocklist
+const POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(300);
+
+// Suspend the global shortcuts while a blocklisted app is in the foreground so it receives the
+// keys itself, and restore them when focus moves elsewhere
+pub fn spawn_watcher(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut suspended_for: Option<String> = None;
+
+        loop {
+            tokio::time::sleep(POLL_INTERVAL).await;
+
+            let state: tauri::State<AppState> = app.state();
+            let blocklist = state.hotkey_blocklist.lock().await.clone();
+
+            // Never suspend mid-recording, the user still needs a way to stop
+            let is_recording = crate::recording_state::is_recording(&state).await;
+
+            let foreground = if blocklist.is_empty() || is_recording {
+                None
+            } else {
+                crate::window_info::foreground_process_name()
+                    .filter(|name| blocklist.iter().any(|b| b.eq_ignore_ascii_case(name)))
+            };
+
+            match (&suspended_for, foreground) {
+                (None, Some(process)) => {
+                    log::info!("⏸️ Hotkeys suspended while {} is focused", process);
+                    crate::set_global_shortcuts_active(&app, false).await;
+                    suspended_for = Some(process);
+                }
+                (Some(process), None) => {
+                    log::info!("▶️ Hotkeys restored ({} lost focus)", process);
+                    crate::set_global_shortcuts_active(&app, true).await;
+                    suspended_for = None;
+                }
+                _ => {}
+            }
+
+            *state.hotkeys_suspended.lock().await = suspended_for.is_some();
+        }
+    });
+}
@@ -0,0 +1,66 @@
+use std::time::Duration;
+use windows::Win32::Foundation::{HWND, LPARAM, WPARAM};
+use windows::Win32::UI::Input::Ime::{ImmGetDefaultIMEWnd, IMC_SETOPENSTATUS};
+use windows::Win32::UI::WindowsAndMessaging::{
+    GetGUIThreadInfo, GetWindowThreadProcessId, SendMessageTimeoutW, GUITHREADINFO, SMTO_ABORTIFHUNG, WM_IME_CONTROL,
+};
+
+// Missing from the windows crate
+const IMC_GETOPENSTATUS: usize = 0x0005;
+// The target app may be busy; injection shouldn't hang on it
+const MESSAGE_TIMEOUT_MS: u32 = 200;
+
+// An open Japanese, Chinese, or Korean IME treats pasted or typed text as keyboard input to
+// compose, which can mangle CJK transcriptions. While this guard lives the target's IME is
+// closed, so text lands verbatim; dropping it reopens the IME.
+pub struct ImeGuard {
+    ime_window: HWND,
+    settle: Duration,  // Time for the app to handle the injected keys before the IME comes back
+}
+
+// Close the IME of the window's focused control if it is open
+pub fn suspend(window: HWND, settle: Duration) -> Option<ImeGuard> {
+    unsafe {
+        let ime_window = ImmGetDefaultIMEWnd(focused_control(window));
+        if ime_window.0 == 0 || send_ime_control(ime_window, IMC_GETOPENSTATUS, 0)? == 0 {
+            return None;
+        }
+        send_ime_control(ime_window, IMC_SETOPENSTATUS as usize, 0)?;
+        log::info!("⌨️ Input method closed for injection");
+        Some(ImeGuard { ime_window, settle })
+    }
+}
+
+impl Drop for ImeGuard {
+    fn drop(&mut self) {
+        std::thread::sleep(self.settle);
+        if unsafe { send_ime_control(self.ime_window, IMC_SETOPENSTATUS as usize, 1) }.is_none() {
+            log::warn!("⚠️ Failed to reopen the input method");
+        }
+    }
+}
+
+// The control with keyboard focus in the window's thread, which owns the IME context
+unsafe fn focused_control(window: HWND) -> HWND {
+    let thread = GetWindowThreadProcessId(window, None);
+    let mut info = GUITHREADINFO { cbSize: std::mem::size_of::<GUITHREADINFO>() as u32, ..Default::default() };
+    match GetGUIThreadInfo(thread, &mut info) {
+        Ok(()) if info.hwndFocus.0 != 0 => info.hwndFocus,
+        _ => window,
+    }
+}
+
+// Cross-process IME control through the default IME window; None if the app didn't answer
+unsafe fn send_ime_control(ime_window: HWND, command: usize, value: isize) -> Option<usize> {
+    let mut result = 0usize;
+    let sent = SendMessageTimeoutW(
+        ime_window,
+        WM_IME_CONTROL,
+        WPARAM(command),
+        LPARAM(value),
+        SMTO_ABORTIFHUNG,
+        MESSAGE_TIMEOUT_MS,
+        Some(&mut result),
+    );
+    (sent.0 != 0).then_some(result)
+}
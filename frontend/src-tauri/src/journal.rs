@@ -0,0 +1,92 @@
+use serde::{Deserialize, Serialize};
+use std::fmt::Write as _;
+use std::io::Write as _;
+use std::path::PathBuf;
+use tauri::{AppHandle, Emitter};
+
+use crate::regional_format::RegionalFormat;
+use crate::window_info::WindowTarget;
+use crate::AppState;
+
+pub const CONFIG_FILE: &str = "journal.json";
+
+// Appending every dictation to a file (e.g. a daily note), in addition to the output mode
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct JournalSettings {
+    pub enabled: bool,
+    pub path: String,  // strftime codes pick the file per day, e.g. C:\Notes\%Y-%m-%d.md
+    pub template: String,  // One entry; {text}, {app}, and the snippet placeholders ({time}, {date:%A}, ...)
+}
+
+impl Default for JournalSettings {
+    fn default() -> Self {
+        Self { enabled: false, path: String::new(), template: "- {time} {text}".to_string() }
+    }
+}
+
+// The file today's entries go to, or an error for an unusable path pattern
+pub fn resolve_path(pattern: &str) -> Result<PathBuf, String> {
+    if pattern.trim().is_empty() {
+        return Err("No journal file set".to_string());
+    }
+    // An invalid format string makes Display fail, which `to_string` would turn into a panic
+    let mut path = String::new();
+    write!(path, "{}", chrono::Local::now().format(pattern.trim()))
+        .map_err(|_| format!("Invalid date pattern in {}", pattern))?;
+    let path = PathBuf::from(path);
+    if !path.is_absolute() {
+        return Err(format!("The journal path must be absolute: {}", path.display()));
+    }
+    Ok(path)
+}
+
+// One entry, with the transcription and app filled in after the date placeholders so braces in them stay as spoken
+fn render(template: &str, text: &str, target: &WindowTarget, format: &RegionalFormat) -> String {
+    crate::snippets::render(template, format)
+        .replace("{app}", target.process_name.as_deref().unwrap_or_default())
+        .replace("{text}", text)
+}
+
+fn append(settings: &JournalSettings, text: &str, target: &WindowTarget, format: &RegionalFormat) -> Result<PathBuf, String> {
+    let path = resolve_path(&settings.path)?;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| format!("Can't create {}: {}", dir.display(), e))?;
+    }
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .map_err(|e| format!("Can't open {}: {}", path.display(), e))?;
+
+    // Start on a new line even if the file was last edited by hand without a trailing newline
+    let needs_newline = std::fs::read(&path).map(|b| !b.is_empty() && !b.ends_with(b"\n")).unwrap_or(false);
+    let mut entry = render(&settings.template, text, target, format);
+    if needs_newline {
+        entry.insert(0, '\n');
+    }
+    entry.push('\n');
+    file.write_all(entry.as_bytes()).map_err(|e| format!("Can't write {}: {}", path.display(), e))?;
+    Ok(path)
+}
+
+// Append a delivered transcription to the journal (skipped in privacy mode, like history)
+pub async fn record(app: &AppHandle, state: &AppState, text: &str, target: &WindowTarget) {
+    if text.is_empty() || crate::privacy::is_enabled(state).await {
+        return;
+    }
+    let settings = state.journal.lock().await.clone();
+    if !settings.enabled {
+        return;
+    }
+
+    let format = crate::regional_format::current(state).await;
+    match append(&settings, text, target, format) {
+        Ok(path) => log::info!("📓 Appended transcription to {}", path.display()),
+        Err(e) => {
+            log::error!("❌ Journal append failed: {}", e);
+            let _ = app.emit("journal-error", e.clone());
+            crate::show_toast(app, &crate::locale::tr("toast.journal_failed"), &e);
+        }
+    }
+}
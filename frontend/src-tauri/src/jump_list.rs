@@ -0,0 +1,89 @@
+use std::path::Path;
+use tauri::AppHandle;
+use windows::core::{ComInterface, GUID, HSTRING};
+use windows::Win32::System::Com::StructuredStorage::{PropVariantClear, PROPVARIANT};
+use windows::Win32::System::Com::{CoCreateInstance, CoInitializeEx, CLSCTX_INPROC_SERVER, COINIT_APARTMENTTHREADED};
+use windows::Win32::System::Variant::VT_LPWSTR;
+use windows::Win32::UI::Shell::Common::{IObjectArray, IObjectCollection};
+use windows::Win32::UI::Shell::PropertiesSystem::{IPropertyStore, PROPERTYKEY};
+use windows::Win32::UI::Shell::{
+    DestinationList, EnumerableObjectCollection, ICustomDestinationList, IShellLinkW, SHStrDupW, ShellLink,
+};
+
+// Tasks in the taskbar button's jump list. Each one starts the executable again with
+// `--task <action>`; the single-instance plugin hands those arguments to the running app, which
+// runs the tray menu item with the same id.
+
+pub const TASK_ARG: &str = "--task";
+
+// (tray menu id, title text key)
+const TASKS: &[(&str, &str)] = &[
+    ("toggle", "jump.toggle"),
+    ("history", "jump.history"),
+    ("transcribe_file", "jump.transcribe_file"),
+    ("privacy", "jump.privacy"),
+];
+
+// System.Title, the text a jump list shows for a link
+const PKEY_TITLE: PROPERTYKEY = PROPERTYKEY { fmtid: GUID::from_u128(0xf29f85e0_4ff9_1068_ab91_08002b27b3d9), pid: 2 };
+
+// Register the tasks with the shell, titled in the active locale; they stay until replaced
+pub fn install() {
+    let exe = match std::env::current_exe() {
+        Ok(exe) => exe,
+        Err(e) => {
+            log::warn!("⚠️ No jump list tasks: {}", e);
+            return;
+        }
+    };
+    // The shell's COM objects want a single-threaded apartment, which pool threads may not be
+    std::thread::spawn(move || match unsafe { add_tasks(&exe) } {
+        Ok(()) => log::info!("✅ Jump list tasks registered"),
+        Err(e) => log::warn!("⚠️ Failed to register jump list tasks: {}", e),
+    });
+}
+
+// The task a jump list launch asked for, from the command line
+pub fn task_from_args(args: &[String]) -> Option<&str> {
+    let index = args.iter().position(|arg| arg == TASK_ARG)?;
+    let task = args.get(index + 1)?.as_str();
+    TASKS.iter().any(|(id, _)| *id == task).then_some(task)
+}
+
+// Run the task from a jump list launch (a second instance's arguments, or our own at startup)
+pub fn run(app: &AppHandle, args: &[String]) -> bool {
+    let Some(task) = task_from_args(args) else { return false };
+    log::info!("📌 Jump list task: {}", task);
+    crate::run_tray_action(app, task);
+    true
+}
+
+unsafe fn add_tasks(exe: &Path) -> windows::core::Result<()> {
+    let _ = CoInitializeEx(None, COINIT_APARTMENTTHREADED);
+    let exe = HSTRING::from(exe.to_string_lossy().as_ref());
+
+    let list: ICustomDestinationList = CoCreateInstance(&DestinationList, None, CLSCTX_INPROC_SERVER)?;
+    let mut slots = 0u32;
+    let _removed: IObjectArray = list.BeginList(&mut slots)?;
+
+    let tasks: IObjectCollection = CoCreateInstance(&EnumerableObjectCollection, None, CLSCTX_INPROC_SERVER)?;
+    for (id, title_key) in TASKS {
+        let link: IShellLinkW = CoCreateInstance(&ShellLink, None, CLSCTX_INPROC_SERVER)?;
+        link.SetPath(&exe)?;
+        link.SetArguments(&HSTRING::from(format!("{} {}", TASK_ARG, id)))?;
+        link.SetIconLocation(&exe, 0)?;
+
+        let store: IPropertyStore = link.cast()?;
+        let mut value = PROPVARIANT::default();
+        (*value.Anonymous.Anonymous).vt = VT_LPWSTR;
+        (*value.Anonymous.Anonymous).Anonymous.pwszVal = SHStrDupW(&HSTRING::from(crate::locale::tr(title_key)))?;
+        let stored = store.SetValue(&PKEY_TITLE, &value).and_then(|_| store.Commit());
+        let _ = PropVariantClear(&mut value);
+        stored?;
+
+        tasks.AddObject(&link)?;
+    }
+
+    list.AddUserTasks(&tasks.cast::<IObjectArray>()?)?;
+    list.CommitList()
+}
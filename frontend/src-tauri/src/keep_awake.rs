@@ -0,0 +1,53 @@
+use serde::{Deserialize, Serialize};
+use std::sync::mpsc::{self, Sender};
+use std::sync::Mutex;
+use windows::Win32::System::Power::{SetThreadExecutionState, ES_CONTINUOUS, ES_DISPLAY_REQUIRED, ES_SYSTEM_REQUIRED};
+
+pub const CONFIG_FILE: &str = "keep_awake.json";
+
+// Keeping Windows awake while recording and transcribing
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct KeepAwakeSettings {
+    pub enabled: bool,  // Block system sleep
+    pub keep_display_on: bool,  // Also block the display from turning off
+}
+
+impl Default for KeepAwakeSettings {
+    fn default() -> Self {
+        Self { enabled: true, keep_display_on: false }
+    }
+}
+
+// SetThreadExecutionState applies to the calling thread, and async tasks hop between threads,
+// so the request is held by a dedicated thread until its sender is dropped
+static HOLDER: Mutex<Option<Sender<()>>> = Mutex::new(None);
+
+pub fn acquire(settings: &KeepAwakeSettings) {
+    if !settings.enabled {
+        return;
+    }
+    let mut holder = HOLDER.lock().unwrap();
+    if holder.is_some() {
+        return;
+    }
+
+    let mut flags = ES_CONTINUOUS | ES_SYSTEM_REQUIRED;
+    if settings.keep_display_on {
+        flags |= ES_DISPLAY_REQUIRED;
+    }
+    let (tx, rx) = mpsc::channel::<()>();
+    std::thread::spawn(move || {
+        unsafe { SetThreadExecutionState(flags) };
+        let _ = rx.recv();  // Errors out once the sender is dropped by `release`
+        unsafe { SetThreadExecutionState(ES_CONTINUOUS) };
+    });
+    *holder = Some(tx);
+    log::info!("☕ Preventing sleep{}", if settings.keep_display_on { " and display-off" } else { "" });
+}
+
+pub fn release() {
+    if HOLDER.lock().unwrap().take().is_some() {
+        log::info!("😴 Sleep allowed again");
+    }
+}
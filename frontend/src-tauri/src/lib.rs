@@ -1,138 +1,492 @@
+mod accessibility;
+mod audio_decode;
+mod backend;
+mod benchmark;
+mod caption_server;
+mod casing;
+mod clipboard;
+mod code_mode;
+mod crash_recovery;
+mod crash_report;
+mod custom_models;
+mod decoding;
+mod dictation;
+mod double_tap;
+mod dpapi;
+mod ducking;
+mod focus_assist;
+mod elevation;
+mod file_transcription;
+mod formatting;
+mod gamepad;
+mod hid_trigger;
+mod history;
+mod history_export;
+mod hotkey_blocklist;
+mod ime;
+mod journal;
+mod jump_list;
+mod keep_awake;
+mod ll_hook;
+mod locale;
+mod main_window;
+mod meeting;
+mod navigation;
+mod output_files;
+mod output_template;
+mod mic_check;
+mod microphones;
+mod model_memory;
+mod model_slots;
+mod mouse_hook;
+mod overlay;
+mod pinned;
+mod policy;
+mod prebuffer;
+mod privacy;
+mod profanity;
+mod provision;
+mod quick_switch;
+mod quit;
+mod spoken_punctuation;
+mod stats;
+mod recording_limits;
+mod recording_meter;
+mod recording_state;
+mod regional_format;
+mod remote;
+mod retention;
+mod rtl;
+mod scratchpad;
+mod shell_menu;
+mod self_test;
+mod sessions;
+mod send_enter;
+mod send_target;
+mod settings_bundle;
+mod snippets;
+mod sounds;
+mod storage;
+mod system_events;
+mod tray_icon;
+mod watch_folder;
+mod window_info;
+mod win_h;
+mod zip;
+
+use std::collections::HashMap;
 use std::sync::Arc;
 use tauri::{
-    menu::{Menu, MenuItem},
+    menu::{CheckMenuItem, Menu, MenuItem},
     tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent},
-    Manager, AppHandle, State,
+    Manager, AppHandle, State, Emitter,
 };
 use windows::Win32::{
     UI::Input::KeyboardAndMouse::{
-        SendInput, INPUT, INPUT_KEYBOARD, KEYBDINPUT, KEYEVENTF_KEYUP,
-        VK_CONTROL, VK_V, KEYEVENTF_EXTENDEDKEY,
-    },
-    System::DataExchange::{
-        OpenClipboard, CloseClipboard, EmptyClipboard, SetClipboardData, GetClipboardData,
+        SendInput, INPUT, INPUT_KEYBOARD, KEYBDINPUT, KEYEVENTF_KEYUP, KEYEVENTF_UNICODE,
+        VK_CONTROL, VK_V, VK_RETURN, KEYEVENTF_EXTENDEDKEY, VIRTUAL_KEY, KEYBD_EVENT_FLAGS,
     },
-    System::Memory::{GlobalAlloc, GlobalLock, GlobalUnlock, GlobalSize, GMEM_MOVEABLE},
-    Foundation::{HWND, HANDLE, HGLOBAL},
+    UI::WindowsAndMessaging::GetForegroundWindow,
 };
 use tokio::sync::Mutex;
 use anyhow::Result;
 use tauri_plugin_global_shortcut::{Code, Modifiers, Shortcut, GlobalShortcutExt};
+use formatting::{FormattingProfiles, FormattingSettings};
+use recording_state::RecordingState;
 
 // Simple state - track model, device, and clipboard setting
 #[derive(Debug, Clone)]
 pub struct AppState {
     pub selected_model: Arc<Mutex<String>>,
     pub selected_device: Arc<Mutex<String>>,
+    pub model_slots: Arc<Mutex<model_slots::ModelSlots>>,  // Quick/accurate models used by their own hotkeys (persisted)
+    pub custom_models: Arc<Mutex<custom_models::CustomModelStore>>,  // Models added from a folder or Hugging Face ID (persisted)
+    pub tray_icon: Arc<Mutex<tray_icon::TrayIconSettings>>,  // Tray icon theme and icon pack (persisted)
+    pub sounds: Arc<Mutex<sounds::SoundSettings>>,  // Audio cues for recording events (persisted)
+    pub accessibility: Arc<Mutex<accessibility::AccessibilitySettings>>,  // Spoken status announcements (persisted)
+    pub locale: Arc<Mutex<locale::LocaleSettings>>,  // Display language override (persisted; texts read the active locale)
+    pub advanced: Arc<Mutex<decoding::AdvancedSettings>>,  // Compute type and decoding parameters, per model slot (persisted)
+    pub next_slot: Arc<Mutex<Option<String>>>,  // Slot the next recording uses, set by a slot's hotkey (None = default)
+    pub recording_slot: Arc<Mutex<String>>,  // Slot of the current or last recording, which Retry transcribes with
     pub selected_microphone: Arc<Mutex<Option<i32>>>,  // Microphone device index (None = default)
-    pub use_clipboard: Arc<Mutex<bool>>,  // New: whether to paste to clipboard
+    pub microphones: Arc<Mutex<microphones::MicrophoneSettings>>,  // Preferred microphones, tried in order (persisted)
+    pub prebuffer: Arc<Mutex<prebuffer::PrebufferSettings>>,  // Audio kept from before a recording starts (persisted)
+    pub audio_processing: Arc<Mutex<AudioProcessingSettings>>,  // Clean-up the backend applies before transcribing (persisted)
+    pub output_mode: Arc<Mutex<OutputMode>>,  // Paste, copy to clipboard, or both (persisted)
+    pub multilingual: Arc<Mutex<MultilingualSettings>>,  // Per-segment language detection (persisted)
+    pub recording_language: Arc<Mutex<Option<String>>>,  // One-off language for the current recording, picked in the overlay
     pub selected_language: Arc<Mutex<String>>,  // Selected language code
-    pub toggle_shortcut: Arc<Mutex<String>>,  // Toggle recording shortcut
-    pub cancel_shortcut: Arc<Mutex<String>>,  // Cancel recording shortcut
+    pub shortcuts: Arc<Mutex<HashMap<String, String>>>,  // Hotkey action -> shortcut string ("" = unbound, persisted)
+    pub hotkey_blocklist: Arc<Mutex<Vec<String>>>,  // Process names for which global shortcuts are released (persisted)
+    pub hotkeys_suspended: Arc<Mutex<bool>>,  // True while a blocklisted app is focused
+    pub double_tap: Arc<Mutex<double_tap::DoubleTapSettings>>,  // Double-tap modifier as an extra toggle trigger (persisted)
+    pub win_h: Arc<Mutex<win_h::WinHSettings>>,  // Win+H toggles recording instead of Windows voice typing (persisted)
+    pub meeting_capture: Arc<Mutex<meeting::MeetingCaptureSettings>>,  // Apps whose calls are offered for capture (persisted)
+    pub formatting: Arc<Mutex<FormattingProfiles>>,  // Text formatting applied before injection, per model slot (persisted)
+    pub snippets: Arc<Mutex<snippets::SnippetStore>>,  // Spoken phrases expanded to text blocks (persisted)
+    pub scratchpad: Arc<Mutex<scratchpad::Scratchpad>>,  // Text collected in the scratchpad window (persisted)
+    pub send_targets: Arc<Mutex<send_target::SendTargetStore>>,  // Fixed apps/URIs to send transcriptions to (persisted)
+    pub code_mode: Arc<Mutex<code_mode::CodeModeSettings>>,  // Model slots that dictate code (persisted)
+    pub send_enter: Arc<Mutex<send_enter::SendEnterSettings>>,  // Model slots that press Enter after injecting (persisted)
+    pub casing: Arc<Mutex<casing::CasingSettings>>,  // Spoken casing commands like "camel case" (persisted)
+    pub regional_formats: Arc<Mutex<regional_format::RegionalSettings>>,  // Date and number formats per model slot (persisted)
+    pub output_files: Arc<Mutex<output_files::OutputFileSettings>>,  // Subtitle/text files saved per model slot (persisted)
+    pub output_templates: Arc<Mutex<output_template::TemplateStore>>,  // Templates wrapping delivered text, per model slot (persisted)
+    pub injection: Arc<Mutex<InjectionSettings>>,  // How text is delivered to the target window (persisted)
+    pub overlay: Arc<Mutex<overlay::OverlayConfig>>,  // Overlay placement and remembered positions (persisted)
+    pub overlay_tracker: Arc<Mutex<overlay::OverlayTracker>>,  // Detects user drags of the overlay
+    pub main_window: Arc<Mutex<main_window::MainWindowSettings>>,  // Start in the tray, close to the tray (persisted; read by the close handler)
+    // A std mutex: show_toast reads it synchronously, and is called from async code where tokio's blocking_lock panics
+    pub focus_assist: Arc<std::sync::Mutex<focus_assist::FocusAssistSettings>>,  // Quiet while Focus Assist is on (persisted; read by toasts)
     pub backend_child: Arc<Mutex<Option<tauri_plugin_shell::process::CommandChild>>>,  // Backend process handle
+    pub recording_state: Arc<Mutex<recording_state::RecordingState>>,  // Idle/recording/processing/...; change via recording_state::transition
+    pub is_paused: Arc<Mutex<bool>>,  // Recording is active but audio is currently discarded
+    pub continuous_dictation: Arc<Mutex<bool>>,  // Inject each pause-delimited segment while still recording (persisted)
+    pub diarization: Arc<Mutex<bool>>,  // Label speakers in the final transcript (needs pyannote in the backend, persisted)
+    pub dictation: Arc<Mutex<dictation::DictationProgress>>,  // Segments delivered in the current dictation
+    pub recording_session: Arc<Mutex<u64>>,  // Incremented per recording so watchdogs can tell them apart
+    pub recording_limits: Arc<Mutex<recording_limits::RecordingLimits>>,  // Max duration, warning, disk check (persisted)
+    pub last_transcription: Arc<Mutex<Option<String>>>,  // Last delivered text (formatted), for paste-last
+    pub history: Arc<Mutex<Option<history::HistoryStore>>>,  // Transcription history (None if the database can't be opened)
+    pub stats: Arc<Mutex<Option<stats::StatsStore>>>,  // Daily usage statistics (None if the database can't be opened)
+    pub stats_settings: Arc<Mutex<stats::StatsSettings>>,  // Whether usage is collected (persisted)
+    pub privacy: Arc<Mutex<privacy::PrivacyMode>>,  // Suspends history, stats, audio retention, and previews
+    pub privacy_settings: Arc<Mutex<privacy::PrivacySettings>>,  // When privacy mode turns itself off (persisted)
+    pub installed_models: Arc<Mutex<Option<Vec<String>>>>,  // Downloaded models, for the tray (None until the backend answers)
+    pub keep_awake: Arc<Mutex<keep_awake::KeepAwakeSettings>>,  // Block sleep while recording or transcribing (persisted)
+    pub ducking: Arc<Mutex<ducking::DuckingSettings>>,  // Lower other apps' volume while recording (persisted)
+    pub mic_check: Arc<Mutex<mic_check::MicCheckSettings>>,  // Warn about (or unmute) a muted mic at start (persisted)
+    pub queue_next_recording: Arc<Mutex<bool>>,  // Toggling while transcribing queues a recording instead of being ignored (persisted)
+    pub recording_queued: Arc<Mutex<bool>>,  // A recording starts as soon as the current transcription is delivered
+    pub journal: Arc<Mutex<journal::JournalSettings>>,  // Append dictations to a file (persisted)
+    pub file_transcription: Arc<Mutex<file_transcription::FileTranscriptionSettings>>,  // Subtitles next to transcribed videos (persisted)
+    pub watch_folder: Arc<Mutex<watch_folder::WatchFolderSettings>>,  // Folder whose audio files are transcribed as they arrive (persisted)
+    pub history_export: Arc<Mutex<history_export::ExportSettings>>,  // Markdown layout for history exports (persisted)
+    pub sessions: Arc<Mutex<sessions::SessionSettings>>,  // Pause that separates dictation sessions (persisted)
+    pub retention: Arc<Mutex<retention::RetentionSettings>>,  // How long history, audio, and logs are kept (persisted)
+    pub model_memory: Arc<Mutex<model_memory::ModelMemorySettings>>,  // When the backend unloads the model (persisted)
+    pub orphaned_recording: Arc<Mutex<Option<crash_recovery::OrphanedRecording>>>,  // Recording cut off by the last crash, awaiting a decision
+    pub caption_server: Arc<Mutex<caption_server::CaptionServer>>,  // WebSocket captions for streaming overlays (persisted settings)
+    pub transcription_interrupt: Arc<Mutex<Option<tokio::sync::oneshot::Sender<backend::Interrupt>>>>,  // Abandons the in-flight transcription request
 }
 
 impl Default for AppState {
     fn default() -> Self {
         Self {
             selected_model: Arc::new(Mutex::new("small".to_string())),
+            model_slots: Arc::new(Mutex::new(model_slots::ModelSlots::default())),  // Loaded from disk in setup
+            custom_models: Arc::new(Mutex::new(custom_models::CustomModelStore::default())),  // Loaded from disk in setup
+            tray_icon: Arc::new(Mutex::new(tray_icon::TrayIconSettings::default())),  // Loaded from disk in setup
+            sounds: Arc::new(Mutex::new(sounds::SoundSettings::default())),  // Loaded from disk in setup
+            accessibility: Arc::new(Mutex::new(accessibility::AccessibilitySettings::default())),  // Loaded from disk in setup
+            locale: Arc::new(Mutex::new(locale::LocaleSettings::default())),  // Loaded from disk in setup
+            advanced: Arc::new(Mutex::new(decoding::AdvancedSettings::default())),  // Loaded from disk in setup
+            next_slot: Arc::new(Mutex::new(None)),
+            recording_slot: Arc::new(Mutex::new(model_slots::DEFAULT.to_string())),
             selected_device: Arc::new(Mutex::new("auto".to_string())),
             selected_microphone: Arc::new(Mutex::new(None)),  // Default: None (use default device)
-            use_clipboard: Arc::new(Mutex::new(true)),  // Default: enabled
+            microphones: Arc::new(Mutex::new(microphones::MicrophoneSettings::default())),  // Loaded from disk in setup
+            prebuffer: Arc::new(Mutex::new(prebuffer::PrebufferSettings::default())),  // Loaded from disk in setup
+            audio_processing: Arc::new(Mutex::new(AudioProcessingSettings::default())),  // Loaded from disk in setup (default: raw audio)
+            output_mode: Arc::new(Mutex::new(OutputMode::Both)),  // Loaded from disk in setup (default: paste and keep on clipboard)
+            multilingual: Arc::new(Mutex::new(MultilingualSettings::default())),  // Loaded from disk in setup (default: off)
+            recording_language: Arc::new(Mutex::new(None)),  // Default: the selected language
             selected_language: Arc::new(Mutex::new("en".to_string())),  // Default: English
-            toggle_shortcut: Arc::new(Mutex::new("F9".to_string())),  // Default: F9
-            cancel_shortcut: Arc::new(Mutex::new("Escape".to_string())),  // Default: Escape
+            shortcuts: Arc::new(Mutex::new(default_keymap())),  // Loaded from disk in setup (default: F9 toggles, Escape cancels)
+            hotkey_blocklist: Arc::new(Mutex::new(Vec::new())),  // Loaded from disk in setup
+            hotkeys_suspended: Arc::new(Mutex::new(false)),
+            double_tap: Arc::new(Mutex::new(double_tap::DoubleTapSettings::default())),  // Loaded from disk in setup (default: off)
+            win_h: Arc::new(Mutex::new(win_h::WinHSettings::default())),  // Loaded from disk in setup
+            meeting_capture: Arc::new(Mutex::new(meeting::MeetingCaptureSettings::default())),  // Loaded from disk in setup
+            formatting: Arc::new(Mutex::new(FormattingProfiles::default())),  // Loaded from disk in setup
+            snippets: Arc::new(Mutex::new(snippets::SnippetStore::default())),  // Loaded from disk in setup
+            scratchpad: Arc::new(Mutex::new(scratchpad::Scratchpad::default())),  // Loaded from disk in setup
+            send_targets: Arc::new(Mutex::new(send_target::SendTargetStore::default())),  // Loaded from disk in setup
+            code_mode: Arc::new(Mutex::new(code_mode::CodeModeSettings::default())),  // Loaded from disk in setup
+            send_enter: Arc::new(Mutex::new(send_enter::SendEnterSettings::default())),  // Loaded from disk in setup
+            casing: Arc::new(Mutex::new(casing::CasingSettings::default())),  // Loaded from disk in setup
+            regional_formats: Arc::new(Mutex::new(regional_format::RegionalSettings::default())),  // Loaded from disk in setup
+            output_files: Arc::new(Mutex::new(output_files::OutputFileSettings::default())),  // Loaded from disk in setup
+            output_templates: Arc::new(Mutex::new(output_template::TemplateStore::default())),  // Loaded from disk in setup
+            injection: Arc::new(Mutex::new(InjectionSettings::default())),  // Loaded from disk in setup
+            overlay: Arc::new(Mutex::new(overlay::OverlayConfig::default())),  // Loaded from disk in setup
+            overlay_tracker: Arc::new(Mutex::new(overlay::OverlayTracker::default())),
+            main_window: Arc::new(Mutex::new(main_window::MainWindowSettings::default())),  // Loaded from disk in setup
+            focus_assist: Arc::new(std::sync::Mutex::new(focus_assist::FocusAssistSettings::default())),  // Loaded from disk in setup
             backend_child: Arc::new(Mutex::new(None)),  // Will be set in setup
+            recording_state: Arc::new(Mutex::new(recording_state::RecordingState::Idle)),
+            is_paused: Arc::new(Mutex::new(false)),
+            continuous_dictation: Arc::new(Mutex::new(false)),  // Loaded from disk in setup
+            diarization: Arc::new(Mutex::new(false)),  // Loaded from disk in setup
+            dictation: Arc::new(Mutex::new(dictation::DictationProgress::default())),
+            recording_session: Arc::new(Mutex::new(0)),
+            recording_limits: Arc::new(Mutex::new(recording_limits::RecordingLimits::default())),  // Loaded from disk in setup
+            last_transcription: Arc::new(Mutex::new(None)),
+            history: Arc::new(Mutex::new(None)),  // Opened in setup
+            stats: Arc::new(Mutex::new(None)),  // Opened in setup
+            stats_settings: Arc::new(Mutex::new(stats::StatsSettings::default())),  // Loaded from disk in setup (default: collect)
+            privacy: Arc::new(Mutex::new(privacy::PrivacyMode::default())),  // Default: off
+            privacy_settings: Arc::new(Mutex::new(privacy::PrivacySettings::default())),  // Loaded from disk in setup
+            installed_models: Arc::new(Mutex::new(None)),
+            keep_awake: Arc::new(Mutex::new(keep_awake::KeepAwakeSettings::default())),  // Loaded from disk in setup (default: system only)
+            ducking: Arc::new(Mutex::new(ducking::DuckingSettings::default())),  // Loaded from disk in setup (default: off)
+            mic_check: Arc::new(Mutex::new(mic_check::MicCheckSettings::default())),  // Loaded from disk in setup (default: warn only)
+            queue_next_recording: Arc::new(Mutex::new(false)),  // Loaded from disk in setup (default: ignore toggles while transcribing)
+            recording_queued: Arc::new(Mutex::new(false)),
+            journal: Arc::new(Mutex::new(journal::JournalSettings::default())),  // Loaded from disk in setup
+            file_transcription: Arc::new(Mutex::new(file_transcription::FileTranscriptionSettings::default())),  // Loaded from disk in setup
+            watch_folder: Arc::new(Mutex::new(watch_folder::WatchFolderSettings::default())),  // Loaded from disk in setup
+            history_export: Arc::new(Mutex::new(history_export::ExportSettings::default())),  // Loaded from disk in setup
+            sessions: Arc::new(Mutex::new(sessions::SessionSettings::default())),  // Loaded from disk in setup
+            retention: Arc::new(Mutex::new(retention::RetentionSettings::default())),  // Loaded from disk in setup
+            model_memory: Arc::new(Mutex::new(model_memory::ModelMemorySettings::default())),  // Loaded from disk in setup
+            orphaned_recording: Arc::new(Mutex::new(None)),  // Asked from the backend in setup
+            caption_server: Arc::new(Mutex::new(caption_server::CaptionServer::default())),  // Started in setup if enabled
+            transcription_interrupt: Arc::new(Mutex::new(None)),  // Set while send_with_progress waits
         }
     }
 }
 
-// Get current clipboard content (UTF-16 text)
-fn get_clipboard_text() -> Option<Vec<u16>> {
-    unsafe {
-        const CF_UNICODETEXT: u32 = 13;
-        
-        if let Err(_) = OpenClipboard(HWND::default()) {
-            return None;
-        }
+// Where the transcription ends up (persisted, so clipboard-only stays clipboard-only after a restart)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OutputMode {
+    Inject,  // Paste into the focused window, then restore the previous clipboard
+    ClipboardOnly,  // Only copy to the clipboard, never simulate Ctrl+V
+    #[default]
+    Both,  // Paste and leave the text on the clipboard
+    Scratchpad,  // Collect in the scratchpad window instead of pasting anywhere
+}
 
-        let h_clipboard_data = match GetClipboardData(CF_UNICODETEXT) {
-            Ok(handle) if !handle.is_invalid() => handle,
-            _ => {
-                let _ = CloseClipboard();
-                return None;
-            }
-        };
+impl OutputMode {
+    pub const CONFIG_FILE: &'static str = "output_mode.json";
+}
 
-        // Convert HANDLE to HGLOBAL
-        let hglobal = HGLOBAL(h_clipboard_data.0 as _);
-        
-        let locked = GlobalLock(hglobal);
-        if locked.is_null() {
-            let _ = CloseClipboard();
-            return None;
-        }
+// Audio pre-processing done by the backend before transcription
+#[derive(Debug, Clone, Copy, Default, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct AudioProcessingSettings {
+    pub denoise: bool,  // Spectral noise gate for steady background noise (fans, hum)
+    pub high_pass: bool,  // Remove rumble below ~80 Hz
+    pub auto_gain: bool,  // Bring quiet or distant speech up to a normal level
+}
 
-        let size = GlobalSize(hglobal);
-        if size == 0 {
-            let _ = GlobalUnlock(hglobal);
-            let _ = CloseClipboard();
-            return None;
-        }
-        
-        let mut data = vec![0u16; size / 2];
-        std::ptr::copy_nonoverlapping(locked as *const u16, data.as_mut_ptr(), size / 2);
+impl AudioProcessingSettings {
+    pub const CONFIG_FILE: &'static str = "audio_processing.json";
+}
 
-        let _ = GlobalUnlock(hglobal);
-        let _ = CloseClipboard();
+// Multilingual mode: the backend detects the language of each segment instead of using one language
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct MultilingualSettings {
+    pub enabled: bool,
+    pub languages: Vec<String>,  // Languages detection may pick, to avoid misdetections (empty = any)
+}
 
-        Some(data)
-    }
+impl MultilingualSettings {
+    pub const CONFIG_FILE: &'static str = "multilingual.json";
 }
 
-// Set clipboard text (UTF-16)
-fn set_clipboard_text(text_utf16: &[u16]) -> Result<()> {
-    unsafe {
-        if let Err(e) = OpenClipboard(HWND::default()) {
-            return Err(anyhow::anyhow!("Failed to open clipboard: {}", e));
-        }
+// Injection behaviour settings (persisted)
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct InjectionSettings {
+    #[serde(skip)]
+    pub press_enter: bool,  // Send Enter after pasting; set per recording from the profile's send_enter setting
+    pub enter_same_window_only: bool,  // Safety: skip Enter if the foreground window changed during paste
+    pub enter_delay_ms: u64,  // Delay between paste and Enter
+    pub clipboard_settle_ms: u64,  // Wait after setting the clipboard before pasting
+    pub focus_return_ms: u64,  // Wait after hiding the overlay for focus to return to the target
+    pub post_paste_ms: u64,  // Wait after pasting before restoring the old clipboard
+    pub adaptive_clipboard: bool,  // Paste once the clipboard reads back the text, waiting up to clipboard_settle_ms
+    pub typed: bool,  // Type the text as Unicode key events instead of pasting it
+    pub rtl_marks: bool,  // Add direction marks to right-to-left text so it keeps its order
+    pub ime_safe: bool,  // Close the target's IME while injecting so CJK text isn't recomposed
+    pub elevated_helper: bool,  // Inject into administrator windows through an elevated helper process
+    pub remote_mode: remote::RemoteMode,  // When to use longer delays and clipboard checks for remote desktops
+}
+
+impl InjectionSettings {
+    pub const CONFIG_FILE: &'static str = "injection.json";
+}
 
-        if let Err(e) = EmptyClipboard() {
-            let _ = CloseClipboard();
-            return Err(anyhow::anyhow!("Failed to empty clipboard: {}", e));
+impl Default for InjectionSettings {
+    fn default() -> Self {
+        Self {
+            press_enter: false,
+            enter_same_window_only: true,
+            enter_delay_ms: 100,
+            clipboard_settle_ms: 10,
+            focus_return_ms: 150,
+            post_paste_ms: 50,
+            adaptive_clipboard: false,
+            typed: false,
+            rtl_marks: true,
+            ime_safe: true,
+            elevated_helper: false,
+            remote_mode: remote::RemoteMode::Auto,
         }
+    }
+}
 
-        let len = text_utf16.len() * std::mem::size_of::<u16>();
-        let hmem = GlobalAlloc(GMEM_MOVEABLE, len)
-            .map_err(|e| anyhow::anyhow!("Failed to allocate memory: {}", e))?;
+// Build a single keyboard INPUT event
+fn key_input(vk: VIRTUAL_KEY, flags: KEYBD_EVENT_FLAGS) -> INPUT {
+    INPUT {
+        r#type: INPUT_KEYBOARD,
+        Anonymous: windows::Win32::UI::Input::KeyboardAndMouse::INPUT_0 {
+            ki: KEYBDINPUT { wVk: vk, wScan: 0, dwFlags: flags, time: 0, dwExtraInfo: 0 },
+        },
+    }
+}
 
-        let locked = GlobalLock(hmem);
-        if locked.is_null() {
-            let _ = CloseClipboard();
-            return Err(anyhow::anyhow!("Failed to lock memory"));
+// Key events that type text in logical order. Each UTF-16 unit (including both halves of a
+// surrogate pair) is sent as a Unicode character; the app lays out right-to-left text itself.
+// Line breaks are sent as Enter, which is what apps expect.
+fn typed_inputs(text: &str) -> Vec<INPUT> {
+    let mut inputs = Vec::new();
+    for c in text.chars().filter(|&c| c != '\r') {
+        if c == '\n' {
+            inputs.push(key_input(VK_RETURN, KEYBD_EVENT_FLAGS(0)));
+            inputs.push(key_input(VK_RETURN, KEYEVENTF_KEYUP));
+            continue;
+        }
+        let mut units = [0u16; 2];
+        for &unit in c.encode_utf16(&mut units).iter() {
+            for flags in [KEYEVENTF_UNICODE, KEYEVENTF_UNICODE | KEYEVENTF_KEYUP] {
+                inputs.push(INPUT {
+                    r#type: INPUT_KEYBOARD,
+                    Anonymous: windows::Win32::UI::Input::KeyboardAndMouse::INPUT_0 {
+                        ki: KEYBDINPUT { wVk: VIRTUAL_KEY(0), wScan: unit, dwFlags: flags, time: 0, dwExtraInfo: 0 },
+                    },
+                });
+            }
         }
+    }
+    inputs
+}
 
-        std::ptr::copy_nonoverlapping(text_utf16.as_ptr(), locked as *mut u16, text_utf16.len());
-        let _ = GlobalUnlock(hmem);
+// Press Enter after the text has landed, unless the foreground window changed
+unsafe fn press_enter_after_injection(settings: &InjectionSettings, target_window: windows::Win32::Foundation::HWND) {
+    std::thread::sleep(std::time::Duration::from_millis(settings.enter_delay_ms));
 
-        const CF_UNICODETEXT: u32 = 13;
-        let result = SetClipboardData(CF_UNICODETEXT, HANDLE(hmem.0 as _));
-        if let Err(e) = result {
-            let _ = CloseClipboard();
-            return Err(anyhow::anyhow!("Failed to set clipboard data: {}", e));
-        }
+    if settings.enter_same_window_only && GetForegroundWindow() != target_window {
+        log::warn!("⚠️ Foreground window changed after paste, skipping Enter");
+    } else {
+        let enter = [
+            key_input(VK_RETURN, KEYBD_EVENT_FLAGS(0)),
+            key_input(VK_RETURN, KEYEVENTF_KEYUP),
+        ];
+        SendInput(&enter, std::mem::size_of::<INPUT>() as i32);
+        log::info!("⏎ Enter sent after injection");
+    }
+}
 
-        let _ = CloseClipboard();
-        Ok(())
+// Type the text with Unicode key events, then optionally press Enter
+unsafe fn type_text(text: &str, settings: &InjectionSettings, target_window: windows::Win32::Foundation::HWND) -> Result<()> {
+    let inputs = typed_inputs(text);
+    let sent = SendInput(&inputs, std::mem::size_of::<INPUT>() as i32);
+    if (sent as usize) < inputs.len() {
+        return Err(anyhow::anyhow!("Only {} of {} key events were sent", sent, inputs.len()));
+    }
+    if settings.press_enter {
+        press_enter_after_injection(settings, target_window);
+    }
+    Ok(())
+}
+
+// Paste with Ctrl+V, then optionally press Enter
+unsafe fn paste_from_clipboard(settings: &InjectionSettings, target_window: windows::Win32::Foundation::HWND) {
+    let inputs = vec![
+        INPUT {
+            r#type: INPUT_KEYBOARD,
+            Anonymous: windows::Win32::UI::Input::KeyboardAndMouse::INPUT_0 {
+                ki: KEYBDINPUT { wVk: VK_CONTROL, wScan: 0, dwFlags: KEYEVENTF_EXTENDEDKEY, time: 0, dwExtraInfo: 0 },
+            },
+        },
+        INPUT {
+            r#type: INPUT_KEYBOARD,
+            Anonymous: windows::Win32::UI::Input::KeyboardAndMouse::INPUT_0 {
+                ki: KEYBDINPUT { wVk: VK_V, wScan: 0, dwFlags: KEYEVENTF_EXTENDEDKEY, time: 0, dwExtraInfo: 0 },
+            },
+        },
+        INPUT {
+            r#type: INPUT_KEYBOARD,
+            Anonymous: windows::Win32::UI::Input::KeyboardAndMouse::INPUT_0 {
+                ki: KEYBDINPUT { wVk: VK_V, wScan: 0, dwFlags: KEYEVENTF_EXTENDEDKEY | KEYEVENTF_KEYUP, time: 0, dwExtraInfo: 0 },
+            },
+        },
+        INPUT {
+            r#type: INPUT_KEYBOARD,
+            Anonymous: windows::Win32::UI::Input::KeyboardAndMouse::INPUT_0 {
+                ki: KEYBDINPUT { wVk: VK_CONTROL, wScan: 0, dwFlags: KEYEVENTF_EXTENDEDKEY | KEYEVENTF_KEYUP, time: 0, dwExtraInfo: 0 },
+            },
+        },
+    ];
+
+    SendInput(&inputs, std::mem::size_of::<INPUT>() as i32);
+
+    // Optionally press Enter once the paste has landed
+    if settings.press_enter {
+        press_enter_after_injection(settings, target_window);
     }
 }
 
-// Text injection via clipboard with optional clipboard preservation
-pub fn inject_text(text: &str, save_to_clipboard: bool) -> Result<()> {
+// Text injection via clipboard with optional clipboard preservation, or by typing
+pub fn inject_text(text: &str, save_to_clipboard: bool, settings: &InjectionSettings) -> Result<()> {
+    let marked;
+    let text = if settings.rtl_marks && rtl::contains_rtl(text) {
+        marked = rtl::add_direction_marks(text);
+        &marked
+    } else {
+        text
+    };
+
     unsafe {
-        // Save old clipboard content if we need to restore it
+        // Remember the target window so Enter is never sent somewhere else
+        let target_window = GetForegroundWindow();
+
+        // Windows drops our keystrokes to administrator windows; keep the text on the clipboard at least
+        if elevation::is_elevated(target_window) {
+            if settings.elevated_helper {
+                return elevation::inject(text, save_to_clipboard, settings);
+            }
+            copy_text_to_clipboard(text)?;
+            return Err(anyhow::anyhow!(elevation::ELEVATED_TARGET_ERROR));
+        }
+
+        // Remote desktop clipboards sync slowly, so remote targets get longer delays
+        let remote = remote::remote_settings(settings, target_window);
+        if remote.is_some() {
+            log::info!("🖥️ Remote target, using remote-friendly injection");
+        }
+        let settings = remote.as_ref().unwrap_or(settings);
+
+        // Reopened when this goes out of scope, after the injected keys have been handled
+        let _ime = if settings.ime_safe {
+            ime::suspend(target_window, std::time::Duration::from_millis(settings.post_paste_ms))
+        } else {
+            None
+        };
+
+        if settings.typed {
+            type_text(text, settings, target_window)?;
+            if save_to_clipboard {
+                copy_text_to_clipboard(text)?;
+            }
+            log::info!("⌨️ Text typed");
+            return Ok(());
+        }
+
+        // Save old clipboard content (all formats) if we need to restore it.
+        // If the snapshot fails we leave the clipboard alone afterwards rather than wiping it.
         let old_clipboard = if !save_to_clipboard {
-            get_clipboard_text()
+            match clipboard::snapshot() {
+                Ok(snapshot) => Some(snapshot),
+                Err(e) => {
+                    log::warn!("⚠️ Could not save clipboard, it won't be restored: {}", e);
+                    None
+                }
+            }
         } else {
             None
         };
@@ -141,55 +495,46 @@ pub fn inject_text(text: &str, save_to_clipboard: bool) -> Result<()> {
         let mut text_utf16: Vec<u16> = text.encode_utf16().collect();
         text_utf16.push(0);
 
-        // Set clipboard with new text
-        set_clipboard_text(&text_utf16)?;
+        // Set clipboard with new text - never paste if this fails, the clipboard would hold stale content
+        clipboard::set_text(&text_utf16)?;
 
         // Wait for clipboard to update
-        std::thread::sleep(std::time::Duration::from_millis(10));
-
-        // Simulate Ctrl+V
-        let inputs = vec![
-            INPUT {
-                r#type: INPUT_KEYBOARD,
-                Anonymous: windows::Win32::UI::Input::KeyboardAndMouse::INPUT_0 {
-                    ki: KEYBDINPUT { wVk: VK_CONTROL, wScan: 0, dwFlags: KEYEVENTF_EXTENDEDKEY, time: 0, dwExtraInfo: 0 },
-                },
-            },
-            INPUT {
-                r#type: INPUT_KEYBOARD,
-                Anonymous: windows::Win32::UI::Input::KeyboardAndMouse::INPUT_0 {
-                    ki: KEYBDINPUT { wVk: VK_V, wScan: 0, dwFlags: KEYEVENTF_EXTENDEDKEY, time: 0, dwExtraInfo: 0 },
-                },
-            },
-            INPUT {
-                r#type: INPUT_KEYBOARD,
-                Anonymous: windows::Win32::UI::Input::KeyboardAndMouse::INPUT_0 {
-                    ki: KEYBDINPUT { wVk: VK_V, wScan: 0, dwFlags: KEYEVENTF_EXTENDEDKEY | KEYEVENTF_KEYUP, time: 0, dwExtraInfo: 0 },
-                },
-            },
-            INPUT {
-                r#type: INPUT_KEYBOARD,
-                Anonymous: windows::Win32::UI::Input::KeyboardAndMouse::INPUT_0 {
-                    ki: KEYBDINPUT { wVk: VK_CONTROL, wScan: 0, dwFlags: KEYEVENTF_EXTENDEDKEY | KEYEVENTF_KEYUP, time: 0, dwExtraInfo: 0 },
-                },
-            },
-        ];
+        let settle = std::time::Duration::from_millis(settings.clipboard_settle_ms);
+        if settings.adaptive_clipboard {
+            // The settle delay is only the upper bound; pasting starts as soon as the text reads back
+            if !clipboard::wait_for_text(text, settle) {
+                log::warn!("⚠️ Clipboard didn't read back the text within {:?}", settle);
+            }
+        } else {
+            std::thread::sleep(settle);
+        }
 
-        SendInput(&inputs, std::mem::size_of::<INPUT>() as i32);
+        // Over a remote connection, make sure the text is really on the clipboard before pasting it
+        if remote.is_some() && !remote::clipboard_holds(text) {
+            log::warn!("⚠️ Clipboard didn't keep the text, typing it instead");
+            type_text(text, settings, target_window)?;
+        } else {
+            paste_from_clipboard(settings, target_window);
+        }
 
         // Restore old clipboard if needed
         if !save_to_clipboard {
-            if let Some(old_text) = old_clipboard {
-                // Wait a bit for paste to complete
-                std::thread::sleep(std::time::Duration::from_millis(50));
-                let _ = set_clipboard_text(&old_text);
-                log::info!("📋 Clipboard restored to previous content");
-            } else {
-                // If there was no previous clipboard content, clear it
-                std::thread::sleep(std::time::Duration::from_millis(50));
-                let empty: Vec<u16> = vec![0];
-                let _ = set_clipboard_text(&empty);
-                log::info!("📋 Clipboard cleared");
+            // Wait a bit for paste to complete. This stays a fixed delay even in adaptive mode:
+            // nothing tells us when the target has read the clipboard.
+            std::thread::sleep(std::time::Duration::from_millis(settings.post_paste_ms));
+            match old_clipboard {
+                Some(snapshot) if !snapshot.is_empty() => {
+                    match clipboard::restore(&snapshot) {
+                        Ok(()) => log::info!("📋 Clipboard restored to previous content"),
+                        Err(e) => log::warn!("⚠️ Failed to restore clipboard: {}", e),
+                    }
+                }
+                Some(_) => {
+                    // If there was no previous clipboard content, clear it
+                    let _ = clipboard::restore(&clipboard::ClipboardSnapshot::default());
+                    log::info!("📋 Clipboard cleared");
+                }
+                None => {}
             }
         } else {
             log::info!("📋 Text saved to clipboard and pasted");
@@ -199,11 +544,55 @@ pub fn inject_text(text: &str, save_to_clipboard: bool) -> Result<()> {
     Ok(())
 }
 
-// Simple command: Inject text (always injects, optionally saves to clipboard)
+// Copy text to the clipboard without any synthetic key events
+pub fn copy_text_to_clipboard(text: &str) -> Result<()> {
+    let mut text_utf16: Vec<u16> = text.encode_utf16().collect();
+    text_utf16.push(0);
+    clipboard::set_text(&text_utf16)
+}
+
+// A toast about something fixed in the settings: opening them next goes to `section`
+fn show_toast_linked(app: &AppHandle, title: &str, body: &str, section: &str) {
+    navigation::link_notification(section);
+    show_toast(app, title, body);
+}
+
+// Show a Windows toast notification
+fn show_toast(app: &AppHandle, title: &str, body: &str) {
+    use tauri_plugin_notification::NotificationExt;
+    if focus_assist::is_quiet(app) {
+        log::info!("🔕 Toast held back (Focus Assist): {} - {}", title, body);
+        return;
+    }
+    if let Err(e) = app.notification().builder().title(title).body(body).show() {
+        log::warn!("⚠️ Failed to show notification: {}", e);
+    }
+}
+
+// How long the latency readout stays up after a dictation
+const LATENCY_DISPLAY_TIME: tokio::time::Duration = tokio::time::Duration::from_secs(4);
+
+// Simple command: Inject text (honors clipboard-only mode, optionally saves to clipboard)
 #[tauri::command]
-async fn inject_text_directly(text: String, save_to_clipboard: bool) -> Result<(), String> {
+async fn inject_text_directly(text: String, save_to_clipboard: bool, app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    let language = selected_language_code(&state).await;
+    let text = process_transcript(&state, &text, language.as_deref()).await;
+
+    let output_mode = *state.output_mode.lock().await;
+    if output_mode == OutputMode::Scratchpad {
+        scratchpad::append(&app, &state, &text, true).await;
+        return Ok(());
+    }
+    if output_mode == OutputMode::ClipboardOnly {
+        copy_text_to_clipboard(&text).map_err(|e| e.to_string())?;
+        show_toast(&app, "Whisper4Windows", &locale::tr("toast.copied"));
+        log::info!("📋 Copied to clipboard only: {}", text);
+        return Ok(());
+    }
+
+    let injection = state.injection.lock().await.clone();
     tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
-    inject_text(&text, save_to_clipboard).map_err(|e| e.to_string())?;
+    inject_text(&text, save_to_clipboard, &injection).map_err(|e| e.to_string())?;
     log::info!("✅ Injected: {} (clipboard: {})", text, if save_to_clipboard { "saved" } else { "not saved" });
     Ok(())
 }
@@ -215,36 +604,68 @@ async fn cmd_start_recording(app: AppHandle, state: State<'_, AppState>) -> Resu
     log::info!("🎬 START RECORDING");
     log::info!("═══════════════════════════════════════════════");
 
-    let model = state.selected_model.lock().await.clone();
-    let device = state.selected_device.lock().await.clone();
-    let microphone = state.selected_microphone.lock().await.clone();
+    let slot = state.next_slot.lock().await.take().unwrap_or_else(|| model_slots::DEFAULT.to_string());
+    let slot_model = model_slots::resolve(&state, &slot).await;
+    let warm_models: Vec<serde_json::Value> = model_slots::warm_models(&state, &slot_model)
+        .await
+        .into_iter()
+        .map(|warm| serde_json::json!({ "model_size": warm.model, "device": warm.device }))
+        .collect();
+    let model_slots::ModelSlot { model, device } = slot_model;
+    if slot != model_slots::DEFAULT {
+        log::info!("🎚️ Using the {} model slot: {} ({})", slot, model, device);
+    }
+    let decoding = decoding::resolve(&state, &slot).await;
+    *state.recording_slot.lock().await = slot;
+    let mut microphone = *state.selected_microphone.lock().await;
     let language = state.selected_language.lock().await.clone();
+    let continuous = *state.continuous_dictation.lock().await;
+    let diarize = *state.diarization.lock().await;
+    let retain_audio = !privacy::is_enabled(&state).await;
+    let processing = *state.audio_processing.lock().await;
+    let multilingual = state.multilingual.lock().await.clone();
 
-    // Position window at top center and show
-    if let Some(win) = app.get_webview_window("recording") {
-        // Get primary monitor to calculate center position
-        if let Some(monitor) = win.current_monitor().map_err(|e| e.to_string())? {
-            let screen_size = monitor.size();
-            let window_size = win.outer_size().map_err(|e| e.to_string())?;
-
-            // Calculate centered X position, top Y position (50px from top)
-            let x = (screen_size.width as i32 - window_size.width as i32) / 2;
-            let y = 50;
+    let mut limits = state.recording_limits.lock().await.clone();
+    if meeting::capturing() {
+        // A call runs as long as it runs; the capture ends when it does
+        limits.max_duration_secs = 0;
+        limits.warn_after_secs = 0;
+    }
+    if let Err(e) = recording_limits::check_disk_space(&limits) {
+        log::error!("❌ Not starting recording: {}", e);
+        show_toast(&app, &locale::tr("toast.low_disk"), &e);
+        return Err(e);
+    }
 
-            win.set_position(tauri::PhysicalPosition::new(x, y)).map_err(|e| e.to_string())?;
-        }
+    recording_state::transition(&app, RecordingState::Recording).await?;
+    *state.is_paused.lock().await = false;
+    keep_awake::acquire(&*state.keep_awake.lock().await);
+    set_recording_hotkeys_active(&app, true).await;
+    *state.recording_language.lock().await = None;
 
-        win.show().map_err(|e| e.to_string())?;
+    let session = {
+        let mut session = state.recording_session.lock().await;
+        *session += 1;
+        *session
+    };
+    recording_limits::spawn_watchdog(app.clone(), session, limits.clone());
+    recording_meter::spawn(app.clone(), session, limits);
 
-        // Play start sound
-        let _ = win.eval("playStartSound()");
+    if continuous {
+        *state.dictation.lock().await = dictation::DictationProgress::default();
+        dictation::spawn_segment_poller(app.clone(), session);
+    }
 
-        log::info!("✅ Window shown at top center");
+    // The recording goes on without the overlay; the tray and hotkeys follow the recording state
+    if let Err(e) = show_recording_overlay(&app, &state).await {
+        log::error!("❌ Failed to show recording overlay: {}", e);
     }
+    show_overlay_languages(&app, &state).await;
 
     // Call backend /start
-    let client = reqwest::Client::new();
+    let client = backend::client();
     tokio::spawn(async move {
+        let state: State<AppState> = app.state();
         // Use None for auto-detect, otherwise use the selected language
         let lang_value = if language == "auto" {
             serde_json::Value::Null
@@ -255,28 +676,179 @@ async fn cmd_start_recording(app: AppHandle, state: State<'_, AppState>) -> Resu
         let mut request_body = serde_json::json!({
             "model_size": model,
             "language": lang_value,
-            "device": device
+            "device": device,
+            "continuous": continuous,
+            "diarize": diarize,
+            "retain_audio": retain_audio,
+            "denoise": processing.denoise,
+            "high_pass": processing.high_pass,
+            "auto_gain": processing.auto_gain,
+            "multilingual": multilingual.enabled,
+            "languages": multilingual.languages,
+            "warm_models": warm_models
         });
+        decoding.add_to_request(&mut request_body);
 
         // Add device_index if a specific microphone is selected
         if let Some(device_index) = microphone {
             request_body["device_index"] = serde_json::json!(device_index);
         }
+        // The backend tries the connected preferred microphones first
+        let preferred = microphones::candidates(&state).await;
+        if !preferred.is_empty() {
+            request_body["device_indices"] = serde_json::json!(preferred);
+        }
+        state.microphones.lock().await.add_to_request(&mut request_body);
 
-        match client.post("http://127.0.0.1:8000/start")
+        let error = match client.post(backend::url("start"))
             .json(&request_body)
             .send()
             .await
         {
-            Ok(resp) if resp.status().is_success() => log::info!("✅ Backend started"),
-            Ok(resp) => log::error!("❌ Backend error: {}", resp.status()),
-            Err(e) => log::error!("❌ Request failed: {}", e),
+            Ok(resp) if resp.status().is_success() => {
+                let data = resp.json::<serde_json::Value>().await.unwrap_or_default();
+                if data.get("status").and_then(|s| s.as_str()) == Some("error") {
+                    let message = data.get("message").and_then(|m| m.as_str()).unwrap_or("Backend error");
+                    Some(message.to_string())
+                } else {
+                    log::info!("✅ Backend started");
+                    if let Some((format, mismatch)) = data.get("capture_format").and_then(microphones::describe_format) {
+                        if mismatch {
+                            log::warn!("⚠️ Capture format differs from the settings: {}", format);
+                        } else {
+                            log::info!("🎚️ Capture format: {}", format);
+                        }
+                    }
+                    // The microphone that opened, when it wasn't the selected one
+                    microphone = data.get("device_index").and_then(|index| index.as_i64()).and_then(|index| i32::try_from(index).ok());
+                    None
+                }
+            }
+            Ok(resp) => Some(format!("Backend error: {}", resp.status())),
+            Err(e) => Some(format!("Request failed: {}", e)),
+        };
+
+        // Nothing is being captured, so don't leave the overlay and tray claiming otherwise
+        let Some(error) = error else {
+            mic_check::spawn(app.clone(), session, microphone);
+            microphones::spawn_loss_watch(app.clone(), session);
+            return;
+        };
+        log::error!("❌ Recording failed to start: {}", error);
+        if *state.recording_session.lock().await == session && recording_state::get(&state).await == RecordingState::Recording {
+            let _ = cmd_cancel_recording(app.clone()).await;
+            sounds::play(&app, sounds::SoundEvent::Error);
+            accessibility::announce(&app, locale::tr_with("announce.recording_failed", &[("error", &error)]));
+            show_toast_linked(&app, &locale::tr("toast.recording_failed"), &error, "microphone");
         }
     });
 
     Ok(())
 }
 
+// Size and position the overlay (remembered position or placement preset) and show it
+async fn show_recording_overlay(app: &AppHandle, state: &AppState) -> Result<(), String> {
+    let Some(win) = app.get_webview_window("recording") else { return Ok(()) };
+    let config = state.overlay.lock().await.clone();
+    let focus_assist = state.focus_assist.lock().unwrap().clone();
+    let display_mode = overlay::effective_display_mode(&config.settings, &focus_assist);
+
+    if display_mode == overlay::DisplayMode::Hidden {
+        log::info!("🙈 Overlay hidden - audio cue and tray only");
+    } else {
+        win.set_size(display_mode.window_size()).map_err(|e| e.to_string())?;
+        let _ = win.eval(format!("setDisplayMode('{}')", display_mode.as_js()));
+        // The latency readout shows the overlay without focus; while recording it takes keys (Escape cancels).
+        // With a screen reader running it never takes focus, so the reader stays on the text field.
+        let _ = win.set_focusable(!accessibility::screen_reader_running());
+
+        if let Some(monitor) = overlay::target_monitor(&win, &config.settings).map_err(|e| e.to_string())? {
+            let mut tracker = state.overlay_tracker.lock().await;
+            overlay::place_window(&win, &monitor, &config, &mut tracker).map_err(|e| e.to_string())?;
+        }
+
+        win.show().map_err(|e| e.to_string())?;
+        log::info!("✅ Window shown ({:?})", display_mode);
+    }
+
+    // Played from here rather than the overlay, so it works while the window is hidden too
+    sounds::play(app, sounds::SoundEvent::Start);
+    Ok(())
+}
+
+// Reflect recording state and privacy mode in the tray (the only indicator in hidden overlay mode)
+pub(crate) async fn refresh_tray(app: &AppHandle) {
+    let Some(tray) = app.tray_by_id("main") else { return };
+    let state: State<AppState> = app.state();
+    let privacy = privacy::is_enabled(&state).await;
+
+    let mut tooltip = String::from("Whisper4Windows");
+    if privacy {
+        tooltip.push_str(&format!(" 🔒 {}", locale::tr("tray.privacy_on")));
+    }
+    // Files waiting in the watch folder show while nothing else is going on
+    let watch_folder_queue = watch_folder::queued();
+    let status = match recording_state::get(&state).await {
+        RecordingState::Idle => None,
+        RecordingState::Recording if *state.is_paused.lock().await => Some("tray.paused"),
+        RecordingState::Recording => Some("tray.recording"),
+        RecordingState::Processing => Some("tray.transcribing"),
+        RecordingState::Injecting => Some("tray.pasting"),
+        RecordingState::Error => Some("tray.failed"),
+    };
+    if let Some(status) = status {
+        tooltip.push_str(&format!(" - {}", locale::tr(status)));
+    } else if watch_folder_queue > 0 {
+        let count = watch_folder_queue.to_string();
+        tooltip.push_str(&format!(" - {}", locale::tr_with("tray.watch_folder", &[("count", &count)])));
+    }
+    // The microphone is open between recordings
+    if status.is_none() && prebuffer::active() {
+        tooltip.push_str(&format!(" 🎙 {}", locale::tr("tray.prebuffer_on")));
+    }
+    let _ = tray.set_tooltip(Some(&tooltip));
+
+    let icon_state = match recording_state::get(&state).await {
+        RecordingState::Idle if watch_folder_queue > 0 => tray_icon::IconState::Processing,
+        RecordingState::Idle => tray_icon::IconState::Idle,
+        RecordingState::Recording if *state.is_paused.lock().await => tray_icon::IconState::Paused,
+        RecordingState::Recording => tray_icon::IconState::Recording,
+        RecordingState::Processing | RecordingState::Injecting => tray_icon::IconState::Processing,
+        RecordingState::Error => tray_icon::IconState::Error,
+    };
+    let icon_settings = state.tray_icon.lock().await.clone();
+    let _ = tray.set_icon(Some(tray_icon::icon(&icon_settings, icon_state)));
+
+    let model = state.selected_model.lock().await.clone();
+    let language = state.selected_language.lock().await.clone();
+    let installed_models = state.installed_models.lock().await.clone();
+    let custom = state.custom_models.lock().await.models.clone();
+    let pinned_entries = pinned::entries(&state).await;
+    match create_tray_menu(app, privacy, &model, &language, installed_models.as_deref(), &custom, &pinned_entries) {
+        Ok(menu) => {
+            let _ = tray.set_menu(Some(menu));
+        }
+        Err(e) => log::warn!("⚠️ Failed to rebuild tray menu: {}", e),
+    }
+}
+
+// Save the overlay position if the user dragged it while it was visible
+async fn remember_overlay_position(app: &AppHandle) {
+    let state: State<AppState> = app.state();
+    let dragged = state.overlay_tracker.lock().await.take_dragged_position();
+    let Some((key, position)) = dragged else { return };
+
+    let mut config = state.overlay.lock().await;
+    if !config.settings.remember_position {
+        return;
+    }
+    config.positions.insert(key, overlay::SavedPosition { x: position.x, y: position.y });
+    match storage::save_json(app, overlay::CONFIG_FILE, &*config) {
+        Ok(()) => log::info!("📌 Overlay position saved: ({}, {})", position.x, position.y),
+        Err(e) => log::warn!("⚠️ Failed to save overlay position: {}", e),
+    }
+}
+
 // Simple command: Cancel recording
 #[tauri::command]
 async fn cmd_cancel_recording(app: AppHandle) -> Result<(), String> {
@@ -284,10 +856,25 @@ async fn cmd_cancel_recording(app: AppHandle) -> Result<(), String> {
     log::info!("❌ CANCEL RECORDING");
     log::info!("═══════════════════════════════════════════════");
 
+    let state: State<AppState> = app.state();
+    recording_state::transition(&app, RecordingState::Idle).await?;
+    *state.is_paused.lock().await = false;
+    keep_awake::release();
+    sounds::play(&app, sounds::SoundEvent::Cancel);
+    accessibility::announce(&app, locale::tr("announce.cancelled"));
+    set_recording_hotkeys_active(&app, false).await;
+
+    // Cancelling while processing discards the transcription instead of delivering it later,
+    // and drops any recording queued behind it
+    *state.recording_queued.lock().await = false;
+    if backend::interrupt(&state, backend::Interrupt::Cancel).await {
+        log::info!("🗑️ Pending transcription discarded");
+    }
+
     // Call backend /cancel
-    let client = reqwest::Client::new();
+    let client = backend::client();
     tokio::spawn(async move {
-        match client.post("http://127.0.0.1:8000/cancel")
+        match client.post(backend::url("cancel"))
             .send()
             .await
         {
@@ -302,6 +889,7 @@ async fn cmd_cancel_recording(app: AppHandle) -> Result<(), String> {
         win.hide().map_err(|e| e.to_string())?;
         log::info!("✅ Window hidden");
     }
+    remember_overlay_position(&app).await;
 
     Ok(())
 }
@@ -312,160 +900,1629 @@ async fn cmd_stop_recording(app: AppHandle, state: State<'_, AppState>) -> Resul
     log::info!("═══════════════════════════════════════════════");
     log::info!("🛑 STOP RECORDING");
     log::info!("═══════════════════════════════════════════════");
+    let stopped_at = std::time::Instant::now();
 
-    // Call showProcessing() in the recording window via eval
-    if let Some(win) = app.get_webview_window("recording") {
-        let _ = win.eval("showProcessing()");
-        let _ = win.eval("playStopSound()");
-        log::info!("📢 Called showProcessing() in frontend");
-    }
+    // The overlay switches to its processing animation on this transition
+    recording_state::transition(&app, RecordingState::Processing).await?;
+    *state.is_paused.lock().await = false;
+    set_recording_hotkeys_active(&app, false).await;
+
+    sounds::play(&app, sounds::SoundEvent::Stop);
 
     // Small delay to let frontend update UI
     tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
 
-    // Call backend /stop to get transcription
-    let client = reqwest::Client::new();
-    let text_to_inject = match client.post("http://127.0.0.1:8000/stop")
-        .send()
-        .await
-    {
+    // Call backend /stop to get transcription, in the language picked in the overlay if any
+    // (a language picked there also turns multilingual detection off for this recording)
+    let client = backend::client();
+    let language = recording_language(&state).await;
+    let multilingual = state.multilingual.lock().await.enabled && state.recording_language.lock().await.is_none();
+    let stop_body = serde_json::json!({
+        "language": if language == "auto" { None } else { Some(language) },
+        "multilingual": multilingual
+    });
+    let mut dictated_segments = None;
+    let mut stop_result = serde_json::Value::Null;
+    let mut failure = None;
+    let request = client.post(backend::url("stop")).json(&stop_body);
+    let text_to_inject = match send_with_progress(&app, &client, request).await {
         Ok(resp) if resp.status().is_success() => {
             log::info!("✅ Backend stopped");
 
             // Get transcription text
             if let Ok(data) = resp.json::<serde_json::Value>().await {
-                if let Some(text) = data.get("text").and_then(|t| t.as_str()) {
+                let text = if data.get("status").and_then(|s| s.as_str()) == Some("error") {
+                    let message = data.get("message").and_then(|m| m.as_str()).unwrap_or("Transcription failed");
+                    log::error!("❌ Transcription failed: {}", message);
+                    failure = Some(message.to_string());
+                    None
+                } else if data.get("continuous").and_then(|c| c.as_bool()).unwrap_or(false) {
+                    // Continuous dictation: most segments were injected already, flush the rest below
+                    dictated_segments = Some(dictation::parse_segments(&data));
+                    None
+                } else if let Some(text) = data.get("text").and_then(|t| t.as_str()) {
                     log::info!("📝 Transcription: {}", text);
+                    report_diarization_error(&app, &data);
                     Some(text.to_string())
                 } else {
                     None
-                }
+                };
+                stop_result = data;
+                text
             } else {
                 None
             }
         }
         Ok(resp) => {
             log::error!("❌ Backend error: {}", resp.status());
+            failure = Some(format!("Backend error: {}", resp.status()));
+            None
+        }
+        Err(backend::TranscriptionError::Interrupted(backend::Interrupt::Cancel)) => {
+            // Cancelling already reset the state and hid the overlay; a new recording may be running
+            log::info!("🗑️ Transcription cancelled");
+            return Ok(());
+        }
+        Err(backend::TranscriptionError::Interrupted(backend::Interrupt::Recover)) => {
+            backend::recover(&app).await;
             None
         }
         Err(e) => {
             log::error!("❌ Request failed: {}", e);
+            failure = Some(e.to_string());
             None
         }
     };
+    // Transcription is done (or failed)
+    keep_awake::release();
+    mic_check::report_levels(&app, &state, &stop_result).await;
 
     // Hide window FIRST (to restore focus to text field)
     if let Some(win) = app.get_webview_window("recording") {
         win.hide().map_err(|e| e.to_string())?;
         log::info!("✅ Window hidden");
     }
+    remember_overlay_position(&app).await;
 
     // Wait for focus to return to the text field
-    tokio::time::sleep(tokio::time::Duration::from_millis(150)).await;
-
-    // THEN inject text (always inject, clipboard setting controls if we save to clipboard)
-    if let Some(text) = text_to_inject {
-        let save_to_clipboard = *state.use_clipboard.lock().await;
-        log::info!("🔧 Clipboard save setting: {}", save_to_clipboard);
-        
-        if let Err(e) = inject_text(&text, save_to_clipboard) {
-            log::error!("❌ Injection failed: {}", e);
-        } else {
-            log::info!("✅ Text injected (clipboard: {})", if save_to_clipboard { "saved" } else { "restored" });
+    let injection = state.injection.lock().await.clone();
+    tokio::time::sleep(tokio::time::Duration::from_millis(injection.focus_return_ms)).await;
+
+    // THEN deliver text according to the output mode (skipped if cancelled or recovered meanwhile)
+    if let Some(error) = &failure {
+        sounds::play(&app, sounds::SoundEvent::Error);
+        accessibility::announce(&app, locale::tr_with("announce.transcription_failed", &[("error", error)]));
+        show_toast_linked(&app, &locale::tr("toast.transcription_failed"), error, "device");
+    } else if recording_state::transition(&app, RecordingState::Injecting).await.is_ok() {
+        let target = window_info::foreground_target();
+        // The profile may also save the recording as files, or only do that
+        let outputs = output_files::current(&state).await;
+        if let Some(segments) = dictated_segments {
+            dictation::deliver_segments(&app, &segments).await;
+            let delivered = std::mem::take(&mut state.dictation.lock().await.delivered);
+            if !delivered.is_empty() {
+                output_files::save(&app, &outputs, &delivered, &stop_result);
+                history::record(&state, &delivered, &stop_result, &target).await;
+                journal::record(&app, &state, &delivered, &target).await;
+                stats::record(&state, &delivered, &stop_result, &target).await;
+                *state.last_transcription.lock().await = Some(delivered);
+            }
+        }
+
+        if let Some(text) = text_to_inject {
+            let language = transcript_language(&state, &stop_result).await;
+            let text = process_transcript(&state, &text, language.as_deref()).await;
+            if !text.is_empty() {
+                *state.last_transcription.lock().await = Some(text.clone());
+            }
+            // The template wraps what's delivered and journaled; history keeps the text as spoken
+            let output = output_template::apply(&state, &text, language.as_deref(), &target).await;
+            let delivery_started = std::time::Instant::now();
+            if outputs.deliver {
+                deliver_text(&app, &state, &output, &injection).await;
+            } else {
+                log::info!("📁 Not delivered, this profile only saves files");
+            }
+            add_delivery_timings(&mut stop_result, stopped_at, delivery_started.elapsed());
+            output_files::save(&app, &outputs, &text, &stop_result);
+            log_stage_timings(&stop_result);
+            show_latency(&app, &state, &stop_result).await;
+            caption_server::publish(&state, caption_server::Caption::Final { text: text.clone() }).await;
+            history::record(&state, &text, &stop_result, &target).await;
+            journal::record(&app, &state, &output, &target).await;
+            stats::record(&state, &text, &stop_result, &target).await;
         }
     }
+    recording_state::finish(&app, if failure.is_some() { RecordingState::Error } else { RecordingState::Idle }).await;
+    start_queued_recording(&app).await;
 
+    // The first recording with a model downloads it
+    quick_switch::refresh_installed_models(&app).await;
     Ok(())
 }
 
-// F9 shortcut handler
-#[tauri::command]
-async fn cmd_toggle_recording(app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
-    log::info!("⌨️ F9 PRESSED");
+// How often chunk progress is polled while a transcription request is in flight
+const PROGRESS_POLL_INTERVAL: tokio::time::Duration = tokio::time::Duration::from_millis(500);
 
-    if let Some(win) = app.get_webview_window("recording") {
-        let is_visible = win.is_visible().unwrap_or(false);
-        log::info!("   Window visible: {}", is_visible);
+// Send a transcription request, relaying chunk progress from the backend to the overlay meanwhile.
+// If nothing moves for STALL_TIMEOUT the overlay offers to recover (or recovers right away when hidden);
+// the user can also cancel, which drops the request without waiting for the backend.
+async fn send_with_progress(
+    app: &AppHandle,
+    client: &reqwest::Client,
+    request: reqwest::RequestBuilder,
+) -> Result<reqwest::Response, backend::TranscriptionError> {
+    let state: State<AppState> = app.state();
+    let (interrupt_tx, mut interrupt_rx) = tokio::sync::oneshot::channel();
+    *state.transcription_interrupt.lock().await = Some(interrupt_tx);
 
-        if is_visible {
-            // Stop - call backend /stop, transcribe, and inject
-            cmd_stop_recording(app, state).await?;
-        } else {
-            // Start
-            cmd_start_recording(app, state).await?;
+    let response = request.timeout(backend::TRANSCRIPTION_TIMEOUT).send();
+    tokio::pin!(response);
+    let mut last_done = None;
+    let mut last_activity = std::time::Instant::now();
+    let mut interrupt_closed = false;  // Replaced by a newer request; keep waiting without it
+
+    let result = loop {
+        tokio::select! {
+            result = &mut response => break result.map_err(backend::TranscriptionError::Request),
+            interrupt = &mut interrupt_rx, if !interrupt_closed => match interrupt {
+                Ok(interrupt) => break Err(backend::TranscriptionError::Interrupted(interrupt)),
+                Err(_) => interrupt_closed = true,
+            },
+            _ = tokio::time::sleep(PROGRESS_POLL_INTERVAL) => {
+                if report_transcription_progress(app, client, &mut last_done).await {
+                    last_activity = std::time::Instant::now();
+                } else if last_activity.elapsed() >= backend::STALL_TIMEOUT {
+                    log::warn!("⏳ No transcription progress for {}s", last_activity.elapsed().as_secs());
+                    let overlay = app.get_webview_window("recording").filter(|win| win.is_visible().unwrap_or(false));
+                    let Some(win) = overlay else {
+                        // Nobody can answer the prompt with the overlay hidden
+                        break Err(backend::TranscriptionError::Interrupted(backend::Interrupt::Recover));
+                    };
+                    let _ = win.eval("showStuck()");
+                    last_activity = std::time::Instant::now();  // Ask again later if the user keeps waiting
+                }
+            }
         }
+    };
+
+    state.transcription_interrupt.lock().await.take();
+    result
+}
+
+// Relay chunk progress to the overlay; true if it moved since the last call
+async fn report_transcription_progress(app: &AppHandle, client: &reqwest::Client, last_done: &mut Option<u64>) -> bool {
+    let request = client.get(backend::url("progress")).timeout(backend::PROGRESS_TIMEOUT);
+    let Ok(resp) = request.send().await else { return false };
+    let Ok(progress) = resp.json::<serde_json::Value>().await else { return false };
+
+    if !progress.get("active").and_then(|a| a.as_bool()).unwrap_or(false) {
+        return false;
     }
+    let done = progress.get("done").and_then(|d| d.as_u64()).unwrap_or(0);
+    let total = progress.get("total").and_then(|t| t.as_u64()).unwrap_or(0);
+    if *last_done == Some(done) {
+        return false;
+    }
+    *last_done = Some(done);
 
-    Ok(())
+    // No transcript previews in privacy mode, just the chunk count
+    let mut progress = progress;
+    let state: State<AppState> = app.state();
+    if privacy::is_enabled(&state).await {
+        progress["partial_text"] = serde_json::Value::String(String::new());
+    }
+    let partial_text = progress.get("partial_text").and_then(|t| t.as_str()).unwrap_or_default();
+    log::info!("🧩 Transcribed {}/{} chunks", done, total);
+    if !partial_text.is_empty() {
+        caption_server::publish(&state, caption_server::Caption::Partial { text: partial_text.to_string() }).await;
+    }
+    let _ = app.emit("transcription-progress", &progress);
+    if let Some(win) = app.get_webview_window("recording") {
+        let partial_js = serde_json::to_string(partial_text).unwrap_or_else(|_| "\"\"".to_string());
+        let _ = win.eval(format!("showProgress({}, {}, {})", done, total, partial_js));
+    }
+    true
 }
 
-// Settings command
-#[tauri::command]
-async fn set_model_and_device(
-    model: String,
-    device: String,
-    state: State<'_, AppState>
-) -> Result<(), String> {
-    *state.selected_model.lock().await = model.clone();
-    *state.selected_device.lock().await = device.clone();
-    log::info!("⚙️ Settings: model={}, device={}", model, device);
-    Ok(())
+// Post-processing applied to every transcription before delivery: formatting, casing commands, and
+// regional number formats (or code mode's symbols and casing instead), then snippet expansion
+pub(crate) async fn process_transcript(state: &AppState, text: &str, language: Option<&str>) -> String {
+    let casing_settings = state.casing.lock().await.clone();
+    let format = regional_format::current(state).await;
+    let text = if code_mode::active(state).await {
+        code_mode::apply(text, &casing_settings)
+    } else {
+        let (settings, profanity_words) = formatting::current(state).await;
+        let text = formatting::apply(text, &settings, &profanity_words, language);
+        regional_format::numbers(&casing::apply(&text, &casing_settings), format)
+    };
+    snippets::expand(&text, &state.snippets.lock().await.snippets, format)
 }
 
-// Set microphone device
-#[tauri::command]
-async fn set_microphone_device(
-    device_index: Option<i32>,
-    state: State<'_, AppState>
-) -> Result<(), String> {
-    *state.selected_microphone.lock().await = device_index;
-    log::info!("🎤 Microphone device set to: {:?}", device_index);
-    Ok(())
+// Language picked in settings (None for auto-detect)
+pub(crate) async fn selected_language_code(state: &AppState) -> Option<String> {
+    Some(state.selected_language.lock().await.clone()).filter(|language| language != "auto")
 }
 
-// Get microphone device
-#[tauri::command]
-async fn get_microphone_device(state: State<'_, AppState>) -> Result<Option<i32>, String> {
-    Ok(*state.selected_microphone.lock().await)
+// Language the current recording is transcribed in: a one-off pick from the overlay, or the selected one
+async fn recording_language(state: &AppState) -> String {
+    match state.recording_language.lock().await.clone() {
+        Some(language) => language,
+        None => state.selected_language.lock().await.clone(),
+    }
 }
 
-// New: Set clipboard paste setting
-#[tauri::command]
-async fn set_clipboard_paste(
-    enabled: bool,
-    state: State<'_, AppState>
-) -> Result<(), String> {
-    *state.use_clipboard.lock().await = enabled;
-    log::info!("⚙️ Clipboard paste setting: {}", enabled);
-    Ok(())
+// Stands for multilingual mode among the overlay's languages
+const MULTILINGUAL: &str = "multi";
+
+// The language the overlay shows as active for the current recording
+async fn overlay_language(state: &AppState) -> String {
+    if state.recording_language.lock().await.is_none() && state.multilingual.lock().await.enabled {
+        return MULTILINGUAL.to_string();
+    }
+    recording_language(state).await
 }
 
-// New: Get clipboard paste setting
-#[tauri::command]
-async fn get_clipboard_paste(state: State<'_, AppState>) -> Result<bool, String> {
-    let enabled = *state.use_clipboard.lock().await;
-    Ok(enabled)
+// Languages the overlay can switch between: multilingual mode if enabled, the selected language,
+// then the overlay's quick languages
+async fn overlay_languages(state: &AppState) -> Vec<String> {
+    let mut languages = Vec::new();
+    if state.multilingual.lock().await.enabled {
+        languages.push(MULTILINGUAL.to_string());
+    }
+    languages.push(state.selected_language.lock().await.clone());
+    for language in state.overlay.lock().await.settings.quick_languages.iter() {
+        if !languages.contains(language) {
+            languages.push(language.clone());
+        }
+    }
+    languages
 }
 
-// Language commands
+// Show the recording's language in the overlay. Continuous dictation transcribes as it goes,
+// so there the language can't be switched; neither can it with an English-only model.
+async fn show_overlay_languages(app: &AppHandle, state: &AppState) {
+    let Some(win) = app.get_webview_window("recording") else { return };
+    let slot = state.recording_slot.lock().await.clone();
+    let model = model_slots::resolve(state, &slot).await.model;
+    let english_only = custom_models::is_english_only(&state.custom_models.lock().await.models, &model);
+    let current = if english_only { "en".to_string() } else { overlay_language(state).await };
+    let options = if english_only || *state.continuous_dictation.lock().await {
+        Vec::new()
+    } else {
+        overlay_languages(state).await
+    };
+    if let (Ok(current), Ok(options)) = (serde_json::to_string(&current), serde_json::to_string(&options)) {
+        let _ = win.eval(format!("setLanguages({}, {})", current, options));
+    }
+}
+
+// Switch the language of the current recording only (from the overlay)
 #[tauri::command]
-async fn set_language(language: String, state: State<'_, AppState>) -> Result<(), String> {
-    *state.selected_language.lock().await = language.clone();
-    log::info!("🌐 Language set to: {}", language);
+async fn set_recording_language(language: String, app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    if !recording_state::is_recording(&state).await {
+        return Err("Not recording".to_string());
+    }
+    if *state.continuous_dictation.lock().await {
+        return Err("Continuous dictation can't switch language mid-recording".to_string());
+    }
+    log::info!("🌐 Language for this recording: {}", language);
+    *state.recording_language.lock().await = Some(language).filter(|l| l != MULTILINGUAL);
+    show_overlay_languages(&app, &state).await;
     Ok(())
 }
 
-#[tauri::command]
-async fn get_language(state: State<'_, AppState>) -> Result<String, String> {
-    Ok(state.selected_language.lock().await.clone())
+// Language of a backend result, falling back to the one picked in settings
+async fn transcript_language(state: &AppState, data: &serde_json::Value) -> Option<String> {
+    match data.get("language").and_then(|l| l.as_str()) {
+        Some(language) => Some(language.to_string()),
+        None => selected_language_code(state).await,
+    }
 }
 
-// Helper function to parse shortcut string to Shortcut object
-fn parse_shortcut(shortcut_str: &str) -> Option<Shortcut> {
+// Tell the user why speaker labels are missing (the transcript itself is still delivered)
+fn report_diarization_error(app: &AppHandle, data: &serde_json::Value) {
+    if let Some(error) = data.get("diarization_error").and_then(|e| e.as_str()) {
+        log::warn!("⚠️ Speaker diarization failed: {}", error);
+        show_toast(app, &locale::tr("toast.no_speaker_labels"), error);
+    }
+}
 
-    let parts: Vec<&str> = shortcut_str.split('+').collect();
+// Where the time after pressing stop went, per stage, as the backend measured it (model_load is how long
+// the load started with the recording took, most of it hidden behind the recording itself)
+fn log_stage_timings(data: &serde_json::Value) {
+    const STAGES: &[&str] = &["finalize", "model_wait", "model_load", "transcription", "diarization", "injection", "total"];
+    let Some(timings) = data.get("timings") else { return };
+    let stages: Vec<String> = STAGES
+        .iter()
+        .filter_map(|stage| timings.get(*stage).and_then(|t| t.as_f64()).map(|t| format!("{} {:.2}s", stage, t)))
+        .collect();
+    if !stages.is_empty() {
+        log::info!("⏱️ Stages: {}", stages.join(", "));
+    }
+}
+
+// Add the app's own stages to the backend's timings: the injection, and the total from pressing stop
+// until the text was delivered (replacing the backend's, which ends with the transcription)
+fn add_delivery_timings(data: &mut serde_json::Value, stopped_at: std::time::Instant, injection: std::time::Duration) {
+    if let Some(timings) = data.get_mut("timings").and_then(|t| t.as_object_mut()) {
+        timings.insert("injection".to_string(), serde_json::json!(injection.as_secs_f64()));
+        timings.insert("total".to_string(), serde_json::json!(stopped_at.elapsed().as_secs_f64()));
+    }
+}
+
+// Briefly show the stage timings in the overlay after delivery, without taking focus from the target
+async fn show_latency(app: &AppHandle, state: &AppState, data: &serde_json::Value) {
+    let Some(timings) = data.get("timings") else { return };
+    let settings = state.overlay.lock().await.settings.clone();
+    let focus_assist = state.focus_assist.lock().unwrap().clone();
+    if !settings.show_latency || overlay::effective_display_mode(&settings, &focus_assist) == overlay::DisplayMode::Hidden {
+        return;
+    }
+    let Some(win) = app.get_webview_window("recording") else { return };
+
+    let _ = win.eval(format!("showLatency({})", timings));
+    let _ = win.set_focusable(false);
+    if let Err(e) = win.show() {
+        log::warn!("⚠️ Failed to show latency: {}", e);
+        return;
+    }
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(LATENCY_DISPLAY_TIME).await;
+        let state: State<AppState> = app.state();
+        // A new recording may have taken over the overlay meanwhile
+        if matches!(recording_state::get(&state).await, RecordingState::Idle | RecordingState::Error) {
+            let _ = win.hide();
+        }
+    });
+}
+
+// Injection settings adjusted for the profile the current recording uses
+pub(crate) async fn profile_injection(state: &AppState, injection: &InjectionSettings) -> InjectionSettings {
+    let injection = code_mode::injection(state, injection).await;
+    send_enter::injection(state, &injection).await
+}
+
+// Paste and/or copy already-formatted text according to the output mode
+async fn deliver_text(app: &AppHandle, state: &AppState, text: &str, injection: &InjectionSettings) {
+    let injection = &profile_injection(state, injection).await;
+    let output_mode = delivery_output_mode(state).await;
+    log::info!("🔧 Output mode: {:?}", output_mode);
+    accessibility::read_before_delivery(state, text).await;
+    if output_mode == OutputMode::Scratchpad {
+        scratchpad::append(app, state, text, true).await;
+        accessibility::announce_delivery(state, &locale::tr("announce.scratchpad"), text).await;
+        return;
+    }
+    if send_target::deliver(app, state, text, injection).await {
+        accessibility::announce_delivery(state, &locale::tr("announce.sent"), text).await;
+        return;
+    }
+
+    if output_mode == OutputMode::ClipboardOnly {
+        if let Err(e) = copy_text_to_clipboard(text) {
+            log::error!("❌ Copy to clipboard failed: {}", e);
+            let _ = app.emit("injection-error", e.to_string());
+        } else {
+            log::info!("📋 Text copied to clipboard (no paste)");
+            show_toast(app, "Whisper4Windows", &locale::tr("toast.copied"));
+            accessibility::announce_delivery(state, &locale::tr("announce.copied"), text).await;
+        }
+    } else {
+        let save_to_clipboard = output_mode == OutputMode::Both;
+        if let Err(e) = inject_text(text, save_to_clipboard, injection) {
+            log::error!("❌ Injection failed: {}", e);
+            show_toast(app, &locale::tr("toast.paste_failed"), &e.to_string());
+            accessibility::announce(app, locale::tr_with("announce.paste_failed", &[("error", &e.to_string())]));
+            let _ = app.emit("injection-error", e.to_string());
+        } else {
+            log::info!("✅ Text injected (clipboard: {})", if save_to_clipboard { "saved" } else { "restored" });
+            accessibility::announce_delivery(state, &locale::tr("announce.inserted"), text).await;
+        }
+    }
+}
+
+// The output mode for the recording being delivered: a meeting capture goes to the scratchpad,
+// never into whatever window has focus when the call ends
+async fn delivery_output_mode(state: &AppState) -> OutputMode {
+    if meeting::capturing() {
+        return OutputMode::Scratchpad;
+    }
+    *state.output_mode.lock().await
+}
+
+// The most recent transcription: delivered this session, or else the newest history entry
+async fn last_transcription_text(state: &AppState) -> Option<String> {
+    if let Some(text) = state.last_transcription.lock().await.clone() {
+        return Some(text);
+    }
+    let history = state.history.lock().await;
+    history.as_ref()?.latest().ok()?.map(|entry| entry.text)
+}
+
+// Re-inject the last transcription into the focused window
+#[tauri::command]
+async fn cmd_paste_last(app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    let Some(text) = last_transcription_text(&state).await else {
+        log::info!("📭 No previous transcription to paste");
+        return Ok(());
+    };
+
+    // Give the hotkey's modifiers a moment to be released before Ctrl+V
+    let injection = state.injection.lock().await.clone();
+    tokio::time::sleep(tokio::time::Duration::from_millis(injection.focus_return_ms)).await;
+    deliver_text(&app, &state, &text, &injection).await;
+    Ok(())
+}
+
+// Put the last transcription on the clipboard, for when the first paste went to the wrong place
+#[tauri::command]
+async fn cmd_copy_last(app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    let Some(text) = last_transcription_text(&state).await else {
+        log::info!("📭 No previous transcription to copy");
+        show_toast(&app, "Whisper4Windows", &locale::tr("toast.nothing_to_copy"));
+        return Ok(());
+    };
+
+    copy_text_to_clipboard(&text).map_err(|e| e.to_string())?;
+    log::info!("📋 Last transcription copied to clipboard");
+    show_toast(&app, "Whisper4Windows", &locale::tr("toast.last_copied"));
+    Ok(())
+}
+
+// Read the last transcription aloud, for checking it without looking
+#[tauri::command]
+async fn cmd_read_last(app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    let rate = state.accessibility.lock().await.speech_rate;
+    let Some(text) = last_transcription_text(&state).await else {
+        log::info!("📭 No previous transcription to read");
+        show_toast(&app, "Whisper4Windows", &locale::tr("toast.nothing_to_read"));
+        accessibility::read_aloud(&locale::tr("announce.no_transcription"), rate).await;
+        return Ok(());
+    };
+
+    log::info!("🗣️ Reading the last transcription aloud");
+    accessibility::read_aloud(&text, rate).await;
+    Ok(())
+}
+
+// Transcribe the last recording again with the current model/language and deliver the result
+#[tauri::command]
+async fn cmd_retry_last(app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    recording_state::transition(&app, RecordingState::Processing)
+        .await
+        .map_err(|_| locale::tr("error.retry_busy"))?;
+
+    let result = retry_last(&app, &state).await;
+    recording_state::finish(&app, if result.is_err() { RecordingState::Error } else { RecordingState::Idle }).await;
+    start_queued_recording(&app).await;
+    result
+}
+
+async fn retry_last(app: &AppHandle, state: &AppState) -> Result<(), String> {
+    let Some((text, data)) = retranscribe(app, state).await? else { return Ok(()) };
+
+    recording_state::transition(app, RecordingState::Injecting).await?;
+    let injection = state.injection.lock().await.clone();
+    let target = window_info::foreground_target();
+    let language = transcript_language(state, &data).await;
+    let outputs = output_files::current(state).await;
+    let output = output_template::apply(state, &text, language.as_deref(), &target).await;
+    if outputs.deliver {
+        deliver_text(app, state, &output, &injection).await;
+    }
+    output_files::save(app, &outputs, &text, &data);
+
+    // Recorded like a fresh dictation, since the retry is the text the user keeps
+    caption_server::publish(state, caption_server::Caption::Final { text: text.clone() }).await;
+    history::record(state, &text, &data, &target).await;
+    journal::record(app, state, &output, &target).await;
+    stats::record(state, &text, &data, &target).await;
+    Ok(())
+}
+
+// Check the whole chain from backend to pasted text, for the settings page to show
+#[tauri::command]
+async fn run_self_test(app: AppHandle, state: State<'_, AppState>) -> Result<self_test::Report, String> {
+    if *state.recording_state.lock().await != RecordingState::Idle {
+        return Err("Finish the current recording first".to_string());
+    }
+    log::info!("🩺 Running self-test");
+    Ok(self_test::run(&app).await)
+}
+
+// Time the downloaded models on CPU and GPU and recommend one (all of them when `models` is empty)
+#[tauri::command]
+async fn run_benchmark(models: Vec<String>, state: State<'_, AppState>) -> Result<benchmark::Report, String> {
+    if *state.recording_state.lock().await != RecordingState::Idle {
+        return Err("Finish the current recording first".to_string());
+    }
+    log::info!("⏱️ Running benchmark");
+    benchmark::run(models).await
+}
+
+// Crash report commands: the report from the last crash the user hasn't dealt with yet
+#[tauri::command]
+async fn get_crash_report(app: AppHandle) -> Result<Option<crash_report::CrashReport>, String> {
+    Ok(crash_report::pending(&app))
+}
+
+// A ZIP of the crash log, minidump, and logs, for attaching to a bug report
+#[tauri::command]
+async fn export_crash_diagnostics(app: AppHandle) -> Result<tauri::ipc::Response, String> {
+    let report = crash_report::pending(&app).ok_or("No crash report")?;
+    let bundle = crash_report::diagnostics(&app, &report).map_err(|e| format!("Failed to collect diagnostics: {}", e))?;
+    Ok(tauri::ipc::Response::new(bundle))
+}
+
+#[tauri::command]
+async fn dismiss_crash_report(app: AppHandle) -> Result<(), String> {
+    crash_report::dismiss(&app).map_err(|e| e.to_string())
+}
+
+// The recording cut off by the last crash, if the backend found one at startup
+#[tauri::command]
+async fn get_orphaned_recording(state: State<'_, AppState>) -> Result<Option<crash_recovery::OrphanedRecording>, String> {
+    Ok(state.orphaned_recording.lock().await.clone())
+}
+
+// Transcribe the interrupted recording. The window it was meant for is long gone, so the text
+// goes to the clipboard and history instead of being pasted.
+#[tauri::command]
+async fn transcribe_orphaned_recording(app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    recording_state::transition(&app, RecordingState::Processing)
+        .await
+        .map_err(|_| "Cannot transcribe while recording or transcribing".to_string())?;
+
+    let result = transcribe_orphan(&app, &state).await;
+    recording_state::finish(&app, if result.is_err() { RecordingState::Error } else { RecordingState::Idle }).await;
+    start_queued_recording(&app).await;
+    result
+}
+
+async fn transcribe_orphan(app: &AppHandle, state: &AppState) -> Result<(), String> {
+    crash_recovery::restore(state).await?;
+    let Some((text, data)) = retranscribe(app, state).await? else { return Ok(()) };
+    copy_text_to_clipboard(&text).map_err(|e| e.to_string())?;
+    history::record(state, &text, &data, &window_info::WindowTarget::default()).await;
+    show_toast(app, &locale::tr("toast.recording_recovered"), &locale::tr("toast.copied"));
+    Ok(())
+}
+
+#[tauri::command]
+async fn discard_orphaned_recording(state: State<'_, AppState>) -> Result<(), String> {
+    crash_recovery::discard(&state).await
+}
+
+// Transcribe the backend's retained recording with the current model/language; returns the processed
+// text and the backend's result, or None if cancelled or nothing was said
+pub(crate) async fn retranscribe(app: &AppHandle, state: &AppState) -> Result<Option<(String, serde_json::Value)>, String> {
+    transcribe_without_recording(app, state, None).await
+}
+
+// Transcribe audio other than a fresh recording: the last recording again, or `file`
+pub(crate) async fn transcribe_without_recording(
+    app: &AppHandle,
+    state: &AppState,
+    file: Option<&file_transcription::AudioFile>,
+) -> Result<Option<(String, serde_json::Value)>, String> {
+    let slot = state.recording_slot.lock().await.clone();
+    let model_slots::ModelSlot { model, device } = model_slots::resolve(state, &slot).await;
+    let language = state.selected_language.lock().await.clone();
+    let diarize = *state.diarization.lock().await;
+    let multilingual = state.multilingual.lock().await.clone();
+    let decoding = decoding::resolve(state, &slot).await;
+    match file {
+        Some(file) => log::info!("📂 Transcribing {} (model: {}, language: {})", file.path, model, language),
+        None => log::info!("🔁 Re-transcribing last recording (model: {}, language: {})", model, language),
+    }
+
+    let mut body = serde_json::json!({
+        "model_size": model,
+        "device": device,
+        "language": language,
+        "diarize": diarize,
+        "multilingual": multilingual.enabled,
+        "languages": multilingual.languages,
+    });
+    decoding.add_to_request(&mut body);
+    // Kept until the request is done; the temporary WAV is deleted when it's dropped
+    let converted = match file {
+        Some(file) => audio_decode::convert(file).await,
+        None => None,
+    };
+    let endpoint = match (file, &converted) {
+        (Some(_), Some(converted)) => {
+            body["path"] = serde_json::json!(converted.path.to_string_lossy());
+            body["audio_track"] = serde_json::json!(0);
+            "transcribe_file"
+        }
+        (Some(file), None) => {
+            body["path"] = serde_json::json!(file.path);
+            body["audio_track"] = serde_json::json!(file.track);
+            "transcribe_file"
+        }
+        (None, _) => "retranscribe",
+    };
+    let client = backend::client();
+    let request = client.post(backend::url(endpoint)).json(&body);
+    let resp = match send_with_progress(app, &client, request).await {
+        Ok(resp) => resp,
+        Err(backend::TranscriptionError::Interrupted(backend::Interrupt::Cancel)) => return Ok(None),
+        Err(e @ backend::TranscriptionError::Interrupted(backend::Interrupt::Recover)) => {
+            backend::recover(app).await;
+            return Err(e.to_string());
+        }
+        Err(e) => return Err(format!("Request failed: {}", e)),
+    };
+    let data = resp
+        .json::<serde_json::Value>()
+        .await
+        .map_err(|e| format!("Invalid response: {}", e))?;
+
+    if data.get("status").and_then(|s| s.as_str()) != Some("success") {
+        let message = data.get("message").and_then(|m| m.as_str()).unwrap_or("Transcription failed");
+        return Err(message.to_string());
+    }
+
+    let text = data.get("text").and_then(|t| t.as_str()).unwrap_or_default();
+    log::info!("📝 Transcription: {}", text);
+    report_diarization_error(app, &data);
+    let language = transcript_language(state, &data).await;
+    let text = process_transcript(state, text, language.as_deref()).await;
+    if text.is_empty() {
+        return Ok(None);
+    }
+    *state.last_transcription.lock().await = Some(text.clone());
+    Ok(Some((text, data)))
+}
+
+// Give up on a stuck transcription: restart the backend and keep the audio for Retry (overlay button)
+#[tauri::command]
+async fn recover_stuck_transcription(state: State<'_, AppState>) -> Result<(), String> {
+    if backend::interrupt(&state, backend::Interrupt::Recover).await {
+        Ok(())
+    } else {
+        Err("No transcription in progress".to_string())
+    }
+}
+
+// Pause or resume the active recording (paused audio is dropped by the backend)
+async fn set_recording_paused(app: &AppHandle, paused: bool) -> Result<(), String> {
+    let state: State<AppState> = app.state();
+    if !recording_state::is_recording(&state).await {
+        return Err("Not recording".to_string());
+    }
+    if *state.is_paused.lock().await == paused {
+        return Ok(());
+    }
+
+    let endpoint = if paused { "pause" } else { "resume" };
+    let client = backend::client();
+    let resp = client.post(backend::url(endpoint))
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {}", e))?;
+    if !resp.status().is_success() {
+        return Err(format!("Backend error: {}", resp.status()));
+    }
+
+    *state.is_paused.lock().await = paused;
+    if let Some(win) = app.get_webview_window("recording") {
+        let _ = win.eval(format!("setPaused({})", paused));
+    }
+    refresh_tray(app).await;
+    accessibility::announce(app, locale::tr(if paused { "announce.paused" } else { "announce.resumed" }));
+    log::info!("{} Recording {}", if paused { "⏸️" } else { "▶️" }, if paused { "paused" } else { "resumed" });
+    Ok(())
+}
+
+#[tauri::command]
+async fn cmd_pause_recording(app: AppHandle) -> Result<(), String> {
+    set_recording_paused(&app, true).await
+}
+
+#[tauri::command]
+async fn cmd_resume_recording(app: AppHandle) -> Result<(), String> {
+    set_recording_paused(&app, false).await
+}
+
+// F9 shortcut handler
+#[tauri::command]
+async fn cmd_toggle_recording(app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    log::info!("⌨️ F9 PRESSED");
+
+    let recording_state = recording_state::get(&state).await;
+    log::info!("   State: {}", recording_state.as_str());
+
+    match recording_state {
+        // Stop - call backend /stop, transcribe, and inject
+        RecordingState::Recording => cmd_stop_recording(app, state).await?,
+        // A second recording now would interleave its text with the one being delivered
+        RecordingState::Processing | RecordingState::Injecting => {
+            if *state.queue_next_recording.lock().await {
+                let queued = {
+                    let mut queued = state.recording_queued.lock().await;
+                    *queued = !*queued;
+                    *queued
+                };
+                log::info!("⏭️ Next recording {}", if queued { "queued" } else { "unqueued" });
+                if let Some(win) = app.get_webview_window("recording") {
+                    let _ = win.eval(format!("setQueued({})", queued));
+                }
+            } else {
+                log::info!("⏳ Still transcribing - toggle ignored");
+            }
+        }
+        // Start
+        RecordingState::Idle | RecordingState::Error => cmd_start_recording(app, state).await?,
+    }
+
+    Ok(())
+}
+
+// Start the recording queued during the last transcription, if any
+async fn start_queued_recording(app: &AppHandle) {
+    let state: State<AppState> = app.state();
+    if !std::mem::take(&mut *state.recording_queued.lock().await) {
+        return;
+    }
+    log::info!("⏭️ Starting queued recording");
+    if let Err(e) = cmd_start_recording(app.clone(), app.state()).await {
+        log::error!("❌ Queued recording failed to start: {}", e);
+    }
+}
+
+// Settings command
+#[tauri::command]
+async fn set_model_and_device(
+    model: String,
+    device: String,
+    app: AppHandle,
+    state: State<'_, AppState>
+) -> Result<(), String> {
+    policy::check("model", policy::get().model.as_deref(), model.as_str())?;
+    policy::check("device", policy::get().device.as_deref(), device.as_str())?;
+    *state.selected_model.lock().await = model.clone();
+    *state.selected_device.lock().await = device.clone();
+    log::info!("⚙️ Settings: model={}, device={}", model, device);
+    quick_switch::save(&app).await;
+    Ok(())
+}
+
+#[tauri::command]
+async fn get_model(state: State<'_, AppState>) -> Result<String, String> {
+    Ok(state.selected_model.lock().await.clone())
+}
+
+// Where the windows reach the backend, which listens on a port of its own per user and session
+#[tauri::command]
+async fn get_backend_url() -> Result<String, String> {
+    Ok(format!("http://127.0.0.1:{}", backend::port()))
+}
+
+#[tauri::command]
+async fn get_device(state: State<'_, AppState>) -> Result<String, String> {
+    Ok(state.selected_device.lock().await.clone())
+}
+
+// Every model the backend offers: repo, download URL and size, whether it's English-only, and whether it's downloaded
+#[tauri::command]
+async fn get_model_catalog() -> Result<Vec<serde_json::Value>, String> {
+    let resp = backend::client()
+        .get(backend::url("models"))
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {}", e))?;
+    let data = resp.json::<serde_json::Value>().await.map_err(|e| format!("Invalid response: {}", e))?;
+    Ok(data.get("catalog").and_then(|c| c.as_array()).cloned().unwrap_or_default())
+}
+
+// Model slot commands: the quick/accurate models and whether they're kept loaded
+#[tauri::command]
+async fn set_model_slots(
+    slots: model_slots::ModelSlots,
+    app: AppHandle,
+    state: State<'_, AppState>
+) -> Result<(), String> {
+    log::info!("⚙️ Model slots: {:?}", slots);
+    model_slots::check_policy(&slots)?;
+    storage::save_json(&app, model_slots::CONFIG_FILE, &slots).map_err(|e| e.to_string())?;
+    *state.model_slots.lock().await = slots;
+    Ok(())
+}
+
+#[tauri::command]
+async fn get_model_slots(state: State<'_, AppState>) -> Result<model_slots::ModelSlots, String> {
+    Ok(state.model_slots.lock().await.clone())
+}
+
+// Advanced settings commands: compute type and decoding parameters, with per-slot overrides
+#[tauri::command]
+async fn set_advanced_settings(
+    settings: decoding::AdvancedSettings,
+    app: AppHandle,
+    state: State<'_, AppState>
+) -> Result<(), String> {
+    log::info!("🎛️ Advanced settings: {:?}", settings);
+    storage::save_json(&app, decoding::CONFIG_FILE, &settings).map_err(|e| e.to_string())?;
+    *state.advanced.lock().await = settings;
+    Ok(())
+}
+
+#[tauri::command]
+async fn get_advanced_settings(state: State<'_, AppState>) -> Result<decoding::AdvancedSettings, String> {
+    Ok(state.advanced.lock().await.clone())
+}
+
+// Back to the defaults, dropping every slot's overrides
+#[tauri::command]
+async fn reset_advanced_settings(app: AppHandle, state: State<'_, AppState>) -> Result<decoding::AdvancedSettings, String> {
+    let settings = decoding::AdvancedSettings::default();
+    storage::save_json(&app, decoding::CONFIG_FILE, &settings).map_err(|e| e.to_string())?;
+    *state.advanced.lock().await = settings.clone();
+    log::info!("🎛️ Advanced settings reset to defaults");
+    Ok(settings)
+}
+
+// Sound commands: per-event audio cues, volume, and silence during Focus Assist
+#[tauri::command]
+async fn set_sound_settings(settings: sounds::SoundSettings, app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    log::info!("🔊 Sound settings: {:?}", settings);
+    storage::save_json(&app, sounds::CONFIG_FILE, &settings).map_err(|e| e.to_string())?;
+    *state.sounds.lock().await = settings;
+    Ok(())
+}
+
+#[tauri::command]
+async fn get_sound_settings(state: State<'_, AppState>) -> Result<sounds::SoundSettings, String> {
+    Ok(state.sounds.lock().await.clone())
+}
+
+// Play a cue as it would sound with these (unsaved) settings
+#[tauri::command]
+async fn preview_sound(event: sounds::SoundEvent, file: Option<String>, volume: u8) -> Result<(), String> {
+    sounds::preview(event, file, volume).await
+}
+
+// Accessibility commands: how status changes are announced
+#[tauri::command]
+async fn set_accessibility_settings(
+    settings: accessibility::AccessibilitySettings,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    log::info!("♿ Accessibility settings: {:?}", settings);
+    storage::save_json(&app, accessibility::CONFIG_FILE, &settings).map_err(|e| e.to_string())?;
+    *state.accessibility.lock().await = settings;
+    Ok(())
+}
+
+#[tauri::command]
+async fn get_accessibility_settings(state: State<'_, AppState>) -> Result<accessibility::AccessibilitySettings, String> {
+    Ok(state.accessibility.lock().await.clone())
+}
+
+// Make an announcement with these (unsaved) settings
+#[tauri::command]
+async fn test_announcement(settings: accessibility::AccessibilitySettings) -> Result<(), String> {
+    accessibility::deliver(&settings, locale::tr("announce.test"));
+    Ok(())
+}
+
+// Show the settings window at a section or page (the last notification's section if None)
+#[tauri::command]
+async fn open_settings(section: Option<String>, app: AppHandle) -> Result<(), String> {
+    navigation::open_settings(&app, section.as_deref());
+    Ok(())
+}
+
+// A section asked for with --settings when the app started, once
+#[tauri::command]
+async fn take_startup_settings_section() -> Result<Option<String>, String> {
+    Ok(navigation::take_startup_section())
+}
+
+// Locale commands: display language and the settings window's texts
+#[tauri::command]
+async fn set_locale_settings(settings: locale::LocaleSettings, app: AppHandle, state: State<'_, AppState>) -> Result<String, String> {
+    storage::save_json(&app, locale::CONFIG_FILE, &settings).map_err(|e| e.to_string())?;
+    let active = locale::apply(&settings);
+    *state.locale.lock().await = settings;
+    refresh_tray(&app).await;
+    jump_list::install();
+    // The context menu entry's title is in the display language
+    let explorer_menu = state.file_transcription.lock().await.explorer_menu;
+    if explorer_menu {
+        shell_menu::apply(true);
+    }
+    Ok(active.to_string())
+}
+
+#[tauri::command]
+async fn get_locale_settings(state: State<'_, AppState>) -> Result<locale::LocaleSettings, String> {
+    Ok(state.locale.lock().await.clone())
+}
+
+#[tauri::command]
+async fn get_available_locales() -> Result<Vec<locale::LocaleInfo>, String> {
+    Ok(locale::available())
+}
+
+// Texts for `locale`, or the active locale if None
+#[tauri::command]
+async fn get_strings(locale: Option<String>) -> Result<HashMap<String, String>, String> {
+    Ok(locale::strings(locale.as_deref()))
+}
+
+// Tray icon commands: taskbar theme and icon pack
+#[tauri::command]
+async fn set_tray_icon_settings(settings: tray_icon::TrayIconSettings, app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    log::info!("🖼️ Tray icon settings: {:?}", settings);
+    storage::save_json(&app, tray_icon::CONFIG_FILE, &settings).map_err(|e| e.to_string())?;
+    *state.tray_icon.lock().await = settings;
+    refresh_tray(&app).await;
+    Ok(())
+}
+
+#[tauri::command]
+async fn get_tray_icon_settings(state: State<'_, AppState>) -> Result<tray_icon::TrayIconSettings, String> {
+    Ok(state.tray_icon.lock().await.clone())
+}
+
+// Main window commands: starting in the tray and what the close button does
+#[tauri::command]
+async fn set_main_window_settings(
+    settings: main_window::MainWindowSettings,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    log::info!("🪟 Main window settings: {:?}", settings);
+    storage::save_json(&app, main_window::CONFIG_FILE, &settings).map_err(|e| e.to_string())?;
+    *state.main_window.lock().await = settings;
+    Ok(())
+}
+
+#[tauri::command]
+async fn get_main_window_settings(state: State<'_, AppState>) -> Result<main_window::MainWindowSettings, String> {
+    Ok(state.main_window.lock().await.clone())
+}
+
+// Focus Assist commands: what to hold back while Windows is in Do Not Disturb
+#[tauri::command]
+async fn set_focus_assist_settings(
+    settings: focus_assist::FocusAssistSettings,
+    app: AppHandle,
+    state: State<'_, AppState>
+) -> Result<(), String> {
+    log::info!("🔕 Focus Assist settings: {:?}", settings);
+    storage::save_json(&app, focus_assist::CONFIG_FILE, &settings).map_err(|e| e.to_string())?;
+    *state.focus_assist.lock().unwrap() = settings;
+    Ok(())
+}
+
+#[tauri::command]
+async fn get_focus_assist_settings(state: State<'_, AppState>) -> Result<focus_assist::FocusAssistSettings, String> {
+    Ok(state.focus_assist.lock().unwrap().clone())
+}
+
+// Whether Focus Assist is on right now, shown next to the settings
+#[tauri::command]
+async fn get_focus_assist_active() -> Result<bool, String> {
+    Ok(focus_assist::is_on())
+}
+
+// Custom model commands: models from a local folder or Hugging Face ID, validated by the backend
+#[tauri::command]
+async fn get_custom_models(state: State<'_, AppState>) -> Result<Vec<custom_models::CustomModel>, String> {
+    Ok(state.custom_models.lock().await.models.clone())
+}
+
+#[tauri::command]
+async fn add_custom_model(
+    name: String,
+    source: String,
+    app: AppHandle,
+    state: State<'_, AppState>
+) -> Result<custom_models::CustomModel, String> {
+    let source = source.trim().to_string();
+    if source.is_empty() {
+        return Err("Enter a folder or a Hugging Face model ID".to_string());
+    }
+    if state.custom_models.lock().await.models.iter().any(|m| m.source == source) {
+        return Err("That model has already been added".to_string());
+    }
+    if !policy::downloads_allowed() && !std::path::Path::new(&source).is_dir() {
+        return Err("Downloading models is turned off by your administrator; pick a folder".to_string());
+    }
+    let english_only = custom_models::validate(&source).await?;
+
+    let name = match name.trim() {
+        "" => source.rsplit(['/', '\\']).find(|part| !part.is_empty()).unwrap_or(&source).to_string(),
+        name => name.to_string(),
+    };
+    let model = custom_models::CustomModel { name, source, english_only };
+    log::info!("🧩 Custom model added: {:?}", model);
+    let store = {
+        let mut store = state.custom_models.lock().await;
+        store.models.push(model.clone());
+        store.clone()
+    };
+    storage::save_json(&app, custom_models::CONFIG_FILE, &store).map_err(|e| e.to_string())?;
+    refresh_tray(&app).await;
+    Ok(model)
+}
+
+#[tauri::command]
+async fn remove_custom_model(source: String, app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    let store = {
+        let mut store = state.custom_models.lock().await;
+        store.models.retain(|m| m.source != source);
+        store.clone()
+    };
+    storage::save_json(&app, custom_models::CONFIG_FILE, &store).map_err(|e| e.to_string())?;
+    log::info!("🧩 Custom model removed: {}", source);
+
+    // Don't leave the removed model selected
+    let selected = state.selected_model.lock().await.clone() == source;
+    if selected {
+        *state.selected_model.lock().await = "small".to_string();
+        quick_switch::save(&app).await;
+    } else {
+        refresh_tray(&app).await;
+    }
+    Ok(())
+}
+
+// Set microphone device
+#[tauri::command]
+async fn set_microphone_device(
+    device_index: Option<i32>,
+    app: AppHandle,
+    state: State<'_, AppState>
+) -> Result<(), String> {
+    *state.selected_microphone.lock().await = device_index;
+    log::info!("🎤 Microphone device set to: {:?}", device_index);
+    if state.prebuffer.lock().await.enabled {
+        prebuffer::apply(&app).await?;
+    }
+    Ok(())
+}
+
+// Get microphone device
+#[tauri::command]
+async fn get_microphone_device(state: State<'_, AppState>) -> Result<Option<i32>, String> {
+    Ok(*state.selected_microphone.lock().await)
+}
+
+// Connected microphones, for picking preferred ones
+#[tauri::command]
+async fn list_microphones() -> Result<Vec<microphones::InputDevice>, String> {
+    microphones::list().await
+}
+
+// The microphone recordings use now, with its channel count
+#[tauri::command]
+async fn get_current_microphone(state: State<'_, AppState>) -> Result<microphones::InputDevice, String> {
+    microphones::current(&state).await
+}
+
+#[tauri::command]
+async fn set_microphone_settings(settings: microphones::MicrophoneSettings, app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    microphones::validate(&settings)?;
+    log::info!("🎤 Microphone settings: {:?}", settings);
+    storage::save_json(&app, microphones::CONFIG_FILE, &settings).map_err(|e| e.to_string())?;
+    *state.microphones.lock().await = settings;
+    // The pre-buffer reopens the microphone the new way
+    if state.prebuffer.lock().await.enabled {
+        prebuffer::apply(&app).await?;
+    }
+    Ok(())
+}
+
+#[tauri::command]
+async fn get_microphone_settings(state: State<'_, AppState>) -> Result<microphones::MicrophoneSettings, String> {
+    Ok(state.microphones.lock().await.clone())
+}
+
+// Returns whether the pre-buffer is listening
+#[tauri::command]
+async fn set_prebuffer_settings(settings: prebuffer::PrebufferSettings, app: AppHandle, state: State<'_, AppState>) -> Result<bool, String> {
+    prebuffer::validate(&settings)?;
+    log::info!("⏪ Pre-buffer settings: {:?}", settings);
+    storage::save_json(&app, prebuffer::CONFIG_FILE, &settings).map_err(|e| e.to_string())?;
+    *state.prebuffer.lock().await = settings;
+    prebuffer::apply(&app).await
+}
+
+#[tauri::command]
+async fn get_prebuffer_settings(state: State<'_, AppState>) -> Result<(prebuffer::PrebufferSettings, bool), String> {
+    Ok((state.prebuffer.lock().await.clone(), prebuffer::active()))
+}
+
+// Audio pre-processing commands
+#[tauri::command]
+async fn set_audio_processing(settings: AudioProcessingSettings, app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    log::info!("🎚️ Audio processing: {:?}", settings);
+    storage::save_json(&app, AudioProcessingSettings::CONFIG_FILE, &settings).map_err(|e| e.to_string())?;
+    *state.audio_processing.lock().await = settings;
+    Ok(())
+}
+
+#[tauri::command]
+async fn get_audio_processing(state: State<'_, AppState>) -> Result<AudioProcessingSettings, String> {
+    Ok(*state.audio_processing.lock().await)
+}
+
+// Record a few seconds from the selected microphone and return it before and after pre-processing,
+// as base64 WAVs ({"original", "processed"}) the settings page can play
+#[tauri::command]
+async fn test_microphone(settings: AudioProcessingSettings, state: State<'_, AppState>) -> Result<serde_json::Value, String> {
+    let mut body = serde_json::json!({
+        "device_index": *state.selected_microphone.lock().await,
+        "seconds": 4.0,
+        "denoise": settings.denoise,
+        "high_pass": settings.high_pass,
+        "auto_gain": settings.auto_gain
+    });
+    state.microphones.lock().await.add_to_request(&mut body);
+    let data = backend::client()
+        .post(backend::url("mic_test"))
+        .json(&body)
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {}", e))?
+        .json::<serde_json::Value>()
+        .await
+        .map_err(|e| format!("Invalid response: {}", e))?;
+
+    if data.get("status").and_then(|s| s.as_str()) != Some("success") {
+        return Err(data.get("message").and_then(|m| m.as_str()).unwrap_or("Mic test failed").to_string());
+    }
+    Ok(data)
+}
+
+// Set clipboard paste setting (kept for the existing toggle: on = Both, off = Inject)
+#[tauri::command]
+async fn set_clipboard_paste(
+    enabled: bool,
+    app: AppHandle,
+    state: State<'_, AppState>
+) -> Result<(), String> {
+    let mut mode = state.output_mode.lock().await;
+    if matches!(*mode, OutputMode::Inject | OutputMode::Both) {
+        *mode = if enabled { OutputMode::Both } else { OutputMode::Inject };
+    }
+    log::info!("⚙️ Clipboard paste setting: {} (output mode: {:?})", enabled, *mode);
+    storage::save_json(&app, OutputMode::CONFIG_FILE, &*mode).map_err(|e| e.to_string())
+}
+
+// Get clipboard paste setting (true whenever the text is left on the clipboard)
+#[tauri::command]
+async fn get_clipboard_paste(state: State<'_, AppState>) -> Result<bool, String> {
+    let enabled = *state.output_mode.lock().await != OutputMode::Inject;
+    Ok(enabled)
+}
+
+// Output mode commands
+#[tauri::command]
+async fn set_output_mode(mode: OutputMode, app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    log::info!("⚙️ Output mode: {:?}", mode);
+    storage::save_json(&app, OutputMode::CONFIG_FILE, &mode).map_err(|e| e.to_string())?;
+    *state.output_mode.lock().await = mode;
+    Ok(())
+}
+
+#[tauri::command]
+async fn get_output_mode(state: State<'_, AppState>) -> Result<OutputMode, String> {
+    Ok(*state.output_mode.lock().await)
+}
+
+// Language commands
+#[tauri::command]
+async fn set_language(language: String, app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    policy::check("language", policy::get().language.as_deref(), language.as_str())?;
+    *state.selected_language.lock().await = language.clone();
+    log::info!("🌐 Language set to: {}", language);
+    quick_switch::save(&app).await;
+    Ok(())
+}
+
+#[tauri::command]
+async fn get_language(state: State<'_, AppState>) -> Result<String, String> {
+    Ok(state.selected_language.lock().await.clone())
+}
+
+#[tauri::command]
+async fn set_multilingual_settings(settings: MultilingualSettings, app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    log::info!("🌍 Multilingual settings: {:?}", settings);
+    storage::save_json(&app, MultilingualSettings::CONFIG_FILE, &settings).map_err(|e| e.to_string())?;
+    *state.multilingual.lock().await = settings;
+    Ok(())
+}
+
+#[tauri::command]
+async fn get_multilingual_settings(state: State<'_, AppState>) -> Result<MultilingualSettings, String> {
+    Ok(state.multilingual.lock().await.clone())
+}
+
+// Formatting commands
+#[tauri::command]
+async fn set_formatting_settings(
+    profile: String,
+    settings: FormattingSettings,
+    app: AppHandle,
+    state: State<'_, AppState>
+) -> Result<(), String> {
+    log::info!("✏️ Formatting settings for {}: {:?}", profile, settings);
+    let mut profiles = state.formatting.lock().await;
+    profiles.select(&profile, settings);
+    storage::save_json(&app, formatting::CONFIG_FILE, &*profiles).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn set_profanity_words(words: profanity::ProfanityWords, app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    log::info!("✏️ Profanity words: {:?}", words);
+    let mut profiles = state.formatting.lock().await;
+    profiles.profanity_words = words;
+    storage::save_json(&app, formatting::CONFIG_FILE, &*profiles).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn get_formatting_settings(state: State<'_, AppState>) -> Result<FormattingProfiles, String> {
+    Ok(state.formatting.lock().await.clone())
+}
+
+// Injection commands
+#[tauri::command]
+async fn set_injection_settings(
+    settings: InjectionSettings,
+    app: AppHandle,
+    state: State<'_, AppState>
+) -> Result<(), String> {
+    log::info!("⚙️ Injection settings: {:?}", settings);
+    let helper = settings.elevated_helper;
+    *state.injection.lock().await = settings;
+
+    // Ask for elevation now rather than in the middle of a dictation
+    let mut result = Ok(());
+    if helper {
+        let started = tauri::async_runtime::spawn_blocking(elevation::ensure_helper).await.map_err(|e| e.to_string())?;
+        if let Err(e) = started {
+            state.injection.lock().await.elevated_helper = false;
+            result = Err(e.to_string());
+        }
+    } else {
+        elevation::stop_helper();
+    }
+    storage::save_json(&app, InjectionSettings::CONFIG_FILE, &*state.injection.lock().await).map_err(|e| e.to_string())?;
+    result
+}
+
+#[tauri::command]
+async fn get_injection_settings(state: State<'_, AppState>) -> Result<InjectionSettings, String> {
+    Ok(state.injection.lock().await.clone())
+}
+
+// Snippet commands
+#[tauri::command]
+async fn get_snippets(state: State<'_, AppState>) -> Result<Vec<snippets::Snippet>, String> {
+    Ok(state.snippets.lock().await.snippets.clone())
+}
+
+// Add a snippet or replace the one with the same name
+#[tauri::command]
+async fn save_snippet(snippet: snippets::Snippet, app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    let mut store = state.snippets.lock().await;
+    store.upsert(snippet)?;
+    log::info!("🧩 Snippets saved ({} total)", store.snippets.len());
+    storage::save_json(&app, snippets::CONFIG_FILE, &*store).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn delete_snippet(name: String, app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    let mut store = state.snippets.lock().await;
+    if !store.remove(&name) {
+        return Err(format!("No snippet named {}", name));
+    }
+    log::info!("🧩 Snippet deleted: {}", name);
+    storage::save_json(&app, snippets::CONFIG_FILE, &*store).map_err(|e| e.to_string())
+}
+
+// HID device (foot pedal) commands
+#[tauri::command]
+async fn get_hid_bindings() -> Result<Vec<hid_trigger::HidBinding>, String> {
+    Ok(hid_trigger::bindings())
+}
+
+// Wait for a button press on any HID device, for binding it
+#[tauri::command]
+async fn capture_hid_button(app: AppHandle) -> Result<hid_trigger::HidButton, String> {
+    hid_trigger::capture(&app).await
+}
+
+// Bind a device button to an action, replacing any binding of that button
+#[tauri::command]
+async fn save_hid_binding(binding: hid_trigger::HidBinding, app: AppHandle) -> Result<(), String> {
+    if !BUTTON_ACTIONS.contains(&binding.action.as_str()) {
+        return Err(format!("Unknown action: {}", binding.action));
+    }
+    let mut bindings = hid_trigger::bindings();
+    bindings.retain(|b| b.button != binding.button);
+    log::info!("🦶 Bound {:?} to {}", binding.button, binding.action);
+    bindings.push(binding);
+    storage::save_json(&app, hid_trigger::CONFIG_FILE, &hid_trigger::HidBindings { bindings: bindings.clone() })
+        .map_err(|e| e.to_string())?;
+    hid_trigger::set_bindings(&app, bindings);
+    Ok(())
+}
+
+#[tauri::command]
+async fn delete_hid_binding(button: hid_trigger::HidButton, app: AppHandle) -> Result<(), String> {
+    let mut bindings = hid_trigger::bindings();
+    bindings.retain(|b| b.button != button);
+    storage::save_json(&app, hid_trigger::CONFIG_FILE, &hid_trigger::HidBindings { bindings: bindings.clone() })
+        .map_err(|e| e.to_string())?;
+    hid_trigger::set_bindings(&app, bindings);
+    Ok(())
+}
+
+// Gamepad commands
+#[tauri::command]
+async fn get_gamepad_bindings() -> Result<Vec<gamepad::GamepadBinding>, String> {
+    Ok(gamepad::bindings())
+}
+
+// Bind a controller button to an action, replacing any binding of that button
+#[tauri::command]
+async fn save_gamepad_binding(binding: gamepad::GamepadBinding, app: AppHandle) -> Result<(), String> {
+    if !BUTTON_ACTIONS.contains(&binding.action.as_str()) {
+        return Err(format!("Unknown action: {}", binding.action));
+    }
+    let mut bindings = gamepad::bindings();
+    bindings.retain(|b| b.button != binding.button);
+    log::info!("🎮 Bound {:?} to {}", binding.button, binding.action);
+    bindings.push(binding);
+    storage::save_json(&app, gamepad::CONFIG_FILE, &gamepad::GamepadBindings { bindings: bindings.clone() })
+        .map_err(|e| e.to_string())?;
+    gamepad::set_bindings(&app, bindings);
+    Ok(())
+}
+
+#[tauri::command]
+async fn delete_gamepad_binding(button: gamepad::GamepadButton, app: AppHandle) -> Result<(), String> {
+    let mut bindings = gamepad::bindings();
+    bindings.retain(|b| b.button != button);
+    storage::save_json(&app, gamepad::CONFIG_FILE, &gamepad::GamepadBindings { bindings: bindings.clone() })
+        .map_err(|e| e.to_string())?;
+    gamepad::set_bindings(&app, bindings);
+    Ok(())
+}
+
+// Scratchpad commands
+#[tauri::command]
+async fn show_scratchpad(app: AppHandle) -> Result<(), String> {
+    scratchpad::show(&app, true).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn get_scratchpad_text(state: State<'_, AppState>) -> Result<String, String> {
+    Ok(state.scratchpad.lock().await.text.clone())
+}
+
+// Called by the scratchpad window on every edit
+#[tauri::command]
+async fn set_scratchpad_text(text: String, app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    scratchpad::set_text(&app, &state, text).await;
+    Ok(())
+}
+
+// Send target commands
+#[tauri::command]
+async fn get_send_targets(state: State<'_, AppState>) -> Result<send_target::SendTargetStore, String> {
+    Ok(state.send_targets.lock().await.clone())
+}
+
+// Add a send target or replace the one with the same name
+#[tauri::command]
+async fn save_send_target(target: send_target::SendTarget, app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    let mut store = state.send_targets.lock().await;
+    store.upsert(target)?;
+    log::info!("🎯 Send targets saved ({} total)", store.targets.len());
+    storage::save_json(&app, send_target::CONFIG_FILE, &*store).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn delete_send_target(name: String, app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    let mut store = state.send_targets.lock().await;
+    if !store.remove(&name) {
+        return Err(format!("No send target named {}", name));
+    }
+    storage::save_json(&app, send_target::CONFIG_FILE, &*store).map_err(|e| e.to_string())
+}
+
+// Pick where transcriptions go: a target's name, or empty for the focused window
+#[tauri::command]
+async fn set_active_send_target(name: String, app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    let mut store = state.send_targets.lock().await;
+    if !name.is_empty() && !store.targets.iter().any(|t| t.name.eq_ignore_ascii_case(&name)) {
+        return Err(format!("No send target named {}", name));
+    }
+    store.active = name;
+    log::info!("🎯 Sending transcriptions to: {}", if store.active.is_empty() { "focused window" } else { &store.active });
+    storage::save_json(&app, send_target::CONFIG_FILE, &*store).map_err(|e| e.to_string())
+}
+
+// Output template commands
+#[tauri::command]
+async fn get_output_templates(state: State<'_, AppState>) -> Result<output_template::TemplateStore, String> {
+    Ok(state.output_templates.lock().await.clone())
+}
+
+// Add an output template or replace the one with the same name
+#[tauri::command]
+async fn save_output_template(template: output_template::OutputTemplate, app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    let mut store = state.output_templates.lock().await;
+    store.upsert(template)?;
+    log::info!("🧾 Output templates saved ({} total)", store.templates.len());
+    storage::save_json(&app, output_template::CONFIG_FILE, &*store).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn delete_output_template(name: String, app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    let mut store = state.output_templates.lock().await;
+    if !store.remove(&name) {
+        return Err(format!("No output template named {}", name));
+    }
+    storage::save_json(&app, output_template::CONFIG_FILE, &*store).map_err(|e| e.to_string())
+}
+
+// Pick the template a profile (model slot) uses, or empty for none
+#[tauri::command]
+async fn set_profile_template(profile: String, name: String, app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    let mut store = state.output_templates.lock().await;
+    store.select(&profile, &name)?;
+    log::info!("🧾 Output template for {}: {}", profile, if name.is_empty() { "none" } else { &name });
+    storage::save_json(&app, output_template::CONFIG_FILE, &*store).map_err(|e| e.to_string())
+}
+
+// Code mode commands
+#[tauri::command]
+async fn set_code_mode_settings(settings: code_mode::CodeModeSettings, app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    log::info!("⚙️ Code mode settings: {:?}", settings);
+    storage::save_json(&app, code_mode::CONFIG_FILE, &settings).map_err(|e| e.to_string())?;
+    *state.code_mode.lock().await = settings;
+    Ok(())
+}
+
+#[tauri::command]
+async fn get_code_mode_settings(state: State<'_, AppState>) -> Result<code_mode::CodeModeSettings, String> {
+    Ok(state.code_mode.lock().await.clone())
+}
+
+// Send-Enter commands
+#[tauri::command]
+async fn set_send_enter_settings(settings: send_enter::SendEnterSettings, app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    log::info!("⚙️ Send-Enter settings: {:?}", settings);
+    storage::save_json(&app, send_enter::CONFIG_FILE, &settings).map_err(|e| e.to_string())?;
+    *state.send_enter.lock().await = settings;
+    Ok(())
+}
+
+#[tauri::command]
+async fn get_send_enter_settings(state: State<'_, AppState>) -> Result<send_enter::SendEnterSettings, String> {
+    Ok(state.send_enter.lock().await.clone())
+}
+
+// Spoken casing commands
+#[tauri::command]
+async fn set_casing_settings(settings: casing::CasingSettings, app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    casing::validate(&settings)?;
+    log::info!("⚙️ Casing settings: {:?}", settings);
+    storage::save_json(&app, casing::CONFIG_FILE, &settings).map_err(|e| e.to_string())?;
+    *state.casing.lock().await = settings;
+    Ok(())
+}
+
+#[tauri::command]
+async fn get_casing_settings(state: State<'_, AppState>) -> Result<casing::CasingSettings, String> {
+    Ok(state.casing.lock().await.clone())
+}
+
+// Preview a snippet's text with placeholders filled in, in the main hotkey's regional format
+#[tauri::command]
+async fn render_snippet(template: String, state: State<'_, AppState>) -> Result<String, String> {
+    let format = state.regional_formats.lock().await.for_profile(model_slots::DEFAULT);
+    Ok(snippets::render(&template, format))
+}
+
+// Regional format commands
+#[tauri::command]
+async fn get_regional_formats(state: State<'_, AppState>) -> Result<regional_format::RegionalSettings, String> {
+    Ok(state.regional_formats.lock().await.clone())
+}
+
+#[tauri::command]
+async fn set_regional_format(profile: String, region: String, app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    let mut settings = state.regional_formats.lock().await;
+    settings.select(&profile, &region)?;
+    log::info!("🌐 Regional format for {}: {}", profile, if region.is_empty() { "ISO" } else { &region });
+    storage::save_json(&app, regional_format::CONFIG_FILE, &*settings).map_err(|e| e.to_string())
+}
+
+// Output file commands
+#[tauri::command]
+async fn get_output_files(state: State<'_, AppState>) -> Result<output_files::OutputFileSettings, String> {
+    Ok(state.output_files.lock().await.clone())
+}
+
+#[tauri::command]
+async fn set_output_files(profile: String, outputs: output_files::OutputSet, app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    output_files::validate(&outputs)?;
+    log::info!("📁 Outputs for {}: {:?}", profile, outputs);
+    let mut settings = state.output_files.lock().await;
+    settings.select(&profile, outputs);
+    storage::save_json(&app, output_files::CONFIG_FILE, &*settings).map_err(|e| e.to_string())
+}
+
+// Overlay placement commands
+#[tauri::command]
+async fn set_overlay_settings(
+    settings: overlay::OverlaySettings,
+    app: AppHandle,
+    state: State<'_, AppState>
+) -> Result<(), String> {
+    log::info!("⚙️ Overlay settings: {:?}", settings);
+    let mut config = state.overlay.lock().await;
+    config.settings = settings;
+    storage::save_json(&app, overlay::CONFIG_FILE, &*config).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn get_overlay_settings(state: State<'_, AppState>) -> Result<overlay::OverlaySettings, String> {
+    Ok(state.overlay.lock().await.settings.clone())
+}
+
+// Names of connected monitors, for pinning the overlay
+#[tauri::command]
+async fn list_monitors(app: AppHandle) -> Result<Vec<String>, String> {
+    let monitors = app.available_monitors().map_err(|e| e.to_string())?;
+    Ok(monitors.iter().filter_map(|m| m.name().cloned()).collect())
+}
+
+// Forget dragged positions so the placement preset applies again
+#[tauri::command]
+async fn reset_overlay_position(app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    let mut config = state.overlay.lock().await;
+    config.positions.clear();
+    log::info!("📌 Overlay positions reset");
+    storage::save_json(&app, overlay::CONFIG_FILE, &*config).map_err(|e| e.to_string())
+}
+
+// A hotkey: a keyboard shortcut (global-shortcut plugin) or a mouse button (low-level mouse hook)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Trigger {
+    Key(Shortcut),
+    Mouse(mouse_hook::MouseBinding),
+}
+
+// Helper function to parse shortcut string to Shortcut object
+fn parse_shortcut(shortcut_str: &str) -> Option<Shortcut> {
+    match parse_trigger(shortcut_str)? {
+        Trigger::Key(shortcut) => Some(shortcut),
+        Trigger::Mouse(_) => None,
+    }
+}
+
+// Parse a shortcut string such as "Ctrl+Alt+Space" or "Ctrl+MouseX1"
+fn parse_trigger(shortcut_str: &str) -> Option<Trigger> {
+
+    let parts: Vec<&str> = shortcut_str.split('+').collect();
     let mut modifiers = Modifiers::empty();
     let mut key_code: Option<Code> = None;
+    let mut mouse_button: Option<mouse_hook::MouseButton> = None;
 
     for part in parts {
         let part = part.trim();
@@ -566,97 +2623,1023 @@ fn parse_shortcut(shortcut_str: &str) -> Option<Shortcut> {
             "`" | "Backquote" => key_code = Some(Code::Backquote),
             "-" | "Minus" => key_code = Some(Code::Minus),
             "=" | "Equal" => key_code = Some(Code::Equal),
+            // Mouse buttons
+            s if mouse_hook::MouseButton::parse(s).is_some() => mouse_button = mouse_hook::MouseButton::parse(s),
             _ => {
                 log::warn!("⚠️ Unknown key: {}", part);
             }
         }
     }
 
-    if let Some(code) = key_code {
-        Some(Shortcut::new(Some(modifiers), code))
-    } else {
-        None
+    match (key_code, mouse_button) {
+        (Some(code), None) => Some(Trigger::Key(Shortcut::new(Some(modifiers), code))),
+        (None, Some(button)) => Some(Trigger::Mouse(mouse_hook::MouseBinding { button, modifiers })),
+        // A key and a mouse button together can't be detected by either mechanism
+        _ => None,
+    }
+}
+
+// Register a keyboard or mouse trigger for `action` ("toggle" or "cancel")
+fn register_trigger(app: &AppHandle, action: &str, shortcut_str: &str) -> Result<(), String> {
+    if shortcut_str.is_empty() {
+        return Ok(());  // Unbound
+    }
+    match parse_trigger(shortcut_str).ok_or_else(|| format!("Invalid shortcut format: {}", shortcut_str))? {
+        Trigger::Key(shortcut) => app.global_shortcut().register(shortcut).map_err(|e| e.to_string()),
+        Trigger::Mouse(binding) => mouse_hook::bind(app, action, binding),
+    }
+}
+
+fn unregister_trigger(app: &AppHandle, action: &str, shortcut_str: &str) -> Result<(), String> {
+    if shortcut_str.is_empty() {
+        return Ok(());
+    }
+    match parse_trigger(shortcut_str).ok_or_else(|| format!("Invalid shortcut format: {}", shortcut_str))? {
+        Trigger::Key(shortcut) => app.global_shortcut().unregister(shortcut).map_err(|e| e.to_string()),
+        Trigger::Mouse(_) => {
+            mouse_hook::unbind(action);
+            Ok(())
+        }
+    }
+}
+
+// How many hotkeys should be registered right now, and which of those aren't ("action (shortcut)")
+pub(crate) async fn hotkey_registration(app: &AppHandle) -> (usize, Vec<String>) {
+    let state: State<AppState> = app.state();
+    let recording = recording_state::is_recording(&state).await;
+    let keymap = state.shortcuts.lock().await.clone();
+    let active: Vec<(String, String)> = keymap
+        .into_iter()
+        .filter(|(action, shortcut)| !shortcut.is_empty() && hotkey_active(action, recording))
+        .collect();
+
+    let missing = active
+        .iter()
+        .filter(|(_, shortcut)| match parse_trigger(shortcut) {
+            Some(Trigger::Key(key)) => !app.global_shortcut().is_registered(key),
+            Some(Trigger::Mouse(binding)) => !mouse_hook::is_bound(binding),
+            None => true,
+        })
+        .map(|(action, shortcut)| format!("{} ({})", action, shortcut))
+        .collect();
+    (active.len(), missing)
+}
+
+// Run a hotkey action outside the hook/handler that detected it
+pub(crate) fn run_hotkey_action(app: AppHandle, action: String) {
+    tauri::async_runtime::spawn(async move {
+        let state: State<AppState> = app.state();
+        match action.as_str() {
+            "cancel" => {
+                log::info!("🔥 CANCEL TRIGGERED");
+                // Only cancel if a recording or its transcription is active
+                if matches!(recording_state::get(&state).await, RecordingState::Recording | RecordingState::Processing) {
+                    let _ = cmd_cancel_recording(app.clone()).await;
+                }
+            }
+            "toggle" => {
+                log::info!("🔥 TOGGLE TRIGGERED");
+                let _ = cmd_toggle_recording(app.clone(), app.state()).await;
+            }
+            // Like toggle, but a recording they start uses the quick or accurate model slot
+            "toggle_quick" | "toggle_accurate" => {
+                log::info!("🔥 {} TRIGGERED", action.to_uppercase());
+                if matches!(recording_state::get(&state).await, RecordingState::Idle | RecordingState::Error) {
+                    let slot = if action == "toggle_quick" { model_slots::QUICK } else { model_slots::ACCURATE };
+                    *state.next_slot.lock().await = Some(slot.to_string());
+                }
+                let _ = cmd_toggle_recording(app.clone(), app.state()).await;
+            }
+            // Unlike toggle, these never do the opposite (used by device buttons, e.g. a foot pedal held down)
+            "start" => {
+                log::info!("🔥 START TRIGGERED");
+                if matches!(recording_state::get(&state).await, RecordingState::Idle | RecordingState::Error) {
+                    let _ = cmd_start_recording(app.clone(), app.state()).await;
+                }
+            }
+            "stop" => {
+                log::info!("🔥 STOP TRIGGERED");
+                if recording_state::is_recording(&state).await {
+                    let _ = cmd_stop_recording(app.clone(), app.state()).await;
+                }
+            }
+            "next_language" => {
+                log::info!("🔥 NEXT LANGUAGE TRIGGERED");
+                let languages = overlay_languages(&state).await;
+                let current = overlay_language(&state).await;
+                let next = languages.iter().position(|l| *l == current).map_or(0, |i| (i + 1) % languages.len());
+                if let Err(e) = set_recording_language(languages[next].clone(), app.clone(), app.state()).await {
+                    log::warn!("⚠️ Language switch failed: {}", e);
+                }
+            }
+            "pause" => {
+                log::info!("🔥 PAUSE TRIGGERED");
+                let paused = *state.is_paused.lock().await;
+                if let Err(e) = set_recording_paused(&app, !paused).await {
+                    log::warn!("⚠️ Pause/resume failed: {}", e);
+                }
+            }
+            "paste_last" => {
+                log::info!("🔥 PASTE LAST TRIGGERED");
+                let _ = cmd_paste_last(app.clone(), app.state()).await;
+            }
+            "copy_last" => {
+                log::info!("🔥 COPY LAST TRIGGERED");
+                if let Err(e) = cmd_copy_last(app.clone(), app.state()).await {
+                    log::error!("❌ Copy last failed: {}", e);
+                }
+            }
+            "open_settings" => {
+                log::info!("🔥 OPEN SETTINGS TRIGGERED");
+                navigation::open_settings(&app, None);
+            }
+            "read_last" => {
+                log::info!("🔥 READ LAST TRIGGERED");
+                if let Err(e) = cmd_read_last(app.clone(), app.state()).await {
+                    log::error!("❌ Read last failed: {}", e);
+                }
+            }
+            "retry" => {
+                log::info!("🔥 RETRY TRIGGERED");
+                if let Err(e) = cmd_retry_last(app.clone(), app.state()).await {
+                    log::error!("❌ Retry failed: {}", e);
+                }
+            }
+            "transcribe_clipboard" => {
+                log::info!("🔥 TRANSCRIBE CLIPBOARD TRIGGERED");
+                file_transcription::start_from_clipboard(&app);
+            }
+            "privacy" => {
+                log::info!("🔥 PRIVACY TRIGGERED");
+                privacy::toggle(&app).await;
+            }
+            _ => log::warn!("⚠️ Unknown hotkey action: {}", action),
+        }
+    });
+}
+
+// Actions a device or gamepad button can trigger; "hold" records while the button is held down (push-to-talk)
+const BUTTON_ACTIONS: &[&str] = &["toggle", "start", "stop", "hold", "cancel", "pause"];
+
+// Run a button's action on press (or, for "hold", stop on release)
+pub(crate) fn run_button_action(app: AppHandle, action: &str, pressed: bool) {
+    let action = match (action, pressed) {
+        ("hold", true) => "start",
+        ("hold", false) => "stop",
+        (_, false) => return,
+        (action, true) => action,
+    };
+    run_hotkey_action(app, action.to_string());
+}
+
+// Common combos offered when a shortcut is already taken
+const SHORTCUT_SUGGESTIONS: &[&str] = &[
+    "F9", "F10", "F8", "Ctrl+Alt+Space", "Ctrl+Shift+Space", "Alt+F9", "Ctrl+F9", "Ctrl+Shift+R", "Ctrl+Alt+D",
+];
+
+// Check whether a shortcut can be registered right now (registers and immediately releases it)
+fn probe_shortcut(app: &AppHandle, shortcut_str: &str) -> Result<(), String> {
+    let shortcut = match parse_trigger(shortcut_str).ok_or_else(|| format!("Invalid shortcut format: {}", shortcut_str))? {
+        Trigger::Key(shortcut) => shortcut,
+        // Mouse buttons are read by our own hook, so only our other binding can conflict
+        Trigger::Mouse(binding) if mouse_hook::is_bound(binding) => {
+            return Err(format!("{} is already used by Whisper4Windows", shortcut_str));
+        }
+        Trigger::Mouse(_) => return Ok(()),
+    };
+    if app.global_shortcut().is_registered(shortcut) {
+        return Err(format!("{} is already used by Whisper4Windows", shortcut_str));
+    }
+    app.global_shortcut().register(shortcut).map_err(|e| e.to_string())?;
+    let _ = app.global_shortcut().unregister(shortcut);
+    Ok(())
+}
+
+// Up to three suggested shortcuts that are currently free
+fn suggest_free_shortcuts(app: &AppHandle, exclude: &[&str]) -> Vec<String> {
+    SHORTCUT_SUGGESTIONS
+        .iter()
+        .filter(|candidate| !exclude.iter().any(|e| e.eq_ignore_ascii_case(candidate)))
+        .filter(|candidate| probe_shortcut(app, candidate).is_ok())
+        .take(3)
+        .map(|candidate| candidate.to_string())
+        .collect()
+}
+
+// Tell the user a shortcut couldn't be registered (toast + event for the settings UI)
+fn notify_shortcut_conflict(app: &AppHandle, kind: &str, shortcut_str: &str, error: &str) {
+    let suggestions = suggest_free_shortcuts(app, &[shortcut_str]);
+    log::error!("❌ {} shortcut {} unavailable: {} (suggestions: {:?})", kind, shortcut_str, error, suggestions);
+    let _ = app.emit("shortcut-conflict", serde_json::json!({
+        "kind": kind,
+        "shortcut": shortcut_str,
+        "error": error,
+        "suggestions": suggestions,
+    }));
+    show_toast_linked(
+        app,
+        &locale::tr("toast.hotkey_unavailable"),
+        &locale::tr_with("toast.hotkey_unavailable_body", &[("shortcut", shortcut_str), ("suggestions", &suggestions.join(", "))]),
+        "shortcuts",
+    );
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+struct ShortcutTestResult {
+    available: bool,
+    error: Option<String>,
+    suggestions: Vec<String>,
+}
+
+// Verify a shortcut can be registered before saving it
+#[tauri::command]
+async fn test_shortcut(shortcut: String, app: AppHandle, state: State<'_, AppState>) -> Result<ShortcutTestResult, String> {
+    // Our own current bindings count as available - saving will re-register them
+    let current: Vec<String> = state.shortcuts.lock().await.values().cloned().collect();
+    if current.iter().any(|c| c.eq_ignore_ascii_case(&shortcut)) && parse_trigger(&shortcut).is_some() {
+        return Ok(ShortcutTestResult { available: true, error: None, suggestions: vec![] });
+    }
+
+    match probe_shortcut(&app, &shortcut) {
+        Ok(()) => {
+            log::info!("✅ Shortcut test passed: {}", shortcut);
+            Ok(ShortcutTestResult { available: true, error: None, suggestions: vec![] })
+        }
+        Err(e) => {
+            log::warn!("⚠️ Shortcut test failed for {}: {}", shortcut, e);
+            let suggestions = suggest_free_shortcuts(&app, &[shortcut.as_str()]);
+            Ok(ShortcutTestResult { available: false, error: Some(e), suggestions })
+        }
+    }
+}
+
+// Actions that can be bound to a hotkey, with their default shortcuts ("" = unbound)
+const HOTKEY_ACTIONS: &[(&str, &str)] = &[
+    ("toggle", "F9"),
+    ("toggle_quick", ""),
+    ("toggle_accurate", ""),
+    ("cancel", "Escape"),
+    ("pause", ""),
+    ("next_language", ""),
+    ("paste_last", ""),
+    ("copy_last", ""),
+    ("read_last", ""),
+    ("open_settings", ""),
+    ("retry", ""),
+    ("privacy", ""),
+    ("transcribe_clipboard", ""),
+];
+
+// Actions only registered while a recording is active, so keys like Escape stay free otherwise
+const RECORDING_ONLY_ACTIONS: &[&str] = &["cancel", "pause", "next_language"];
+
+// Whether `action`'s shortcut should currently be registered
+fn hotkey_active(action: &str, recording: bool) -> bool {
+    recording || !RECORDING_ONLY_ACTIONS.contains(&action)
+}
+
+// Register or unregister the recording-only shortcuts (called on start, stop, and cancel)
+async fn set_recording_hotkeys_active(app: &AppHandle, active: bool) {
+    let state: State<AppState> = app.state();
+    let keymap = state.shortcuts.lock().await.clone();
+    for action in RECORDING_ONLY_ACTIONS {
+        let shortcut = &keymap[*action];
+        let result = if active {
+            register_trigger(app, action, shortcut)
+        } else {
+            unregister_trigger(app, action, shortcut)
+        };
+        if let Err(e) = result {
+            log::warn!("⚠️ Failed to {} {} shortcut {}: {}", if active { "register" } else { "unregister" }, action, shortcut, e);
+        }
+    }
+}
+
+// The keymap as saved from the settings window, action -> shortcut
+const SHORTCUTS_FILE: &str = "shortcuts.json";
+
+fn default_keymap() -> HashMap<String, String> {
+    HOTKEY_ACTIONS
+        .iter()
+        .map(|(action, shortcut)| (action.to_string(), shortcut.to_string()))
+        .collect()
+}
+
+// The saved keymap over the defaults, so actions added since it was saved keep theirs
+fn load_keymap(app: &AppHandle) -> HashMap<String, String> {
+    let mut saved: HashMap<String, String> = storage::load_json(app, SHORTCUTS_FILE);
+    saved.retain(|action, _| HOTKEY_ACTIONS.iter().any(|(known, _)| known == action));
+    merge_shortcuts(&default_keymap(), &saved).unwrap_or_else(|e| {
+        log::warn!("⚠️ Saved shortcuts not used: {}", e);
+        default_keymap()
+    })
+}
+
+// Fail if two actions are bound to the same key or mouse button
+fn check_collisions(keymap: &HashMap<String, String>) -> Result<(), String> {
+    let mut seen: Vec<(&str, Trigger)> = Vec::new();
+    for (action, _) in HOTKEY_ACTIONS {
+        let Some(trigger) = keymap.get(*action).and_then(|s| parse_trigger(s)) else { continue };
+        if let Some((other, _)) = seen.iter().find(|(_, t)| *t == trigger) {
+            return Err(format!("{} is assigned to both {} and {}", keymap[*action], other, action));
+        }
+        seen.push((action, trigger));
+    }
+    Ok(())
+}
+
+// The keymap after applying `shortcuts` to `keymap`, or why it can't be used (nothing is registered)
+pub(crate) fn merge_shortcuts(
+    keymap: &HashMap<String, String>,
+    shortcuts: &HashMap<String, String>,
+) -> Result<HashMap<String, String>, String> {
+    let mut new_keymap = keymap.clone();
+    for (action, shortcut) in shortcuts {
+        if !keymap.contains_key(action) {
+            return Err(format!("Unknown hotkey action: {}", action));
+        }
+        let shortcut = shortcut.trim();
+        if !shortcut.is_empty() && parse_trigger(shortcut).is_none() {
+            log::error!("❌ Failed to parse {} shortcut: {}", action, shortcut);
+            return Err(format!("Invalid {} shortcut format: {}", action, shortcut));
+        }
+        new_keymap.insert(action.clone(), shortcut.to_string());
+    }
+
+    check_collisions(&new_keymap)?;
+    Ok(new_keymap)
+}
+
+// Shortcut commands
+#[tauri::command]
+async fn save_shortcuts(
+    shortcuts: HashMap<String, String>,
+    app: AppHandle,
+    state: State<'_, AppState>
+) -> Result<(), String> {
+    let mut keymap = state.shortcuts.lock().await;
+    let old_keymap = keymap.clone();
+    let new_keymap = merge_shortcuts(&old_keymap, &shortcuts)?;
+
+    // Only touch registrations that are live right now; recording-only ones are picked up on the next start
+    let recording = recording_state::is_recording(&state).await;
+    let changed: Vec<&str> = HOTKEY_ACTIONS
+        .iter()
+        .map(|(action, _)| *action)
+        .filter(|action| new_keymap[*action] != old_keymap[*action] && hotkey_active(action, recording))
+        .collect();
+
+    // Release every old binding first so two actions can swap keys
+    for action in &changed {
+        let old_shortcut = &old_keymap[*action];
+        if let Err(e) = unregister_trigger(&app, action, old_shortcut) {
+            log::warn!("⚠️ Failed to unregister old {} shortcut {}: {}", action, old_shortcut, e);
+        }
+    }
+
+    for (i, action) in changed.iter().enumerate() {
+        let shortcut = &new_keymap[*action];
+        if let Err(e) = register_trigger(&app, action, shortcut) {
+            log::error!("❌ Failed to register new {} shortcut {}: {}", action, shortcut, e);
+
+            // Roll back to the previous keymap
+            for done in &changed[..i] {
+                let _ = unregister_trigger(&app, done, &new_keymap[*done]);
+            }
+            for action in &changed {
+                let _ = register_trigger(&app, action, &old_keymap[*action]);
+            }
+
+            let suggestions = suggest_free_shortcuts(&app, &[shortcut.as_str()]);
+            return Err(format!(
+                "Failed to register {} shortcut {} (probably used by another app): {}. Kept previous shortcuts. Try: {}",
+                action, shortcut, e, suggestions.join(", ")
+            ));
+        }
+        log::info!("⌨️ {} shortcut saved: {} (was: {})", action, shortcut, old_keymap[*action]);
+    }
+
+    // The new shortcuts are live by now, so a failed save only costs them at the next start
+    if let Err(e) = storage::save_json(&app, SHORTCUTS_FILE, &new_keymap) {
+        log::error!("❌ Failed to save shortcuts: {}", e);
+    }
+    *keymap = new_keymap;
+    Ok(())
+}
+
+// Register or unregister all bound shortcuts without changing the saved keymap
+pub(crate) async fn set_global_shortcuts_active(app: &AppHandle, active: bool) {
+    let state: State<AppState> = app.state();
+    double_tap::set_suspended(!active);
+    win_h::set_suspended(!active);
+    let shortcuts = state.shortcuts.lock().await.clone();
+
+    // Recording-only shortcuts follow the recording lifecycle instead (suspension never happens mid-recording)
+    for (action, shortcut_str) in shortcuts.into_iter().filter(|(action, _)| hotkey_active(action, false)) {
+        let result = if active {
+            register_trigger(app, &action, &shortcut_str)
+        } else {
+            unregister_trigger(app, &action, &shortcut_str)
+        };
+        if let Err(e) = result {
+            log::warn!("⚠️ Failed to {} shortcut {}: {}", if active { "register" } else { "unregister" }, shortcut_str, e);
+        }
+    }
+}
+
+// Continuous dictation commands
+pub(crate) const CONTINUOUS_DICTATION_FILE: &str = "continuous_dictation.json";
+
+#[tauri::command]
+async fn set_continuous_dictation(enabled: bool, app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    log::info!("⚙️ Continuous dictation: {}", enabled);
+    storage::save_json(&app, CONTINUOUS_DICTATION_FILE, &enabled).map_err(|e| e.to_string())?;
+    *state.continuous_dictation.lock().await = enabled;
+    Ok(())
+}
+
+#[tauri::command]
+async fn get_continuous_dictation(state: State<'_, AppState>) -> Result<bool, String> {
+    Ok(*state.continuous_dictation.lock().await)
+}
+
+// Journal commands
+#[tauri::command]
+async fn set_journal_settings(settings: journal::JournalSettings, app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    if settings.enabled {
+        journal::resolve_path(&settings.path)?;
+    }
+    log::info!("⚙️ Journal: enabled={}, path={}", settings.enabled, settings.path);
+    storage::save_json(&app, journal::CONFIG_FILE, &settings).map_err(|e| e.to_string())?;
+    *state.journal.lock().await = settings;
+    Ok(())
+}
+
+#[tauri::command]
+async fn get_journal_settings(state: State<'_, AppState>) -> Result<journal::JournalSettings, String> {
+    Ok(state.journal.lock().await.clone())
+}
+
+// File transcription commands
+#[tauri::command]
+async fn set_file_transcription_settings(
+    settings: file_transcription::FileTranscriptionSettings,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    log::info!("⚙️ File transcription settings: {:?}", settings);
+    storage::save_json(&app, file_transcription::CONFIG_FILE, &settings).map_err(|e| e.to_string())?;
+    if settings.explorer_menu != state.file_transcription.lock().await.explorer_menu {
+        shell_menu::apply(settings.explorer_menu);
+    }
+    *state.file_transcription.lock().await = settings;
+    Ok(())
+}
+
+#[tauri::command]
+async fn get_file_transcription_settings(state: State<'_, AppState>) -> Result<file_transcription::FileTranscriptionSettings, String> {
+    Ok(state.file_transcription.lock().await.clone())
+}
+
+// Watch folder commands
+#[tauri::command]
+async fn set_watch_folder_settings(
+    settings: watch_folder::WatchFolderSettings,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    watch_folder::validate(&settings)?;
+    log::info!("⚙️ Watch folder: enabled={}, folder={}", settings.enabled, settings.folder);
+    storage::save_json(&app, watch_folder::CONFIG_FILE, &settings).map_err(|e| e.to_string())?;
+    *state.watch_folder.lock().await = settings;
+    Ok(())
+}
+
+#[tauri::command]
+async fn get_watch_folder_settings(state: State<'_, AppState>) -> Result<watch_folder::WatchFolderSettings, String> {
+    Ok(state.watch_folder.lock().await.clone())
+}
+
+// Caption server commands (settings are persisted so the server comes back on the next launch)
+#[tauri::command]
+async fn set_caption_server_settings(
+    mut settings: caption_server::CaptionServerSettings,
+    app: AppHandle,
+    state: State<'_, AppState>
+) -> Result<caption_server::CaptionServerSettings, String> {
+    log::info!("⚙️ Caption server: enabled={}, port={}", settings.enabled, settings.port);
+    caption_server::ensure_token(&mut settings)?;
+    caption_server::apply(&state, settings.clone()).await?;
+    storage::save_json(&app, caption_server::CONFIG_FILE, &settings).map_err(|e| e.to_string())?;
+    Ok(settings)
+}
+
+#[tauri::command]
+async fn get_caption_server_settings(state: State<'_, AppState>) -> Result<caption_server::CaptionServerSettings, String> {
+    Ok(state.caption_server.lock().await.settings.clone())
+}
+
+// Queue-next-recording commands
+pub(crate) const QUEUE_NEXT_RECORDING_FILE: &str = "queue_next_recording.json";
+
+#[tauri::command]
+async fn set_queue_next_recording(enabled: bool, app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    log::info!("⚙️ Queue next recording: {}", enabled);
+    storage::save_json(&app, QUEUE_NEXT_RECORDING_FILE, &enabled).map_err(|e| e.to_string())?;
+    *state.queue_next_recording.lock().await = enabled;
+    if !enabled {
+        *state.recording_queued.lock().await = false;
+    }
+    Ok(())
+}
+
+#[tauri::command]
+async fn get_queue_next_recording(state: State<'_, AppState>) -> Result<bool, String> {
+    Ok(*state.queue_next_recording.lock().await)
+}
+
+// Speaker diarization commands
+pub(crate) const DIARIZATION_FILE: &str = "diarization.json";
+
+#[tauri::command]
+async fn set_diarization(enabled: bool, app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    log::info!("⚙️ Speaker diarization: {}", enabled);
+    storage::save_json(&app, DIARIZATION_FILE, &enabled).map_err(|e| e.to_string())?;
+    *state.diarization.lock().await = enabled;
+    Ok(())
+}
+
+#[tauri::command]
+async fn get_diarization(state: State<'_, AppState>) -> Result<bool, String> {
+    Ok(*state.diarization.lock().await)
+}
+
+// Privacy mode commands
+#[tauri::command]
+async fn set_privacy_mode(enabled: bool, app: AppHandle) -> Result<(), String> {
+    policy::check("privacy_mode", policy::get().privacy_mode.as_ref(), &enabled)?;
+    privacy::set_enabled(&app, enabled).await;
+    Ok(())
+}
+
+#[tauri::command]
+async fn get_policy() -> Result<policy::PolicyInfo, String> {
+    Ok(policy::info())
+}
+
+#[tauri::command]
+async fn get_privacy_mode(state: State<'_, AppState>) -> Result<privacy::PrivacyMode, String> {
+    Ok(state.privacy.lock().await.clone())
+}
+
+#[tauri::command]
+async fn set_privacy_settings(settings: privacy::PrivacySettings, app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    log::info!("⚙️ Privacy settings: {:?}", settings);
+    storage::save_json(&app, privacy::CONFIG_FILE, &settings).map_err(|e| e.to_string())?;
+    *state.privacy_settings.lock().await = settings;
+    Ok(())
+}
+
+#[tauri::command]
+async fn get_privacy_settings(state: State<'_, AppState>) -> Result<privacy::PrivacySettings, String> {
+    Ok(state.privacy_settings.lock().await.clone())
+}
+
+// Statistics commands
+#[tauri::command]
+async fn set_stats_settings(settings: stats::StatsSettings, app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    log::info!("⚙️ Statistics settings: {:?}", settings);
+    storage::save_json(&app, stats::CONFIG_FILE, &settings).map_err(|e| e.to_string())?;
+    *state.stats_settings.lock().await = settings;
+    Ok(())
+}
+
+#[tauri::command]
+async fn get_stats_settings(state: State<'_, AppState>) -> Result<stats::StatsSettings, String> {
+    Ok(state.stats_settings.lock().await.clone())
+}
+
+// Per-day usage for the last `days` days (default 30), newest first
+#[tauri::command]
+async fn get_daily_stats(state: State<'_, AppState>, days: Option<u32>) -> Result<Vec<stats::StatsPeriod>, String> {
+    let typing_wpm = state.stats_settings.lock().await.typing_wpm;
+    let stats = state.stats.lock().await;
+    let store = stats.as_ref().ok_or("Statistics are not available")?;
+    store.daily(days.unwrap_or(30), typing_wpm).map_err(|e| e.to_string())
+}
+
+// Per-week usage for the last `weeks` weeks (default 12), newest first
+#[tauri::command]
+async fn get_weekly_stats(state: State<'_, AppState>, weeks: Option<u32>) -> Result<Vec<stats::StatsPeriod>, String> {
+    let typing_wpm = state.stats_settings.lock().await.typing_wpm;
+    let stats = state.stats.lock().await;
+    let store = stats.as_ref().ok_or("Statistics are not available")?;
+    store.weekly(weeks.unwrap_or(12), typing_wpm).map_err(|e| e.to_string())
+}
+
+// Per-app usage for the last `days` days (default 30), most used first
+#[tauri::command]
+async fn get_app_stats(state: State<'_, AppState>, days: Option<u32>) -> Result<Vec<stats::AppUsage>, String> {
+    let stats = state.stats.lock().await;
+    let store = stats.as_ref().ok_or("Statistics are not available")?;
+    store.by_app(days.unwrap_or(30)).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn clear_stats(state: State<'_, AppState>) -> Result<(), String> {
+    let stats = state.stats.lock().await;
+    let store = stats.as_ref().ok_or("Statistics are not available")?;
+    store.clear().map_err(|e| e.to_string())?;
+    log::info!("🧹 Statistics cleared");
+    Ok(())
+}
+
+// Pinned transcriptions, then the most recent, newest first (only those delivered to `app` if given)
+#[tauri::command]
+async fn get_history(
+    state: State<'_, AppState>,
+    limit: Option<u32>,
+    app: Option<String>,
+) -> Result<Vec<history::HistoryEntry>, String> {
+    let history = state.history.lock().await;
+    let store = history.as_ref().ok_or("History is not available")?;
+    store.list(limit.unwrap_or(100), app.as_deref()).map_err(|e| e.to_string())
+}
+
+// Keyword search over history with optional filters, best matches first
+#[tauri::command]
+async fn search_history(query: history::SearchQuery, state: State<'_, AppState>) -> Result<Vec<history::SearchHit>, String> {
+    let history = state.history.lock().await;
+    let store = history.as_ref().ok_or("History is not available")?;
+    store.search(&query).map_err(|e| e.to_string())
+}
+
+// Apps, languages, and models history can be filtered by
+#[tauri::command]
+async fn get_history_filters(state: State<'_, AppState>) -> Result<history::FilterValues, String> {
+    let history = state.history.lock().await;
+    let store = history.as_ref().ok_or("History is not available")?;
+    store.filter_values().map_err(|e| e.to_string())
+}
+
+// Keep a transcription at the top of history and in the tray's Pinned menu
+#[tauri::command]
+async fn pin_transcription(id: i64, app: AppHandle) -> Result<(), String> {
+    pinned::set(&app, id, true).await
+}
+
+#[tauri::command]
+async fn unpin_transcription(id: i64, app: AppHandle) -> Result<(), String> {
+    pinned::set(&app, id, false).await
+}
+
+// Apps that received transcriptions, most used first
+#[tauri::command]
+async fn get_history_apps(state: State<'_, AppState>) -> Result<Vec<String>, String> {
+    let history = state.history.lock().await;
+    let store = history.as_ref().ok_or("History is not available")?;
+    store.apps().map_err(|e| e.to_string())
+}
+
+// Word timings and confidence for a history entry (empty for entries recorded without them)
+#[tauri::command]
+async fn get_transcription_words(state: State<'_, AppState>, id: i64) -> Result<Vec<history::Word>, String> {
+    let history = state.history.lock().await;
+    let store = history.as_ref().ok_or("History is not available")?;
+    store
+        .words(id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("No history entry with id {}", id))
+}
+
+// Timed (and, if diarized, speaker-labeled) segments of a history entry
+#[tauri::command]
+async fn get_transcription_segments(state: State<'_, AppState>, id: i64) -> Result<Vec<history::Segment>, String> {
+    let history = state.history.lock().await;
+    let store = history.as_ref().ok_or("History is not available")?;
+    store
+        .segments(id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("No history entry with id {}", id))
+}
+
+// Export history entries (the given ids, or a date range in Unix ms) as Markdown, CSV, or DOCX bytes
+#[tauri::command]
+async fn export_history(
+    state: State<'_, AppState>,
+    format: history_export::ExportFormat,
+    ids: Option<Vec<i64>>,
+    from: Option<i64>,
+    to: Option<i64>,
+) -> Result<tauri::ipc::Response, String> {
+    let entries = {
+        let history = state.history.lock().await;
+        let store = history.as_ref().ok_or("History is not available")?;
+        store.select(ids.as_deref(), from, to).map_err(|e| e.to_string())?
+    };
+    if entries.is_empty() {
+        return Err("No transcriptions to export".to_string());
     }
+    let settings = state.history_export.lock().await.clone();
+    log::info!("📤 Exporting {} history entries as {:?}", entries.len(), format);
+    Ok(tauri::ipc::Response::new(history_export::export(format, &entries, &settings)))
 }
 
-// Shortcut commands
+// Dictation sessions of the last `days` days, newest first
 #[tauri::command]
-async fn save_shortcuts(
-    shortcuts: std::collections::HashMap<String, String>,
+async fn get_sessions(state: State<'_, AppState>, days: Option<u32>) -> Result<Vec<sessions::Session>, String> {
+    let settings = state.sessions.lock().await.clone();
+    let history = state.history.lock().await;
+    let store = history.as_ref().ok_or("History is not available")?;
+    sessions::list(store, &settings, days).map_err(|e| e.to_string())
+}
+
+// The session starting with history entry `id` as one document, each dictation under its time
+#[tauri::command]
+async fn export_session(
+    state: State<'_, AppState>,
+    id: i64,
+    format: history_export::ExportFormat,
+) -> Result<tauri::ipc::Response, String> {
+    let settings = state.sessions.lock().await.clone();
+    let entries = {
+        let history = state.history.lock().await;
+        let store = history.as_ref().ok_or("History is not available")?;
+        sessions::entries(store, &settings, id).map_err(|e| e.to_string())?
+    };
+    let entries = entries.ok_or_else(|| format!("No history entry with id {}", id))?;
+    log::info!("📤 Exporting session #{} ({} entries) as {:?}", id, entries.len(), format);
+    Ok(tauri::ipc::Response::new(history_export::export_session(format, &entries)))
+}
+
+#[tauri::command]
+async fn set_session_settings(settings: sessions::SessionSettings, app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    storage::save_json(&app, sessions::CONFIG_FILE, &settings).map_err(|e| e.to_string())?;
+    *state.sessions.lock().await = settings;
+    Ok(())
+}
+
+#[tauri::command]
+async fn get_session_settings(state: State<'_, AppState>) -> Result<sessions::SessionSettings, String> {
+    Ok(state.sessions.lock().await.clone())
+}
+
+#[tauri::command]
+async fn set_history_export_settings(
+    settings: history_export::ExportSettings,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    storage::save_json(&app, history_export::CONFIG_FILE, &settings).map_err(|e| e.to_string())?;
+    *state.history_export.lock().await = settings;
+    Ok(())
+}
+
+#[tauri::command]
+async fn get_history_export_settings(state: State<'_, AppState>) -> Result<history_export::ExportSettings, String> {
+    Ok(state.history_export.lock().await.clone())
+}
+
+// Retention policy commands (applied right away, then daily)
+#[tauri::command]
+async fn set_retention_settings(
+    settings: retention::RetentionSettings,
     app: AppHandle,
     state: State<'_, AppState>
 ) -> Result<(), String> {
-    // Handle toggle shortcut
-    if let Some(toggle) = shortcuts.get("toggle") {
-        let old_shortcut = state.toggle_shortcut.lock().await.clone();
-        *state.toggle_shortcut.lock().await = toggle.clone();
-        log::info!("⌨️ Toggle shortcut saved: {} (was: {})", toggle, old_shortcut);
-
-        // Re-register the shortcut
-        // First, unregister old shortcut
-        if let Some(old_sc) = parse_shortcut(&old_shortcut) {
-            if let Err(e) = app.global_shortcut().unregister(old_sc) {
-                log::warn!("⚠️ Failed to unregister old toggle shortcut {}: {}", old_shortcut, e);
-            } else {
-                log::info!("✅ Unregistered old toggle shortcut: {}", old_shortcut);
-            }
-        }
+    log::info!("⚙️ Retention: {:?}", settings);
+    storage::save_json(&app, retention::CONFIG_FILE, &settings).map_err(|e| e.to_string())?;
+    *state.retention.lock().await = settings;
+    retention::enforce(&app).await;
+    Ok(())
+}
 
-        // Register new shortcut
-        if let Some(new_sc) = parse_shortcut(toggle) {
-            if let Err(e) = app.global_shortcut().register(new_sc) {
-                log::error!("❌ Failed to register new toggle shortcut {}: {}", toggle, e);
-                return Err(format!("Failed to register toggle shortcut: {}", e));
-            } else {
-                log::info!("✅ Registered new toggle shortcut: {}", toggle);
-            }
-        } else {
-            log::error!("❌ Failed to parse toggle shortcut: {}", toggle);
-            return Err(format!("Invalid toggle shortcut format: {}", toggle));
-        }
+#[tauri::command]
+async fn get_retention_settings(state: State<'_, AppState>) -> Result<retention::RetentionSettings, String> {
+    Ok(state.retention.lock().await.clone())
+}
+
+// Model memory commands: when the backend frees the model, and loading it ahead of a dictation
+#[tauri::command]
+async fn set_model_memory_settings(
+    settings: model_memory::ModelMemorySettings,
+    app: AppHandle,
+    state: State<'_, AppState>
+) -> Result<(), String> {
+    log::info!("⚙️ Model memory: {:?}", settings);
+    storage::save_json(&app, model_memory::CONFIG_FILE, &settings).map_err(|e| e.to_string())?;
+    *state.model_memory.lock().await = settings.clone();
+    model_memory::apply(&settings).await
+}
+
+#[tauri::command]
+async fn get_model_memory_settings(state: State<'_, AppState>) -> Result<model_memory::ModelMemorySettings, String> {
+    Ok(state.model_memory.lock().await.clone())
+}
+
+#[tauri::command]
+async fn preload_model(state: State<'_, AppState>) -> Result<model_memory::Preloaded, String> {
+    if *state.recording_state.lock().await != RecordingState::Idle {
+        return Err("Finish the current recording first".to_string());
     }
+    let preloaded = model_memory::preload(&state).await?;
+    log::info!("🔥 Preloaded {} on {} in {:.1}s", preloaded.model, preloaded.device, preloaded.load_time);
+    Ok(preloaded)
+}
+
+// Settings import/export: a single JSON bundle of settings, hotkeys, and snippets
+#[tauri::command]
+async fn export_settings(app: AppHandle) -> Result<String, String> {
+    let bundle = settings_bundle::export(&app).await;
+    serde_json::to_string_pretty(&bundle).map_err(|e| e.to_string())
+}
 
-    // Handle cancel shortcut
-    if let Some(cancel) = shortcuts.get("cancel") {
-        let old_shortcut = state.cancel_shortcut.lock().await.clone();
-        *state.cancel_shortcut.lock().await = cancel.clone();
-        log::info!("⌨️ Cancel shortcut saved: {} (was: {})", cancel, old_shortcut);
+#[tauri::command]
+async fn import_settings(bundle: String, app: AppHandle) -> Result<(), String> {
+    settings_bundle::import(&app, &bundle).await
+}
 
-        // Re-register the shortcut
-        // First, unregister old shortcut
-        if let Some(old_sc) = parse_shortcut(&old_shortcut) {
-            if let Err(e) = app.global_shortcut().unregister(old_sc) {
-                log::warn!("⚠️ Failed to unregister old cancel shortcut {}: {}", old_shortcut, e);
-            } else {
-                log::info!("✅ Unregistered old cancel shortcut: {}", old_shortcut);
-            }
-        }
+// Delete everything the app has stored: history, statistics, snippets, overlay placement,
+// the last transcription, logs, and the audio the backend keeps for retry
+#[tauri::command]
+async fn purge_all_data(app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    if let Some(store) = state.history.lock().await.as_mut() {
+        store.clear().map_err(|e| format!("Failed to clear history: {}", e))?;
+    }
+    if let Some(store) = state.stats.lock().await.as_ref() {
+        store.clear().map_err(|e| format!("Failed to clear statistics: {}", e))?;
+    }
 
-        // Register new shortcut
-        if let Some(new_sc) = parse_shortcut(cancel) {
-            if let Err(e) = app.global_shortcut().register(new_sc) {
-                log::error!("❌ Failed to register new cancel shortcut {}: {}", cancel, e);
-                return Err(format!("Failed to register cancel shortcut: {}", e));
-            } else {
-                log::info!("✅ Registered new cancel shortcut: {}", cancel);
-            }
-        } else {
-            log::error!("❌ Failed to parse cancel shortcut: {}", cancel);
-            return Err(format!("Invalid cancel shortcut format: {}", cancel));
-        }
+    *state.snippets.lock().await = snippets::SnippetStore::default();
+    *state.overlay.lock().await = overlay::OverlayConfig::default();
+    *state.last_transcription.lock().await = None;
+    *state.orphaned_recording.lock().await = None;
+    *state.scratchpad.lock().await = scratchpad::Scratchpad::default();
+    if let Some(win) = app.get_webview_window(scratchpad::WINDOW_LABEL) {
+        let _ = win.eval("setScratchpadText('')");
+    }
+    // Pinned transcriptions went with the history
+    refresh_tray(&app).await;
+    for file_name in [snippets::CONFIG_FILE, overlay::CONFIG_FILE, scratchpad::CONFIG_FILE] {
+        storage::remove_json(&app, file_name).map_err(|e| format!("Failed to delete {}: {}", file_name, e))?;
+    }
+
+    // The backend may not be running; it keeps nothing on disk, so that's not an error
+    let client = backend::client();
+    if let Err(e) = client.post(backend::url("purge")).send().await {
+        log::warn!("⚠️ Could not reach backend to purge retained audio: {}", e);
     }
 
+    retention::clear_logs(&app).map_err(|e| format!("Failed to delete logs: {}", e))?;
+
+    log::info!("🧹 All stored data purged");
+    Ok(())
+}
+
+// Recording limit commands
+#[tauri::command]
+async fn set_recording_limits(
+    limits: recording_limits::RecordingLimits,
+    app: AppHandle,
+    state: State<'_, AppState>
+) -> Result<(), String> {
+    log::info!("⚙️ Recording limits: {:?}", limits);
+    storage::save_json(&app, recording_limits::CONFIG_FILE, &limits).map_err(|e| e.to_string())?;
+    *state.recording_limits.lock().await = limits;
+    Ok(())
+}
+
+#[tauri::command]
+async fn get_recording_limits(state: State<'_, AppState>) -> Result<recording_limits::RecordingLimits, String> {
+    Ok(state.recording_limits.lock().await.clone())
+}
+
+// Double-tap trigger commands
+#[tauri::command]
+async fn set_double_tap_settings(
+    settings: double_tap::DoubleTapSettings,
+    app: AppHandle,
+    state: State<'_, AppState>
+) -> Result<(), String> {
+    log::info!("⚙️ Double-tap settings: {:?}", settings);
+    double_tap::apply(&app, &settings)?;
+    storage::save_json(&app, double_tap::CONFIG_FILE, &settings).map_err(|e| e.to_string())?;
+    *state.double_tap.lock().await = settings;
+    Ok(())
+}
+
+#[tauri::command]
+async fn get_double_tap_settings(state: State<'_, AppState>) -> Result<double_tap::DoubleTapSettings, String> {
+    Ok(state.double_tap.lock().await.clone())
+}
+
+// Win+H commands
+#[tauri::command]
+async fn set_win_h_settings(settings: win_h::WinHSettings, app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    log::info!("⚙️ Win+H settings: {:?}", settings);
+    win_h::apply(&app, &settings)?;
+    storage::save_json(&app, win_h::CONFIG_FILE, &settings).map_err(|e| e.to_string())?;
+    *state.win_h.lock().await = settings;
+    Ok(())
+}
+
+#[tauri::command]
+async fn get_win_h_settings(state: State<'_, AppState>) -> Result<win_h::WinHSettings, String> {
+    Ok(state.win_h.lock().await.clone())
+}
+
+// Meeting capture commands
+#[tauri::command]
+async fn set_meeting_capture_settings(
+    settings: meeting::MeetingCaptureSettings,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    log::info!("⚙️ Meeting capture settings: {:?}", settings);
+    storage::save_json(&app, meeting::CONFIG_FILE, &settings).map_err(|e| e.to_string())?;
+    *state.meeting_capture.lock().await = settings;
+    Ok(())
+}
+
+#[tauri::command]
+async fn get_meeting_capture_settings(state: State<'_, AppState>) -> Result<meeting::MeetingCaptureSettings, String> {
+    Ok(state.meeting_capture.lock().await.clone())
+}
+
+// Keep-awake commands
+#[tauri::command]
+async fn set_keep_awake_settings(settings: keep_awake::KeepAwakeSettings, app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    log::info!("⚙️ Keep-awake settings: {:?}", settings);
+    storage::save_json(&app, keep_awake::CONFIG_FILE, &settings).map_err(|e| e.to_string())?;
+    *state.keep_awake.lock().await = settings;
+    Ok(())
+}
+
+#[tauri::command]
+async fn get_keep_awake_settings(state: State<'_, AppState>) -> Result<keep_awake::KeepAwakeSettings, String> {
+    Ok(state.keep_awake.lock().await.clone())
+}
+
+// Ducking commands
+#[tauri::command]
+async fn set_ducking_settings(settings: ducking::DuckingSettings, app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    log::info!("⚙️ Ducking settings: {:?}", settings);
+    storage::save_json(&app, ducking::CONFIG_FILE, &settings).map_err(|e| e.to_string())?;
+    *state.ducking.lock().await = settings;
+    Ok(())
+}
+
+#[tauri::command]
+async fn get_ducking_settings(state: State<'_, AppState>) -> Result<ducking::DuckingSettings, String> {
+    Ok(state.ducking.lock().await.clone())
+}
+
+// Mic check commands
+#[tauri::command]
+async fn set_mic_check_settings(settings: mic_check::MicCheckSettings, app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    log::info!("⚙️ Mic check settings: {:?}", settings);
+    storage::save_json(&app, mic_check::CONFIG_FILE, &settings).map_err(|e| e.to_string())?;
+    *state.mic_check.lock().await = settings;
+    Ok(())
+}
+
+#[tauri::command]
+async fn get_mic_check_settings(state: State<'_, AppState>) -> Result<mic_check::MicCheckSettings, String> {
+    Ok(state.mic_check.lock().await.clone())
+}
+
+// Per-app hotkey blocklist commands
+#[tauri::command]
+async fn set_hotkey_blocklist(processes: Vec<String>, app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    let processes: Vec<String> = processes
+        .into_iter()
+        .map(|p| p.trim().to_string())
+        .filter(|p| !p.is_empty())
+        .collect();
+    log::info!("⚙️ Hotkey blocklist: {:?}", processes);
+    storage::save_json(&app, hotkey_blocklist::CONFIG_FILE, &processes).map_err(|e| e.to_string())?;
+    *state.hotkey_blocklist.lock().await = processes;
     Ok(())
 }
 
+#[tauri::command]
+async fn get_hotkey_blocklist(state: State<'_, AppState>) -> Result<Vec<String>, String> {
+    Ok(state.hotkey_blocklist.lock().await.clone())
+}
+
+#[tauri::command]
+async fn get_shortcuts(state: State<'_, AppState>) -> Result<HashMap<String, String>, String> {
+    Ok(state.shortcuts.lock().await.clone())
+}
+
 #[tauri::command]
 async fn get_toggle_shortcut(state: State<'_, AppState>) -> Result<String, String> {
-    Ok(state.toggle_shortcut.lock().await.clone())
+    Ok(state.shortcuts.lock().await["toggle"].clone())
 }
 
 #[tauri::command]
 async fn get_cancel_shortcut(state: State<'_, AppState>) -> Result<String, String> {
-    Ok(state.cancel_shortcut.lock().await.clone())
+    Ok(state.shortcuts.lock().await["cancel"].clone())
 }
 
 // Stub commands for settings that don't need backend implementation yet
@@ -686,112 +3669,231 @@ async fn check_for_updates() -> Result<String, String> {
 }
 
 // Tray menu
-fn create_tray_menu(app: &AppHandle) -> Result<Menu<tauri::Wry>, tauri::Error> {
-    let toggle = MenuItem::with_id(app, "toggle", "🎙️ Start/Stop Recording (F9)", true, None::<&str>)?;
-    let settings = MenuItem::with_id(app, "settings", "⚙️ Settings", true, None::<&str>)?;
-    let quit = MenuItem::with_id(app, "quit", "❌ Quit", true, None::<&str>)?;
-    Menu::with_items(app, &[&toggle, &settings, &quit])
+// Launch the backend sidecar (the caller keeps the handle in `AppState::backend_child`)
+pub(crate) fn spawn_backend(app: &AppHandle) -> Result<tauri_plugin_shell::process::CommandChild, String> {
+    use tauri_plugin_shell::ShellExt;
+
+    let mut sidecar_command = app
+        .shell()
+        .sidecar("whisper-backend")
+        .map_err(|e| format!("Failed to create sidecar command: {}", e))?;
+    // Tell the backend where to keep models and GPU libraries in portable mode
+    if let Some(base) = storage::portable_base() {
+        sidecar_command = sidecar_command.env("WHISPER4WINDOWS_DATA_DIR", base);
+    }
+    // A policy may point it at shared models and keep it offline
+    for (key, value) in policy::backend_env() {
+        sidecar_command = sidecar_command.env(key, value);
+    }
+
+    // Its own port, so backends of other users on the same machine don't collide
+    sidecar_command = sidecar_command.env("WHISPER4WINDOWS_PORT", backend::port().to_string());
+    if let Some(session) = backend::session_id() {
+        sidecar_command = sidecar_command.env("WHISPER4WINDOWS_SESSION", session.to_string());
+    }
+
+    let (events, child) = sidecar_command.spawn().map_err(|e| e.to_string())?;
+    backend::watch_bind(app.clone(), events);
+    if let Err(e) = backend::tie_to_app(child.pid()) {
+        log::warn!("⚠️ Backend may outlive a crash: {}", e);
+    }
+    Ok(child)
+}
+
+fn create_tray_menu(
+    app: &AppHandle,
+    privacy: bool,
+    model: &str,
+    language: &str,
+    installed_models: Option<&[String]>,
+    custom: &[custom_models::CustomModel],
+    pinned_entries: &[history::HistoryEntry],
+) -> Result<Menu<tauri::Wry>, tauri::Error> {
+    let label = |icon: &str, key: &str| format!("{} {}", icon, locale::tr(key));
+    let toggle = MenuItem::with_id(app, "toggle", label("🎙️", "tray.toggle"), true, None::<&str>)?;
+    let models = quick_switch::model_submenu(app, model, installed_models, custom)?;
+    let languages = quick_switch::language_submenu(app, language, custom_models::is_english_only(custom, model))?;
+    let copy_last = MenuItem::with_id(app, "copy_last", label("📋", "tray.copy_last"), true, None::<&str>)?;
+    let pinned = pinned::submenu(app, pinned_entries)?;
+    let scratchpad = MenuItem::with_id(app, "scratchpad", label("📝", "tray.scratchpad"), true, None::<&str>)?;
+    let transcribe_file = MenuItem::with_id(app, "transcribe_file", label("📂", "tray.transcribe_file"), true, None::<&str>)?;
+    let transcribe_clipboard =
+        MenuItem::with_id(app, "transcribe_clipboard", label("📎", "tray.transcribe_clipboard"), true, None::<&str>)?;
+    let history = MenuItem::with_id(app, "history", label("📜", "tray.history"), true, None::<&str>)?;
+    let preload = MenuItem::with_id(app, "preload", label("🔥", "tray.preload"), true, None::<&str>)?;
+    let privacy = CheckMenuItem::with_id(app, "privacy", label("🔒", "tray.privacy"), true, privacy, None::<&str>)?;
+    let settings = MenuItem::with_id(app, "settings", label("⚙️", "tray.settings"), true, None::<&str>)?;
+    let quit = MenuItem::with_id(app, "quit", label("❌", "tray.quit"), true, None::<&str>)?;
+    Menu::with_items(
+        app,
+        &[
+            &toggle,
+            &copy_last,
+            &pinned,
+            &scratchpad,
+            &transcribe_file,
+            &transcribe_clipboard,
+            &history,
+            &models,
+            &languages,
+            &preload,
+            &privacy,
+            &settings,
+            &quit,
+        ],
+    )
 }
 
 fn handle_tray_event(app: &AppHandle, event: TrayIconEvent) {
     // Handle both Up and Down states to be more reliable
     // Use Down for immediate feedback
-    match event {
-        TrayIconEvent::Click {
-            button: MouseButton::Left,
-            button_state: MouseButtonState::Down,
-            ..
-        } => {
-            if let Some(win) = app.get_webview_window("main") {
-                let _ = if win.is_visible().unwrap_or(false) {
-                    win.hide()
-                } else {
-                    win.show().and_then(|_| win.set_focus())
-                };
-            }
+    if let TrayIconEvent::Click {
+        button: MouseButton::Left,
+        button_state: MouseButtonState::Down,
+        ..
+    } = event
+    {
+        if let Some(win) = app.get_webview_window("main") {
+            let _ = if win.is_visible().unwrap_or(false) {
+                win.hide()
+            } else {
+                win.show().and_then(|_| win.set_focus())
+            };
         }
-        _ => {}
     }
 }
 
 fn handle_menu_event(app: &AppHandle, event: tauri::menu::MenuEvent) {
     log::info!("📋 Menu clicked: {}", event.id.as_ref());
+    run_tray_action(app, event.id.as_ref());
+}
 
-    match event.id.as_ref() {
+// Run a tray menu item by id (also used by the taskbar jump list)
+pub(crate) fn run_tray_action(app: &AppHandle, id: &str) {
+    match id {
         "toggle" => {
             let app_clone = app.clone();
             tauri::async_runtime::spawn(async move {
                 let _ = cmd_toggle_recording(app_clone.clone(), app_clone.state()).await;
             });
         }
-        "settings" => {
-            if let Some(win) = app.get_webview_window("main") {
-                let _ = win.show().and_then(|_| win.set_focus());
+        "copy_last" => {
+            let app_clone = app.clone();
+            tauri::async_runtime::spawn(async move {
+                if let Err(e) = cmd_copy_last(app_clone.clone(), app_clone.state()).await {
+                    log::error!("❌ Copy last failed: {}", e);
+                }
+            });
+        }
+        "scratchpad" => {
+            if let Err(e) = scratchpad::show(app, true) {
+                log::error!("❌ Failed to show scratchpad: {}", e);
             }
         }
-        "quit" => {
+        "transcribe_file" => file_transcription::start(app),
+        "transcribe_clipboard" => file_transcription::start_from_clipboard(app),
+        "history" => navigation::open_settings(app, Some("history")),
+        "privacy" => {
             let app_clone = app.clone();
             tauri::async_runtime::spawn(async move {
-                let state: tauri::State<AppState> = app_clone.state();
-                if let Some(child) = state.backend_child.lock().await.take() {
-                    log::info!("🛑 Killing backend process...");
-                    match child.kill() {
-                        Ok(_) => {
-                            log::info!("✅ Backend process kill signal sent");
-                            // Give it a moment to terminate
-                            tokio::time::sleep(tokio::time::Duration::from_millis(200)).await;
-                        }
-                        Err(e) => {
-                            log::warn!("⚠️ Failed to kill backend: {}", e);
-                        }
-                    }
+                privacy::toggle(&app_clone).await;
+            });
+        }
+        "preload" => {
+            let app_clone = app.clone();
+            tauri::async_runtime::spawn(async move {
+                match preload_model(app_clone.state()).await {
+                    Ok(model) => show_toast(
+                        &app_clone,
+                        &locale::tr("toast.model_ready"),
+                        &locale::tr_with("toast.model_ready_body", &[("model", &model.model)]),
+                    ),
+                    Err(e) => show_toast_linked(&app_clone, &locale::tr("toast.preload_failed"), &e, "device"),
                 }
-                log::info!("👋 Exiting application");
-                app_clone.exit(0);
             });
         }
+        id if id.starts_with(quick_switch::MODEL_ID_PREFIX) => {
+            let model = id[quick_switch::MODEL_ID_PREFIX.len()..].to_string();
+            let app_clone = app.clone();
+            tauri::async_runtime::spawn(async move {
+                quick_switch::select_model(&app_clone, &model).await;
+            });
+        }
+        id if id.starts_with(pinned::ID_PREFIX) => {
+            let Ok(entry_id) = id[pinned::ID_PREFIX.len()..].parse::<i64>() else { return };
+            let app_clone = app.clone();
+            tauri::async_runtime::spawn(async move {
+                pinned::insert(&app_clone, entry_id).await;
+            });
+        }
+        id if id.starts_with(quick_switch::LANGUAGE_ID_PREFIX) => {
+            let language = id[quick_switch::LANGUAGE_ID_PREFIX.len()..].to_string();
+            let app_clone = app.clone();
+            tauri::async_runtime::spawn(async move {
+                quick_switch::select_language(&app_clone, &language).await;
+            });
+        }
+        "settings" => navigation::open_settings(app, None),
+        "quit" => quit::request(app),
         _ => {}
     }
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    if elevation::run_helper_if_requested() {
+        return;
+    }
+
     tauri::Builder::default()
         .plugin(tauri_plugin_shell::init())
-        .plugin(tauri_plugin_single_instance::init(|app, _args, _cwd| {
-            log::info!("🔒 Single instance check - app already running, focusing existing window");
-            // Bring main window to front if already running
-            if let Some(window) = app.get_webview_window("main") {
-                let _ = window.set_focus();
+        .plugin(tauri_plugin_notification::init())
+        .plugin(tauri_plugin_single_instance::init(|app, args, cwd| {
+            // A jump list task, settings deep link, or Explorer's context menu starts a second instance with its action
+            if jump_list::run(app, &args) || navigation::run(app, &args) || shell_menu::run(&args) {
+                return;
             }
+            if provision::requested(&args) {
+                let app = app.clone();
+                tauri::async_runtime::spawn(async move {
+                    provision::run(&app, &args, std::path::Path::new(&cwd)).await;
+                });
+                return;
+            }
+            log::info!("🔒 Single instance check - app already running, focusing existing window");
+            // Bring main window to front if already running (it may be hidden in the tray)
+            main_window::bring_to_front(app);
         }))
         .setup(|app| {
             use tauri::WebviewWindowBuilder;
 
-            // Logging
+            // Logging (next to the executable in portable mode)
+            let log_target = match storage::portable_log_dir() {
+                Some(path) => tauri_plugin_log::TargetKind::Folder { path, file_name: Some("app".to_string()) },
+                None => tauri_plugin_log::TargetKind::LogDir { file_name: Some("app".to_string()) },
+            };
             app.handle().plugin(
                 tauri_plugin_log::Builder::default()
                     .level(log::LevelFilter::Info)
                     .target(tauri_plugin_log::Target::new(tauri_plugin_log::TargetKind::Stdout))
-                    .target(tauri_plugin_log::Target::new(tauri_plugin_log::TargetKind::LogDir { file_name: Some("app".to_string()) }))
+                    .target(tauri_plugin_log::Target::new(log_target))
+                    // Rotated logs are kept and deleted by age under the retention policy
+                    .max_file_size(1_000_000)
+                    .rotation_strategy(tauri_plugin_log::RotationStrategy::KeepAll)
                     .build(),
             )?;
 
             log::info!("🚀 Whisper4Windows starting...");
+            crash_report::install(app.handle());
+            if let Some(base) = storage::portable_base() {
+                log::info!("💼 Portable mode: storing data in {}", base.display());
+            }
 
             // Start backend sidecar
-            log::info!("🔧 Starting backend server...");
+            log::info!("🔧 Starting backend server ({})...", backend::describe());
             use tauri::Manager;
-            use tauri_plugin_shell::ShellExt;
 
-            let sidecar_command = app.app_handle()
-                .shell()
-                .sidecar("whisper-backend")
-                .expect("Failed to create sidecar command");
-
-            let (_rx, child) = sidecar_command
-                .spawn()
-                .expect("Failed to spawn backend sidecar");
+            let child = spawn_backend(app.handle()).expect("Failed to spawn backend sidecar");
 
             // Store the child process in state so we can kill it on app exit
             let state: tauri::State<AppState> = app.state();
@@ -819,12 +3921,165 @@ pub fn run() {
 
             log::info!("✅ Recording window created");
 
+            // Load persisted overlay placement, snippets, and model/language, and watch for the user dragging the overlay
+            let state: tauri::State<AppState> = app.state();
+            let overlay_config: overlay::OverlayConfig = storage::load_json(app.handle(), overlay::CONFIG_FILE);
+            let snippet_store: snippets::SnippetStore = storage::load_json(app.handle(), snippets::CONFIG_FILE);
+            let send_targets: send_target::SendTargetStore = storage::load_json(app.handle(), send_target::CONFIG_FILE);
+            let template_store: output_template::TemplateStore = storage::load_json(app.handle(), output_template::CONFIG_FILE);
+            let code_mode_settings: code_mode::CodeModeSettings = storage::load_json(app.handle(), code_mode::CONFIG_FILE);
+            let send_enter_settings: send_enter::SendEnterSettings = storage::load_json(app.handle(), send_enter::CONFIG_FILE);
+            let injection_settings: InjectionSettings = storage::load_json(app.handle(), InjectionSettings::CONFIG_FILE);
+            let output_mode: OutputMode = storage::load_json(app.handle(), OutputMode::CONFIG_FILE);
+            let keymap = load_keymap(app.handle());
+            let continuous_dictation: bool = storage::load_json(app.handle(), CONTINUOUS_DICTATION_FILE);
+            let diarization: bool = storage::load_json(app.handle(), DIARIZATION_FILE);
+            let queue_next_recording: bool = storage::load_json(app.handle(), QUEUE_NEXT_RECORDING_FILE);
+            let blocklisted_processes: Vec<String> = storage::load_json(app.handle(), hotkey_blocklist::CONFIG_FILE);
+            let ducking_settings: ducking::DuckingSettings = storage::load_json(app.handle(), ducking::CONFIG_FILE);
+            let keep_awake_settings: keep_awake::KeepAwakeSettings = storage::load_json(app.handle(), keep_awake::CONFIG_FILE);
+            let mic_check_settings: mic_check::MicCheckSettings = storage::load_json(app.handle(), mic_check::CONFIG_FILE);
+            let recording_limit_settings: recording_limits::RecordingLimits = storage::load_json(app.handle(), recording_limits::CONFIG_FILE);
+            let privacy_settings: privacy::PrivacySettings = storage::load_json(app.handle(), privacy::CONFIG_FILE);
+            let audio_processing: AudioProcessingSettings = storage::load_json(app.handle(), AudioProcessingSettings::CONFIG_FILE);
+            let multilingual_settings: MultilingualSettings = storage::load_json(app.handle(), MultilingualSettings::CONFIG_FILE);
+            let stats_settings: stats::StatsSettings = storage::load_json(app.handle(), stats::CONFIG_FILE);
+            let casing_settings: casing::CasingSettings = storage::load_json(app.handle(), casing::CONFIG_FILE);
+            let regional_settings: regional_format::RegionalSettings = storage::load_json(app.handle(), regional_format::CONFIG_FILE);
+            let output_file_settings: output_files::OutputFileSettings = storage::load_json(app.handle(), output_files::CONFIG_FILE);
+            let formatting_profiles: FormattingProfiles = storage::load_json(app.handle(), formatting::CONFIG_FILE);
+            let microphone_settings: microphones::MicrophoneSettings = storage::load_json(app.handle(), microphones::CONFIG_FILE);
+            let prebuffer_settings: prebuffer::PrebufferSettings = storage::load_json(app.handle(), prebuffer::CONFIG_FILE);
+            let scratchpad_text: scratchpad::Scratchpad = storage::load_json(app.handle(), scratchpad::CONFIG_FILE);
+            let journal_settings: journal::JournalSettings = storage::load_json(app.handle(), journal::CONFIG_FILE);
+            let file_transcription_settings: file_transcription::FileTranscriptionSettings =
+                storage::load_json(app.handle(), file_transcription::CONFIG_FILE);
+            let watch_folder_settings: watch_folder::WatchFolderSettings =
+                storage::load_json(app.handle(), watch_folder::CONFIG_FILE);
+            let export_settings: history_export::ExportSettings =
+                storage::load_json(app.handle(), history_export::CONFIG_FILE);
+            let session_settings: sessions::SessionSettings = storage::load_json(app.handle(), sessions::CONFIG_FILE);
+            let meeting_settings: meeting::MeetingCaptureSettings = storage::load_json(app.handle(), meeting::CONFIG_FILE);
+            let mut caption_settings: caption_server::CaptionServerSettings =
+                storage::load_json(app.handle(), caption_server::CONFIG_FILE);
+            // Settings from before tokens were required get one now
+            if caption_server::ensure_token(&mut caption_settings).unwrap_or(false) {
+                if let Err(e) = storage::save_json(app.handle(), caption_server::CONFIG_FILE, &caption_settings) {
+                    log::error!("❌ Failed to save the caption server token: {}", e);
+                }
+            }
+            let retention_settings: retention::RetentionSettings = storage::load_json(app.handle(), retention::CONFIG_FILE);
+            let model_memory_settings: model_memory::ModelMemorySettings =
+                storage::load_json(app.handle(), model_memory::CONFIG_FILE);
+            let slot_settings: model_slots::ModelSlots = storage::load_json(app.handle(), model_slots::CONFIG_FILE);
+            let custom_model_store: custom_models::CustomModelStore =
+                storage::load_json(app.handle(), custom_models::CONFIG_FILE);
+            let advanced_settings: decoding::AdvancedSettings = storage::load_json(app.handle(), decoding::CONFIG_FILE);
+            let sound_settings: sounds::SoundSettings = storage::load_json(app.handle(), sounds::CONFIG_FILE);
+            let accessibility_settings: accessibility::AccessibilitySettings =
+                storage::load_json(app.handle(), accessibility::CONFIG_FILE);
+            // Before the tray menu and jump list are built with its texts
+            let locale_settings: locale::LocaleSettings = storage::load_json(app.handle(), locale::CONFIG_FILE);
+            locale::apply(&locale_settings);
+            shell_menu::apply(file_transcription_settings.explorer_menu);
+            let tray_icon_settings: tray_icon::TrayIconSettings = storage::load_json(app.handle(), tray_icon::CONFIG_FILE);
+            *state.focus_assist.lock().unwrap() = storage::load_json(app.handle(), focus_assist::CONFIG_FILE);
+            let main_window_settings: main_window::MainWindowSettings = storage::load_json(app.handle(), main_window::CONFIG_FILE);
+            tauri::async_runtime::block_on(async {
+                *state.main_window.lock().await = main_window_settings;
+                *state.overlay.lock().await = overlay_config;
+                *state.snippets.lock().await = snippet_store;
+                *state.send_targets.lock().await = send_targets;
+                *state.output_templates.lock().await = template_store;
+                *state.code_mode.lock().await = code_mode_settings;
+                *state.send_enter.lock().await = send_enter_settings;
+                *state.injection.lock().await = injection_settings;
+                *state.output_mode.lock().await = output_mode;
+                *state.shortcuts.lock().await = keymap;
+                *state.continuous_dictation.lock().await = continuous_dictation;
+                *state.diarization.lock().await = diarization;
+                *state.queue_next_recording.lock().await = queue_next_recording;
+                *state.hotkey_blocklist.lock().await = blocklisted_processes;
+                *state.ducking.lock().await = ducking_settings;
+                *state.keep_awake.lock().await = keep_awake_settings;
+                *state.mic_check.lock().await = mic_check_settings;
+                *state.recording_limits.lock().await = recording_limit_settings;
+                *state.privacy_settings.lock().await = privacy_settings;
+                *state.audio_processing.lock().await = audio_processing;
+                *state.multilingual.lock().await = multilingual_settings;
+                *state.stats_settings.lock().await = stats_settings;
+                *state.casing.lock().await = casing_settings;
+                *state.regional_formats.lock().await = regional_settings;
+                *state.output_files.lock().await = output_file_settings;
+                *state.formatting.lock().await = formatting_profiles;
+                *state.microphones.lock().await = microphone_settings;
+                *state.prebuffer.lock().await = prebuffer_settings;
+                *state.scratchpad.lock().await = scratchpad_text;
+                quick_switch::load(app.handle()).await;
+                *state.journal.lock().await = journal_settings;
+                *state.file_transcription.lock().await = file_transcription_settings;
+                *state.watch_folder.lock().await = watch_folder_settings;
+                *state.history_export.lock().await = export_settings;
+                *state.sessions.lock().await = session_settings;
+                *state.meeting_capture.lock().await = meeting_settings;
+                *state.retention.lock().await = retention_settings;
+                *state.model_memory.lock().await = model_memory_settings;
+                *state.model_slots.lock().await = slot_settings;
+                *state.custom_models.lock().await = custom_model_store;
+                *state.advanced.lock().await = advanced_settings;
+                *state.sounds.lock().await = sound_settings;
+                *state.accessibility.lock().await = accessibility_settings;
+                *state.locale.lock().await = locale_settings;
+                *state.tray_icon.lock().await = tray_icon_settings.clone();
+                if let Err(e) = caption_server::apply(&state, caption_settings).await {
+                    log::error!("❌ Caption server not started: {}", e);
+                }
+                policy::apply(app.handle()).await;
+            });
+
+            // Open the history and statistics databases
+            match storage::config_dir(app.handle()) {
+                Ok(dir) => {
+                    let history_store = history::HistoryStore::open(&dir.join(history::DB_FILE))
+                        .map_err(|e| log::error!("❌ Failed to open history database: {}", e))
+                        .ok();
+                    let stats_store = stats::StatsStore::open(&dir.join(stats::DB_FILE))
+                        .map_err(|e| log::error!("❌ Failed to open statistics database: {}", e))
+                        .ok();
+                    log::info!("🗂️ Databases in {}", dir.display());
+                    tauri::async_runtime::block_on(async {
+                        *state.history.lock().await = history_store;
+                        *state.stats.lock().await = stats_store;
+                    });
+                }
+                Err(e) => log::error!("❌ {}", e),
+            }
+
+            if let Some(recording_window) = app.get_webview_window("recording") {
+                let tracker = state.overlay_tracker.clone();
+                // Window events come on the event loop thread, outside the async runtime, so blocking is fine
+                recording_window.on_window_event(move |event| {
+                    if let tauri::WindowEvent::Moved(position) = event {
+                        tracker.blocking_lock().on_moved(*position);
+                    }
+                });
+            }
+
             // Tray
-            let menu = create_tray_menu(app.handle())?;
-            let tray = TrayIconBuilder::new()
+            let (model, language, custom, pinned_entries) = tauri::async_runtime::block_on(async {
+                (
+                    state.selected_model.lock().await.clone(),
+                    state.selected_language.lock().await.clone(),
+                    state.custom_models.lock().await.models.clone(),
+                    pinned::entries(&state).await,
+                )
+            });
+            let menu = create_tray_menu(app.handle(), false, &model, &language, None, &custom, &pinned_entries)?;
+            let tray = TrayIconBuilder::with_id("main")
+                .tooltip("Whisper4Windows")
                 .menu(&menu)
-                .icon(app.default_window_icon().unwrap().clone())
-                .on_menu_event(|app, event| handle_menu_event(app, event))
+                .icon(tray_icon::icon(&tray_icon_settings, tray_icon::IconState::Idle))
+                .on_menu_event(handle_menu_event)
                 .build(app)?;
 
             let app_handle = app.handle().clone();
@@ -832,20 +4087,12 @@ pub fn run() {
 
             log::info!("✅ Tray icon created");
 
-            // Intercept main window close event to hide instead of destroy
-            if let Some(main_window) = app.get_webview_window("main") {
-                let app_handle_close = app.handle().clone();
-                main_window.on_window_event(move |event| {
-                    if let tauri::WindowEvent::CloseRequested { api, .. } = event {
-                        // Prevent window from closing
-                        api.prevent_close();
-                        // Hide it instead
-                        if let Some(win) = app_handle_close.get_webview_window("main") {
-                            let _ = win.hide();
-                        }
-                    }
-                });
-            }
+            jump_list::install();
+            let args: Vec<String> = std::env::args().collect();
+            jump_list::run(app.handle(), &args);
+            shell_menu::spawn_worker(app.handle().clone());
+            shell_menu::run(&args);
+            navigation::remember_startup_section(&args);
 
             // Global shortcuts handler
             let app_handle_hotkey = app.handle().clone();
@@ -857,35 +4104,20 @@ pub fn run() {
                         // Only trigger on key press, not release
                         if event.state == ShortcutState::Pressed {
                             let app_clone = app_handle_hotkey.clone();
-                            let shortcut_str = format!("{:?}", shortcut); // Format outside async block
+                            let shortcut = *shortcut;
 
                             tauri::async_runtime::spawn(async move {
                                 let state: tauri::State<AppState> = app_clone.state();
-                                let toggle_sc = state.toggle_shortcut.lock().await.clone();
-                                let cancel_sc = state.cancel_shortcut.lock().await.clone();
-
-                                // Check if this is the cancel shortcut
-                                if let Some(parsed_cancel) = parse_shortcut(&cancel_sc) {
-                                    let cancel_str = format!("{:?}", parsed_cancel);
-                                    if shortcut_str == cancel_str {
-                                        log::info!("🔥 CANCEL SHORTCUT TRIGGERED ({})", cancel_sc);
-                                        // Only cancel if recording window is visible
-                                        if let Some(win) = app_clone.get_webview_window("recording") {
-                                            if win.is_visible().unwrap_or(false) {
-                                                let _ = cmd_cancel_recording(app_clone.clone()).await;
-                                                return;
-                                            }
-                                        }
-                                    }
-                                }
+                                let action = state
+                                    .shortcuts
+                                    .lock()
+                                    .await
+                                    .iter()
+                                    .find(|(_, s)| parse_shortcut(s) == Some(shortcut))
+                                    .map(|(action, _)| action.clone());
 
-                                // Check if this is the toggle shortcut
-                                if let Some(parsed_toggle) = parse_shortcut(&toggle_sc) {
-                                    let toggle_str = format!("{:?}", parsed_toggle);
-                                    if shortcut_str == toggle_str {
-                                        log::info!("🔥 TOGGLE SHORTCUT TRIGGERED ({})", toggle_sc);
-                                        let _ = cmd_toggle_recording(app_clone.clone(), app_clone.state()).await;
-                                    }
+                                if let Some(action) = action {
+                                    run_hotkey_action(app_clone.clone(), action);
                                 }
                             });
                         }
@@ -895,34 +4127,93 @@ pub fn run() {
 
             // Register initial shortcuts
             let state: tauri::State<AppState> = app.state();
-            let (initial_toggle, initial_cancel) = tauri::async_runtime::block_on(async {
-                (
-                    state.toggle_shortcut.lock().await.clone(),
-                    state.cancel_shortcut.lock().await.clone()
-                )
-            });
+            let initial_keymap = tauri::async_runtime::block_on(async { state.shortcuts.lock().await.clone() });
 
-            // Register toggle shortcut
-            if let Some(toggle_sc) = parse_shortcut(&initial_toggle) {
-                if let Err(e) = app.global_shortcut().register(toggle_sc) {
-                    log::error!("❌ Failed to register toggle shortcut {}: {}", initial_toggle, e);
+            for (action, _) in HOTKEY_ACTIONS {
+                let shortcut = &initial_keymap[*action];
+                if shortcut.is_empty() || !hotkey_active(action, false) {
+                    continue;
+                }
+                if let Err(e) = register_trigger(app.handle(), action, shortcut) {
+                    notify_shortcut_conflict(app.handle(), action, shortcut, &e);
                 } else {
-                    log::info!("✅ Toggle shortcut registered: {}", initial_toggle);
+                    log::info!("✅ {} shortcut registered: {}", action, shortcut);
                 }
-            } else {
-                log::error!("❌ Failed to parse initial toggle shortcut: {}", initial_toggle);
             }
 
-            // Register cancel shortcut
-            if let Some(cancel_sc) = parse_shortcut(&initial_cancel) {
-                if let Err(e) = app.global_shortcut().register(cancel_sc) {
-                    log::error!("❌ Failed to register cancel shortcut {}: {}", initial_cancel, e);
-                } else {
-                    log::info!("✅ Cancel shortcut registered: {}", initial_cancel);
-                }
-            } else {
-                log::error!("❌ Failed to parse initial cancel shortcut: {}", initial_cancel);
+            // Settings from a deployment script, before the window shows: after the shortcuts, which
+            // they may change
+            let cwd = std::env::current_dir().unwrap_or_default();
+            tauri::async_runtime::block_on(provision::run(app.handle(), &args, &cwd));
+
+            // The window starts hidden so starting in the tray doesn't flash it
+            let window_settings = tauri::async_runtime::block_on(async { state.main_window.lock().await.clone() });
+            main_window::show_at_startup(app.handle(), &window_settings, navigation::section_from_args(&args).is_some());
+
+            // Mark models that still need downloading in the tray's model menu
+            let app_handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                quick_switch::refresh_installed_models(&app_handle).await;
+            });
+
+            // Intercept main window close event to hide or quit instead of destroying the window
+            if let Some(window) = app.get_webview_window("main") {
+                let app_handle_close = app.handle().clone();
+                window.on_window_event(move |event| {
+                    if let tauri::WindowEvent::CloseRequested { api, .. } = event {
+                        api.prevent_close();
+                        main_window::close_requested(&app_handle_close);
+                    }
+                });
+            }
+
+            // Offer to send diagnostics if the app crashed last time
+            if let Some(report) = crash_report::pending(app.handle()) {
+                log::info!("💥 Crashed last time: {}", report.reason);
+                show_toast(app.handle(), &locale::tr("toast.crashed"), &locale::tr("toast.crashed_body"));
+            }
+
+            // Offer to transcribe a recording the last crash cut off
+            crash_recovery::spawn_check(app.handle().clone());
+
+            // Delete history, retained audio, and logs past their retention period, now and daily
+            retention::spawn(app.handle().clone());
+
+            // Tell the backend when to unload the model, and preload it if asked to
+            model_memory::spawn_apply(app.handle().clone());
+            prebuffer::spawn_apply(app.handle().clone());
+
+            // Release the hotkeys while blocklisted apps are focused
+            hotkey_blocklist::spawn_watcher(app.handle().clone());
+
+            // Cancel recordings on lock/sleep; restore hotkeys and the backend afterwards
+            system_events::spawn_listener(app.handle().clone());
+
+            // Offer to capture Teams/Zoom/Meet calls in the apps opted in
+            meeting::spawn_watcher(app.handle().clone());
+
+            // Transcribe audio files dropped into the watch folder
+            watch_folder::spawn_watcher(app.handle().clone());
+
+            // Foot pedals, other HID buttons, and gamepads (listening only starts once something is bound)
+            let hid_bindings: hid_trigger::HidBindings = storage::load_json(app.handle(), hid_trigger::CONFIG_FILE);
+            hid_trigger::set_bindings(app.handle(), hid_bindings.bindings);
+            let gamepad_bindings: gamepad::GamepadBindings = storage::load_json(app.handle(), gamepad::CONFIG_FILE);
+            gamepad::set_bindings(app.handle(), gamepad_bindings.bindings);
+
+            // The double-tap trigger, if it's on
+            let double_tap_settings: double_tap::DoubleTapSettings = storage::load_json(app.handle(), double_tap::CONFIG_FILE);
+            if let Err(e) = double_tap::apply(app.handle(), &double_tap_settings) {
+                log::error!("❌ Double-tap trigger not installed: {}", e);
+            }
+            tauri::async_runtime::block_on(async { *state.double_tap.lock().await = double_tap_settings });
+
+            // Win+H, if it's taken over from Windows voice typing
+            let win_h_settings: win_h::WinHSettings = storage::load_json(app.handle(), win_h::CONFIG_FILE);
+            if let Err(e) = win_h::apply(app.handle(), &win_h_settings) {
+                log::error!("❌ Win+H not taken over: {}", e);
             }
+            tauri::async_runtime::block_on(async { *state.win_h.lock().await = win_h_settings });
 
             log::info!("💡 Press F9 to start/stop recording");
             Ok(())
@@ -934,14 +4225,181 @@ pub fn run() {
             cmd_stop_recording,
             cmd_cancel_recording,
             cmd_toggle_recording,
+            cmd_pause_recording,
+            cmd_resume_recording,
+            cmd_paste_last,
+            cmd_copy_last,
+            cmd_read_last,
+            cmd_retry_last,
+            recover_stuck_transcription,
             set_model_and_device,
+            get_model,
+            get_device,
+            get_backend_url,
             set_microphone_device,
             get_microphone_device,
+            list_microphones,
+            get_current_microphone,
+            set_microphone_settings,
+            get_microphone_settings,
+            set_prebuffer_settings,
+            get_prebuffer_settings,
             set_clipboard_paste,
             get_clipboard_paste,
+            set_output_mode,
+            get_output_mode,
+            set_overlay_settings,
+            get_overlay_settings,
+            reset_overlay_position,
+            list_monitors,
             set_language,
+            set_recording_language,
+            set_multilingual_settings,
+            get_multilingual_settings,
             get_language,
+            set_formatting_settings,
+            get_formatting_settings,
+            set_profanity_words,
+            get_snippets,
+            save_snippet,
+            delete_snippet,
+            get_hid_bindings,
+            capture_hid_button,
+            save_hid_binding,
+            delete_hid_binding,
+            get_gamepad_bindings,
+            save_gamepad_binding,
+            delete_gamepad_binding,
+            show_scratchpad,
+            get_scratchpad_text,
+            set_scratchpad_text,
+            get_send_targets,
+            save_send_target,
+            delete_send_target,
+            set_active_send_target,
+            get_output_templates,
+            save_output_template,
+            delete_output_template,
+            set_profile_template,
+            set_code_mode_settings,
+            get_code_mode_settings,
+            set_send_enter_settings,
+            get_send_enter_settings,
+            set_casing_settings,
+            get_casing_settings,
+            get_regional_formats,
+            set_regional_format,
+            get_output_files,
+            set_output_files,
+            render_snippet,
+            set_injection_settings,
+            get_injection_settings,
             save_shortcuts,
+            set_continuous_dictation,
+            get_continuous_dictation,
+            set_diarization,
+            get_diarization,
+            set_queue_next_recording,
+            get_queue_next_recording,
+            set_journal_settings,
+            get_journal_settings,
+            set_file_transcription_settings,
+            get_file_transcription_settings,
+            set_watch_folder_settings,
+            get_watch_folder_settings,
+            set_caption_server_settings,
+            get_caption_server_settings,
+            set_privacy_mode,
+            get_privacy_mode,
+            get_policy,
+            set_privacy_settings,
+            get_privacy_settings,
+            set_stats_settings,
+            get_stats_settings,
+            get_daily_stats,
+            get_weekly_stats,
+            get_app_stats,
+            clear_stats,
+            purge_all_data,
+            export_settings,
+            import_settings,
+            get_history,
+            get_history_apps,
+            get_sessions,
+            export_session,
+            set_session_settings,
+            get_session_settings,
+            search_history,
+            get_history_filters,
+            pin_transcription,
+            unpin_transcription,
+            get_transcription_words,
+            get_transcription_segments,
+            export_history,
+            set_history_export_settings,
+            get_history_export_settings,
+            set_retention_settings,
+            get_retention_settings,
+            set_model_memory_settings,
+            get_model_catalog,
+            set_model_slots,
+            get_model_slots,
+            get_custom_models,
+            add_custom_model,
+            remove_custom_model,
+            set_advanced_settings,
+            get_advanced_settings,
+            reset_advanced_settings,
+            set_sound_settings,
+            get_sound_settings,
+            preview_sound,
+            set_accessibility_settings,
+            get_accessibility_settings,
+            test_announcement,
+            open_settings,
+            take_startup_settings_section,
+            set_locale_settings,
+            get_locale_settings,
+            get_available_locales,
+            get_strings,
+            set_main_window_settings,
+            get_main_window_settings,
+            set_focus_assist_settings,
+            get_focus_assist_settings,
+            get_focus_assist_active,
+            set_tray_icon_settings,
+            get_tray_icon_settings,
+            get_model_memory_settings,
+            preload_model,
+            run_self_test,
+            run_benchmark,
+            get_crash_report,
+            export_crash_diagnostics,
+            dismiss_crash_report,
+            get_orphaned_recording,
+            transcribe_orphaned_recording,
+            discard_orphaned_recording,
+            set_recording_limits,
+            get_recording_limits,
+            set_double_tap_settings,
+            get_double_tap_settings,
+            set_win_h_settings,
+            get_win_h_settings,
+            set_meeting_capture_settings,
+            get_meeting_capture_settings,
+            set_keep_awake_settings,
+            get_keep_awake_settings,
+            set_ducking_settings,
+            get_ducking_settings,
+            set_mic_check_settings,
+            get_mic_check_settings,
+            set_audio_processing,
+            get_audio_processing,
+            test_microphone,
+            set_hotkey_blocklist,
+            get_hotkey_blocklist,
+            test_shortcut,
+            get_shortcuts,
             get_toggle_shortcut,
             get_cancel_shortcut,
             get_preferred_languages,
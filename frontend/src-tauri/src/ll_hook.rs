@@ -0,0 +1,44 @@
+use windows::Win32::{
+    Foundation::{HINSTANCE, HWND, LPARAM, WPARAM},
+    System::Threading::GetCurrentThreadId,
+    UI::WindowsAndMessaging::{
+        DispatchMessageW, GetMessageW, PeekMessageW, PostThreadMessageW, SetWindowsHookExW, TranslateMessage,
+        UnhookWindowsHookEx, HOOKPROC, MSG, PM_NOREMOVE, WINDOWS_HOOK_ID, WM_QUIT,
+    },
+};
+
+// Install a low-level (WH_*_LL) hook on its own message-loop thread and return that thread's id
+pub fn install(id: WINDOWS_HOOK_ID, proc: HOOKPROC) -> Result<u32, String> {
+    let (tx, rx) = std::sync::mpsc::channel();
+
+    std::thread::spawn(move || unsafe {
+        let hook = match SetWindowsHookExW(id, proc, HINSTANCE::default(), 0) {
+            Ok(hook) => hook,
+            Err(e) => {
+                let _ = tx.send(Err(format!("Failed to install hook: {}", e)));
+                return;
+            }
+        };
+
+        // Make sure the thread has a message queue before anyone posts WM_QUIT to it
+        let mut msg = MSG::default();
+        let _ = PeekMessageW(&mut msg, HWND::default(), 0, 0, PM_NOREMOVE);
+        let _ = tx.send(Ok(GetCurrentThreadId()));
+
+        while GetMessageW(&mut msg, HWND::default(), 0, 0).as_bool() {
+            let _ = TranslateMessage(&msg);
+            DispatchMessageW(&msg);
+        }
+
+        let _ = UnhookWindowsHookEx(hook);
+    });
+
+    rx.recv().map_err(|e| e.to_string())?
+}
+
+// Stop a hook thread started by `install` (the hook is removed as its message loop exits)
+pub fn uninstall(thread_id: u32) {
+    unsafe {
+        let _ = PostThreadMessageW(thread_id, WM_QUIT, WPARAM(0), LPARAM(0));
+    }
+}
@@ -0,0 +1,117 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{OnceLock, RwLock};
+use windows::core::PWSTR;
+use windows::Win32::Globalization::{GetUserPreferredUILanguages, MUI_LANGUAGE_NAME};
+
+// Translations of the tray menu, notifications, announcements, and settings window. Each locale
+// is a flat key -> text bundle; a key missing from one falls back to English. Texts can take
+// `{name}` placeholders, filled in by `tr_with`.
+
+pub const CONFIG_FILE: &str = "locale.json";
+
+const FALLBACK: &str = "en";
+
+// (code, name in that language, bundle)
+const BUNDLES: &[(&str, &str, &str)] = &[
+    ("en", "English", include_str!("locales/en.json")),
+    ("de", "Deutsch", include_str!("locales/de.json")),
+    ("es", "Español", include_str!("locales/es.json")),
+    ("fr", "Français", include_str!("locales/fr.json")),
+];
+
+// The locale texts are currently shown in
+static ACTIVE: RwLock<&str> = RwLock::new(FALLBACK);
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct LocaleSettings {
+    pub locale: Option<String>,  // None = follow the Windows display language
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LocaleInfo {
+    pub code: &'static str,
+    pub name: &'static str,
+}
+
+pub fn available() -> Vec<LocaleInfo> {
+    BUNDLES.iter().map(|(code, name, _)| LocaleInfo { code, name }).collect()
+}
+
+fn bundles() -> &'static HashMap<&'static str, HashMap<String, String>> {
+    static PARSED: OnceLock<HashMap<&'static str, HashMap<String, String>>> = OnceLock::new();
+    PARSED.get_or_init(|| {
+        BUNDLES
+            .iter()
+            .map(|(code, _, json)| {
+                let strings = serde_json::from_str(json).unwrap_or_else(|e| {
+                    log::error!("❌ Broken {} translations: {}", code, e);
+                    HashMap::new()
+                });
+                (*code, strings)
+            })
+            .collect()
+    })
+}
+
+// The supported locale for a language tag like "de-AT", if any
+fn supported(tag: &str) -> Option<&'static str> {
+    let language = tag.split(['-', '_']).next()?.to_ascii_lowercase();
+    BUNDLES.iter().map(|(code, _, _)| *code).find(|code| *code == language)
+}
+
+// The first of the user's Windows display languages we have texts for
+pub fn system_locale() -> Option<&'static str> {
+    let mut count = 0u32;
+    let mut len = 0u32;
+    unsafe { GetUserPreferredUILanguages(MUI_LANGUAGE_NAME, &mut count, PWSTR::null(), &mut len) }.ok()?;
+    let mut buffer = vec![0u16; len as usize];
+    unsafe { GetUserPreferredUILanguages(MUI_LANGUAGE_NAME, &mut count, PWSTR(buffer.as_mut_ptr()), &mut len) }.ok()?;
+    // A list of null-terminated names, ended by an empty one
+    buffer.split(|&c| c == 0).map(String::from_utf16_lossy).take_while(|tag| !tag.is_empty()).find_map(|tag| supported(&tag))
+}
+
+// The locale these settings select
+pub fn resolve(settings: &LocaleSettings) -> &'static str {
+    settings.locale.as_deref().and_then(supported).or_else(system_locale).unwrap_or(FALLBACK)
+}
+
+// Switch texts to the locale these settings select; returns it
+pub fn apply(settings: &LocaleSettings) -> &'static str {
+    let locale = resolve(settings);
+    *ACTIVE.write().unwrap() = locale;
+    log::info!("🌍 Display language: {}", locale);
+    locale
+}
+
+pub fn active() -> &'static str {
+    *ACTIVE.read().unwrap()
+}
+
+// Every text in `locale` (the active one if None), English where it has no translation
+pub fn strings(locale: Option<&str>) -> HashMap<String, String> {
+    let locale = locale.and_then(supported).unwrap_or_else(active);
+    let mut strings = bundles().get(FALLBACK).cloned().unwrap_or_default();
+    if let Some(translated) = bundles().get(locale) {
+        strings.extend(translated.iter().map(|(key, text)| (key.clone(), text.clone())));
+    }
+    strings
+}
+
+// The text for `key` in the active locale
+pub fn tr(key: &str) -> String {
+    [active(), FALLBACK]
+        .iter()
+        .find_map(|locale| bundles().get(locale)?.get(key))
+        .cloned()
+        .unwrap_or_else(|| {
+            log::warn!("⚠️ No text for {}", key);
+            key.to_string()
+        })
+}
+
+// The text for `key` with its `{name}` placeholders filled in
+pub fn tr_with(key: &str, args: &[(&str, &str)]) -> String {
+    args.iter().fold(tr(key), |text, (name, value)| text.replace(&format!("{{{}}}", name), value))
+}
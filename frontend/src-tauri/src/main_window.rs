@@ -0,0 +1,53 @@
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+
+use crate::AppState;
+
+// How the settings window behaves at startup and when closed. Recording runs from the tray and
+// hotkeys, so the window can stay out of the way entirely.
+
+pub const CONFIG_FILE: &str = "main_window.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MainWindowSettings {
+    pub start_minimized: bool,  // Start in the tray without showing the window
+    pub close_to_tray: bool,  // Closing the window hides it (off = closing quits the app)
+}
+
+impl Default for MainWindowSettings {
+    fn default() -> Self {
+        Self { start_minimized: false, close_to_tray: true }
+    }
+}
+
+// Show the window (restored if minimized) and give it focus
+pub fn bring_to_front(app: &AppHandle) {
+    let Some(win) = app.get_webview_window("main") else { return };
+    let _ = win.unminimize();
+    let _ = win.show().and_then(|_| win.set_focus());
+}
+
+// The window starts hidden; show it unless the settings say to start in the tray
+pub fn show_at_startup(app: &AppHandle, settings: &MainWindowSettings, deep_link: bool) {
+    if settings.start_minimized && !deep_link {
+        log::info!("🫥 Starting in the tray");
+        return;
+    }
+    bring_to_front(app);
+}
+
+// The close button: hide to the tray, or quit like the tray's Quit item
+pub fn close_requested(app: &AppHandle) {
+    let state: tauri::State<AppState> = app.state();
+    // Called from the close event on the event loop thread, outside the async runtime
+    let close_to_tray = state.main_window.blocking_lock().close_to_tray;
+    if close_to_tray {
+        if let Some(win) = app.get_webview_window("main") {
+            let _ = win.hide();
+        }
+    } else {
+        log::info!("🚪 Main window closed - quitting");
+        crate::run_tray_action(app, "quit");
+    }
+}
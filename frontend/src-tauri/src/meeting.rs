@@ -0,0 +1,312 @@
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicIsize, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+use windows::core::{ComInterface, HRESULT, HSTRING, PCWSTR};
+use windows::Win32::Foundation::{BOOL, HWND, LPARAM, S_OK, WPARAM};
+use windows::Win32::Media::Audio::{
+    eCapture, AudioSessionStateActive, IAudioSessionControl2, IAudioSessionManager2, IMMDeviceEnumerator, MMDeviceEnumerator,
+    DEVICE_STATE_ACTIVE,
+};
+use windows::Win32::System::Com::{CoCreateInstance, CoInitializeEx, CLSCTX_ALL, COINIT_MULTITHREADED};
+use windows::Win32::UI::Controls::{
+    TaskDialogIndirect, TASKDIALOGCONFIG, TASKDIALOG_BUTTON, TASKDIALOG_FLAGS, TDF_ALLOW_DIALOG_CANCELLATION, TDF_CALLBACK_TIMER,
+    TDF_USE_COMMAND_LINKS, TDM_CLICK_BUTTON, TDN_CREATED, TDN_DESTROYED, TDN_TIMER, TD_INFORMATION_ICON,
+};
+use windows::Win32::UI::WindowsAndMessaging::{
+    EnumWindows, GetWindowTextW, IsWindowVisible, PostMessageW, SetWindowPos, HWND_TOPMOST, IDCANCEL, SWP_NOACTIVATE,
+    SWP_NOMOVE, SWP_NOSIZE,
+};
+
+use crate::locale::{tr, tr_with};
+use crate::recording_state::{self, RecordingState};
+use crate::AppState;
+
+// Meeting capture: when a Teams, Zoom, or Google Meet call starts (the app, opted in per app, opens
+// the microphone), offer to transcribe it into the scratchpad, and stop once the call hangs up.
+
+pub const CONFIG_FILE: &str = "meeting_capture.json";
+
+// How often the microphone's audio sessions are checked for a call
+const POLL_INTERVAL: Duration = Duration::from_secs(3);
+// How long the offer stays up before it counts as "Not now"
+const OFFER_TIMEOUT_MS: usize = 30_000;
+
+// Task dialog button IDs
+const START_BUTTON: i32 = 100;
+const SKIP_BUTTON: i32 = 101;
+
+struct CallApp {
+    id: &'static str,
+    name: &'static str,
+    processes: &'static [&'static str],
+    title_prefix: Option<&'static str>,  // A window titled like this must be open too
+}
+
+const CALL_APPS: [CallApp; 3] = [
+    CallApp { id: "teams", name: "Microsoft Teams", processes: &["ms-teams.exe", "teams.exe"], title_prefix: None },
+    CallApp { id: "zoom", name: "Zoom", processes: &["zoom.exe"], title_prefix: None },
+    // Meet runs in a browser, which uses the microphone for plenty else
+    CallApp {
+        id: "meet",
+        name: "Google Meet",
+        processes: &["chrome.exe", "msedge.exe", "firefox.exe", "brave.exe"],
+        title_prefix: Some("Meet - "),
+    },
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MeetingCaptureSettings {
+    pub teams: bool,
+    pub zoom: bool,
+    pub meet: bool,
+    pub stop_when_call_ends: bool,  // Off = the capture runs until stopped by hand
+}
+
+impl Default for MeetingCaptureSettings {
+    fn default() -> Self {
+        Self { teams: false, zoom: false, meet: false, stop_when_call_ends: true }
+    }
+}
+
+impl MeetingCaptureSettings {
+    fn watches(&self, app: &CallApp) -> bool {
+        match app.id {
+            "teams" => self.teams,
+            "zoom" => self.zoom,
+            "meet" => self.meet,
+            _ => false,
+        }
+    }
+}
+
+// The app whose call the current recording captures
+static CAPTURING: Mutex<Option<&'static str>> = Mutex::new(None);
+// The offer dialog while it's up (0 = none)
+static OFFER_WINDOW: AtomicIsize = AtomicIsize::new(0);
+
+// Whether the current recording is a meeting capture, delivered to the scratchpad
+pub fn capturing() -> bool {
+    CAPTURING.lock().unwrap().is_some()
+}
+
+// The recording ended (delivered, cancelled, or failed); the next one is an ordinary dictation
+pub fn recording_ended() {
+    if let Some(id) = CAPTURING.lock().unwrap().take() {
+        log::info!("📞 Meeting capture ({}) finished", id);
+    }
+}
+
+fn app_name(id: &str) -> &'static str {
+    CALL_APPS.iter().find(|app| app.id == id).map(|app| app.name).unwrap_or_default()
+}
+
+// Watch for calls starting and ending in the apps opted in
+pub fn spawn_watcher(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut in_call: Option<&'static str> = None;
+
+        loop {
+            tokio::time::sleep(POLL_INTERVAL).await;
+
+            let state: tauri::State<AppState> = app.state();
+            let settings = state.meeting_capture.lock().await.clone();
+            let call = if CALL_APPS.iter().any(|call_app| settings.watches(call_app)) {
+                let watched = settings.clone();
+                match tauri::async_runtime::spawn_blocking(move || find_call(&watched)).await {
+                    Ok(Ok(call)) => call,
+                    // Keep what we knew rather than mistaking a failed check for a hang-up
+                    Ok(Err(e)) => {
+                        log::warn!("⚠️ Failed to check for calls: {}", e);
+                        continue;
+                    }
+                    Err(e) => {
+                        log::warn!("⚠️ Failed to check for calls: {}", e);
+                        continue;
+                    }
+                }
+            } else {
+                None
+            };
+
+            match (in_call, call) {
+                (None, Some(id)) => {
+                    log::info!("📞 {} call started", app_name(id));
+                    tauri::async_runtime::spawn(offer(app.clone(), id));
+                }
+                (Some(id), None) => {
+                    log::info!("📞 {} call ended", app_name(id));
+                    call_ended(&app, id, &settings).await;
+                }
+                _ => {}
+            }
+            in_call = call;
+        }
+    });
+}
+
+// Ask whether to capture the call, and start recording if so
+async fn offer(app: AppHandle, id: &'static str) {
+    let name = app_name(id);
+    let (sender, receiver) = tokio::sync::oneshot::channel();
+    // The dialog runs its own message loop, so it gets a thread of its own
+    std::thread::spawn(move || {
+        let _ = sender.send(show_offer(name));
+    });
+    if !receiver.await.unwrap_or(false) {
+        log::info!("📞 Not capturing the {} call", name);
+        return;
+    }
+
+    let state: tauri::State<AppState> = app.state();
+    if !matches!(recording_state::get(&state).await, RecordingState::Idle | RecordingState::Error) {
+        log::info!("📞 Not capturing the {} call: already recording", name);
+        return;
+    }
+    log::info!("📞 Capturing the {} call", name);
+    *CAPTURING.lock().unwrap() = Some(id);
+    if crate::cmd_start_recording(app.clone(), app.state()).await.is_err() {
+        recording_ended();
+    }
+}
+
+async fn call_ended(app: &AppHandle, id: &'static str, settings: &MeetingCaptureSettings) {
+    // An unanswered offer is moot now
+    let offer_window = OFFER_WINDOW.load(Ordering::SeqCst);
+    if offer_window != 0 {
+        let _ = unsafe { PostMessageW(HWND(offer_window), TDM_CLICK_BUTTON.0 as u32, WPARAM(IDCANCEL.0 as usize), LPARAM(0)) };
+    }
+
+    if *CAPTURING.lock().unwrap() != Some(id) || !settings.stop_when_call_ends {
+        return;
+    }
+    let state: tauri::State<AppState> = app.state();
+    if recording_state::is_recording(&state).await {
+        log::info!("📞 Stopping the capture of the {} call", app_name(id));
+        let _ = crate::cmd_stop_recording(app.clone(), app.state()).await;
+    }
+}
+
+// The first watched app with a call in progress: it's recording from a microphone
+fn find_call(settings: &MeetingCaptureSettings) -> windows::core::Result<Option<&'static str>> {
+    let capturing: Vec<String> = capturing_processes()?.into_iter().filter_map(crate::window_info::process_name).collect();
+    let call = CALL_APPS
+        .iter()
+        .filter(|call_app| settings.watches(call_app))
+        .find(|call_app| {
+            let uses_mic = capturing.iter().any(|name| call_app.processes.iter().any(|p| p.eq_ignore_ascii_case(name)));
+            uses_mic && call_app.title_prefix.map_or(true, has_window_titled)
+        })
+        .map(|call_app| call_app.id);
+    Ok(call)
+}
+
+// Process IDs of the active audio sessions on the active recording devices
+fn capturing_processes() -> windows::core::Result<Vec<u32>> {
+    let mut pids = Vec::new();
+    unsafe {
+        // Runs on a blocking pool thread, which may already be initialized
+        let _ = CoInitializeEx(None, COINIT_MULTITHREADED);
+        let enumerator: IMMDeviceEnumerator = CoCreateInstance(&MMDeviceEnumerator, None, CLSCTX_ALL)?;
+        let devices = enumerator.EnumAudioEndpoints(eCapture, DEVICE_STATE_ACTIVE)?;
+
+        for device_index in 0..devices.GetCount()? {
+            let manager: IAudioSessionManager2 = devices.Item(device_index)?.Activate(CLSCTX_ALL, None)?;
+            let sessions = manager.GetSessionEnumerator()?;
+            for session_index in 0..sessions.GetCount()? {
+                let Ok(control) = sessions.GetSession(session_index).and_then(|s| s.cast::<IAudioSessionControl2>()) else {
+                    continue;
+                };
+                if control.GetState().is_ok_and(|state| state == AudioSessionStateActive) {
+                    if let Ok(pid) = control.GetProcessId() {
+                        pids.push(pid);
+                    }
+                }
+            }
+        }
+    }
+    Ok(pids)
+}
+
+// Whether a visible window's title starts with `prefix` (a browser shows its active tab's title)
+fn has_window_titled(prefix: &str) -> bool {
+    struct Search<'a> {
+        prefix: &'a str,
+        found: bool,
+    }
+
+    unsafe extern "system" fn visit(hwnd: HWND, lparam: LPARAM) -> BOOL {
+        let search = &mut *(lparam.0 as *mut Search);
+        if !IsWindowVisible(hwnd).as_bool() {
+            return true.into();
+        }
+        let mut buffer = [0u16; 256];
+        let len = GetWindowTextW(hwnd, &mut buffer);
+        if len > 0 && String::from_utf16_lossy(&buffer[..len as usize]).starts_with(search.prefix) {
+            search.found = true;
+            return false.into();
+        }
+        true.into()
+    }
+
+    let mut search = Search { prefix, found: false };
+    unsafe {
+        // Stopping early makes EnumWindows report an error, so its result says nothing
+        let _ = EnumWindows(Some(visit), LPARAM(&mut search as *mut Search as isize));
+    }
+    search.found
+}
+
+// Keeps the offer above the call's window without taking focus from it, tracks it so a hang-up can
+// close it, and gives up after a while
+unsafe extern "system" fn offer_callback(hwnd: HWND, msg: u32, wparam: WPARAM, _lparam: LPARAM, _data: isize) -> HRESULT {
+    match msg as i32 {
+        n if n == TDN_CREATED.0 => {
+            OFFER_WINDOW.store(hwnd.0, Ordering::SeqCst);
+            let _ = SetWindowPos(hwnd, HWND_TOPMOST, 0, 0, 0, 0, SWP_NOMOVE | SWP_NOSIZE | SWP_NOACTIVATE);
+        }
+        n if n == TDN_DESTROYED.0 => OFFER_WINDOW.store(0, Ordering::SeqCst),
+        // The timer reports the time since the dialog opened
+        n if n == TDN_TIMER.0 && wparam.0 >= OFFER_TIMEOUT_MS => {
+            let _ = PostMessageW(hwnd, TDM_CLICK_BUTTON.0 as u32, WPARAM(IDCANCEL.0 as usize), LPARAM(0));
+        }
+        _ => {}
+    }
+    S_OK
+}
+
+// True if the user chose to start the capture
+fn show_offer(name: &str) -> bool {
+    let title = HSTRING::from("Whisper4Windows");
+    let instruction = HSTRING::from(tr_with("meeting.call_started", &[("app", name)]));
+    let content = HSTRING::from(tr("meeting.content"));
+    let labels = [(START_BUTTON, HSTRING::from(tr("meeting.start"))), (SKIP_BUTTON, HSTRING::from(tr("meeting.skip")))];
+    let buttons: Vec<TASKDIALOG_BUTTON> = labels
+        .iter()
+        .map(|(id, label)| TASKDIALOG_BUTTON { nButtonID: *id, pszButtonText: PCWSTR(label.as_ptr()) })
+        .collect();
+
+    let mut config = TASKDIALOGCONFIG {
+        cbSize: std::mem::size_of::<TASKDIALOGCONFIG>() as u32,
+        // Escape and the close button count as "Not now"
+        dwFlags: TASKDIALOG_FLAGS(TDF_USE_COMMAND_LINKS.0 | TDF_ALLOW_DIALOG_CANCELLATION.0 | TDF_CALLBACK_TIMER.0),
+        pszWindowTitle: PCWSTR(title.as_ptr()),
+        pszMainInstruction: PCWSTR(instruction.as_ptr()),
+        pszContent: PCWSTR(content.as_ptr()),
+        cButtons: buttons.len() as u32,
+        pButtons: buttons.as_ptr(),
+        nDefaultButton: START_BUTTON,
+        pfCallback: Some(offer_callback),
+        ..Default::default()
+    };
+    config.Anonymous1.pszMainIcon = TD_INFORMATION_ICON;
+
+    let mut pressed = 0;
+    if let Err(e) = unsafe { TaskDialogIndirect(&config, Some(&mut pressed), None, None) } {
+        log::error!("❌ Meeting capture offer failed: {}", e);
+        return false;
+    }
+    pressed == START_BUTTON
+}
@@ -0,0 +1,187 @@
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+use windows::Win32::Devices::FunctionDiscovery::PKEY_Device_FriendlyName;
+use windows::Win32::Media::Audio::Endpoints::IAudioEndpointVolume;
+use windows::Win32::Media::Audio::{eCapture, eConsole, IMMDevice, IMMDeviceEnumerator, MMDeviceEnumerator, DEVICE_STATE_ACTIVE};
+use windows::Win32::System::Com::StructuredStorage::{PropVariantClear, PropVariantToStringAlloc};
+use windows::Win32::System::Com::{CoCreateInstance, CoInitializeEx, CoTaskMemFree, CLSCTX_ALL, COINIT_MULTITHREADED, STGM_READ};
+
+use crate::{backend, recording_state, AppState};
+
+pub const CONFIG_FILE: &str = "mic_check.json";
+
+// How long a recording runs before a mic that captured nothing counts as silent
+const SILENCE_CHECK_DELAY: Duration = Duration::from_secs(2);
+// Peak sample level below which the input is digital silence rather than a quiet room
+const SILENT_PEAK: f64 = 0.0005;
+
+// Catching a muted or dead microphone as soon as a recording starts, and a badly set gain after it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MicCheckSettings {
+    pub enabled: bool,  // Warn when the mic is muted or records nothing
+    pub auto_unmute: bool,  // Unmute a mic muted in Windows instead of only warning
+    pub level_warnings: bool,  // Warn after a recording that clipped or was very quiet
+}
+
+impl Default for MicCheckSettings {
+    fn default() -> Self {
+        Self { enabled: true, auto_unmute: false, level_warnings: true }
+    }
+}
+
+enum MuteState {
+    Live,
+    Muted,
+    Unmuted,  // Was muted, and auto-unmute turned it back on
+}
+
+// Check the microphone of a recording that just started, in the background
+pub fn spawn(app: AppHandle, session: u64, microphone: Option<i32>) {
+    tauri::async_runtime::spawn(async move {
+        let state: tauri::State<AppState> = app.state();
+        let settings = state.mic_check.lock().await.clone();
+        if settings.enabled {
+            check(&app, &state, session, microphone, settings.auto_unmute).await;
+        }
+    });
+}
+
+async fn check(app: &AppHandle, state: &AppState, session: u64, microphone: Option<i32>, auto_unmute: bool) {
+    // A specific mic is picked by its backend index; its Windows endpoint is found by name
+    let name = match microphone {
+        Some(index) => match device_name(index).await {
+            Some(name) => Some(name),
+            None => {
+                log::warn!("⚠️ Microphone {} not found, skipping the mute check", index);
+                return;
+            }
+        },
+        None => None,
+    };
+
+    let mute = tauri::async_runtime::spawn_blocking(move || check_mute(name.as_deref(), auto_unmute)).await;
+    match mute {
+        Ok(Ok(MuteState::Live)) => {}
+        Ok(Ok(MuteState::Muted)) => {
+            warn(app, "toast.mic_muted", "toast.mic_muted_body");
+            return;
+        }
+        Ok(Ok(MuteState::Unmuted)) => {
+            log::info!("🎤 Unmuted the microphone");
+            crate::show_toast(app, &crate::locale::tr("toast.mic_unmuted"), &crate::locale::tr("toast.mic_unmuted_body"));
+        }
+        Ok(Err(e)) => log::warn!("⚠️ Couldn't read the microphone mute state: {}", e),
+        Err(e) => log::warn!("⚠️ Couldn't read the microphone mute state: {}", e),
+    }
+
+    // A hardware mute switch doesn't show up in Windows, but it records pure silence
+    tokio::time::sleep(SILENCE_CHECK_DELAY).await;
+    let still_recording = *state.recording_session.lock().await == session
+        && recording_state::is_recording(state).await
+        && !*state.is_paused.lock().await;
+    if still_recording && input_peak().await.is_some_and(|peak| peak < SILENT_PEAK) {
+        warn(app, "toast.mic_silent", "toast.mic_silent_body");
+    }
+}
+
+// The level flag the backend attached to a stopped recording ("clipping", "quiet", or "ok")
+pub fn quality_flag(data: &serde_json::Value) -> Option<&str> {
+    data.get("quality")?.get("flag")?.as_str()
+}
+
+// Suggest a gain change after a recording that clipped or was barely audible, which is what
+// usually garbles a transcription
+pub async fn report_levels(app: &AppHandle, state: &AppState, data: &serde_json::Value) {
+    let keys = match quality_flag(data) {
+        Some("clipping") => ("toast.mic_clipping", "toast.mic_clipping_body"),
+        Some("quiet") => ("toast.mic_quiet", "toast.mic_quiet_body"),
+        _ => return,
+    };
+    log::warn!("⚠️ Recording level: {}", data["quality"]);
+    if state.mic_check.lock().await.level_warnings {
+        crate::navigation::link_notification("microphone");
+        crate::show_toast(app, &crate::locale::tr(keys.0), &crate::locale::tr(keys.1));
+    }
+}
+
+// Toast and overlay warning, from the texts under these keys
+fn warn(app: &AppHandle, title_key: &str, body_key: &str) {
+    let title = crate::locale::tr(title_key);
+    log::warn!("⚠️ {}", title);
+    crate::navigation::link_notification("microphone");
+    crate::show_toast(app, &title, &crate::locale::tr(body_key));
+    if let (Some(win), Ok(json)) = (app.get_webview_window("recording"), serde_json::to_string(&title)) {
+        let _ = win.eval(format!("showMicWarning({})", json));
+    }
+}
+
+// Name of an input device by its backend (sounddevice) index
+async fn device_name(index: i32) -> Option<String> {
+    let devices = crate::microphones::list().await.ok()?;
+    devices.into_iter().find(|device| device.id == index).map(|device| device.name)
+}
+
+// Loudest sample captured so far in this recording
+async fn input_peak() -> Option<f64> {
+    let data = backend::client()
+        .get(backend::url("mic_status"))
+        .send()
+        .await
+        .ok()?
+        .json::<serde_json::Value>()
+        .await
+        .ok()?;
+    if data.get("recording").and_then(|r| r.as_bool()) != Some(true) {
+        return None;
+    }
+    data.get("peak").and_then(|p| p.as_f64())
+}
+
+fn check_mute(name: Option<&str>, auto_unmute: bool) -> windows::core::Result<MuteState> {
+    unsafe {
+        // Runs on a blocking pool thread, which may already be initialized
+        let _ = CoInitializeEx(None, COINIT_MULTITHREADED);
+        let Some(device) = find_endpoint(name)? else {
+            return Ok(MuteState::Live);
+        };
+        let volume: IAudioEndpointVolume = device.Activate(CLSCTX_ALL, None)?;
+        if !volume.GetMute()?.as_bool() {
+            return Ok(MuteState::Live);
+        }
+        if auto_unmute {
+            volume.SetMute(false, std::ptr::null())?;
+            return Ok(MuteState::Unmuted);
+        }
+        Ok(MuteState::Muted)
+    }
+}
+
+// The default recording device, or the one whose name matches. MME device names are cut off
+// at 31 characters, so a prefix match is enough.
+unsafe fn find_endpoint(name: Option<&str>) -> windows::core::Result<Option<IMMDevice>> {
+    let enumerator: IMMDeviceEnumerator = CoCreateInstance(&MMDeviceEnumerator, None, CLSCTX_ALL)?;
+    let Some(name) = name else {
+        return enumerator.GetDefaultAudioEndpoint(eCapture, eConsole).map(Some);
+    };
+
+    let devices = enumerator.EnumAudioEndpoints(eCapture, DEVICE_STATE_ACTIVE)?;
+    for index in 0..devices.GetCount()? {
+        let device = devices.Item(index)?;
+        if friendly_name(&device).is_some_and(|friendly| friendly.starts_with(name.trim())) {
+            return Ok(Some(device));
+        }
+    }
+    Ok(None)
+}
+
+unsafe fn friendly_name(device: &IMMDevice) -> Option<String> {
+    let mut value = device.OpenPropertyStore(STGM_READ).ok()?.GetValue(&PKEY_Device_FriendlyName).ok()?;
+    let text = PropVariantToStringAlloc(&value);
+    let _ = PropVariantClear(&mut value);
+    let text = text.ok()?;
+    let name = text.to_string().ok();
+    CoTaskMemFree(Some(text.0 as *const _));
+    name
+}
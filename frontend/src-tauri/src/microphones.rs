@@ -0,0 +1,227 @@
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+use crate::{backend, recording_state, AppState};
+
+// An ordered list of preferred microphones, for devices that come and go (Bluetooth headsets): a
+// recording opens the first one that's connected, then the microphone picked in settings. If the
+// microphone disappears mid-recording, the recording is stopped and what was captured transcribed.
+// For multi-channel audio interfaces, recordings can also use one channel or a mix of all of them.
+// The capture format (sample rate, buffer size, event-driven WASAPI in shared or exclusive mode) is
+// configurable too, for lower latency or devices that misbehave at the defaults; the backend
+// reports what the device was actually opened with, which goes to the log and the self-test.
+
+pub const CONFIG_FILE: &str = "microphones.json";
+
+// How often a recording's microphone is checked for having gone away
+const LOSS_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+// MME, one of the APIs the backend lists devices from, cuts names off at this many characters
+const MME_NAME_LENGTH: usize = 31;
+
+// Which input channels make up a recording
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ChannelMode {
+    #[default]
+    Mono,  // The device's own mono input, usually its first channel
+    Mix,  // All its channels averaged
+    Channel,  // Only `channel`
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MicrophoneSettings {
+    pub preferred: Vec<String>,  // Device names, most preferred first
+    pub channel_mode: ChannelMode,
+    pub channel: u16,  // 1-based, with ChannelMode::Channel
+    pub capture_rate: u32,  // Hz the device is opened at; the backend resamples anything else to 16 kHz
+    pub buffer_ms: u32,  // Capture block length at low latency (0 = the audio API's default)
+    pub wasapi: bool,  // Event-driven WASAPI instead of the default audio API (MME)
+    pub exclusive: bool,  // WASAPI exclusive mode; shared mode runs at the device's mix rate
+}
+
+impl Default for MicrophoneSettings {
+    fn default() -> Self {
+        Self {
+            preferred: Vec::new(),
+            channel_mode: ChannelMode::default(),
+            channel: 1,
+            capture_rate: 16000,
+            buffer_ms: 0,
+            wasapi: false,
+            exclusive: false,
+        }
+    }
+}
+
+impl MicrophoneSettings {
+    // Add the channel selection and capture format to a /start, /mic_test, or /self_test/mic request
+    pub fn add_to_request(&self, body: &mut serde_json::Value) {
+        body["channel_mode"] = serde_json::json!(self.channel_mode);
+        body["channel"] = serde_json::json!(self.channel.max(1));
+        body["capture_rate"] = serde_json::json!(self.capture_rate);
+        body["buffer_ms"] = serde_json::json!(self.buffer_ms);
+        body["wasapi"] = serde_json::json!(self.wasapi);
+        body["exclusive"] = serde_json::json!(self.wasapi && self.exclusive);
+    }
+}
+
+pub fn validate(settings: &MicrophoneSettings) -> Result<(), String> {
+    if !(8000..=192000).contains(&settings.capture_rate) {
+        return Err(format!("Unsupported sample rate: {} Hz", settings.capture_rate));
+    }
+    if settings.buffer_ms > 500 {
+        return Err("The buffer can be at most 500 ms".to_string());
+    }
+    Ok(())
+}
+
+// The capture format the backend reported, e.g. "48000 Hz, 1 channel, Windows WASAPI (exclusive),
+// 480-frame blocks, 10.0 ms latency", and whether it differs from what was asked for
+pub fn describe_format(format: &serde_json::Value) -> Option<(String, bool)> {
+    let number = |key: &str| format.get(key).and_then(|v| v.as_f64()).unwrap_or_default();
+    let rate = number("sample_rate");
+    let requested_rate = number("requested_rate");
+    let blocksize = number("blocksize");
+    let requested_blocksize = number("requested_blocksize");
+    let channels = number("channels");
+
+    let mut description = format!(
+        "{} on {}{}: {:.0} Hz",
+        format.get("device")?.as_str()?,
+        format.get("host_api").and_then(|v| v.as_str()).unwrap_or("unknown API"),
+        if format.get("exclusive").and_then(|v| v.as_bool()).unwrap_or(false) { " (exclusive)" } else { "" },
+        rate,
+    );
+    let mismatch = rate != requested_rate || (requested_blocksize > 0.0 && blocksize != requested_blocksize);
+    if rate != requested_rate {
+        description.push_str(&format!(" (asked for {:.0})", requested_rate));
+    }
+    description.push_str(&format!(", {:.0} channel{}", channels, if channels == 1.0 { "" } else { "s" }));
+    if blocksize > 0.0 {
+        description.push_str(&format!(", {:.0}-frame blocks", blocksize));
+    }
+    if blocksize != requested_blocksize && requested_blocksize > 0.0 {
+        description.push_str(&format!(" (asked for {:.0})", requested_blocksize));
+    }
+    description.push_str(&format!(", {:.1} ms latency", number("latency_ms")));
+    Some((description, mismatch))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InputDevice {
+    pub id: i32,  // Backend (sounddevice) index
+    pub name: String,
+    pub channels: u16,
+    pub default: bool,  // The Windows default recording device
+}
+
+// Input devices connected right now
+pub async fn list() -> Result<Vec<InputDevice>, String> {
+    let data = backend::client()
+        .get(backend::url("devices"))
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {}", e))?
+        .json::<serde_json::Value>()
+        .await
+        .map_err(|e| format!("Invalid response: {}", e))?;
+    let inputs = data.get("inputs").and_then(|inputs| inputs.as_array()).ok_or("Invalid response: no inputs")?;
+    Ok(inputs
+        .iter()
+        .filter_map(|device| {
+            let id = i32::try_from(device.get("id")?.as_i64()?).ok()?;
+            let name = device.get("name")?.as_str()?.to_string();
+            let channels = device.get("channels").and_then(|c| c.as_u64()).and_then(|c| u16::try_from(c).ok()).unwrap_or(1);
+            let default = device.get("default").and_then(|d| d.as_bool()).unwrap_or(false);
+            Some(InputDevice { id, name, channels, default })
+        })
+        .collect())
+}
+
+// Whether two device names are the same device, allowing for MME's cut-off names
+pub fn same_device(a: &str, b: &str) -> bool {
+    let (shorter, longer) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+    shorter.eq_ignore_ascii_case(longer)
+        || (shorter.len() >= MME_NAME_LENGTH && longer.to_lowercase().starts_with(&shorter.to_lowercase()))
+}
+
+// Backend indices of the connected preferred microphones, in order, for the backend to try before
+// the selected one
+pub async fn candidates(state: &AppState) -> Vec<i32> {
+    let preferred = state.microphones.lock().await.preferred.clone();
+    if preferred.is_empty() {
+        return Vec::new();
+    }
+    match list().await {
+        Ok(devices) => connected(&devices, &preferred).iter().map(|device| device.id).collect(),
+        Err(e) => {
+            log::warn!("⚠️ Couldn't list microphones, using the selected one: {}", e);
+            Vec::new()
+        }
+    }
+}
+
+// The preferred microphones among `devices`, in order
+fn connected<'a>(devices: &'a [InputDevice], preferred: &[String]) -> Vec<&'a InputDevice> {
+    preferred
+        .iter()
+        .filter_map(|name| {
+            let device = devices.iter().find(|device| same_device(&device.name, name));
+            if device.is_none() {
+                log::info!("🎤 Preferred microphone not connected: {}", name);
+            }
+            device
+        })
+        .collect()
+}
+
+// The microphone a recording would open now (preferred, selected, or default) and its channel count
+pub async fn current(state: &AppState) -> Result<InputDevice, String> {
+    let preferred = state.microphones.lock().await.preferred.clone();
+    let selected = *state.selected_microphone.lock().await;
+    let devices = list().await?;
+    let device = connected(&devices, &preferred)
+        .into_iter()
+        .next()
+        .or_else(|| selected.and_then(|id| devices.iter().find(|device| device.id == id)))
+        .or_else(|| devices.iter().find(|device| device.default))
+        .ok_or("No microphone found")?;
+    Ok(device.clone())
+}
+
+// Stop and transcribe the recording started as `session` if its microphone goes away
+pub fn spawn_loss_watch(app: AppHandle, session: u64) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(LOSS_CHECK_INTERVAL).await;
+            let state: tauri::State<AppState> = app.state();
+            if *state.recording_session.lock().await != session || !recording_state::is_recording(&state).await {
+                return;
+            }
+            if !device_lost().await {
+                continue;
+            }
+
+            log::warn!("🎤 The microphone went away, stopping the recording");
+            crate::navigation::link_notification("microphone");
+            crate::show_toast(&app, &crate::locale::tr("toast.mic_lost"), &crate::locale::tr("toast.mic_lost_body"));
+            let _ = crate::cmd_stop_recording(app.clone(), app.state()).await;
+            return;
+        }
+    });
+}
+
+async fn device_lost() -> bool {
+    let Ok(response) = backend::client().get(backend::url("mic_status")).send().await else {
+        return false;
+    };
+    response
+        .json::<serde_json::Value>()
+        .await
+        .ok()
+        .and_then(|data| data.get("device_lost").and_then(|lost| lost.as_bool()))
+        .unwrap_or(false)
+}
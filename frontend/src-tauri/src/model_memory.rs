@@ -0,0 +1,109 @@
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+use crate::{backend, decoding, model_slots, AppState};
+
+// When the backend frees the model's memory. Keeping it loaded makes every dictation start
+// instantly but holds on to several GB; the backend enforces the policy, the app only tells it.
+
+pub const CONFIG_FILE: &str = "model_memory.json";
+
+// How long to wait for the backend to answer after it starts
+const BACKEND_ATTEMPTS: u32 = 30;
+const BACKEND_RETRY: Duration = Duration::from_secs(1);
+// Loading a large model from disk can take a while, especially on CPU
+const PRELOAD_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MemoryPolicy {
+    Keep,  // Stay loaded once used
+    Idle,  // Unload after `idle_minutes` without a transcription
+    OnDemand,  // Unload after every transcription
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ModelMemorySettings {
+    pub policy: MemoryPolicy,
+    pub idle_minutes: u32,
+    pub preload_at_startup: bool,  // Load the selected model as soon as the backend is up
+}
+
+impl Default for ModelMemorySettings {
+    fn default() -> Self {
+        Self { policy: MemoryPolicy::Keep, idle_minutes: 15, preload_at_startup: false }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Preloaded {
+    pub model: String,
+    pub device: String,  // Where it actually loaded; "cpu" if the GPU couldn't be used
+    pub load_time: f64,
+}
+
+// Send the policy once the (possibly just started) backend answers, then preload if asked to
+pub fn spawn_apply(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        for attempt in 0..BACKEND_ATTEMPTS {
+            if attempt > 0 {
+                tokio::time::sleep(BACKEND_RETRY).await;
+            }
+            let state: tauri::State<AppState> = app.state();
+            let settings = state.model_memory.lock().await.clone();
+            if apply(&settings).await.is_err() {
+                continue;
+            }
+            if settings.preload_at_startup {
+                match preload(&state).await {
+                    Ok(model) => log::info!("🔥 Preloaded {} on {} in {:.1}s", model.model, model.device, model.load_time),
+                    Err(e) => log::warn!("⚠️ Preload failed: {}", e),
+                }
+            }
+            return;
+        }
+        log::warn!("⚠️ Backend didn't answer; model memory policy not applied");
+    });
+}
+
+pub async fn apply(settings: &ModelMemorySettings) -> Result<(), String> {
+    let request = serde_json::json!({ "policy": settings.policy, "idle_minutes": settings.idle_minutes });
+    let resp = backend::client()
+        .post(backend::url("model_policy"))
+        .json(&request)
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {}", e))?;
+    let data = resp.json::<serde_json::Value>().await.map_err(|e| format!("Invalid response: {}", e))?;
+    if data.get("status").and_then(|s| s.as_str()) != Some("success") {
+        let message = data.get("message").and_then(|m| m.as_str()).unwrap_or("Could not set the memory policy");
+        return Err(message.to_string());
+    }
+    Ok(())
+}
+
+// Load the selected model now so the next dictation doesn't wait for it
+pub async fn preload(state: &AppState) -> Result<Preloaded, String> {
+    let model = state.selected_model.lock().await.clone();
+    let device = state.selected_device.lock().await.clone();
+    let compute_type = decoding::resolve(state, model_slots::DEFAULT).await.compute_type;
+    let resp = backend::client()
+        .post(backend::url("preload"))
+        .timeout(PRELOAD_TIMEOUT)
+        .json(&serde_json::json!({ "model_size": model, "device": device, "compute_type": compute_type }))
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {}", e))?;
+    let data = resp.json::<serde_json::Value>().await.map_err(|e| format!("Invalid response: {}", e))?;
+    if data.get("status").and_then(|s| s.as_str()) != Some("success") {
+        let message = data.get("message").and_then(|m| m.as_str()).unwrap_or("Failed to load the model");
+        return Err(message.to_string());
+    }
+    Ok(Preloaded {
+        model,
+        device: data.get("device").and_then(|d| d.as_str()).unwrap_or_default().to_string(),
+        load_time: data.get("load_time").and_then(|t| t.as_f64()).unwrap_or_default(),
+    })
+}
@@ -0,0 +1,90 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::AppState;
+
+// Named model/device pairs a recording can use instead of the main selection, e.g. a fast model
+// for short notes and an accurate one for long dictation, each started by its own hotkey
+
+pub const CONFIG_FILE: &str = "model_slots.json";
+
+// The main model selection (`AppState::selected_model`/`selected_device`)
+pub const DEFAULT: &str = "default";
+pub const QUICK: &str = "quick";
+pub const ACCURATE: &str = "accurate";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModelSlot {
+    pub model: String,
+    pub device: String,
+}
+
+impl ModelSlot {
+    // The model and device an administrator's policy locks win over the slot's own
+    fn with_policy(mut self) -> Self {
+        let policy = crate::policy::get();
+        if let Some(model) = &policy.model {
+            self.model = model.clone();
+        }
+        if let Some(device) = &policy.device {
+            self.device = device.clone();
+        }
+        self
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ModelSlots {
+    pub slots: HashMap<String, ModelSlot>,  // Slot name -> model; the default slot isn't stored here
+    pub keep_warm: bool,  // Ask the backend to keep every slot's model loaded if memory allows
+}
+
+impl Default for ModelSlots {
+    fn default() -> Self {
+        let slot = |model: &str| ModelSlot { model: model.to_string(), device: "auto".to_string() };
+        Self {
+            slots: HashMap::from([(QUICK.to_string(), slot("base")), (ACCURATE.to_string(), slot("large-v3"))]),
+            keep_warm: false,
+        }
+    }
+}
+
+// Refuse slots that use a model or device other than the one the policy locks
+pub fn check_policy(slots: &ModelSlots) -> Result<(), String> {
+    let policy = crate::policy::get();
+    for slot in slots.slots.values() {
+        crate::policy::check("model", policy.model.as_deref(), slot.model.as_str())?;
+        crate::policy::check("device", policy.device.as_deref(), slot.device.as_str())?;
+    }
+    Ok(())
+}
+
+// The model and device a slot records with; unknown slots fall back to the main selection
+pub async fn resolve(state: &AppState, slot: &str) -> ModelSlot {
+    if slot != DEFAULT {
+        if let Some(model) = state.model_slots.lock().await.slots.get(slot) {
+            return model.clone().with_policy();
+        }
+    }
+    ModelSlot {
+        model: state.selected_model.lock().await.clone(),
+        device: state.selected_device.lock().await.clone(),
+    }
+    .with_policy()
+}
+
+// The other slots' models the backend may keep loaded next to `active` (none unless keep_warm is on)
+pub async fn warm_models(state: &AppState, active: &ModelSlot) -> Vec<ModelSlot> {
+    let slots = state.model_slots.lock().await.clone();
+    if !slots.keep_warm {
+        return Vec::new();
+    }
+    let mut warm: Vec<ModelSlot> = Vec::new();
+    for slot in std::iter::once(resolve(state, DEFAULT).await).chain(slots.slots.into_values().map(ModelSlot::with_policy)) {
+        if slot != *active && !warm.contains(&slot) {
+            warm.push(slot);
+        }
+    }
+    warm
+}
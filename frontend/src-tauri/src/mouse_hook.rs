@@ -0,0 +1,163 @@
+use std::sync::atomic::{AtomicU32, AtomicU8, Ordering};
+use std::sync::{Mutex, OnceLock};
+use tauri::AppHandle;
+use tauri_plugin_global_shortcut::Modifiers;
+use windows::Win32::{
+    Foundation::{LPARAM, LRESULT, WPARAM},
+    UI::Input::KeyboardAndMouse::{GetAsyncKeyState, VIRTUAL_KEY, VK_CONTROL, VK_LWIN, VK_MENU, VK_RWIN, VK_SHIFT},
+    UI::WindowsAndMessaging::{
+        CallNextHookEx, HC_ACTION, HHOOK, LLMHF_INJECTED, MSLLHOOKSTRUCT, WH_MOUSE_LL, WM_MBUTTONDOWN, WM_MBUTTONUP,
+        WM_XBUTTONDOWN, WM_XBUTTONUP, XBUTTON1, XBUTTON2,
+    },
+};
+
+// Mouse buttons that can be bound (left/right are never swallowed)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MouseButton {
+    Middle,
+    X1,
+    X2,
+}
+
+impl MouseButton {
+    // Shortcut-string token, e.g. "MouseX1"
+    pub fn parse(token: &str) -> Option<Self> {
+        match token {
+            "MouseMiddle" | "MiddleClick" | "MButton" => Some(MouseButton::Middle),
+            "MouseX1" | "XButton1" | "Mouse4" => Some(MouseButton::X1),
+            "MouseX2" | "XButton2" | "Mouse5" => Some(MouseButton::X2),
+            _ => None,
+        }
+    }
+
+    fn mask(self) -> u8 {
+        match self {
+            MouseButton::Middle => 1,
+            MouseButton::X1 => 2,
+            MouseButton::X2 => 4,
+        }
+    }
+}
+
+// A mouse button plus the modifiers that must be held with it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MouseBinding {
+    pub button: MouseButton,
+    pub modifiers: Modifiers,
+}
+
+// Bound buttons and the hotkey action ("toggle", "cancel") each one triggers
+static BINDINGS: Mutex<Vec<(MouseBinding, String)>> = Mutex::new(Vec::new());
+static APP: OnceLock<AppHandle> = OnceLock::new();
+// Thread running the hook's message loop (0 = hook not installed)
+static HOOK_THREAD: AtomicU32 = AtomicU32::new(0);
+// Buttons whose press we swallowed, so the matching release is swallowed too
+static SWALLOWED: AtomicU8 = AtomicU8::new(0);
+
+// Bind `binding` to `action`, installing the hook on first use
+pub fn bind(app: &AppHandle, action: &str, binding: MouseBinding) -> Result<(), String> {
+    let _ = APP.set(app.clone());
+    let mut bindings = BINDINGS.lock().unwrap();
+
+    if let Some((_, other)) = bindings.iter().find(|(b, a)| *b == binding && a != action) {
+        return Err(format!("Mouse binding is already used for {}", other));
+    }
+
+    bindings.retain(|(_, a)| a != action);
+    bindings.push((binding, action.to_string()));
+
+    if HOOK_THREAD.load(Ordering::SeqCst) == 0 {
+        if let Err(e) = start_hook() {
+            bindings.retain(|(_, a)| a != action);
+            return Err(e);
+        }
+    }
+    Ok(())
+}
+
+// Remove the binding for `action`, uninstalling the hook when nothing is bound
+pub fn unbind(action: &str) {
+    let mut bindings = BINDINGS.lock().unwrap();
+    bindings.retain(|(_, a)| a != action);
+
+    if bindings.is_empty() {
+        let thread_id = HOOK_THREAD.swap(0, Ordering::SeqCst);
+        if thread_id != 0 {
+            crate::ll_hook::uninstall(thread_id);
+            log::info!("🖱️ Mouse hook removed");
+        }
+    }
+}
+
+// Whether `binding` is currently bound (to any action)
+pub fn is_bound(binding: MouseBinding) -> bool {
+    BINDINGS.lock().unwrap().iter().any(|(b, _)| *b == binding)
+}
+
+fn start_hook() -> Result<(), String> {
+    let thread_id = crate::ll_hook::install(WH_MOUSE_LL, Some(mouse_proc))?;
+    HOOK_THREAD.store(thread_id, Ordering::SeqCst);
+    log::info!("🖱️ Mouse hook installed");
+    Ok(())
+}
+
+fn key_down(vk: VIRTUAL_KEY) -> bool {
+    unsafe { GetAsyncKeyState(vk.0 as i32) as u16 & 0x8000 != 0 }
+}
+
+fn current_modifiers() -> Modifiers {
+    let mut modifiers = Modifiers::empty();
+    if key_down(VK_CONTROL) {
+        modifiers |= Modifiers::CONTROL;
+    }
+    if key_down(VK_MENU) {
+        modifiers |= Modifiers::ALT;
+    }
+    if key_down(VK_SHIFT) {
+        modifiers |= Modifiers::SHIFT;
+    }
+    if key_down(VK_LWIN) || key_down(VK_RWIN) {
+        modifiers |= Modifiers::SUPER;
+    }
+    modifiers
+}
+
+unsafe extern "system" fn mouse_proc(code: i32, wparam: WPARAM, lparam: LPARAM) -> LRESULT {
+    if code == HC_ACTION as i32 {
+        let info = &*(lparam.0 as *const MSLLHOOKSTRUCT);
+        let message = wparam.0 as u32;
+        let x_button = || match (info.mouseData >> 16) as u16 {
+            XBUTTON1 => Some(MouseButton::X1),
+            XBUTTON2 => Some(MouseButton::X2),
+            _ => None,
+        };
+
+        let (button, pressed) = match message {
+            WM_MBUTTONDOWN => (Some(MouseButton::Middle), true),
+            WM_MBUTTONUP => (Some(MouseButton::Middle), false),
+            WM_XBUTTONDOWN => (x_button(), true),
+            WM_XBUTTONUP => (x_button(), false),
+            _ => (None, false),
+        };
+
+        // Ignore clicks synthesized by other tools (and by us)
+        if let Some(button) = button.filter(|_| info.flags & LLMHF_INJECTED == 0) {
+            if pressed {
+                let binding = MouseBinding { button, modifiers: current_modifiers() };
+                let action = BINDINGS
+                    .try_lock()
+                    .ok()
+                    .and_then(|bindings| bindings.iter().find(|(b, _)| *b == binding).map(|(_, a)| a.clone()));
+                if let (Some(action), Some(app)) = (action, APP.get()) {
+                    SWALLOWED.fetch_or(button.mask(), Ordering::SeqCst);
+                    crate::run_hotkey_action(app.clone(), action);
+                    return LRESULT(1);
+                }
+            } else if SWALLOWED.fetch_and(!button.mask(), Ordering::SeqCst) & button.mask() != 0 {
+                return LRESULT(1);
+            }
+        }
+    }
+
+    CallNextHookEx(HHOOK::default(), code, wparam, lparam)
+}
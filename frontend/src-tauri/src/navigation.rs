@@ -0,0 +1,73 @@
+use serde::Serialize;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager};
+
+// Opening the settings window at a page or section: from the hotkey, the tray, the command line
+// (`--settings <section>`), or after a notification that named the section that fixes its problem.
+// Sections are the settings page's `section-<name>` elements; page names work too.
+
+pub const SETTINGS_ARG: &str = "--settings";
+
+// How long after a notification opening the settings still goes to the section it named, since
+// Windows doesn't tell us when a toast is clicked
+const LINK_LIFETIME: Duration = Duration::from_secs(120);
+
+static NOTIFICATION_LINK: Mutex<Option<(String, Instant)>> = Mutex::new(None);
+// Asked for on the command line at startup, before the settings page could take it
+static STARTUP_SECTION: Mutex<Option<String>> = Mutex::new(None);
+
+// Sent to the main window as the "navigate" event
+#[derive(Debug, Clone, Serialize)]
+struct Navigate<'a> {
+    section: Option<&'a str>,
+}
+
+// Show and focus the settings window at `section`, or where the last notification pointed
+pub fn open_settings(app: &AppHandle, section: Option<&str>) {
+    let linked = if section.is_none() { take_notification_link() } else { None };
+    let section = section.or(linked.as_deref());
+    crate::main_window::bring_to_front(app);
+    let Some(win) = app.get_webview_window("main") else { return };
+
+    log::info!("🧭 Opening settings{}", section.map(|s| format!(" at {}", s)).unwrap_or_default());
+    let _ = app.emit_to("main", "navigate", Navigate { section });
+    if let Some(json) = section.and_then(|s| serde_json::to_string(s).ok()) {
+        let _ = win.eval(format!("openSettingsSection({})", json));
+    }
+}
+
+// Remember the section a notification is about, for the next time the settings are opened
+pub fn link_notification(section: &str) {
+    *NOTIFICATION_LINK.lock().unwrap() = Some((section.to_string(), Instant::now()));
+}
+
+fn take_notification_link() -> Option<String> {
+    let (section, at) = NOTIFICATION_LINK.lock().unwrap().take()?;
+    (at.elapsed() < LINK_LIFETIME).then_some(section)
+}
+
+// The section a `--settings <section>` launch asked for
+pub fn section_from_args(args: &[String]) -> Option<&str> {
+    let index = args.iter().position(|arg| arg == SETTINGS_ARG)?;
+    args.get(index + 1).map(String::as_str)
+}
+
+// Open the settings from a second instance's deep-link launch
+pub fn run(app: &AppHandle, args: &[String]) -> bool {
+    let Some(section) = section_from_args(args) else { return false };
+    open_settings(app, Some(section));
+    true
+}
+
+// Keep our own deep link for the settings page, which asks for it once it has loaded
+pub fn remember_startup_section(args: &[String]) {
+    if let Some(section) = section_from_args(args) {
+        log::info!("🧭 Settings will open at {}", section);
+        *STARTUP_SECTION.lock().unwrap() = Some(section.to_string());
+    }
+}
+
+pub fn take_startup_section() -> Option<String> {
+    STARTUP_SECTION.lock().unwrap().take()
+}
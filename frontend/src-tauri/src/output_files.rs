@@ -0,0 +1,102 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use tauri::AppHandle;
+
+use crate::AppState;
+
+// Files a dictation is saved to besides being delivered, for content creators: a profile (model
+// slot) can write each recording as timed SubRip subtitles and/or plain text into a folder, while
+// the text is still pasted as usual (or, if the profile says so, only saved).
+
+pub const CONFIG_FILE: &str = "output_files.json";
+
+// What one profile does with a finished dictation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OutputSet {
+    pub deliver: bool,  // Deliver the text by the output mode (paste, clipboard, scratchpad)
+    pub srt: bool,  // Save timed subtitles
+    pub txt: bool,  // Save the text
+    pub folder: String,  // Where the files go, one pair per recording
+}
+
+impl Default for OutputSet {
+    fn default() -> Self {
+        Self { deliver: true, srt: false, txt: false, folder: String::new() }
+    }
+}
+
+impl OutputSet {
+    fn writes_files(&self) -> bool {
+        self.srt || self.txt
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct OutputFileSettings {
+    pub profiles: HashMap<String, OutputSet>,  // Model slot -> outputs; slots without one only deliver
+}
+
+impl OutputFileSettings {
+    pub fn for_profile(&self, profile: &str) -> OutputSet {
+        self.profiles.get(profile).cloned().unwrap_or_default()
+    }
+
+    pub fn select(&mut self, profile: &str, outputs: OutputSet) {
+        if outputs == OutputSet::default() {
+            self.profiles.remove(profile);
+        } else {
+            self.profiles.insert(profile.to_string(), outputs);
+        }
+    }
+}
+
+pub fn validate(outputs: &OutputSet) -> Result<(), String> {
+    if !outputs.deliver && !outputs.writes_files() {
+        return Err("Pick at least one output".to_string());
+    }
+    if outputs.writes_files() {
+        let folder = Path::new(outputs.folder.trim());
+        if outputs.folder.trim().is_empty() {
+            return Err("No output folder set".to_string());
+        }
+        if !folder.is_dir() {
+            return Err(format!("{} is not a folder", folder.display()));
+        }
+    }
+    Ok(())
+}
+
+// The outputs of the profile the current recording uses
+pub async fn current(state: &AppState) -> OutputSet {
+    let profile = state.recording_slot.lock().await.clone();
+    state.output_files.lock().await.for_profile(&profile)
+}
+
+// Save a recording's files, "Dictation 2024-03-01 14-05-09.srt" and/or ".txt", from the backend
+// result (`data`) and the text as spoken
+pub fn save(app: &AppHandle, outputs: &OutputSet, text: &str, data: &serde_json::Value) {
+    if !outputs.writes_files() || text.is_empty() {
+        return;
+    }
+    let name = format!("Dictation {}", chrono::Local::now().format("%Y-%m-%d %H-%M-%S"));
+    let base = Path::new(outputs.folder.trim()).join(name);
+    let mut files = Vec::new();
+    if outputs.srt {
+        files.push((base.with_extension("srt"), crate::file_transcription::srt(data, text)));
+    }
+    if outputs.txt {
+        files.push((base.with_extension("txt"), text.to_string()));
+    }
+    for (path, contents) in files {
+        match std::fs::write(&path, contents) {
+            Ok(()) => log::info!("📁 Saved {}", path.display()),
+            Err(e) => {
+                log::error!("❌ Failed to save {}: {}", path.display(), e);
+                crate::show_toast(app, &crate::locale::tr("toast.transcript_failed"), &format!("{}: {}", path.display(), e));
+            }
+        }
+    }
+}
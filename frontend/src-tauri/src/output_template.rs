@@ -0,0 +1,92 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::regional_format::{self, RegionalFormat};
+use crate::window_info::WindowTarget;
+use crate::AppState;
+
+// Output templates wrap a transcription before it's delivered and appended to the journal, e.g.
+// "[{time}] {text}". Each profile picks its own: profiles are the model slots a recording can use
+// (the main hotkey's, quick, and accurate). History keeps the text as spoken.
+
+pub const CONFIG_FILE: &str = "output_templates.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct OutputTemplate {
+    pub name: String,  // Unique, e.g. "Timestamped"
+    pub template: String,  // {text}, {app}, {language}, {profile}, and the snippet date placeholders ({time}, {date:%A}, ...)
+}
+
+// Templates persisted in the config directory
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct TemplateStore {
+    pub templates: Vec<OutputTemplate>,
+    pub profiles: HashMap<String, String>,  // Model slot -> template name; slots without one deliver the text as is
+}
+
+impl TemplateStore {
+    // Add a template, or replace the one with the same name
+    pub fn upsert(&mut self, template: OutputTemplate) -> Result<(), String> {
+        if template.name.trim().is_empty() {
+            return Err("Template name cannot be empty".to_string());
+        }
+        if !template.template.contains("{text}") {
+            return Err("Template must contain {text}".to_string());
+        }
+
+        match self.templates.iter_mut().find(|t| t.name.eq_ignore_ascii_case(&template.name)) {
+            Some(existing) => *existing = template,
+            None => self.templates.push(template),
+        }
+        Ok(())
+    }
+
+    // Remove a template by name (and from the profiles using it); returns false if there was none
+    pub fn remove(&mut self, name: &str) -> bool {
+        let before = self.templates.len();
+        self.templates.retain(|t| !t.name.eq_ignore_ascii_case(name));
+        self.profiles.retain(|_, template| !template.eq_ignore_ascii_case(name));
+        self.templates.len() != before
+    }
+
+    // Use a template for a profile; an empty name stops using one
+    pub fn select(&mut self, profile: &str, name: &str) -> Result<(), String> {
+        if name.is_empty() {
+            self.profiles.remove(profile);
+            return Ok(());
+        }
+        let template = self.templates.iter().find(|t| t.name.eq_ignore_ascii_case(name)).ok_or(format!("No template named {}", name))?;
+        self.profiles.insert(profile.to_string(), template.name.clone());
+        Ok(())
+    }
+
+    fn for_profile(&self, profile: &str) -> Option<&OutputTemplate> {
+        let name = self.profiles.get(profile)?;
+        self.templates.iter().find(|t| t.name.eq_ignore_ascii_case(name))
+    }
+}
+
+// Fill in a template, with the transcription going in last so braces in it stay as spoken
+fn render(template: &str, text: &str, app: Option<&str>, language: Option<&str>, profile: &str, format: &RegionalFormat) -> String {
+    crate::snippets::render_at(template, chrono::Local::now(), format, false)
+        .replace("{app}", app.unwrap_or_default())
+        .replace("{language}", language.unwrap_or_default())
+        .replace("{profile}", profile)
+        .replace("{text}", text)
+}
+
+// `text` in the template of the profile the recording used (unchanged if it has none)
+pub async fn apply(state: &AppState, text: &str, language: Option<&str>, target: &WindowTarget) -> String {
+    if text.is_empty() {
+        return String::new();
+    }
+    let profile = state.recording_slot.lock().await.clone();
+    let format = regional_format::current(state).await;
+    let store = state.output_templates.lock().await;
+    match store.for_profile(&profile) {
+        Some(template) => render(&template.template, text, target.process_name.as_deref(), language, &profile, format),
+        None => text.to_string(),
+    }
+}
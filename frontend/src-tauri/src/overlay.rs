@@ -0,0 +1,274 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use tauri::{Monitor, PhysicalPosition, WebviewWindow};
+
+use crate::focus_assist::FocusAssistSettings;
+
+pub const CONFIG_FILE: &str = "overlay.json";
+
+// Moves reported this soon after we positioned the window ourselves are not user drags
+const PROGRAMMATIC_MOVE_GRACE: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum VerticalPlacement {
+    #[default]
+    Top,
+    Bottom,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum HorizontalPlacement {
+    Left,
+    #[default]
+    Center,
+    Right,
+}
+
+// How much of the overlay is shown while recording
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum DisplayMode {
+    #[default]
+    Full,  // Visualizer, model picker, and buttons
+    Compact,  // Small pill with a pulsing dot and timer
+    Hidden,  // No window; audio cues and tray tooltip only
+}
+
+impl DisplayMode {
+    // Logical window size for the visible modes
+    pub fn window_size(self) -> tauri::LogicalSize<f64> {
+        match self {
+            DisplayMode::Compact => tauri::LogicalSize::new(120.0, 48.0),
+            DisplayMode::Full | DisplayMode::Hidden => tauri::LogicalSize::new(616.0, 140.0),
+        }
+    }
+
+    // Name passed to the overlay's setDisplayMode()
+    pub fn as_js(self) -> &'static str {
+        match self {
+            DisplayMode::Compact => "compact",
+            DisplayMode::Full | DisplayMode::Hidden => "full",
+        }
+    }
+}
+
+// Display mode to use right now, taking the foreground app and Focus Assist into account
+pub fn effective_display_mode(settings: &OverlaySettings, focus_assist: &FocusAssistSettings) -> DisplayMode {
+    if settings.display_mode == DisplayMode::Hidden {
+        return DisplayMode::Hidden;
+    }
+
+    if focus_assist.hides_overlay() {
+        log::info!("🔕 Focus Assist is on - using hidden overlay");
+        return DisplayMode::Hidden;
+    }
+
+    if !settings.never_show_for.is_empty() {
+        if let Some(process) = crate::window_info::foreground_process_name() {
+            if settings.never_show_for.iter().any(|p| p.eq_ignore_ascii_case(&process)) {
+                log::info!("🙈 Overlay disabled for {}", process);
+                return DisplayMode::Hidden;
+            }
+        }
+    }
+
+    if settings.game_safe && crate::window_info::is_foreground_fullscreen() {
+        log::info!("🎮 Fullscreen app detected - using hidden overlay");
+        return DisplayMode::Hidden;
+    }
+
+    settings.display_mode
+}
+
+// Which monitor the overlay appears on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum MonitorMode {
+    #[default]
+    FocusedWindow,  // Monitor containing the foreground window
+    Cursor,  // Monitor containing the mouse cursor
+    Pinned,  // Always the monitor named in `pinned_monitor`
+}
+
+// Where the recording overlay appears
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OverlaySettings {
+    pub display_mode: DisplayMode,
+    pub monitor_mode: MonitorMode,
+    pub pinned_monitor: Option<String>,
+    pub vertical: VerticalPlacement,
+    pub horizontal: HorizontalPlacement,
+    pub offset_x: i32,  // Distance from the chosen edge (a plain shift when centered)
+    pub offset_y: i32,
+    pub remember_position: bool,  // Reuse the last dragged position for this monitor in this layout
+    pub game_safe: bool,  // Switch to hidden mode while a fullscreen app (game, presentation) is in front
+    pub never_show_for: Vec<String>,  // Process names (e.g. "game.exe") that never get a visible overlay
+    pub quick_languages: Vec<String>,  // Languages the overlay offers for a one-off switch, besides the selected one
+    pub show_latency: bool,  // Briefly show how long each stage took after the text is delivered
+}
+
+impl Default for OverlaySettings {
+    fn default() -> Self {
+        Self {
+            display_mode: DisplayMode::Full,
+            monitor_mode: MonitorMode::FocusedWindow,
+            pinned_monitor: None,
+            vertical: VerticalPlacement::Top,
+            horizontal: HorizontalPlacement::Center,
+            offset_x: 0,
+            offset_y: 50,
+            remember_position: true,
+            game_safe: true,
+            never_show_for: Vec::new(),
+            quick_languages: Vec::new(),
+            show_latency: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct SavedPosition {
+    pub x: i32,
+    pub y: i32,
+}
+
+// Persisted overlay config: placement settings plus dragged positions keyed by monitor layout and monitor
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct OverlayConfig {
+    pub settings: OverlaySettings,
+    pub positions: HashMap<String, SavedPosition>,
+}
+
+// Tracks user drags of the overlay between show and hide
+#[derive(Debug, Default)]
+pub struct OverlayTracker {
+    positioned_at: Option<Instant>,
+    dragged_to: Option<PhysicalPosition<i32>>,
+    position_key: Option<String>,  // Key the current placement is remembered under
+}
+
+impl OverlayTracker {
+    // Called from the window's Moved event
+    pub fn on_moved(&mut self, position: PhysicalPosition<i32>) {
+        let programmatic = self
+            .positioned_at
+            .map(|at| at.elapsed() < PROGRAMMATIC_MOVE_GRACE)
+            .unwrap_or(false);
+        if !programmatic {
+            self.dragged_to = Some(position);
+        }
+    }
+
+    // Dragged position and the key to remember it under, if the user moved the overlay
+    pub fn take_dragged_position(&mut self) -> Option<(String, PhysicalPosition<i32>)> {
+        let position = self.dragged_to.take()?;
+        self.position_key.clone().map(|key| (key, position))
+    }
+}
+
+// Identifies the current monitor arrangement so positions survive docking/undocking
+pub fn layout_key(monitors: &[Monitor]) -> String {
+    let mut parts: Vec<String> = monitors
+        .iter()
+        .map(|m| {
+            format!(
+                "{}@{},{}:{}x{}",
+                m.name().map(|n| n.as_str()).unwrap_or("?"),
+                m.position().x,
+                m.position().y,
+                m.size().width,
+                m.size().height
+            )
+        })
+        .collect();
+    parts.sort();
+    parts.join("|")
+}
+
+// Remembered positions are per monitor within a given layout
+fn position_key(monitors: &[Monitor], monitor: &Monitor) -> String {
+    format!("{}#{}", layout_key(monitors), monitor.name().map(|n| n.as_str()).unwrap_or("?"))
+}
+
+fn contains_point(monitor: &Monitor, x: i32, y: i32) -> bool {
+    let pos = monitor.position();
+    let size = monitor.size();
+    x >= pos.x && y >= pos.y && x < pos.x + size.width as i32 && y < pos.y + size.height as i32
+}
+
+// Position of the overlay on `monitor` according to the placement preset
+pub fn preset_position(monitor: &Monitor, window_width: u32, window_height: u32, settings: &OverlaySettings) -> PhysicalPosition<i32> {
+    let pos = monitor.position();
+    let size = monitor.size();
+    let (mon_w, mon_h) = (size.width as i32, size.height as i32);
+    let (win_w, win_h) = (window_width as i32, window_height as i32);
+
+    let x = match settings.horizontal {
+        HorizontalPlacement::Left => pos.x + settings.offset_x,
+        HorizontalPlacement::Center => pos.x + (mon_w - win_w) / 2 + settings.offset_x,
+        HorizontalPlacement::Right => pos.x + mon_w - win_w - settings.offset_x,
+    };
+    let y = match settings.vertical {
+        VerticalPlacement::Top => pos.y + settings.offset_y,
+        VerticalPlacement::Bottom => pos.y + mon_h - win_h - settings.offset_y,
+    };
+
+    PhysicalPosition::new(x, y)
+}
+
+// Pick the monitor the overlay should appear on according to the monitor mode
+pub fn target_monitor(win: &WebviewWindow, settings: &OverlaySettings) -> tauri::Result<Option<Monitor>> {
+    let monitors = win.available_monitors()?;
+
+    let point = match settings.monitor_mode {
+        MonitorMode::FocusedWindow => crate::window_info::foreground_window_center(),
+        MonitorMode::Cursor => crate::window_info::cursor_position(),
+        MonitorMode::Pinned => {
+            let pinned = settings.pinned_monitor.as_deref().and_then(|name| {
+                monitors.iter().find(|m| m.name().map(|n| n.as_str()) == Some(name))
+            });
+            if let Some(monitor) = pinned {
+                return Ok(Some(monitor.clone()));
+            }
+            log::warn!("⚠️ Pinned monitor {:?} not connected, using primary", settings.pinned_monitor);
+            None
+        }
+    };
+
+    if let Some((x, y)) = point {
+        if let Some(monitor) = monitors.iter().find(|m| contains_point(m, x, y)) {
+            return Ok(Some(monitor.clone()));
+        }
+    }
+
+    win.primary_monitor()
+}
+
+// Move the overlay to its remembered position (if still on-screen) or its preset position
+pub fn place_window(win: &WebviewWindow, monitor: &Monitor, config: &OverlayConfig, tracker: &mut OverlayTracker) -> tauri::Result<()> {
+    let monitors = win.available_monitors()?;
+    let window_size = win.outer_size()?;
+    let key = position_key(&monitors, monitor);
+
+    let remembered = if config.settings.remember_position {
+        config
+            .positions
+            .get(&key)
+            .filter(|p| contains_point(monitor, p.x, p.y))
+            .map(|p| PhysicalPosition::new(p.x, p.y))
+    } else {
+        None
+    };
+
+    let position = remembered.unwrap_or_else(|| preset_position(monitor, window_size.width, window_size.height, &config.settings));
+
+    tracker.positioned_at = Some(Instant::now());
+    tracker.dragged_to = None;
+    tracker.position_key = Some(key);
+    win.set_position(position)
+}
@@ -0,0 +1,80 @@
+use tauri::menu::{IsMenuItem, MenuItem, Submenu};
+use tauri::{AppHandle, Manager, Wry};
+
+use crate::history::HistoryEntry;
+use crate::AppState;
+
+// Pinned transcriptions (addresses, boilerplate paragraphs): listed first in history, spared by
+// retention, and offered in the tray's "Pinned" submenu to be inserted again with one click.
+
+// Menu item ids are "<prefix><entry id>"
+pub const ID_PREFIX: &str = "pinned:";
+
+// Longest menu label before the text is cut off
+const LABEL_CHARS: usize = 48;
+
+// Pinned entries, newest first (none if history can't be opened)
+pub async fn entries(state: &AppState) -> Vec<HistoryEntry> {
+    let history = state.history.lock().await;
+    let Some(store) = history.as_ref() else { return Vec::new() };
+    store.pinned().unwrap_or_else(|e| {
+        log::warn!("⚠️ Failed to read pinned transcriptions: {}", e);
+        Vec::new()
+    })
+}
+
+// Pin or unpin a history entry and rebuild the tray menu
+pub async fn set(app: &AppHandle, id: i64, pinned: bool) -> Result<(), String> {
+    let state: tauri::State<AppState> = app.state();
+    {
+        let mut history = state.history.lock().await;
+        let store = history.as_mut().ok_or("History is not available")?;
+        if !store.set_pinned(id, pinned).map_err(|e| e.to_string())? {
+            return Err(format!("No history entry with id {}", id));
+        }
+    }
+    log::info!("📌 Transcription #{} {}", id, if pinned { "pinned" } else { "unpinned" });
+    crate::refresh_tray(app).await;
+    Ok(())
+}
+
+// Insert a pinned entry's text into the window that had focus before the tray menu
+pub async fn insert(app: &AppHandle, id: i64) {
+    let state: tauri::State<AppState> = app.state();
+    let Some(text) = entries(&state).await.into_iter().find(|entry| entry.id == id).map(|entry| entry.text) else {
+        log::warn!("⚠️ Pinned transcription #{} is gone", id);
+        return;
+    };
+
+    // Focus goes back to that window once the menu closes
+    let injection = state.injection.lock().await.clone();
+    tokio::time::sleep(tokio::time::Duration::from_millis(injection.focus_return_ms)).await;
+    log::info!("📌 Inserting pinned transcription #{}", id);
+    crate::deliver_text(app, &state, &text, &injection).await;
+}
+
+// "Pinned" submenu: the first line of each entry, shortened
+pub fn submenu(app: &AppHandle, entries: &[HistoryEntry]) -> tauri::Result<Submenu<Wry>> {
+    let text = format!("📌 {}", crate::locale::tr("tray.pinned"));
+    if entries.is_empty() {
+        let none = MenuItem::with_id(app, "pinned_none", crate::locale::tr("tray.pinned_none"), false, None::<&str>)?;
+        return Submenu::with_items(app, text, true, &[&none]);
+    }
+
+    let items = entries
+        .iter()
+        .map(|entry| MenuItem::with_id(app, format!("{}{}", ID_PREFIX, entry.id), label(&entry.text), true, None::<&str>))
+        .collect::<tauri::Result<Vec<_>>>()?;
+    let items: Vec<&dyn IsMenuItem<Wry>> = items.iter().map(|item| item as &dyn IsMenuItem<Wry>).collect();
+    Submenu::with_items(app, text, true, &items)
+}
+
+fn label(text: &str) -> String {
+    let line = text.lines().map(str::trim).find(|line| !line.is_empty()).unwrap_or_default();
+    let mut label: String = line.chars().take(LABEL_CHARS).collect();
+    if line.chars().count() > LABEL_CHARS || text.trim().lines().count() > 1 {
+        label.push('…');
+    }
+    // A single & marks a menu shortcut key on Windows
+    label.replace('&', "&&")
+}
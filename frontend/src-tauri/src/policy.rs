@@ -0,0 +1,151 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use tauri::{AppHandle, Manager};
+use windows::core::w;
+use windows::Win32::System::Registry::{RegGetValueW, HKEY_LOCAL_MACHINE, RRF_RT_REG_SZ};
+
+use crate::{privacy, quick_switch, AppState};
+
+// Machine-wide policy for managed PCs: IT can pre-configure settings for every user and lock them,
+// with %ProgramData%\Whisper4Windows\policy.json or, taking precedence (for Group Policy), the same
+// JSON in the "Policy" string value under HKLM\SOFTWARE\Policies\Whisper4Windows. Every key is
+// optional; a key that's set is applied at start and after a settings import, and changing it is
+// refused. Both places are only writable by administrators.
+
+const POLICY_FILE: &str = "Whisper4Windows\\policy.json";
+const POLICY_VALUE: &str = "HKLM\\SOFTWARE\\Policies\\Whisper4Windows\\Policy";
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Policy {
+    pub model: Option<String>,  // Model of the main hotkey
+    pub device: Option<String>,  // Where the backend transcribes: "auto", "cuda", or "cpu"
+    pub language: Option<String>,
+    pub models_dir: Option<String>,  // Folder the backend keeps models in, e.g. a share with pre-deployed models
+    pub allow_downloads: Option<bool>,  // false: models are never downloaded, only loaded from the models folder
+    pub allow_caption_server: Option<bool>,  // false: the caption server stays off. It only listens on 127.0.0.1, where apps with its token read the live captions
+    pub privacy_mode: Option<bool>,  // Privacy mode always on (or always off)
+}
+
+// Where the policy came from, for the settings window and the log
+#[derive(Debug, Clone, Serialize)]
+pub struct PolicyInfo {
+    pub source: Option<String>,
+    pub locked: Vec<&'static str>,  // Keys of the settings the policy sets
+    pub values: Policy,
+}
+
+static POLICY: OnceLock<(Policy, Option<String>)> = OnceLock::new();
+
+pub fn get() -> &'static Policy {
+    &loaded().0
+}
+
+pub fn info() -> PolicyInfo {
+    PolicyInfo { source: loaded().1.clone(), locked: locked(), values: get().clone() }
+}
+
+fn loaded() -> &'static (Policy, Option<String>) {
+    POLICY.get_or_init(|| {
+        let found = read_registry().map(|json| (json, POLICY_VALUE.to_string())).or_else(|| {
+            let path = policy_path()?;
+            std::fs::read_to_string(&path).ok().map(|json| (json, path.display().to_string()))
+        });
+        let Some((json, source)) = found else {
+            return (Policy::default(), None);
+        };
+        match serde_json::from_str::<Policy>(&json) {
+            Ok(policy) => {
+                log::info!("🏢 Policy from {}: {:?}", source, policy);
+                (policy, Some(source))
+            }
+            // A broken policy locks nothing rather than keeping the app from starting
+            Err(e) => {
+                log::error!("❌ Ignoring invalid policy in {}: {}", source, e);
+                (Policy::default(), None)
+            }
+        }
+    })
+}
+
+fn policy_path() -> Option<PathBuf> {
+    Some(PathBuf::from(std::env::var_os("ProgramData")?).join(POLICY_FILE))
+}
+
+fn read_registry() -> Option<String> {
+    let mut size = 0u32;
+    let key = w!("SOFTWARE\\Policies\\Whisper4Windows");
+    let value = w!("Policy");
+    unsafe {
+        RegGetValueW(HKEY_LOCAL_MACHINE, key, value, RRF_RT_REG_SZ, None, None, Some(&mut size)).ok()?;
+        let mut buffer = vec![0u16; size as usize / 2];
+        RegGetValueW(HKEY_LOCAL_MACHINE, key, value, RRF_RT_REG_SZ, None, Some(buffer.as_mut_ptr().cast()), Some(&mut size))
+            .ok()?;
+        let text = String::from_utf16_lossy(&buffer);
+        Some(text.trim_end_matches('\0').to_string())
+    }
+}
+
+// Keys of the settings the policy sets, as named in the policy file
+pub fn locked() -> Vec<&'static str> {
+    let policy = get();
+    let keys = [
+        ("model", policy.model.is_some()),
+        ("device", policy.device.is_some()),
+        ("language", policy.language.is_some()),
+        ("models_dir", policy.models_dir.is_some()),
+        ("allow_downloads", policy.allow_downloads.is_some()),
+        ("allow_caption_server", policy.allow_caption_server.is_some()),
+        ("privacy_mode", policy.privacy_mode.is_some()),
+    ];
+    keys.into_iter().filter(|(_, set)| *set).map(|(key, _)| key).collect()
+}
+
+// Refuse changing a setting to anything but the policy's value
+pub fn check<T: PartialEq + ?Sized>(key: &str, fixed: Option<&T>, value: &T) -> Result<(), String> {
+    match fixed {
+        Some(fixed) if fixed != value => Err(format!("\"{}\" is set by your administrator", key)),
+        _ => Ok(()),
+    }
+}
+
+pub fn downloads_allowed() -> bool {
+    get().allow_downloads != Some(false)
+}
+
+// Put the settings the policy sets into effect (at start, and after an import may have changed them)
+pub async fn apply(app: &AppHandle) {
+    let policy = get();
+    let state: tauri::State<AppState> = app.state();
+    let mut selection_changed = false;
+    if let Some(model) = &policy.model {
+        selection_changed |= std::mem::replace(&mut *state.selected_model.lock().await, model.clone()) != *model;
+    }
+    if let Some(language) = &policy.language {
+        selection_changed |= std::mem::replace(&mut *state.selected_language.lock().await, language.clone()) != *language;
+    }
+    if let Some(device) = &policy.device {
+        selection_changed |= std::mem::replace(&mut *state.selected_device.lock().await, device.clone()) != *device;
+    }
+    if selection_changed {
+        quick_switch::save(app).await;
+    }
+    if let Some(enabled) = policy.privacy_mode {
+        if state.privacy.lock().await.enabled != enabled {
+            privacy::set_enabled(app, enabled).await;
+        }
+    }
+}
+
+// Environment for the backend: the models folder, and offline mode when downloads aren't allowed
+pub fn backend_env() -> Vec<(&'static str, String)> {
+    let mut env = Vec::new();
+    if let Some(dir) = &get().models_dir {
+        env.push(("WHISPER4WINDOWS_MODELS_DIR", dir.clone()));
+    }
+    if !downloads_allowed() {
+        env.push(("HF_HUB_OFFLINE", "1".to_string()));
+    }
+    env
+}
@@ -0,0 +1,93 @@
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+use crate::{backend, microphones, AppState};
+
+// A rolling pre-buffer so the first word isn't clipped: while it's on, the backend keeps the
+// microphone open between recordings and holds its last few seconds in memory, and a recording
+// starts with them. Off by default since the microphone is then always open (Windows shows it in
+// use); the audio never leaves memory and is overwritten continuously unless a recording starts.
+// The tray tooltip says when it's listening.
+
+pub const CONFIG_FILE: &str = "prebuffer.json";
+
+// How long to wait for the backend to answer after it starts
+const BACKEND_ATTEMPTS: u32 = 30;
+const BACKEND_RETRY: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PrebufferSettings {
+    pub enabled: bool,
+    pub seconds: f64,  // Audio kept from before a recording starts, 1-3
+}
+
+impl Default for PrebufferSettings {
+    fn default() -> Self {
+        Self { enabled: false, seconds: 2.0 }
+    }
+}
+
+pub fn validate(settings: &PrebufferSettings) -> Result<(), String> {
+    if !(1.0..=3.0).contains(&settings.seconds) {
+        return Err("The pre-buffer can hold 1 to 3 seconds".to_string());
+    }
+    Ok(())
+}
+
+// Whether the backend has the microphone open into the pre-buffer, for the tray
+static ACTIVE: AtomicBool = AtomicBool::new(false);
+
+pub fn active() -> bool {
+    ACTIVE.load(Ordering::SeqCst)
+}
+
+// Send the settings once the (possibly just started) backend answers
+pub fn spawn_apply(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        for attempt in 0..BACKEND_ATTEMPTS {
+            if attempt > 0 {
+                tokio::time::sleep(BACKEND_RETRY).await;
+            }
+            if apply(&app).await.is_ok() {
+                return;
+            }
+        }
+        log::warn!("⚠️ Backend didn't answer; pre-buffer not applied");
+    });
+}
+
+// Send the settings and the microphone a recording would use; returns whether the pre-buffer is
+// listening. Called again whenever either changes.
+pub async fn apply(app: &AppHandle) -> Result<bool, String> {
+    let state: tauri::State<AppState> = app.state();
+    let settings = state.prebuffer.lock().await.clone();
+    let selected = *state.selected_microphone.lock().await;
+    let candidates = if settings.enabled { microphones::candidates(&state).await } else { Vec::new() };
+    let mut request = serde_json::json!({
+        "enabled": settings.enabled,
+        "seconds": settings.seconds,
+        "device_index": selected,
+        "device_indices": candidates,
+    });
+    state.microphones.lock().await.add_to_request(&mut request);
+
+    let resp = backend::client()
+        .post(backend::url("prebuffer"))
+        .json(&request)
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {}", e))?;
+    let data = resp.json::<serde_json::Value>().await.map_err(|e| format!("Invalid response: {}", e))?;
+    if data.get("status").and_then(|s| s.as_str()) != Some("success") {
+        let message = data.get("message").and_then(|m| m.as_str()).unwrap_or("Could not set the pre-buffer");
+        return Err(message.to_string());
+    }
+    let active = data.get("active").and_then(|a| a.as_bool()).unwrap_or(false);
+    if ACTIVE.swap(active, Ordering::SeqCst) != active {
+        crate::refresh_tray(app).await;
+    }
+    Ok(active)
+}
@@ -0,0 +1,83 @@
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::AppState;
+
+pub const CONFIG_FILE: &str = "privacy.json";
+
+// Privacy mode config
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PrivacySettings {
+    pub expire_after_mins: u64,  // Turn privacy mode off again after this long (0 = stay on until turned off)
+}
+
+impl Default for PrivacySettings {
+    fn default() -> Self {
+        Self { expire_after_mins: 60 }
+    }
+}
+
+// While enabled: no history, no statistics, no retained audio, no transcript previews
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct PrivacyMode {
+    pub enabled: bool,
+    pub expires_at: Option<i64>,  // Unix time in milliseconds
+    #[serde(skip)]
+    generation: u64,  // Bumped on every change so stale expiry timers do nothing
+}
+
+pub async fn is_enabled(state: &AppState) -> bool {
+    state.privacy.lock().await.enabled
+}
+
+// Turn privacy mode on or off, (re)starting its expiry timer
+pub async fn set_enabled(app: &AppHandle, enabled: bool) {
+    // A policy that sets privacy mode keeps it that way, without expiring
+    let fixed = crate::policy::get().privacy_mode;
+    if fixed.is_some_and(|fixed| fixed != enabled) {
+        log::warn!("🏢 Privacy mode is set by your administrator");
+        return;
+    }
+    let state: tauri::State<AppState> = app.state();
+    let expire_after_mins = if fixed.is_some() { 0 } else { state.privacy_settings.lock().await.expire_after_mins };
+    let generation = apply(app, enabled, expire_after_mins).await;
+
+    if enabled && expire_after_mins > 0 {
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move {
+            tokio::time::sleep(std::time::Duration::from_secs(expire_after_mins * 60)).await;
+
+            let state: tauri::State<AppState> = app.state();
+            let current = state.privacy.lock().await.generation;
+            if current == generation {
+                apply(&app, false, 0).await;
+                crate::show_toast(&app, &crate::locale::tr("toast.privacy_ended"), &crate::locale::tr("toast.privacy_ended_body"));
+            }
+        });
+    }
+}
+
+pub async fn toggle(app: &AppHandle) {
+    let state: tauri::State<AppState> = app.state();
+    let enabled = is_enabled(&state).await;
+    set_enabled(app, !enabled).await;
+}
+
+// Update the mode and everything showing it; returns the new generation
+async fn apply(app: &AppHandle, enabled: bool, expire_after_mins: u64) -> u64 {
+    let state: tauri::State<AppState> = app.state();
+    let mode = {
+        let mut privacy = state.privacy.lock().await;
+        privacy.enabled = enabled;
+        privacy.generation += 1;
+        privacy.expires_at = (enabled && expire_after_mins > 0)
+            .then(|| chrono::Utc::now().timestamp_millis() + expire_after_mins as i64 * 60_000);
+        privacy.clone()
+    };
+
+    log::info!("🔒 Privacy mode {}", if enabled { "on" } else { "off" });
+    let _ = app.emit("privacy-mode-changed", &mode);
+    crate::refresh_tray(app).await;
+    mode.generation
+}
@@ -0,0 +1,134 @@
+use serde::{Deserialize, Serialize};
+
+// Built-in profanity per language code (matched case-insensitively on whole words)
+const BUILTIN_WORDS: &[(&str, &[&str])] = &[
+    ("en", &[
+        "fuck", "fucks", "fucking", "fucked", "fucker", "motherfucker", "shit", "shits", "shitty", "bullshit",
+        "bitch", "bitches", "bastard", "asshole", "assholes", "dick", "dickhead", "cunt", "damn", "goddamn",
+        "crap", "piss", "pissed", "wanker", "twat", "prick", "slut", "whore",
+    ]),
+    ("es", &[
+        "mierda", "puta", "puto", "putas", "joder", "jodido", "coño", "cabrón", "cabron", "gilipollas", "pendejo",
+        "pendeja", "carajo", "chingar", "chingada", "culero", "hostia",
+    ]),
+    ("fr", &[
+        "merde", "putain", "pute", "connard", "connasse", "salope", "salaud", "enculé", "encule", "bordel",
+        "foutre", "couille", "couilles", "bite",
+    ]),
+    ("de", &[
+        "scheiße", "scheisse", "scheiß", "scheiss", "arschloch", "fotze", "wichser", "verdammt", "miststück",
+        "hurensohn", "schlampe", "fick", "ficken", "kacke",
+    ]),
+    ("it", &[
+        "cazzo", "merda", "stronzo", "stronza", "puttana", "vaffanculo", "minchia", "coglione", "porca",
+    ]),
+    ("pt", &[
+        "merda", "porra", "caralho", "foda", "fodase", "puta", "cacete", "buceta", "viado",
+    ]),
+];
+
+// What happens to a matched word
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ProfanityMode {
+    #[default]
+    Off,
+    Mask,  // Keep the first letter: "shit" -> "s***"
+    Remove,
+}
+
+// Words censored as part of formatting, shared by every profile; each profile picks its own mode
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProfanityWords {
+    pub use_builtin: bool,  // Use the built-in list for the transcription language
+    pub custom_words: Vec<String>,  // Extra words to censor; a trailing '*' matches any ending ("damn*")
+    pub allowed_words: Vec<String>,  // Built-in words to let through
+}
+
+impl Default for ProfanityWords {
+    fn default() -> Self {
+        Self {
+            use_builtin: true,
+            custom_words: Vec::new(),
+            allowed_words: Vec::new(),
+        }
+    }
+}
+
+// Built-in words for `language` ("en", "en-US"...), or for every language if unknown/auto
+fn builtin_words(language: Option<&str>) -> Vec<&'static str> {
+    let code = language
+        .map(|l| l.split(['-', '_']).next().unwrap_or(l).to_lowercase())
+        .filter(|code| BUILTIN_WORDS.iter().any(|(lang, _)| lang == code));
+
+    BUILTIN_WORDS
+        .iter()
+        .filter(|(lang, _)| code.as_deref().map_or(true, |code| code == *lang))
+        .flat_map(|(_, words)| words.iter().copied())
+        .collect()
+}
+
+fn matches_pattern(word: &str, pattern: &str) -> bool {
+    let pattern = pattern.trim().to_lowercase();
+    match pattern.strip_suffix('*') {
+        Some(prefix) => !prefix.is_empty() && word.starts_with(prefix),
+        None => !pattern.is_empty() && word == pattern,
+    }
+}
+
+fn is_profane(word: &str, words: &ProfanityWords, builtin: &[&str]) -> bool {
+    let word = word.to_lowercase();
+    if words.allowed_words.iter().any(|allowed| allowed.trim().to_lowercase() == word) {
+        return false;
+    }
+    builtin.contains(&word.as_str()) || words.custom_words.iter().any(|pattern| matches_pattern(&word, pattern))
+}
+
+fn mask(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => std::iter::once(first).chain(chars.map(|_| '*')).collect(),
+        None => String::new(),
+    }
+}
+
+// Mask or remove profane words, keeping the punctuation around them
+pub fn apply(text: &str, mode: ProfanityMode, words: &ProfanityWords, language: Option<&str>) -> String {
+    if mode == ProfanityMode::Off {
+        return text.to_string();
+    }
+    let builtin = if words.use_builtin { builtin_words(language) } else { Vec::new() };
+
+    let mut kept: Vec<String> = Vec::new();
+    for token in text.split_whitespace() {
+        // Only the letters are compared, so "word," and "(word)" still match
+        let Some(start) = token.find(char::is_alphanumeric) else {
+            kept.push(token.to_string());
+            continue;
+        };
+        let end = token
+            .char_indices()
+            .rev()
+            .find(|(_, c)| c.is_alphanumeric())
+            .map(|(i, c)| i + c.len_utf8())
+            .unwrap_or(token.len());
+        let word = &token[start..end];
+
+        if !is_profane(word, words, &builtin) {
+            kept.push(token.to_string());
+        } else if mode == ProfanityMode::Mask {
+            kept.push(format!("{}{}{}", &token[..start], mask(word), &token[end..]));
+        } else if let Some(last_char) = token[end..].chars().last().filter(|c| matches!(c, '.' | '!' | '?')) {
+            // Removed: keep a sentence ending on the previous word ("...that shit." -> "...that.")
+            if let Some(prev) = kept.last_mut() {
+                let prev_trimmed = prev.trim_end_matches([',', ';', ':']).to_string();
+                if !prev_trimmed.ends_with(['.', '!', '?']) {
+                    *prev = format!("{}{}", prev_trimmed, last_char);
+                }
+            }
+        }
+    }
+
+    kept.join(" ")
+}
@@ -0,0 +1,88 @@
+use serde_json::Value;
+use std::path::Path;
+use tauri::AppHandle;
+
+use crate::settings_bundle;
+
+// Unattended setup for deployment scripts: `--import-config <file>` applies a settings file as
+// exported from the settings window, and `--set key=value` (repeatable) changes one setting, e.g.
+// `--set model=small --set device=cpu --set hotkeys.toggle=Ctrl+Alt+Space`. Keys are the
+// settings file's field names, with dots for nested ones; values are JSON, or text for strings.
+// The file goes first, then the `--set`s in order. Both are applied at startup, or by the running
+// instance when it's started again with them. An administrator's policy still wins.
+
+pub const IMPORT_ARG: &str = "--import-config";
+pub const SET_ARG: &str = "--set";
+
+pub fn requested(args: &[String]) -> bool {
+    args.iter().any(|arg| arg == IMPORT_ARG || arg == SET_ARG)
+}
+
+// Apply the settings on the command line (relative paths are from `cwd`); returns whether there were any
+pub async fn run(app: &AppHandle, args: &[String], cwd: &Path) -> bool {
+    if !requested(args) {
+        return false;
+    }
+    match apply(app, args, cwd).await {
+        Ok(()) => log::info!("📥 Applied settings from the command line"),
+        Err(e) => {
+            log::error!("❌ Settings from the command line not applied: {}", e);
+            crate::show_toast(app, &crate::locale::tr("toast.settings_not_applied"), &e);
+        }
+    }
+    true
+}
+
+async fn apply(app: &AppHandle, args: &[String], cwd: &Path) -> Result<(), String> {
+    let mut file = None;
+    let mut sets = Vec::new();
+    let mut args = args.iter().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            IMPORT_ARG => file = Some(args.next().ok_or("--import-config needs a settings file")?),
+            SET_ARG => sets.push(args.next().ok_or("--set needs key=value")?.as_str()),
+            _ => {}
+        }
+    }
+
+    if let Some(file) = file {
+        let path = cwd.join(file);
+        let json = std::fs::read_to_string(&path).map_err(|e| format!("Couldn't read {}: {}", path.display(), e))?;
+        settings_bundle::import(app, &json).await.map_err(|e| format!("{}: {}", path.display(), e))?;
+        log::info!("📥 Imported {}", path.display());
+    }
+    if !sets.is_empty() {
+        let bundle = changes(app, &sets).await?;
+        settings_bundle::import(app, &bundle.to_string()).await?;
+    }
+    Ok(())
+}
+
+// A settings bundle with just the settings `sets` touch, each starting from its current value so
+// a nested key leaves its neighbours alone
+async fn changes(app: &AppHandle, sets: &[&str]) -> Result<Value, String> {
+    let current = serde_json::to_value(settings_bundle::export(app).await).map_err(|e| e.to_string())?;
+    let mut bundle = serde_json::json!({ "schema_version": settings_bundle::SCHEMA_VERSION });
+    for set in sets {
+        let (key, text) = set.split_once('=').ok_or_else(|| format!("Expected key=value: {}", set))?;
+        let value = serde_json::from_str(text).unwrap_or_else(|_| Value::String(text.to_string()));
+        let unknown = || format!("Unknown setting: {}", key);
+
+        // Hotkeys are a section of their own; everything else is in "settings"
+        let mut path = key.trim().split('.').peekable();
+        let section = if path.peek() == Some(&"hotkeys") { path.next().unwrap_or_default() } else { "settings" };
+        let field = path.next().filter(|field| !field.is_empty()).ok_or_else(unknown)?;
+        let start = current[section].get(field).ok_or_else(unknown)?.clone();
+
+        if bundle[section].is_null() {
+            bundle[section] = serde_json::json!({});
+        }
+        let mut target = bundle[section].as_object_mut().ok_or_else(unknown)?.entry(field).or_insert(start);
+        for segment in path {
+            target = target.get_mut(segment).ok_or_else(unknown)?;
+        }
+        log::info!("⚙️ Setting {} = {}", key, value);
+        *target = value;
+    }
+    Ok(bundle)
+}
@@ -0,0 +1,175 @@
+use serde::{Deserialize, Serialize};
+use tauri::menu::{CheckMenuItem, IsMenuItem, Submenu};
+use tauri::{AppHandle, Manager, Wry};
+
+use crate::custom_models::CustomModel;
+use crate::{storage, AppState};
+
+pub const CONFIG_FILE: &str = "selection.json";
+
+// Menu item ids are "<prefix><value>"
+pub const MODEL_ID_PREFIX: &str = "model:";
+pub const LANGUAGE_ID_PREFIX: &str = "language:";
+
+// Models offered in the tray, in the settings window's order
+const MODELS: &[(&str, &str)] = &[
+    ("tiny", "Tiny (Fastest)"),
+    ("base", "Base"),
+    ("small", "Small (Recommended)"),
+    ("medium", "Medium"),
+    ("large-v3", "Large V3 (Best)"),
+    ("large-v3-turbo", "Large V3 Turbo (Fast)"),
+    ("distil-large-v3", "Distil Large V3 (English)"),
+    ("distil-medium.en", "Distil Medium (English)"),
+    ("distil-small.en", "Distil Small (English)"),
+];
+
+// Distil-Whisper and ".en" models only transcribe English (the backend transcribes everything as English with them)
+pub fn is_english_only(model: &str) -> bool {
+    model.starts_with("distil-") || model.ends_with(".en")
+}
+
+// Languages offered in the tray; the rest stay in the settings window (the current one is always listed)
+const LANGUAGES: &[(&str, &str)] = &[
+    ("auto", "Auto-Detect"),
+    ("en", "English"),
+    ("es", "Spanish"),
+    ("fr", "French"),
+    ("de", "German"),
+    ("it", "Italian"),
+    ("pt", "Portuguese"),
+    ("nl", "Dutch"),
+    ("pl", "Polish"),
+    ("ru", "Russian"),
+    ("zh", "Chinese"),
+    ("ja", "Japanese"),
+    ("ko", "Korean"),
+];
+
+// Model, language, and device, persisted so a choice made from the tray survives a restart
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct Selection {
+    pub model: Option<String>,
+    pub language: Option<String>,
+    pub device: Option<String>,
+}
+
+// Restore the persisted selection into the app state (called once in setup)
+pub async fn load(app: &AppHandle) {
+    let selection: Selection = storage::load_json(app, CONFIG_FILE);
+    let state: tauri::State<AppState> = app.state();
+    if let Some(model) = selection.model {
+        *state.selected_model.lock().await = model;
+    }
+    if let Some(language) = selection.language {
+        *state.selected_language.lock().await = language;
+    }
+    if let Some(device) = selection.device {
+        *state.selected_device.lock().await = device;
+    }
+}
+
+// Persist the current selection, rebuild the tray, and update the settings window if it is open
+pub async fn save(app: &AppHandle) {
+    let state: tauri::State<AppState> = app.state();
+    let selection = Selection {
+        model: Some(state.selected_model.lock().await.clone()),
+        language: Some(state.selected_language.lock().await.clone()),
+        device: Some(state.selected_device.lock().await.clone()),
+    };
+    if let Err(e) = storage::save_json(app, CONFIG_FILE, &selection) {
+        log::error!("❌ Failed to save model/language selection: {}", e);
+    }
+
+    crate::refresh_tray(app).await;
+    if let (Some(win), Ok(json)) = (app.get_webview_window("main"), serde_json::to_string(&selection)) {
+        let _ = win.eval(format!("if (window.applySelection) applySelection({})", json));
+    }
+}
+
+pub async fn select_model(app: &AppHandle, model: &str) {
+    if let Err(e) = crate::policy::check("model", crate::policy::get().model.as_deref(), model) {
+        log::warn!("🏢 {}", e);
+        return;
+    }
+    let state: tauri::State<AppState> = app.state();
+    *state.selected_model.lock().await = model.to_string();
+    log::info!("🧠 Model switched from tray: {}", model);
+    save(app).await;
+}
+
+pub async fn select_language(app: &AppHandle, language: &str) {
+    if let Err(e) = crate::policy::check("language", crate::policy::get().language.as_deref(), language) {
+        log::warn!("🏢 {}", e);
+        return;
+    }
+    let state: tauri::State<AppState> = app.state();
+    *state.selected_language.lock().await = language.to_string();
+    log::info!("🌐 Language switched from tray: {}", language);
+    save(app).await;
+}
+
+// Ask the backend which models are downloaded and rebuild the tray if that changed
+pub async fn refresh_installed_models(app: &AppHandle) {
+    let client = crate::backend::client();
+    let Ok(resp) = client.get(crate::backend::url("models")).send().await else { return };
+    let Ok(data) = resp.json::<serde_json::Value>().await else { return };
+    let Some(models) = data.get("models").and_then(|m| serde_json::from_value::<Vec<String>>(m.clone()).ok()) else {
+        return;
+    };
+
+    let state: tauri::State<AppState> = app.state();
+    {
+        let mut installed = state.installed_models.lock().await;
+        if installed.as_ref() == Some(&models) {
+            return;
+        }
+        *installed = Some(models);
+    }
+    crate::refresh_tray(app).await;
+}
+
+// "Model" submenu with the custom models last; offered models not downloaded yet are marked
+// (None = backend not asked yet)
+pub fn model_submenu(
+    app: &AppHandle,
+    current: &str,
+    installed: Option<&[String]>,
+    custom: &[CustomModel],
+) -> tauri::Result<Submenu<Wry>> {
+    let offered = MODELS.iter().map(|(value, name)| {
+        let downloaded = installed.map_or(true, |models| models.iter().any(|m| m == value));
+        let label = if downloaded { name.to_string() } else { crate::locale::tr_with("tray.download", &[("name", name)]) };
+        (value.to_string(), label)
+    });
+    let items = offered
+        .chain(custom.iter().map(|m| (m.source.clone(), m.name.clone())))
+        .map(|(value, label)| {
+            CheckMenuItem::with_id(app, format!("{}{}", MODEL_ID_PREFIX, value), label, true, value == current, None::<&str>)
+        })
+        .collect::<tauri::Result<Vec<_>>>()?;
+    submenu(app, &format!("🧠 {}", crate::locale::tr("tray.model")), &items)
+}
+
+// With an English-only model, the other languages are listed but disabled
+pub fn language_submenu(app: &AppHandle, current: &str, english_only: bool) -> tauri::Result<Submenu<Wry>> {
+    let mut languages: Vec<(&str, &str)> = LANGUAGES.to_vec();
+    if !languages.iter().any(|(code, _)| *code == current) {
+        languages.push((current, current));
+    }
+
+    let items = languages
+        .iter()
+        .map(|(code, name)| {
+            let enabled = !english_only || *code == "en";
+            CheckMenuItem::with_id(app, format!("{}{}", LANGUAGE_ID_PREFIX, code), *name, enabled, *code == current, None::<&str>)
+        })
+        .collect::<tauri::Result<Vec<_>>>()?;
+    submenu(app, &format!("🌐 {}", crate::locale::tr("tray.language")), &items)
+}
+
+fn submenu(app: &AppHandle, text: &str, items: &[CheckMenuItem<Wry>]) -> tauri::Result<Submenu<Wry>> {
+    let items: Vec<&dyn IsMenuItem<Wry>> = items.iter().map(|item| item as &dyn IsMenuItem<Wry>).collect();
+    Submenu::with_items(app, text, true, &items)
+}
@@ -0,0 +1,167 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+use windows::core::{HSTRING, PCWSTR};
+use windows::Win32::UI::Controls::{
+    TaskDialogIndirect, TASKDIALOGCONFIG, TASKDIALOG_BUTTON, TASKDIALOG_FLAGS, TDF_ALLOW_DIALOG_CANCELLATION, TDF_USE_COMMAND_LINKS,
+    TD_WARNING_ICON,
+};
+
+use crate::locale::tr;
+use crate::recording_state::{self, RecordingState};
+use crate::{ducking, AppState};
+
+// Quitting the app (tray, jump list, or closing the window when it doesn't close to the tray).
+// A recording or transcription still in flight is finished or discarded first, as the user picks,
+// and history and statistics are written out before the process exits.
+
+// How often finishing checks whether the transcription is done
+const FINISH_POLL: Duration = Duration::from_millis(200);
+
+// Task dialog button IDs
+const FINISH_BUTTON: i32 = 100;
+const DISCARD_BUTTON: i32 = 101;
+const STAY_BUTTON: i32 = 102;
+
+// Set while a quit is being asked about or carried out, so a second Quit doesn't stack another dialog
+static QUITTING: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Choice {
+    Finish,
+    Discard,
+    Stay,
+}
+
+fn in_flight(current: RecordingState) -> bool {
+    matches!(current, RecordingState::Recording | RecordingState::Processing | RecordingState::Injecting)
+}
+
+// Quit, asking first if that would lose a recording or transcription
+pub fn request(app: &AppHandle) {
+    if QUITTING.swap(true, Ordering::SeqCst) {
+        log::info!("👋 Already quitting");
+        return;
+    }
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let state: tauri::State<AppState> = app.state();
+        let current = recording_state::get(&state).await;
+        if in_flight(current) {
+            match ask(current).await {
+                Choice::Stay => {
+                    log::info!("↩️ Quit cancelled");
+                    QUITTING.store(false, Ordering::SeqCst);
+                    return;
+                }
+                Choice::Finish => finish_work(&app).await,
+                Choice::Discard => {
+                    // It may have finished on its own while the dialog was open
+                    if in_flight(recording_state::get(&state).await) {
+                        log::info!("🗑️ Discarding work in flight before quitting");
+                        let _ = crate::cmd_cancel_recording(app.clone()).await;
+                    }
+                }
+            }
+        }
+        exit(&app).await;
+    });
+}
+
+// Stop a recording and wait for its transcription to be delivered
+async fn finish_work(app: &AppHandle) {
+    let state: tauri::State<AppState> = app.state();
+    // Nothing queued behind it should start once it's done
+    *state.recording_queued.lock().await = false;
+    log::info!("⏳ Finishing work in flight before quitting");
+    if recording_state::is_recording(&state).await {
+        // Returns once the text is delivered (or the transcription failed)
+        let _ = crate::cmd_stop_recording(app.clone(), app.state()).await;
+    }
+    while matches!(recording_state::get(&state).await, RecordingState::Processing | RecordingState::Injecting) {
+        tokio::time::sleep(FINISH_POLL).await;
+    }
+}
+
+// Write everything out, stop the backend, and exit
+async fn exit(app: &AppHandle) {
+    let state: tauri::State<AppState> = app.state();
+    ducking::restore().await;
+    crate::remember_overlay_position(app).await;
+
+    // Taking the stores waits for a write in progress; history is saved after every change, and
+    // closing the statistics database checkpoints it
+    if state.history.lock().await.take().is_some() {
+        log::info!("🗂️ History closed");
+    }
+    if state.stats.lock().await.take().is_some() {
+        log::info!("📊 Statistics closed");
+    }
+
+    if let Some(child) = state.backend_child.lock().await.take() {
+        log::info!("🛑 Killing backend process...");
+        match child.kill() {
+            Ok(_) => {
+                log::info!("✅ Backend process kill signal sent");
+                // Give it a moment to terminate
+                tokio::time::sleep(Duration::from_millis(200)).await;
+            }
+            Err(e) => {
+                log::warn!("⚠️ Failed to kill backend: {}", e);
+            }
+        }
+    }
+    log::info!("👋 Exiting application");
+    app.exit(0);
+}
+
+// Ask what to do with the work in flight
+async fn ask(current: RecordingState) -> Choice {
+    let instruction = tr(if current == RecordingState::Recording { "quit.recording" } else { "quit.processing" });
+    let (sender, receiver) = tokio::sync::oneshot::channel();
+    // The dialog runs its own message loop, so it gets a thread of its own
+    std::thread::spawn(move || {
+        let _ = sender.send(show_dialog(&instruction));
+    });
+    receiver.await.unwrap_or(Choice::Stay)
+}
+
+fn show_dialog(instruction: &str) -> Choice {
+    let title = HSTRING::from(tr("quit.title"));
+    let instruction = HSTRING::from(instruction);
+    let content = HSTRING::from(tr("quit.content"));
+    let labels = [
+        (FINISH_BUTTON, HSTRING::from(tr("quit.finish"))),
+        (DISCARD_BUTTON, HSTRING::from(tr("quit.discard"))),
+        (STAY_BUTTON, HSTRING::from(tr("quit.stay"))),
+    ];
+    let buttons: Vec<TASKDIALOG_BUTTON> = labels
+        .iter()
+        .map(|(id, label)| TASKDIALOG_BUTTON { nButtonID: *id, pszButtonText: PCWSTR(label.as_ptr()) })
+        .collect();
+
+    let mut config = TASKDIALOGCONFIG {
+        cbSize: std::mem::size_of::<TASKDIALOGCONFIG>() as u32,
+        // Escape and the close button count as "Don't quit"
+        dwFlags: TASKDIALOG_FLAGS(TDF_USE_COMMAND_LINKS.0 | TDF_ALLOW_DIALOG_CANCELLATION.0),
+        pszWindowTitle: PCWSTR(title.as_ptr()),
+        pszMainInstruction: PCWSTR(instruction.as_ptr()),
+        pszContent: PCWSTR(content.as_ptr()),
+        cButtons: buttons.len() as u32,
+        pButtons: buttons.as_ptr(),
+        nDefaultButton: FINISH_BUTTON,
+        ..Default::default()
+    };
+    config.Anonymous1.pszMainIcon = TD_WARNING_ICON;
+
+    let mut pressed = 0;
+    if let Err(e) = unsafe { TaskDialogIndirect(&config, Some(&mut pressed), None, None) } {
+        log::error!("❌ Quit dialog failed: {}", e);
+        return Choice::Stay;
+    }
+    match pressed {
+        FINISH_BUTTON => Choice::Finish,
+        DISCARD_BUTTON => Choice::Discard,
+        _ => Choice::Stay,
+    }
+}
@@ -0,0 +1,125 @@
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use tauri::{AppHandle, Emitter, Manager};
+use windows::core::HSTRING;
+use windows::Win32::Storage::FileSystem::GetDiskFreeSpaceExW;
+
+use crate::AppState;
+
+pub const CONFIG_FILE: &str = "recording_limits.json";
+
+// What happens when a recording reaches `max_duration_secs`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum LimitAction {
+    #[default]
+    Stop,  // Stop, transcribe, and deliver as usual
+    Chunk,  // Deliver what we have and immediately keep recording
+}
+
+// Guards against runaway recordings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RecordingLimits {
+    pub max_duration_secs: u64,  // 0 = unlimited; paused time doesn't count
+    pub on_limit: LimitAction,
+    pub warn_after_secs: u64,  // 0 = no warning
+    pub min_free_disk_mb: u64,  // Refuse to start below this much free space in the temp directory
+}
+
+impl Default for RecordingLimits {
+    fn default() -> Self {
+        Self {
+            max_duration_secs: 15 * 60,
+            on_limit: LimitAction::Stop,
+            warn_after_secs: 10 * 60,
+            min_free_disk_mb: 200,
+        }
+    }
+}
+
+// Free space available to us on the volume holding `path`
+fn free_disk_bytes(path: &Path) -> Option<u64> {
+    let mut free = 0u64;
+    unsafe { GetDiskFreeSpaceExW(&HSTRING::from(path.to_string_lossy().as_ref()), Some(&mut free), None, None) }.ok()?;
+    Some(free)
+}
+
+// Fail if the temp directory (where audio is buffered) is nearly full
+pub fn check_disk_space(limits: &RecordingLimits) -> Result<(), String> {
+    if limits.min_free_disk_mb == 0 {
+        return Ok(());
+    }
+
+    let temp_dir = std::env::temp_dir();
+    let Some(free) = free_disk_bytes(&temp_dir) else {
+        log::warn!("⚠️ Could not read free space for {}", temp_dir.display());
+        return Ok(());
+    };
+
+    let free_mb = free / (1024 * 1024);
+    if free_mb < limits.min_free_disk_mb {
+        return Err(format!(
+            "Only {} MB free on the drive holding {} (need {} MB)",
+            free_mb,
+            temp_dir.display(),
+            limits.min_free_disk_mb
+        ));
+    }
+    Ok(())
+}
+
+// Watch the recording started as `session`: warn once, then stop or chunk at the limit.
+// Exits as soon as that recording ends.
+pub fn spawn_watchdog(app: AppHandle, session: u64, limits: RecordingLimits) {
+    if limits.max_duration_secs == 0 && limits.warn_after_secs == 0 {
+        return;
+    }
+
+    tauri::async_runtime::spawn(async move {
+        let mut elapsed_secs = 0u64;
+        let mut warned = false;
+
+        loop {
+            tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+
+            let state: tauri::State<AppState> = app.state();
+            if *state.recording_session.lock().await != session || !crate::recording_state::is_recording(&state).await {
+                return;
+            }
+            if *state.is_paused.lock().await {
+                continue;
+            }
+            elapsed_secs += 1;
+
+            if !warned && limits.warn_after_secs > 0 && elapsed_secs >= limits.warn_after_secs {
+                warned = true;
+                log::warn!("⏰ Recording has been running for {} minutes", elapsed_secs / 60);
+                let _ = app.emit("recording-warning", serde_json::json!({
+                    "elapsed_secs": elapsed_secs,
+                    "max_duration_secs": limits.max_duration_secs,
+                }));
+                crate::show_toast(
+                    &app,
+                    &crate::locale::tr("toast.still_recording"),
+                    &crate::locale::tr_with("toast.still_recording_body", &[("minutes", &(elapsed_secs / 60).to_string())]),
+                );
+            }
+
+            if limits.max_duration_secs > 0 && elapsed_secs >= limits.max_duration_secs {
+                log::warn!("⏰ Maximum recording duration reached ({}s) - {:?}", elapsed_secs, limits.on_limit);
+                let _ = app.emit("recording-limit-reached", serde_json::json!({
+                    "elapsed_secs": elapsed_secs,
+                    "action": limits.on_limit,
+                }));
+
+                let _ = crate::cmd_stop_recording(app.clone(), app.state()).await;
+                if limits.on_limit == LimitAction::Chunk {
+                    // The restarted recording gets its own watchdog
+                    let _ = crate::cmd_start_recording(app.clone(), app.state()).await;
+                }
+                return;
+            }
+        }
+    });
+}
@@ -0,0 +1,116 @@
+use serde::Serialize;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::recording_limits::{LimitAction, RecordingLimits};
+use crate::{backend, recording_state, AppState};
+
+// Live readout for the overlay while recording: elapsed time, input level, roughly how many
+// words fit before the limit, and a warning when the limit is close or the mic has gone quiet.
+
+const UPDATE_INTERVAL: Duration = Duration::from_millis(500);
+// Normalized input level (see /audio_level) below which the mic counts as picking up nothing
+const QUIET_LEVEL: f64 = 0.01;
+// How long the input has to stay that quiet before the overlay says so
+const QUIET_WARNING: Duration = Duration::from_secs(5);
+// Warn this long before `max_duration_secs` (at most a quarter of a short limit)
+const LIMIT_WARNING: Duration = Duration::from_secs(30);
+// Conversational dictation pace, for the word budget
+const WORDS_PER_MINUTE: f64 = 150.0;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MeterUpdate {
+    pub elapsed_secs: f64,  // Paused time doesn't count
+    pub level: f64,  // 0 to 1
+    pub words: u64,  // Approximate words spoken so far at a normal pace
+    pub word_budget: Option<u64>,  // Approximate words that fit in the max duration (None = unlimited)
+    pub warning: Option<String>,
+}
+
+// Push meter updates for the recording started as `session` until it ends
+pub fn spawn(app: AppHandle, session: u64, limits: RecordingLimits) {
+    tauri::async_runtime::spawn(async move {
+        let mut elapsed = Duration::ZERO;
+        let mut quiet = Duration::ZERO;
+        let mut last_tick = Instant::now();
+
+        loop {
+            tokio::time::sleep(UPDATE_INTERVAL).await;
+            let now = Instant::now();
+            let tick = now - last_tick;
+            last_tick = now;
+
+            let state: tauri::State<AppState> = app.state();
+            if *state.recording_session.lock().await != session || !recording_state::is_recording(&state).await {
+                return;
+            }
+            if *state.is_paused.lock().await {
+                quiet = Duration::ZERO;
+                continue;
+            }
+            elapsed += tick;
+
+            // None until the backend has started capturing
+            let level = input_level().await;
+            match level {
+                Some(level) if level < QUIET_LEVEL => quiet += tick,
+                _ => quiet = Duration::ZERO,
+            }
+
+            let update = MeterUpdate {
+                elapsed_secs: elapsed.as_secs_f64(),
+                level: level.unwrap_or(0.0),
+                words: words_in(elapsed),
+                word_budget: (limits.max_duration_secs > 0).then(|| words_in(Duration::from_secs(limits.max_duration_secs))),
+                warning: warning(&limits, elapsed, quiet),
+            };
+            push(&app, &update);
+        }
+    });
+}
+
+fn words_in(duration: Duration) -> u64 {
+    (duration.as_secs_f64() / 60.0 * WORDS_PER_MINUTE).round() as u64
+}
+
+fn warning(limits: &RecordingLimits, elapsed: Duration, quiet: Duration) -> Option<String> {
+    if limits.max_duration_secs > 0 {
+        let max = Duration::from_secs(limits.max_duration_secs);
+        let remaining = max.saturating_sub(elapsed);
+        if remaining <= LIMIT_WARNING.min(max / 4) {
+            let verb = match limits.on_limit {
+                LimitAction::Stop => "Stops",
+                LimitAction::Chunk => "Splits",
+            };
+            return Some(format!("{} in {}s", verb, remaining.as_secs_f64().ceil() as u64));
+        }
+    }
+    if quiet >= QUIET_WARNING {
+        return Some(format!("No input for {}s", quiet.as_secs()));
+    }
+    None
+}
+
+fn push(app: &AppHandle, update: &MeterUpdate) {
+    let _ = app.emit("recording-meter", update);
+    if let (Some(win), Ok(json)) = (app.get_webview_window("recording"), serde_json::to_string(update)) {
+        let _ = win.eval(format!("updateRecordingMeter({})", json));
+    }
+}
+
+// Current input level, or None when the backend isn't capturing (yet)
+async fn input_level() -> Option<f64> {
+    let data = backend::client()
+        .get(backend::url("audio_level"))
+        .timeout(UPDATE_INTERVAL)
+        .send()
+        .await
+        .ok()?
+        .json::<serde_json::Value>()
+        .await
+        .ok()?;
+    if data.get("recording").and_then(|r| r.as_bool()) != Some(true) {
+        return None;
+    }
+    data.get("level").and_then(|l| l.as_f64())
+}
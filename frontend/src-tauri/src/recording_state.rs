@@ -0,0 +1,98 @@
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::AppState;
+
+// Where the app is in the record -> transcribe -> deliver cycle. This is the single source of truth;
+// the overlay, tray, and hotkeys only reflect it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RecordingState {
+    #[default]
+    Idle,
+    Recording,  // Capturing audio (possibly paused)
+    Processing,  // Waiting for the backend to transcribe
+    Injecting,  // Delivering the text to the target window
+    Error,  // The last transcription failed; cleared by the next recording or retry
+}
+
+impl RecordingState {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            RecordingState::Idle => "idle",
+            RecordingState::Recording => "recording",
+            RecordingState::Processing => "processing",
+            RecordingState::Injecting => "injecting",
+            RecordingState::Error => "error",
+        }
+    }
+
+    // Allowed transitions (staying in the same state is always allowed and does nothing)
+    fn can_become(self, next: RecordingState) -> bool {
+        use RecordingState::*;
+        match (self, next) {
+            (current, next) if current == next => true,
+            (Idle | Error, Recording | Processing) => true,  // Start, or retry the last recording
+            (Recording, Processing | Idle) => true,  // Stop, or cancel
+            (Processing, Injecting | Idle | Error) => true,
+            (Injecting, Idle | Error) => true,
+            (Error, Idle) => true,
+            _ => false,
+        }
+    }
+}
+
+pub async fn get(state: &AppState) -> RecordingState {
+    *state.recording_state.lock().await
+}
+
+pub async fn is_recording(state: &AppState) -> bool {
+    get(state).await == RecordingState::Recording
+}
+
+// Move to `next`, or fail if the current state doesn't allow it (e.g. starting while processing).
+// Returns the previous state.
+pub async fn transition(app: &AppHandle, next: RecordingState) -> Result<RecordingState, String> {
+    let state: tauri::State<AppState> = app.state();
+    let previous = {
+        let mut current = state.recording_state.lock().await;
+        if !current.can_become(next) {
+            return Err(format!("Can't go from {} to {}", current.as_str(), next.as_str()));
+        }
+        std::mem::replace(&mut *current, next)
+    };
+
+    if previous != next {
+        log::info!("🔄 Recording state: {} → {}", previous.as_str(), next.as_str());
+        crate::win_h::set_busy(matches!(next, RecordingState::Processing | RecordingState::Injecting));
+        if matches!(next, RecordingState::Idle | RecordingState::Error) {
+            crate::meeting::recording_ended();
+        }
+        let _ = app.emit("recording-state-changed", next);
+        if let Some(win) = app.get_webview_window("recording") {
+            let _ = win.eval(format!("applyRecordingState('{}')", next.as_str()));
+        }
+        crate::refresh_tray(app).await;
+        crate::caption_server::publish(&state, crate::caption_server::Caption::State { state: next }).await;
+        match next {
+            RecordingState::Recording => crate::accessibility::announce(app, crate::locale::tr("announce.recording")),
+            RecordingState::Processing => crate::accessibility::announce(app, crate::locale::tr("announce.transcribing")),
+            _ => {}
+        }
+        if next == RecordingState::Recording {
+            let settings = state.ducking.lock().await.clone();
+            crate::ducking::duck(&settings).await;
+        } else if previous == RecordingState::Recording {
+            crate::ducking::restore().await;
+        }
+    }
+    Ok(previous)
+}
+
+// End a transcription in `next`, unless it was cancelled meanwhile (a new recording may have started since)
+pub async fn finish(app: &AppHandle, next: RecordingState) {
+    let state: tauri::State<AppState> = app.state();
+    if matches!(get(&state).await, RecordingState::Processing | RecordingState::Injecting) {
+        let _ = transition(app, next).await;
+    }
+}
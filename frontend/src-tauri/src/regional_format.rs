@@ -0,0 +1,129 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::AppState;
+
+// Regional formats for the dates, times, and numbers put into text: the {date}/{time}/{datetime}
+// placeholders of snippets, output templates, and the journal, and the numbers in transcriptions
+// (the model writes them the English way, 1,234.5). Each profile (model slot) can pick a region,
+// e.g. German for 01.03.2024 and 1.234,5; profiles without one keep ISO dates (2024-03-01), 24-hour
+// times, and numbers as the model wrote them.
+
+pub const CONFIG_FILE: &str = "regional_formats.json";
+
+pub struct RegionalFormat {
+    pub id: &'static str,  // Stored in settings; "" for ISO
+    pub date: &'static str,  // strftime format
+    pub time: &'static str,
+    pub decimal: char,
+    pub group: char,  // Thousands separator
+}
+
+impl RegionalFormat {
+    pub fn datetime(&self) -> String {
+        format!("{} {}", self.date, self.time)
+    }
+}
+
+pub const ISO: RegionalFormat = RegionalFormat { id: "", date: "%Y-%m-%d", time: "%H:%M", decimal: '.', group: ',' };
+
+const REGIONS: &[RegionalFormat] = &[
+    ISO,
+    RegionalFormat { id: "en-US", date: "%m/%d/%Y", time: "%-I:%M %p", decimal: '.', group: ',' },
+    RegionalFormat { id: "en-GB", date: "%d/%m/%Y", time: "%H:%M", decimal: '.', group: ',' },
+    RegionalFormat { id: "de-DE", date: "%d.%m.%Y", time: "%H:%M", decimal: ',', group: '.' },
+    RegionalFormat { id: "fr-FR", date: "%d/%m/%Y", time: "%H:%M", decimal: ',', group: '\u{202F}' },  // Narrow no-break space
+    RegionalFormat { id: "es-ES", date: "%d/%m/%Y", time: "%H:%M", decimal: ',', group: '.' },
+    RegionalFormat { id: "ja-JP", date: "%Y/%m/%d", time: "%H:%M", decimal: '.', group: ',' },
+];
+
+pub fn find(id: &str) -> Option<&'static RegionalFormat> {
+    REGIONS.iter().find(|region| region.id.eq_ignore_ascii_case(id))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct RegionalSettings {
+    pub profiles: HashMap<String, String>,  // Model slot -> region id; slots without one use ISO
+}
+
+impl RegionalSettings {
+    // Use a region for a profile; "" goes back to ISO
+    pub fn select(&mut self, profile: &str, region: &str) -> Result<(), String> {
+        let format = find(region).ok_or(format!("Unknown regional format: {}", region))?;
+        if format.id.is_empty() {
+            self.profiles.remove(profile);
+        } else {
+            self.profiles.insert(profile.to_string(), format.id.to_string());
+        }
+        Ok(())
+    }
+
+    pub fn for_profile(&self, profile: &str) -> &'static RegionalFormat {
+        self.profiles.get(profile).and_then(|id| find(id)).unwrap_or(&ISO)
+    }
+}
+
+// The format of the profile the current recording uses
+pub async fn current(state: &AppState) -> &'static RegionalFormat {
+    let profile = state.recording_slot.lock().await.clone();
+    state.regional_formats.lock().await.for_profile(&profile)
+}
+
+// Rewrite the numbers the model wrote the English way with the region's separators: 1,234.5 ->
+// 1.234,5. Digit runs that aren't clearly one number (versions, IP addresses, "1,2,3") are kept.
+pub fn numbers(text: &str, format: &RegionalFormat) -> String {
+    if format.decimal == '.' && format.group == ',' {
+        return text.to_string();
+    }
+    let chars: Vec<char> = text.chars().collect();
+    let mut result = String::with_capacity(text.len());
+    let mut i = 0;
+    while i < chars.len() {
+        let starts_number = chars[i].is_ascii_digit() && (i == 0 || !chars[i - 1].is_alphanumeric());
+        if !starts_number {
+            result.push(chars[i]);
+            i += 1;
+            continue;
+        }
+
+        // Digits with separators between them; a separator at the end is punctuation
+        let mut end = i;
+        while end < chars.len()
+            && (chars[end].is_ascii_digit() || (matches!(chars[end], ',' | '.') && chars.get(end + 1).is_some_and(|c| c.is_ascii_digit())))
+        {
+            end += 1;
+        }
+        let run: String = chars[i..end].iter().collect();
+        match localize(&run, format) {
+            Some(localized) => result.push_str(&localized),
+            None => result.push_str(&run),
+        }
+        i = end;
+    }
+    result
+}
+
+// "1,234.5" in the region's format, or None if it isn't a number grouped by thousands with an
+// optional decimal part
+fn localize(run: &str, format: &RegionalFormat) -> Option<String> {
+    let (whole, fraction) = match run.split_once('.') {
+        Some((whole, fraction)) if !fraction.contains(['.', ',']) => (whole, Some(fraction)),
+        Some(_) => return None,
+        None => (run, None),
+    };
+    let mut groups = whole.split(',');
+    let first = groups.next()?;
+    let rest: Vec<&str> = groups.collect();
+    if !rest.is_empty() && (first.len() > 3 || rest.iter().any(|group| group.len() != 3)) {
+        return None;
+    }
+
+    let group = format.group.to_string();
+    let mut localized = std::iter::once(first).chain(rest).collect::<Vec<_>>().join(&group);
+    if let Some(fraction) = fraction {
+        localized.push(format.decimal);
+        localized.push_str(fraction);
+    }
+    Some(localized)
+}
@@ -0,0 +1,69 @@
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use windows::Win32::Foundation::HWND;
+use windows::Win32::UI::WindowsAndMessaging::{GetSystemMetrics, SM_REMOTESESSION};
+
+use crate::{clipboard, window_info, InjectionSettings};
+
+// Remote desktop clients, whose window forwards keystrokes and the clipboard to another machine
+const REMOTE_CLIENTS: &[&str] = &["mstsc.exe", "msrdc.exe", "wfica32.exe", "cdviewer.exe", "vmware-view.exe", "vmconnect.exe"];
+
+// Lower bounds for the injection delays over a remote connection. The clipboard reaches the other
+// side only after the client has announced and sent it, and restoring the old clipboard too early
+// makes the remote app paste that instead.
+const CLIPBOARD_SETTLE_MS: u64 = 150;
+const ENTER_DELAY_MS: u64 = 300;
+const POST_PASTE_MS: u64 = 750;
+
+// Clipboard read-back attempts before giving up on pasting
+const VERIFY_ATTEMPTS: u32 = 5;
+const VERIFY_INTERVAL: Duration = Duration::from_millis(50);
+
+// When injection uses the remote-friendly timing
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RemoteMode {
+    Auto,  // When running in a remote session or pasting into a remote desktop client
+    Always,
+    Never,
+}
+
+// Whether this app runs in a remote session (RDP, Citrix) or the target is a remote desktop client
+pub fn is_remote(target: HWND) -> bool {
+    let remote_session = unsafe { GetSystemMetrics(SM_REMOTESESSION) } != 0;
+    remote_session
+        || window_info::process_name_for_window(target)
+            .is_some_and(|name| REMOTE_CLIENTS.iter().any(|client| name.eq_ignore_ascii_case(client)))
+}
+
+// Settings with the remote-friendly delays if the mode applies to this target, or None
+pub fn remote_settings(settings: &InjectionSettings, target: HWND) -> Option<InjectionSettings> {
+    let remote = match settings.remote_mode {
+        RemoteMode::Auto => is_remote(target),
+        RemoteMode::Always => true,
+        RemoteMode::Never => false,
+    };
+    if !remote {
+        return None;
+    }
+    Some(InjectionSettings {
+        clipboard_settle_ms: settings.clipboard_settle_ms.max(CLIPBOARD_SETTLE_MS),
+        enter_delay_ms: settings.enter_delay_ms.max(ENTER_DELAY_MS),
+        post_paste_ms: settings.post_paste_ms.max(POST_PASTE_MS),
+        ..settings.clone()
+    })
+}
+
+// Whether the clipboard really holds `text`; clipboard sync tools and remote clients can be holding
+// it open or replace it right after we set it
+pub fn clipboard_holds(text: &str) -> bool {
+    for attempt in 0..VERIFY_ATTEMPTS {
+        if attempt > 0 {
+            std::thread::sleep(VERIFY_INTERVAL);
+        }
+        if clipboard::get_text().is_ok_and(|current| current.as_deref() == Some(text)) {
+            return true;
+        }
+    }
+    false
+}
@@ -0,0 +1,113 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, SystemTime};
+use tauri::{AppHandle, Manager};
+
+use crate::{backend, storage, AppState};
+
+pub const CONFIG_FILE: &str = "retention.json";
+
+// How often the policy is enforced after the check at startup
+const ENFORCE_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+const DAY_SECS: u64 = 24 * 60 * 60;
+
+// How long each kind of stored data is kept, in days (0 = keep forever)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RetentionSettings {
+    pub history_days: u32,
+    pub audio_days: u32,  // Audio the backend keeps for retrying a transcription
+    pub log_days: u32,
+}
+
+impl Default for RetentionSettings {
+    fn default() -> Self {
+        Self { history_days: 90, audio_days: 7, log_days: 14 }
+    }
+}
+
+// Enforce the policy now and then once a day for as long as the app runs
+pub fn spawn(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            enforce(&app).await;
+            tokio::time::sleep(ENFORCE_INTERVAL).await;
+        }
+    });
+}
+
+// Delete whatever is older than its category allows
+pub async fn enforce(app: &AppHandle) {
+    let state: tauri::State<AppState> = app.state();
+    let settings = state.retention.lock().await.clone();
+
+    if let Some(cutoff) = cutoff(settings.history_days) {
+        let cutoff_ms = cutoff.duration_since(SystemTime::UNIX_EPOCH).map(|d| d.as_millis() as i64).unwrap_or_default();
+        if let Some(store) = state.history.lock().await.as_mut() {
+            match store.delete_before(cutoff_ms) {
+                Ok(0) => {}
+                Ok(count) => log::info!("🧹 Deleted {} history entries older than {} days", count, settings.history_days),
+                Err(e) => log::error!("❌ Failed to delete old history: {}", e),
+            }
+        }
+    }
+
+    if settings.audio_days > 0 {
+        // The backend may still be starting; the next run catches up
+        let request = serde_json::json!({ "older_than_secs": u64::from(settings.audio_days) * DAY_SECS });
+        if let Err(e) = backend::client().post(backend::url("purge")).json(&request).send().await {
+            log::warn!("⚠️ Could not reach backend to purge old audio: {}", e);
+        }
+    }
+
+    if let Some(cutoff) = cutoff(settings.log_days) {
+        match remove_logs(app, Some(cutoff)) {
+            Ok(0) => {}
+            Ok(count) => log::info!("🧹 Deleted {} log files older than {} days", count, settings.log_days),
+            Err(e) => log::error!("❌ Failed to delete old logs: {}", e),
+        }
+    }
+}
+
+fn cutoff(days: u32) -> Option<SystemTime> {
+    if days == 0 {
+        return None;
+    }
+    SystemTime::now().checked_sub(Duration::from_secs(u64::from(days) * DAY_SECS))
+}
+
+// Delete every log file, emptying the one being written to (the logger keeps it open)
+pub fn clear_logs(app: &AppHandle) -> Result<()> {
+    remove_logs(app, None)?;
+    let current = storage::log_dir(app)?.join("app.log");
+    if current.exists() {
+        std::fs::File::create(current)?;
+    }
+    Ok(())
+}
+
+// Remove the rotated logs last written before `cutoff` (all of them without one); returns how many
+fn remove_logs(app: &AppHandle, cutoff: Option<SystemTime>) -> Result<usize> {
+    let entries = match std::fs::read_dir(storage::log_dir(app)?) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+        Err(e) => return Err(e.into()),
+    };
+
+    let mut removed = 0;
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().into_owned();
+        // Rotated logs are named app_<date>.log (app.log is the one in use); crash reports crash-<time>.*
+        let rotated = name.starts_with("app_") && name.ends_with(".log");
+        if !rotated && !name.starts_with(crate::crash_report::FILE_PREFIX) {
+            continue;
+        }
+        let modified = entry.metadata().and_then(|m| m.modified());
+        if cutoff.is_some_and(|cutoff| modified.is_ok_and(|modified| modified >= cutoff)) {
+            continue;
+        }
+        std::fs::remove_file(entry.path())?;
+        removed += 1;
+    }
+    Ok(removed)
+}
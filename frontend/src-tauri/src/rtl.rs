@@ -0,0 +1,139 @@
+// Bidirectional text fixes for Arabic, Hebrew, and other right-to-left scripts.
+// Apps lay out pasted text with the Unicode bidi algorithm, which decides where punctuation,
+// digits, and spaces between scripts go from the strong characters around them. Text that starts
+// or ends with those (e.g. a trailing period), or mixes directions, comes out scrambled unless
+// invisible direction marks pin them to the right side.
+
+pub const LRM: char = '\u{200E}';  // Left-to-right mark
+pub const RLM: char = '\u{200F}';  // Right-to-left mark
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Ltr,
+    Rtl,
+}
+
+impl Direction {
+    fn mark(self) -> char {
+        match self {
+            Direction::Ltr => LRM,
+            Direction::Rtl => RLM,
+        }
+    }
+}
+
+// Letters of right-to-left scripts. Digits and separators in these blocks are weak, like ASCII digits.
+fn is_rtl(c: char) -> bool {
+    match c {
+        // Arabic digits, number signs, and separators
+        '\u{0600}'..='\u{0605}' | '\u{060C}' | '\u{0660}'..='\u{066C}' | '\u{06DD}' | '\u{06F0}'..='\u{06F9}' => false,
+        '\u{0590}'..='\u{08FF}' => true,  // Hebrew, Arabic, Syriac, Thaana, NKo, Samaritan, Mandaic
+        '\u{FB1D}'..='\u{FDFF}' | '\u{FE70}'..='\u{FEFC}' => true,  // Presentation forms (not U+FEFF, the byte order mark)
+        '\u{10800}'..='\u{10FFF}' | '\u{1E800}'..='\u{1EFFF}' => true,  // Historic RTL scripts, Arabic math
+        _ => false,
+    }
+}
+
+// Direction of a character that sets the direction around it, or None for digits, punctuation,
+// spaces, and symbols, which take their direction from their neighbours
+pub fn strong_direction(c: char) -> Option<Direction> {
+    match c {
+        RLM => Some(Direction::Rtl),
+        LRM => Some(Direction::Ltr),
+        c if is_rtl(c) => Some(Direction::Rtl),
+        c if c.is_alphabetic() => Some(Direction::Ltr),
+        _ => None,
+    }
+}
+
+// Paragraph direction from the first strong character, as most apps work it out
+pub fn base_direction(text: &str) -> Option<Direction> {
+    text.chars().find_map(strong_direction)
+}
+
+pub fn contains_rtl(text: &str) -> bool {
+    text.chars().any(is_rtl)
+}
+
+// Insert direction marks so the text keeps its order in any app, whatever direction the field has.
+// Text without right-to-left characters is returned unchanged, and marking twice changes nothing.
+//
+// - Right-to-left text gets an RLM at the start and end if it begins or ends with weak characters,
+//   so "مرحبا." keeps its period on the left even in a left-to-right field
+// - A run in the other direction followed by weak characters gets a mark of the base direction
+//   after it, so in "say שלום 3 times" the 3 stays with the English instead of joining the Hebrew
+pub fn add_direction_marks(text: &str) -> String {
+    if !contains_rtl(text) {
+        return text.to_string();
+    }
+    let Some(base) = base_direction(text) else {
+        return text.to_string();
+    };
+
+    let chars: Vec<char> = text.chars().collect();
+    let mut marked = String::with_capacity(text.len() + 16);
+    if base == Direction::Rtl && chars.first().is_some_and(|&c| strong_direction(c).is_none()) {
+        marked.push(RLM);
+    }
+
+    for (index, &c) in chars.iter().enumerate() {
+        marked.push(c);
+        let ends_opposite_run = strong_direction(c).is_some_and(|direction| direction != base);
+        let next_is_weak = chars.get(index + 1).is_some_and(|&next| strong_direction(next).is_none());
+        if ends_opposite_run && next_is_weak {
+            marked.push(base.mark());
+        }
+    }
+
+    if base == Direction::Rtl && chars.last().is_some_and(|&c| strong_direction(c).is_none()) {
+        marked.push(RLM);
+    }
+    marked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn leaves_left_to_right_text_alone() {
+        assert_eq!(add_direction_marks("Hello, world."), "Hello, world.");
+        assert_eq!(add_direction_marks("123 + 4"), "123 + 4");
+    }
+
+    #[test]
+    fn pins_trailing_period_of_right_to_left_text() {
+        assert_eq!(add_direction_marks("שלום."), format!("שלום.{}", RLM));
+        assert_eq!(add_direction_marks("مرحبا."), format!("مرحبا.{}", RLM));
+        assert_eq!(add_direction_marks("\"שלום\""), format!("{}\"שלום\"{}", RLM, RLM));
+    }
+
+    #[test]
+    fn keeps_digits_after_a_right_to_left_run_with_the_base_text() {
+        assert_eq!(add_direction_marks("say שלום 3 times"), format!("say שלום{} 3 times", LRM));
+        assert_eq!(add_direction_marks("שלום hello 3"), format!("שלום hello{} 3{}", RLM, RLM));
+    }
+
+    #[test]
+    fn marking_twice_changes_nothing() {
+        for text in ["שלום.", "say שלום 3 times", "שלום hello 3", ".מה?", "Hello"] {
+            let once = add_direction_marks(text);
+            assert_eq!(add_direction_marks(&once), once, "{:?}", text);
+        }
+    }
+
+    #[test]
+    fn detects_direction() {
+        assert!(contains_rtl("say שלום"));
+        assert!(contains_rtl("مرحبا"));
+        assert!(!contains_rtl("hello 123"));
+        assert!(!contains_rtl("\u{FEFF}hello"));
+        assert!(!contains_rtl("٣٤٥"));  // Arabic-Indic digits are weak
+
+        assert_eq!(base_direction("שלום world"), Some(Direction::Rtl));
+        assert_eq!(base_direction("hello שלום"), Some(Direction::Ltr));
+        assert_eq!(base_direction("123 שלום"), Some(Direction::Rtl));
+        assert_eq!(base_direction("\u{FEFF}hello"), Some(Direction::Ltr));
+        assert_eq!(base_direction("123 ..."), None);
+    }
+}
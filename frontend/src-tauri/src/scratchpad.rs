@@ -0,0 +1,83 @@
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, WebviewUrl, WebviewWindowBuilder};
+
+use crate::{storage, AppState};
+
+pub const CONFIG_FILE: &str = "scratchpad.json";
+pub const WINDOW_LABEL: &str = "scratchpad";
+
+// Text collected in the scratchpad window, kept across restarts (except in privacy mode)
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct Scratchpad {
+    pub text: String,
+}
+
+// Add a transcription to the end of the scratchpad and show it without taking focus.
+// `new_entry` starts a new line; continuous-dictation segments continue the current one.
+pub async fn append(app: &AppHandle, state: &AppState, text: &str, new_entry: bool) {
+    let text = {
+        let mut scratchpad = state.scratchpad.lock().await;
+        if new_entry && !scratchpad.text.is_empty() && !scratchpad.text.ends_with('\n') {
+            scratchpad.text.push('\n');
+        }
+        scratchpad.text.push_str(if new_entry { text.trim_start() } else { text });
+        scratchpad.text.clone()
+    };
+    save(app, state, &text).await;
+    log::info!("📝 Added transcription to the scratchpad");
+
+    if let Err(e) = show(app, false) {
+        log::error!("❌ Failed to show scratchpad: {}", e);
+        return;
+    }
+    if let (Some(win), Ok(json)) = (app.get_webview_window(WINDOW_LABEL), serde_json::to_string(&text)) {
+        let _ = win.eval(format!("setScratchpadText({})", json));
+    }
+}
+
+// Replace the text after an edit in the window
+pub async fn set_text(app: &AppHandle, state: &AppState, text: String) {
+    state.scratchpad.lock().await.text = text.clone();
+    save(app, state, &text).await;
+}
+
+async fn save(app: &AppHandle, state: &AppState, text: &str) {
+    if crate::privacy::is_enabled(state).await {
+        return;
+    }
+    let scratchpad = Scratchpad { text: text.to_string() };
+    if let Err(e) = storage::save_json(app, CONFIG_FILE, &scratchpad) {
+        log::error!("❌ Failed to save scratchpad: {}", e);
+    }
+}
+
+// Show the scratchpad window, creating it on first use. Closing it only hides it.
+pub fn show(app: &AppHandle, focus: bool) -> tauri::Result<()> {
+    let win = match app.get_webview_window(WINDOW_LABEL) {
+        Some(win) => win,
+        None => {
+            let win = WebviewWindowBuilder::new(app, WINDOW_LABEL, WebviewUrl::App("scratchpad.html".into()))
+                .title("Whisper4Windows - Scratchpad")
+                .inner_size(520.0, 420.0)
+                .min_inner_size(320.0, 200.0)
+                .focused(focus)
+                .build()?;
+            let app_handle = app.clone();
+            win.on_window_event(move |event| {
+                if let tauri::WindowEvent::CloseRequested { api, .. } = event {
+                    api.prevent_close();
+                    if let Some(win) = app_handle.get_webview_window(WINDOW_LABEL) {
+                        let _ = win.hide();
+                    }
+                }
+            });
+            win
+        }
+    };
+    win.show()?;
+    if focus {
+        win.set_focus()?;
+    }
+    Ok(())
+}
@@ -0,0 +1,278 @@
+use anyhow::Result;
+use serde::Serialize;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
+use windows::core::w;
+use windows::Win32::Foundation::{HWND, LPARAM, WPARAM};
+use windows::Win32::System::Threading::GetCurrentThreadId;
+use windows::Win32::UI::WindowsAndMessaging::{
+    CreateWindowExW, DispatchMessageW, GetForegroundWindow, GetMessageW, GetWindowTextW, PostThreadMessageW,
+    SetForegroundWindow, TranslateMessage, CW_USEDEFAULT, ES_AUTOVSCROLL, ES_MULTILINE, MSG, WINDOW_STYLE, WM_QUIT,
+    WS_EX_TOPMOST, WS_OVERLAPPEDWINDOW, WS_VISIBLE,
+};
+
+use crate::{backend, clipboard, AppState, InjectionSettings, OutputMode};
+
+// Checks every step from hotkey to pasted text, so "it doesn't work" comes with the step that fails
+
+// Loading a large model from disk can take a while, especially on CPU
+const MODEL_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+const MIC_SECONDS: f64 = 1.0;
+// Below this peak level the microphone is most likely muted or the wrong device
+const QUIET_PEAK: f64 = 0.001;
+// Has a non-ASCII character so a paste that loses Unicode shows up
+const TEST_TEXT: &str = "Whisper4Windows self-test ✓ 123";
+const FOCUS_DELAY: Duration = Duration::from_millis(200);
+const INJECTION_TIMEOUT: Duration = Duration::from_secs(3);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    Pass,
+    Warn,  // Works, but probably not the way the user expects
+    Fail,
+    Skipped,  // Depends on a check that failed, or doesn't apply to the current settings
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Check {
+    pub name: &'static str,
+    pub status: Status,
+    pub detail: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Report {
+    pub passed: bool,  // No check failed
+    pub checks: Vec<Check>,
+}
+
+fn check(name: &'static str, status: Status, detail: impl Into<String>) -> Check {
+    Check { name, status, detail: detail.into() }
+}
+
+pub async fn run(app: &AppHandle) -> Report {
+    let state: tauri::State<AppState> = app.state();
+    let mut checks = vec![check_backend().await];
+
+    if checks[0].status == Status::Pass {
+        checks.push(check_model(&state).await);
+        checks.push(check_microphone(&state).await);
+    } else {
+        checks.push(check("Model", Status::Skipped, "The backend isn't running"));
+        checks.push(check("Microphone", Status::Skipped, "The backend isn't running"));
+    }
+    checks.push(check_hotkeys(app, &state).await);
+    checks.push(check_clipboard().await);
+    checks.push(check_injection(&state).await);
+
+    for c in &checks {
+        log::info!("🩺 Self-test {}: {:?} - {}", c.name, c.status, c.detail);
+    }
+    Report { passed: checks.iter().all(|c| c.status != Status::Fail), checks }
+}
+
+async fn check_backend() -> Check {
+    const NAME: &str = "Backend";
+    let resp = match backend::client().get(backend::url("health")).send().await {
+        Ok(resp) => resp,
+        Err(e) => return check(NAME, Status::Fail, format!("Not reachable: {}", e)),
+    };
+    match resp.json::<serde_json::Value>().await {
+        // Another user's backend on the port this one was meant to have
+        Ok(data) if other_session(&data) => {
+            check(NAME, Status::Fail, format!("Answered by a backend of another Windows session ({})", backend::describe()))
+        }
+        Ok(data) => check(
+            NAME,
+            Status::Pass,
+            format!(
+                "Running ({}, {})",
+                data.get("backend").and_then(|b| b.as_str()).unwrap_or("unknown engine"),
+                backend::describe()
+            ),
+        ),
+        Err(e) => check(NAME, Status::Fail, format!("Invalid response: {}", e)),
+    }
+}
+
+// Whether /health came from a backend another session started
+fn other_session(health: &serde_json::Value) -> bool {
+    let session = health.get("session").and_then(|s| s.as_str());
+    backend::session_id().is_some_and(|id| session.is_some_and(|session| session != id.to_string()))
+}
+
+async fn check_model(state: &AppState) -> Check {
+    const NAME: &str = "Model";
+    let model = state.selected_model.lock().await.clone();
+    let device = state.selected_device.lock().await.clone();
+    let request = backend::client()
+        .post(backend::url("self_test/model"))
+        .timeout(MODEL_TIMEOUT)
+        .json(&serde_json::json!({ "model_size": model, "device": device }));
+    let data = match request.send().await {
+        Ok(resp) => resp.json::<serde_json::Value>().await.unwrap_or_default(),
+        Err(e) => return check(NAME, Status::Fail, format!("Request failed: {}", e)),
+    };
+    if data.get("status").and_then(|s| s.as_str()) != Some("success") {
+        let message = data.get("message").and_then(|m| m.as_str()).unwrap_or("Failed to load the model");
+        return check(NAME, Status::Fail, message);
+    }
+
+    let loaded_on = data.get("device").and_then(|d| d.as_str()).unwrap_or_default();
+    let seconds = data.get("load_time").and_then(|t| t.as_f64()).unwrap_or_default();
+    let detail = format!("{} loaded on {} in {:.1}s", model, loaded_on, seconds);
+    if device == "cuda" && loaded_on != "cuda" {
+        return check(NAME, Status::Warn, format!("{}, the GPU couldn't be used", detail));
+    }
+    check(NAME, Status::Pass, detail)
+}
+
+async fn check_microphone(state: &AppState) -> Check {
+    const NAME: &str = "Microphone";
+    let microphone = *state.selected_microphone.lock().await;
+    let mut body = serde_json::json!({ "device_index": microphone, "seconds": MIC_SECONDS });
+    state.microphones.lock().await.add_to_request(&mut body);
+    let request = backend::client().post(backend::url("self_test/mic")).json(&body);
+    let data = match request.send().await {
+        Ok(resp) => resp.json::<serde_json::Value>().await.unwrap_or_default(),
+        Err(e) => return check(NAME, Status::Fail, format!("Request failed: {}", e)),
+    };
+    if data.get("status").and_then(|s| s.as_str()) != Some("success") {
+        let message = data.get("message").and_then(|m| m.as_str()).unwrap_or("Recording failed");
+        return check(NAME, Status::Fail, message);
+    }
+
+    let peak = data.get("peak").and_then(|p| p.as_f64()).unwrap_or_default();
+    let format = data.get("format").and_then(crate::microphones::describe_format);
+    if peak < QUIET_PEAK {
+        return check(NAME, Status::Warn, "Recorded only silence; the microphone may be muted or the wrong one is selected");
+    }
+    match format {
+        Some((format, true)) => check(NAME, Status::Warn, format!("The device didn't accept the capture settings: {}", format)),
+        Some((format, false)) => check(NAME, Status::Pass, format!("Recorded {:.0}s, peak level {:.0}%; {}", MIC_SECONDS, peak * 100.0, format)),
+        None => check(NAME, Status::Pass, format!("Recorded {:.0}s, peak level {:.0}%", MIC_SECONDS, peak * 100.0)),
+    }
+}
+
+async fn check_hotkeys(app: &AppHandle, state: &AppState) -> Check {
+    const NAME: &str = "Hotkeys";
+    if *state.hotkeys_suspended.lock().await {
+        return check(NAME, Status::Warn, "Suspended while a blocklisted app is focused");
+    }
+    let (bound, missing) = crate::hotkey_registration(app).await;
+    if bound == 0 {
+        return check(NAME, Status::Warn, "No hotkeys are bound");
+    }
+    if !missing.is_empty() {
+        return check(NAME, Status::Fail, format!("Not registered: {}", missing.join(", ")));
+    }
+    check(NAME, Status::Pass, format!("{} registered", bound))
+}
+
+async fn check_clipboard() -> Check {
+    const NAME: &str = "Clipboard";
+    let result = tauri::async_runtime::spawn_blocking(|| -> Result<bool> {
+        let snapshot = clipboard::snapshot().map_err(|e| anyhow::anyhow!("{}", e))?;
+        let utf16: Vec<u16> = TEST_TEXT.encode_utf16().chain(std::iter::once(0)).collect();
+        let written = clipboard::set_text(&utf16).and_then(|_| clipboard::get_text());
+        // The user's clipboard comes back whatever happened
+        clipboard::restore(&snapshot)?;
+        Ok(written?.as_deref() == Some(TEST_TEXT))
+    })
+    .await;
+
+    match result {
+        Ok(Ok(true)) => check(NAME, Status::Pass, "Writable"),
+        Ok(Ok(false)) => check(NAME, Status::Fail, "Text written to the clipboard didn't read back; another app may be replacing it"),
+        Ok(Err(e)) => check(NAME, Status::Fail, e.to_string()),
+        Err(e) => check(NAME, Status::Fail, e.to_string()),
+    }
+}
+
+async fn check_injection(state: &AppState) -> Check {
+    const NAME: &str = "Injection";
+    match *state.output_mode.lock().await {
+        OutputMode::ClipboardOnly => return check(NAME, Status::Skipped, "Transcriptions are only copied to the clipboard"),
+        OutputMode::Scratchpad => return check(NAME, Status::Skipped, "Transcriptions go to the scratchpad"),
+        OutputMode::Inject | OutputMode::Both => {}
+    }
+    let settings = state.injection.lock().await.clone();
+    match tauri::async_runtime::spawn_blocking(move || inject_into_test_window(&settings)).await {
+        Ok(Ok(())) => check(NAME, Status::Pass, "Text arrived in a test window"),
+        Ok(Err(e)) => check(NAME, Status::Fail, e.to_string()),
+        Err(e) => check(NAME, Status::Fail, e.to_string()),
+    }
+}
+
+// Open a text box on its own thread (which keeps its messages flowing), inject into it the way a
+// transcription would be, and read back what arrived
+fn inject_into_test_window(settings: &InjectionSettings) -> Result<()> {
+    let (sender, receiver) = std::sync::mpsc::channel();
+    let window_thread = std::thread::spawn(move || unsafe {
+        let style = WS_OVERLAPPEDWINDOW | WS_VISIBLE | WINDOW_STYLE((ES_MULTILINE | ES_AUTOVSCROLL) as u32);
+        let edit = CreateWindowExW(
+            WS_EX_TOPMOST,
+            w!("EDIT"),
+            w!("Whisper4Windows self-test"),
+            style,
+            CW_USEDEFAULT,
+            CW_USEDEFAULT,
+            420,
+            160,
+            None,
+            None,
+            None,
+            None,
+        );
+        let _ = sender.send((edit.0, GetCurrentThreadId()));
+        if edit.0 == 0 {
+            return;
+        }
+        // Ends with WM_QUIT; the window goes away with the thread
+        let mut msg = MSG::default();
+        while GetMessageW(&mut msg, None, 0, 0).as_bool() {
+            TranslateMessage(&msg);
+            DispatchMessageW(&msg);
+        }
+    });
+
+    let (edit, thread_id) = receiver.recv()?;
+    let edit = HWND(edit);
+    let result = unsafe { inject_and_read(edit, settings) };
+    unsafe {
+        let _ = PostThreadMessageW(thread_id, WM_QUIT, WPARAM(0), LPARAM(0));
+    }
+    let _ = window_thread.join();
+    result
+}
+
+unsafe fn inject_and_read(edit: HWND, settings: &InjectionSettings) -> Result<()> {
+    if edit.0 == 0 {
+        return Err(anyhow::anyhow!("Couldn't open the test window"));
+    }
+    let _ = SetForegroundWindow(edit);
+    std::thread::sleep(FOCUS_DELAY);
+    if GetForegroundWindow() != edit {
+        return Err(anyhow::anyhow!("Windows didn't let the test window take focus"));
+    }
+
+    crate::inject_text(TEST_TEXT, false, settings)?;
+
+    let deadline = Instant::now() + INJECTION_TIMEOUT;
+    let mut received = String::new();
+    while Instant::now() < deadline {
+        let mut buffer = [0u16; 256];
+        let len = GetWindowTextW(edit, &mut buffer);
+        received = String::from_utf16_lossy(&buffer[..len as usize]);
+        if received.contains(TEST_TEXT) {
+            return Ok(());
+        }
+        std::thread::sleep(Duration::from_millis(50));
+    }
+    if received.is_empty() {
+        Err(anyhow::anyhow!("Nothing arrived in the test window"))
+    } else {
+        Err(anyhow::anyhow!("The test window received \"{}\" instead", received.trim()))
+    }
+}
@@ -0,0 +1,28 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{AppState, InjectionSettings};
+
+// Sending the text as a message, for chat apps: a profile (model slot) can press Enter once its
+// dictation has been pasted or typed. The delay and the same-window safety check are injection
+// settings shared by all profiles.
+
+pub const CONFIG_FILE: &str = "send_enter.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct SendEnterSettings {
+    pub profiles: Vec<String>,  // Model slots that press Enter after injecting
+}
+
+// Whether recordings in the current profile are sent with Enter
+pub async fn active(state: &AppState) -> bool {
+    let slot = state.recording_slot.lock().await.clone();
+    state.send_enter.lock().await.profiles.contains(&slot)
+}
+
+// Injection settings for the current profile: Enter follows the text if the profile sends it
+pub async fn injection(state: &AppState, injection: &InjectionSettings) -> InjectionSettings {
+    let mut injection = injection.clone();
+    injection.press_enter = active(state).await;
+    injection
+}
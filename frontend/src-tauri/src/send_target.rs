@@ -0,0 +1,202 @@
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+use windows::core::{w, HSTRING};
+use windows::Win32::Foundation::{BOOL, HWND, LPARAM};
+use windows::Win32::UI::Shell::ShellExecuteW;
+use windows::Win32::UI::WindowsAndMessaging::{
+    EnumWindows, GetForegroundWindow, GetWindow, GetWindowTextLengthW, IsIconic, IsWindowVisible, SetForegroundWindow,
+    ShowWindow, GW_OWNER, SW_RESTORE, SW_SHOWNORMAL,
+};
+
+use crate::{AppState, InjectionSettings, OutputMode};
+
+pub const CONFIG_FILE: &str = "send_targets.json";
+
+// How long a launched app gets to show its window
+const LAUNCH_TIMEOUT: Duration = Duration::from_secs(15);
+// How long Windows gets to actually bring the window to the front
+const FOCUS_TIMEOUT: Duration = Duration::from_secs(1);
+const POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+// A fixed destination for transcriptions instead of whatever window has focus
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct SendTarget {
+    pub name: String,  // Unique, e.g. "Notepad"
+    pub app: String,  // Executable to focus and paste into, launched if not running (e.g. notepad.exe or a full path)
+    pub uri: String,  // Open this URI instead of pasting; {text} is URL-encoded (e.g. obsidian://new?vault=Notes&content={text})
+}
+
+// Targets persisted in the config directory
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct SendTargetStore {
+    pub active: String,  // Name of the target in use; empty = the focused window
+    pub targets: Vec<SendTarget>,
+}
+
+impl SendTargetStore {
+    // Add a target, or replace the one with the same name
+    pub fn upsert(&mut self, target: SendTarget) -> Result<(), String> {
+        if target.name.trim().is_empty() {
+            return Err("Target name cannot be empty".to_string());
+        }
+        if target.app.trim().is_empty() && target.uri.trim().is_empty() {
+            return Err("Target needs an app or a URI".to_string());
+        }
+        if !target.uri.trim().is_empty() && !target.uri.contains("{text}") {
+            return Err("Target URI must contain {text}".to_string());
+        }
+
+        match self.targets.iter_mut().find(|t| t.name.eq_ignore_ascii_case(&target.name)) {
+            Some(existing) => *existing = target,
+            None => self.targets.push(target),
+        }
+        Ok(())
+    }
+
+    // Remove a target by name (deactivating it if it was in use); returns false if there was none
+    pub fn remove(&mut self, name: &str) -> bool {
+        let before = self.targets.len();
+        self.targets.retain(|t| !t.name.eq_ignore_ascii_case(name));
+        if self.active.eq_ignore_ascii_case(name) {
+            self.active.clear();
+        }
+        self.targets.len() != before
+    }
+
+    // The target in use, if any
+    pub fn active(&self) -> Option<&SendTarget> {
+        if self.active.is_empty() {
+            return None;
+        }
+        self.targets.iter().find(|t| t.name.eq_ignore_ascii_case(&self.active))
+    }
+}
+
+// Deliver `text` to the active target; returns false when there is none and the focused window should get it
+pub async fn deliver(app: &tauri::AppHandle, state: &AppState, text: &str, injection: &InjectionSettings) -> bool {
+    let Some(target) = state.send_targets.lock().await.active().cloned() else {
+        return false;
+    };
+
+    let result = if target.uri.trim().is_empty() {
+        let save_to_clipboard = *state.output_mode.lock().await == OutputMode::Both;
+        inject_into_app(&target.app, text, save_to_clipboard, injection).await
+    } else {
+        open_uri(&target.uri, text)
+    };
+    match result {
+        Ok(()) => log::info!("🎯 Sent transcription to {}", target.name),
+        Err(e) => {
+            log::error!("❌ Sending to {} failed: {}", target.name, e);
+            crate::show_toast(app, &crate::locale::tr_with("toast.send_failed", &[("target", &target.name)]), &e);
+        }
+    }
+    true
+}
+
+// Launch the app if it isn't running, wait for its window, bring it to the front, and paste
+async fn inject_into_app(app: &str, text: &str, save_to_clipboard: bool, injection: &InjectionSettings) -> Result<(), String> {
+    let exe = exe_name(app);
+    let hwnd = match find_window(&exe) {
+        Some(hwnd) => hwnd,
+        None => {
+            log::info!("🚀 Launching {}", app);
+            std::process::Command::new(app.trim()).spawn().map_err(|e| format!("Can't start {}: {}", app, e))?;
+            let deadline = Instant::now() + LAUNCH_TIMEOUT;
+            loop {
+                tokio::time::sleep(POLL_INTERVAL).await;
+                if let Some(hwnd) = find_window(&exe) {
+                    break hwnd;
+                }
+                if Instant::now() > deadline {
+                    return Err(format!("{} didn't open a window", exe));
+                }
+            }
+        }
+    };
+
+    focus(hwnd).await?;
+    tokio::time::sleep(Duration::from_millis(injection.focus_return_ms)).await;
+    crate::inject_text(text, save_to_clipboard, injection).map_err(|e| e.to_string())
+}
+
+async fn focus(hwnd: HWND) -> Result<(), String> {
+    unsafe {
+        if IsIconic(hwnd).as_bool() {
+            ShowWindow(hwnd, SW_RESTORE);
+        }
+        let _ = SetForegroundWindow(hwnd);
+    }
+    let deadline = Instant::now() + FOCUS_TIMEOUT;
+    while unsafe { GetForegroundWindow() } != hwnd {
+        if Instant::now() > deadline {
+            // Never paste into whatever else has focus
+            return Err("Windows didn't let the window come to the front".to_string());
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    Ok(())
+}
+
+// "C:\Tools\notepad++.exe" and "notepad" both match processes by file name
+fn exe_name(app: &str) -> String {
+    let name = app.trim().rsplit(['\\', '/']).next().unwrap_or_default();
+    if name.to_lowercase().ends_with(".exe") {
+        name.to_string()
+    } else {
+        format!("{}.exe", name)
+    }
+}
+
+// A visible main window (titled, not owned by another window) of a process named `exe`
+fn find_window(exe: &str) -> Option<HWND> {
+    struct Search<'a> {
+        exe: &'a str,
+        found: Option<HWND>,
+    }
+
+    unsafe extern "system" fn visit(hwnd: HWND, lparam: LPARAM) -> BOOL {
+        let search = &mut *(lparam.0 as *mut Search);
+        let is_main_window = IsWindowVisible(hwnd).as_bool()
+            && GetWindow(hwnd, GW_OWNER).0 == 0
+            && GetWindowTextLengthW(hwnd) > 0;
+        if is_main_window
+            && crate::window_info::process_name_for_window(hwnd).is_some_and(|name| name.eq_ignore_ascii_case(search.exe))
+        {
+            search.found = Some(hwnd);
+            return false.into();
+        }
+        true.into()
+    }
+
+    let mut search = Search { exe, found: None };
+    unsafe {
+        // Stopping early makes EnumWindows report an error, so its result says nothing
+        let _ = EnumWindows(Some(visit), LPARAM(&mut search as *mut Search as isize));
+    }
+    search.found
+}
+
+fn open_uri(template: &str, text: &str) -> Result<(), String> {
+    let uri = template.trim().replace("{text}", &percent_encode(text));
+    let result = unsafe { ShellExecuteW(None, w!("open"), &HSTRING::from(uri.as_str()), None, None, SW_SHOWNORMAL) };
+    // Values above 32 mean success
+    if result.0 <= 32 {
+        return Err(format!("No app handles {}", uri.split(':').next().unwrap_or_default()));
+    }
+    Ok(())
+}
+
+// Encode everything but unreserved characters (RFC 3986), so the text survives as a single query value
+fn percent_encode(text: &str) -> String {
+    let mut encoded = String::with_capacity(text.len());
+    for byte in text.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => encoded.push(byte as char),
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
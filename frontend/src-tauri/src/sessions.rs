@@ -0,0 +1,103 @@
+use serde::{Deserialize, Serialize};
+
+use crate::history::{HistoryEntry, HistoryStore};
+
+// Sessions: dictations close together in time (a meeting, a writing stretch) grouped so they can be
+// reviewed and exported as one document. They're worked out from history timestamps when asked
+// for, so changing the gap regroups everything.
+
+pub const CONFIG_FILE: &str = "sessions.json";
+
+// How far back the History page looks for sessions by default
+const DEFAULT_DAYS: u32 = 30;
+const PREVIEW_CHARS: usize = 120;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SessionSettings {
+    pub gap_minutes: u32,  // A longer pause between dictations starts a new session
+}
+
+impl Default for SessionSettings {
+    fn default() -> Self {
+        Self { gap_minutes: 10 }
+    }
+}
+
+impl SessionSettings {
+    fn gap_ms(&self) -> i64 {
+        self.gap_minutes.max(1) as i64 * 60_000
+    }
+}
+
+// A session as listed in history
+#[derive(Debug, Clone, Serialize)]
+pub struct Session {
+    pub id: i64,  // Its first entry's id
+    pub started_at: i64,  // Unix ms of the first and last dictation
+    pub ended_at: i64,
+    pub entry_count: usize,
+    pub word_count: usize,
+    pub duration_secs: f64,  // Recorded audio, summed
+    pub apps: Vec<String>,  // Where the text went, most used first
+    pub preview: String,  // The start of the first dictation
+}
+
+// Sessions of the last `days` days (30 by default), newest first
+pub fn list(store: &HistoryStore, settings: &SessionSettings, days: Option<u32>) -> rusqlite::Result<Vec<Session>> {
+    let now = chrono::Utc::now().timestamp_millis();
+    let from = now - days.unwrap_or(DEFAULT_DAYS) as i64 * 86_400_000;
+    let entries = store.select(None, Some(from), None)?;
+    let mut sessions: Vec<Session> = group(&entries, settings.gap_ms()).into_iter().map(summarize).collect();
+    sessions.reverse();
+    Ok(sessions)
+}
+
+// The entries of the session that starts with entry `id`, oldest first (None if there's no such entry)
+pub fn entries(store: &HistoryStore, settings: &SessionSettings, id: i64) -> rusqlite::Result<Option<Vec<HistoryEntry>>> {
+    let Some(first) = store.entry(id)? else { return Ok(None) };
+    let following = store.select(None, Some(first.created_at), None)?;
+    let session = group(&following, settings.gap_ms()).into_iter().next().unwrap_or_default();
+    Ok(Some(session.into_iter().cloned().collect()))
+}
+
+// Split entries (oldest first) wherever the pause between two is longer than `gap_ms`
+fn group(entries: &[HistoryEntry], gap_ms: i64) -> Vec<Vec<&HistoryEntry>> {
+    let mut sessions: Vec<Vec<&HistoryEntry>> = Vec::new();
+    for entry in entries {
+        match sessions.last_mut() {
+            Some(session) if session.last().is_some_and(|last| entry.created_at - last.created_at <= gap_ms) => {
+                session.push(entry)
+            }
+            _ => sessions.push(vec![entry]),
+        }
+    }
+    sessions
+}
+
+fn summarize(entries: Vec<&HistoryEntry>) -> Session {
+    let mut apps: Vec<(String, usize)> = Vec::new();
+    for app in entries.iter().filter_map(|entry| entry.app.as_ref()) {
+        match apps.iter_mut().find(|(name, _)| name.eq_ignore_ascii_case(app)) {
+            Some((_, count)) => *count += 1,
+            None => apps.push((app.clone(), 1)),
+        }
+    }
+    apps.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+
+    let first = entries[0];
+    let mut preview: String = first.text.chars().take(PREVIEW_CHARS).collect();
+    if first.text.chars().count() > PREVIEW_CHARS {
+        preview.push('…');
+    }
+    Session {
+        id: first.id,
+        started_at: first.created_at,
+        ended_at: entries[entries.len() - 1].created_at,
+        entry_count: entries.len(),
+        word_count: entries.iter().map(|entry| entry.text.split_whitespace().count()).sum(),
+        duration_secs: entries.iter().filter_map(|entry| entry.duration_secs).sum(),
+        apps: apps.into_iter().map(|(app, _)| app).collect(),
+        preview,
+    }
+}
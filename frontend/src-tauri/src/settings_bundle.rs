@@ -0,0 +1,453 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use tauri::{AppHandle, Manager};
+
+use crate::formatting::{self, FormattingProfiles, FormattingSettings};
+use crate::profanity::ProfanityWords;
+use crate::snippets::{Snippet, SnippetStore};
+use crate::{accessibility, caption_server, casing, code_mode, custom_models, decoding, double_tap, ducking, file_transcription, focus_assist, history_export, hotkey_blocklist, journal, keep_awake, locale, main_window, meeting, mic_check, microphones, model_memory, model_slots, output_files, output_template, overlay, prebuffer, privacy, recording_limits, regional_format, retention, send_enter, send_target, sessions, shell_menu, snippets, sounds, stats, tray_icon, storage, watch_folder, win_h, AppState, AudioProcessingSettings, InjectionSettings, MultilingualSettings, OutputMode};
+
+// Bumped whenever a field changes meaning; bundles from newer versions are rejected, older ones
+// are migrated. 2: formatting is per model slot, with the profanity word lists shared.
+pub const SCHEMA_VERSION: u32 = 2;
+
+// Everything a user configures, as one JSON document for backup or moving to another machine
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SettingsBundle {
+    pub schema_version: u32,
+    #[serde(default)]
+    pub app_version: String,
+    #[serde(default)]
+    pub settings: Settings,
+    #[serde(default)]
+    pub hotkeys: Option<HashMap<String, String>>,  // Hotkey action -> shortcut string
+    #[serde(default)]
+    pub snippets: Option<Vec<Snippet>>,
+}
+
+// Settings in a bundle; missing fields (here and in the bundle) keep their current value on import.
+// The microphone and dragged overlay positions are left out since they only make sense on one machine.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct Settings {
+    pub model: Option<String>,
+    pub device: Option<String>,
+    pub language: Option<String>,
+    pub output_mode: Option<OutputMode>,
+    pub formatting: Option<FormattingProfiles>,
+    pub injection: Option<InjectionSettings>,
+    pub continuous_dictation: Option<bool>,
+    pub diarization: Option<bool>,
+    pub recording_limits: Option<recording_limits::RecordingLimits>,
+    pub double_tap: Option<double_tap::DoubleTapSettings>,
+    pub win_h: Option<win_h::WinHSettings>,
+    pub hotkey_blocklist: Option<Vec<String>>,
+    pub overlay: Option<overlay::OverlaySettings>,
+    pub stats: Option<stats::StatsSettings>,
+    pub privacy: Option<privacy::PrivacySettings>,
+    pub keep_awake: Option<keep_awake::KeepAwakeSettings>,
+    pub ducking: Option<ducking::DuckingSettings>,
+    pub mic_check: Option<mic_check::MicCheckSettings>,
+    pub queue_next_recording: Option<bool>,
+    pub audio_processing: Option<AudioProcessingSettings>,
+    pub multilingual: Option<MultilingualSettings>,
+    pub caption_server: Option<caption_server::CaptionServerSettings>,
+    pub journal: Option<journal::JournalSettings>,
+    pub file_transcription: Option<file_transcription::FileTranscriptionSettings>,
+    pub watch_folder: Option<watch_folder::WatchFolderSettings>,
+    pub history_export: Option<history_export::ExportSettings>,
+    pub sessions: Option<sessions::SessionSettings>,
+    pub meeting_capture: Option<meeting::MeetingCaptureSettings>,
+    pub retention: Option<retention::RetentionSettings>,
+    pub model_memory: Option<model_memory::ModelMemorySettings>,
+    pub model_slots: Option<model_slots::ModelSlots>,
+    pub custom_models: Option<custom_models::CustomModelStore>,
+    pub advanced: Option<decoding::AdvancedSettings>,
+    pub sounds: Option<sounds::SoundSettings>,
+    pub accessibility: Option<accessibility::AccessibilitySettings>,
+    pub locale: Option<locale::LocaleSettings>,
+    pub focus_assist: Option<focus_assist::FocusAssistSettings>,
+    pub main_window: Option<main_window::MainWindowSettings>,
+    pub tray_icon: Option<tray_icon::TrayIconSettings>,
+    pub send_targets: Option<send_target::SendTargetStore>,
+    pub output_templates: Option<output_template::TemplateStore>,
+    pub code_mode: Option<code_mode::CodeModeSettings>,
+    pub send_enter: Option<send_enter::SendEnterSettings>,
+    pub casing: Option<casing::CasingSettings>,
+    pub regional_formats: Option<regional_format::RegionalSettings>,
+    pub output_files: Option<output_files::OutputFileSettings>,
+    pub microphones: Option<microphones::MicrophoneSettings>,
+    pub prebuffer: Option<prebuffer::PrebufferSettings>,
+}
+
+pub async fn export(app: &AppHandle) -> SettingsBundle {
+    let state: tauri::State<AppState> = app.state();
+    let focus_assist_settings = state.focus_assist.lock().unwrap().clone();
+    let main_window_settings = state.main_window.lock().await.clone();
+    let bundle = SettingsBundle {
+        schema_version: SCHEMA_VERSION,
+        app_version: app.package_info().version.to_string(),
+        settings: Settings {
+            model: Some(state.selected_model.lock().await.clone()),
+            device: Some(state.selected_device.lock().await.clone()),
+            language: Some(state.selected_language.lock().await.clone()),
+            output_mode: Some(*state.output_mode.lock().await),
+            formatting: Some(state.formatting.lock().await.clone()),
+            injection: Some(state.injection.lock().await.clone()),
+            continuous_dictation: Some(*state.continuous_dictation.lock().await),
+            diarization: Some(*state.diarization.lock().await),
+            recording_limits: Some(state.recording_limits.lock().await.clone()),
+            double_tap: Some(state.double_tap.lock().await.clone()),
+            win_h: Some(state.win_h.lock().await.clone()),
+            hotkey_blocklist: Some(state.hotkey_blocklist.lock().await.clone()),
+            overlay: Some(state.overlay.lock().await.settings.clone()),
+            stats: Some(state.stats_settings.lock().await.clone()),
+            privacy: Some(state.privacy_settings.lock().await.clone()),
+            keep_awake: Some(state.keep_awake.lock().await.clone()),
+            ducking: Some(state.ducking.lock().await.clone()),
+            mic_check: Some(state.mic_check.lock().await.clone()),
+            queue_next_recording: Some(*state.queue_next_recording.lock().await),
+            audio_processing: Some(*state.audio_processing.lock().await),
+            multilingual: Some(state.multilingual.lock().await.clone()),
+            caption_server: Some(state.caption_server.lock().await.settings.clone()),
+            journal: Some(state.journal.lock().await.clone()),
+            file_transcription: Some(state.file_transcription.lock().await.clone()),
+            watch_folder: Some(state.watch_folder.lock().await.clone()),
+            history_export: Some(state.history_export.lock().await.clone()),
+            sessions: Some(state.sessions.lock().await.clone()),
+            meeting_capture: Some(state.meeting_capture.lock().await.clone()),
+            retention: Some(state.retention.lock().await.clone()),
+            model_memory: Some(state.model_memory.lock().await.clone()),
+            model_slots: Some(state.model_slots.lock().await.clone()),
+            custom_models: Some(state.custom_models.lock().await.clone()),
+            advanced: Some(state.advanced.lock().await.clone()),
+            sounds: Some(state.sounds.lock().await.clone()),
+            accessibility: Some(state.accessibility.lock().await.clone()),
+            locale: Some(state.locale.lock().await.clone()),
+            focus_assist: Some(focus_assist_settings),
+            main_window: Some(main_window_settings),
+            tray_icon: Some(state.tray_icon.lock().await.clone()),
+            send_targets: Some(state.send_targets.lock().await.clone()),
+            output_templates: Some(state.output_templates.lock().await.clone()),
+            code_mode: Some(state.code_mode.lock().await.clone()),
+            send_enter: Some(state.send_enter.lock().await.clone()),
+            casing: Some(state.casing.lock().await.clone()),
+            regional_formats: Some(state.regional_formats.lock().await.clone()),
+            output_files: Some(state.output_files.lock().await.clone()),
+            microphones: Some(state.microphones.lock().await.clone()),
+            prebuffer: Some(state.prebuffer.lock().await.clone()),
+        },
+        hotkeys: Some(state.shortcuts.lock().await.clone()),
+        snippets: Some(state.snippets.lock().await.snippets.clone()),
+    };
+    bundle
+}
+
+// Apply a bundle. The version, snippets, policy locks, and hotkeys are checked before anything is
+// applied. The keyboard hooks and hotkeys are then applied and rolled back together if one of them
+// fails (e.g. a hotkey another app holds), so a bad bundle leaves the current settings alone.
+pub async fn import(app: &AppHandle, json: &str) -> Result<(), String> {
+    let mut value: Value = serde_json::from_str(json).map_err(|e| format!("Not a valid settings file: {}", e))?;
+    if value["schema_version"] == 1 {
+        migrate_v1(&mut value)?;
+    }
+    let bundle: SettingsBundle = serde_json::from_value(value).map_err(|e| format!("Not a valid settings file: {}", e))?;
+    if bundle.schema_version == 0 || bundle.schema_version > SCHEMA_VERSION {
+        return Err(format!(
+            "Settings file has schema version {} but this version of Whisper4Windows supports up to {}",
+            bundle.schema_version, SCHEMA_VERSION
+        ));
+    }
+
+    let snippet_store = match bundle.snippets {
+        Some(snippets) => {
+            let mut store = SnippetStore::default();
+            for snippet in snippets {
+                store.upsert(snippet)?;
+            }
+            Some(store)
+        }
+        None => None,
+    };
+    if let Some(slot_settings) = &bundle.settings.model_slots {
+        model_slots::check_policy(slot_settings)?;
+    }
+
+    let state: tauri::State<AppState> = app.state();
+    if let Some(hotkeys) = &bundle.hotkeys {
+        crate::merge_shortcuts(&*state.shortcuts.lock().await, hotkeys)?;
+    }
+
+    // Hooks first: unlike hotkey registration, installing one can't be checked beforehand
+    let settings = bundle.settings;
+    let old_double_tap = state.double_tap.lock().await.clone();
+    let old_win_h = state.win_h.lock().await.clone();
+    if let Some(double_tap) = &settings.double_tap {
+        double_tap::apply(app, double_tap)?;
+    }
+    if let Some(win_h_settings) = &settings.win_h {
+        if let Err(e) = win_h::apply(app, win_h_settings) {
+            let _ = double_tap::apply(app, &old_double_tap);
+            return Err(e);
+        }
+    }
+    if let Some(hotkeys) = bundle.hotkeys {
+        if let Err(e) = crate::save_shortcuts(hotkeys, app.clone(), app.state()).await {
+            let _ = double_tap::apply(app, &old_double_tap);
+            let _ = win_h::apply(app, &old_win_h);
+            return Err(e);
+        }
+    }
+    if let Some(double_tap) = settings.double_tap {
+        storage::save_json(app, double_tap::CONFIG_FILE, &double_tap).map_err(|e| e.to_string())?;
+        *state.double_tap.lock().await = double_tap;
+    }
+    if let Some(win_h_settings) = settings.win_h {
+        storage::save_json(app, win_h::CONFIG_FILE, &win_h_settings).map_err(|e| e.to_string())?;
+        *state.win_h.lock().await = win_h_settings;
+    }
+
+    // Every section is saved as it's applied, and save_json keeps the previous file as a backup. The
+    // model, device, and language are saved together with the tray's selection further down.
+    if let Some(model) = settings.model {
+        *state.selected_model.lock().await = model;
+    }
+    if let Some(device) = settings.device {
+        *state.selected_device.lock().await = device;
+    }
+    if let Some(language) = settings.language {
+        *state.selected_language.lock().await = language;
+    }
+    if let Some(output_mode) = settings.output_mode {
+        storage::save_json(app, OutputMode::CONFIG_FILE, &output_mode).map_err(|e| e.to_string())?;
+        *state.output_mode.lock().await = output_mode;
+    }
+    if let Some(formatting_profiles) = settings.formatting {
+        storage::save_json(app, formatting::CONFIG_FILE, &formatting_profiles).map_err(|e| e.to_string())?;
+        *state.formatting.lock().await = formatting_profiles;
+    }
+    if let Some(injection) = settings.injection {
+        storage::save_json(app, InjectionSettings::CONFIG_FILE, &injection).map_err(|e| e.to_string())?;
+        *state.injection.lock().await = injection;
+    }
+    if let Some(enabled) = settings.continuous_dictation {
+        storage::save_json(app, crate::CONTINUOUS_DICTATION_FILE, &enabled).map_err(|e| e.to_string())?;
+        *state.continuous_dictation.lock().await = enabled;
+    }
+    if let Some(enabled) = settings.diarization {
+        storage::save_json(app, crate::DIARIZATION_FILE, &enabled).map_err(|e| e.to_string())?;
+        *state.diarization.lock().await = enabled;
+    }
+    if let Some(limits) = settings.recording_limits {
+        storage::save_json(app, recording_limits::CONFIG_FILE, &limits).map_err(|e| e.to_string())?;
+        *state.recording_limits.lock().await = limits;
+    }
+    if let Some(blocklist) = settings.hotkey_blocklist {
+        storage::save_json(app, hotkey_blocklist::CONFIG_FILE, &blocklist).map_err(|e| e.to_string())?;
+        *state.hotkey_blocklist.lock().await = blocklist;
+    }
+    if let Some(stats_settings) = settings.stats {
+        storage::save_json(app, stats::CONFIG_FILE, &stats_settings).map_err(|e| e.to_string())?;
+        *state.stats_settings.lock().await = stats_settings;
+    }
+    if let Some(privacy_settings) = settings.privacy {
+        storage::save_json(app, privacy::CONFIG_FILE, &privacy_settings).map_err(|e| e.to_string())?;
+        *state.privacy_settings.lock().await = privacy_settings;
+    }
+    if let Some(keep_awake_settings) = settings.keep_awake {
+        storage::save_json(app, keep_awake::CONFIG_FILE, &keep_awake_settings).map_err(|e| e.to_string())?;
+        *state.keep_awake.lock().await = keep_awake_settings;
+    }
+    if let Some(ducking_settings) = settings.ducking {
+        storage::save_json(app, ducking::CONFIG_FILE, &ducking_settings).map_err(|e| e.to_string())?;
+        *state.ducking.lock().await = ducking_settings;
+    }
+    if let Some(mic_check_settings) = settings.mic_check {
+        storage::save_json(app, mic_check::CONFIG_FILE, &mic_check_settings).map_err(|e| e.to_string())?;
+        *state.mic_check.lock().await = mic_check_settings;
+    }
+    if let Some(enabled) = settings.queue_next_recording {
+        storage::save_json(app, crate::QUEUE_NEXT_RECORDING_FILE, &enabled).map_err(|e| e.to_string())?;
+        *state.queue_next_recording.lock().await = enabled;
+    }
+    if let Some(processing) = settings.audio_processing {
+        storage::save_json(app, AudioProcessingSettings::CONFIG_FILE, &processing).map_err(|e| e.to_string())?;
+        *state.audio_processing.lock().await = processing;
+    }
+    if let Some(multilingual) = settings.multilingual {
+        storage::save_json(app, MultilingualSettings::CONFIG_FILE, &multilingual).map_err(|e| e.to_string())?;
+        *state.multilingual.lock().await = multilingual;
+    }
+
+    if let Some(overlay_settings) = settings.overlay {
+        let mut config = state.overlay.lock().await;
+        config.settings = overlay_settings;
+        storage::save_json(app, overlay::CONFIG_FILE, &*config).map_err(|e| e.to_string())?;
+    }
+    crate::quick_switch::save(app).await;
+    if let Some(journal_settings) = settings.journal {
+        storage::save_json(app, journal::CONFIG_FILE, &journal_settings).map_err(|e| e.to_string())?;
+        *state.journal.lock().await = journal_settings;
+    }
+    if let Some(file_transcription_settings) = settings.file_transcription {
+        storage::save_json(app, file_transcription::CONFIG_FILE, &file_transcription_settings).map_err(|e| e.to_string())?;
+        shell_menu::apply(file_transcription_settings.explorer_menu);
+        *state.file_transcription.lock().await = file_transcription_settings;
+    }
+    if let Some(watch_folder_settings) = settings.watch_folder {
+        storage::save_json(app, watch_folder::CONFIG_FILE, &watch_folder_settings).map_err(|e| e.to_string())?;
+        *state.watch_folder.lock().await = watch_folder_settings;
+    }
+    if let Some(export_settings) = settings.history_export {
+        storage::save_json(app, history_export::CONFIG_FILE, &export_settings).map_err(|e| e.to_string())?;
+        *state.history_export.lock().await = export_settings;
+    }
+    if let Some(session_settings) = settings.sessions {
+        storage::save_json(app, sessions::CONFIG_FILE, &session_settings).map_err(|e| e.to_string())?;
+        *state.sessions.lock().await = session_settings;
+    }
+    if let Some(meeting_settings) = settings.meeting_capture {
+        storage::save_json(app, meeting::CONFIG_FILE, &meeting_settings).map_err(|e| e.to_string())?;
+        *state.meeting_capture.lock().await = meeting_settings;
+    }
+    if let Some(retention_settings) = settings.retention {
+        storage::save_json(app, retention::CONFIG_FILE, &retention_settings).map_err(|e| e.to_string())?;
+        *state.retention.lock().await = retention_settings;
+    }
+    if let Some(slot_settings) = settings.model_slots {
+        storage::save_json(app, model_slots::CONFIG_FILE, &slot_settings).map_err(|e| e.to_string())?;
+        *state.model_slots.lock().await = slot_settings;
+    }
+    if let Some(custom_model_store) = settings.custom_models {
+        storage::save_json(app, custom_models::CONFIG_FILE, &custom_model_store).map_err(|e| e.to_string())?;
+        *state.custom_models.lock().await = custom_model_store;
+    }
+    if let Some(advanced_settings) = settings.advanced {
+        storage::save_json(app, decoding::CONFIG_FILE, &advanced_settings).map_err(|e| e.to_string())?;
+        *state.advanced.lock().await = advanced_settings;
+    }
+    if let Some(sound_settings) = settings.sounds {
+        storage::save_json(app, sounds::CONFIG_FILE, &sound_settings).map_err(|e| e.to_string())?;
+        *state.sounds.lock().await = sound_settings;
+    }
+    if let Some(accessibility_settings) = settings.accessibility {
+        storage::save_json(app, accessibility::CONFIG_FILE, &accessibility_settings).map_err(|e| e.to_string())?;
+        *state.accessibility.lock().await = accessibility_settings;
+    }
+    if let Some(locale_settings) = settings.locale {
+        storage::save_json(app, locale::CONFIG_FILE, &locale_settings).map_err(|e| e.to_string())?;
+        locale::apply(&locale_settings);
+        *state.locale.lock().await = locale_settings;
+        crate::refresh_tray(app).await;
+    }
+    if let Some(focus_assist_settings) = settings.focus_assist {
+        storage::save_json(app, focus_assist::CONFIG_FILE, &focus_assist_settings).map_err(|e| e.to_string())?;
+        *state.focus_assist.lock().unwrap() = focus_assist_settings;
+    }
+    if let Some(main_window_settings) = settings.main_window {
+        storage::save_json(app, main_window::CONFIG_FILE, &main_window_settings).map_err(|e| e.to_string())?;
+        *state.main_window.lock().await = main_window_settings;
+    }
+    if let Some(tray_icon_settings) = settings.tray_icon {
+        storage::save_json(app, tray_icon::CONFIG_FILE, &tray_icon_settings).map_err(|e| e.to_string())?;
+        *state.tray_icon.lock().await = tray_icon_settings;
+        crate::refresh_tray(app).await;
+    }
+    if let Some(memory_settings) = settings.model_memory {
+        storage::save_json(app, model_memory::CONFIG_FILE, &memory_settings).map_err(|e| e.to_string())?;
+        *state.model_memory.lock().await = memory_settings.clone();
+        // The backend may be unreachable; the policy is sent again at the next start
+        if let Err(e) = model_memory::apply(&memory_settings).await {
+            log::warn!("⚠️ Model memory policy not applied after import: {}", e);
+        }
+    }
+    if let Some(send_targets) = settings.send_targets {
+        storage::save_json(app, send_target::CONFIG_FILE, &send_targets).map_err(|e| e.to_string())?;
+        *state.send_targets.lock().await = send_targets;
+    }
+    if let Some(template_store) = settings.output_templates {
+        storage::save_json(app, output_template::CONFIG_FILE, &template_store).map_err(|e| e.to_string())?;
+        *state.output_templates.lock().await = template_store;
+    }
+    if let Some(code_mode_settings) = settings.code_mode {
+        storage::save_json(app, code_mode::CONFIG_FILE, &code_mode_settings).map_err(|e| e.to_string())?;
+        *state.code_mode.lock().await = code_mode_settings;
+    }
+    if let Some(send_enter_settings) = settings.send_enter {
+        storage::save_json(app, send_enter::CONFIG_FILE, &send_enter_settings).map_err(|e| e.to_string())?;
+        *state.send_enter.lock().await = send_enter_settings;
+    }
+    if let Some(casing_settings) = settings.casing {
+        storage::save_json(app, casing::CONFIG_FILE, &casing_settings).map_err(|e| e.to_string())?;
+        *state.casing.lock().await = casing_settings;
+    }
+    if let Some(regional_settings) = settings.regional_formats {
+        storage::save_json(app, regional_format::CONFIG_FILE, &regional_settings).map_err(|e| e.to_string())?;
+        *state.regional_formats.lock().await = regional_settings;
+    }
+    if let Some(output_file_settings) = settings.output_files {
+        storage::save_json(app, output_files::CONFIG_FILE, &output_file_settings).map_err(|e| e.to_string())?;
+        *state.output_files.lock().await = output_file_settings;
+    }
+    if let Some(microphone_settings) = settings.microphones {
+        storage::save_json(app, microphones::CONFIG_FILE, &microphone_settings).map_err(|e| e.to_string())?;
+        *state.microphones.lock().await = microphone_settings;
+    }
+    if let Some(prebuffer_settings) = settings.prebuffer {
+        storage::save_json(app, prebuffer::CONFIG_FILE, &prebuffer_settings).map_err(|e| e.to_string())?;
+        *state.prebuffer.lock().await = prebuffer_settings;
+        // The backend may be unreachable; the pre-buffer is sent again at the next start
+        if let Err(e) = prebuffer::apply(app).await {
+            log::warn!("⚠️ Pre-buffer not applied after import: {}", e);
+        }
+    }
+    if let Some(mut caption_settings) = settings.caption_server {
+        if let Err(e) = caption_server::ensure_token(&mut caption_settings) {
+            log::warn!("⚠️ {}", e);
+        }
+        storage::save_json(app, caption_server::CONFIG_FILE, &caption_settings).map_err(|e| e.to_string())?;
+        // A busy port shouldn't fail the import; the settings are kept and the server can be restarted later
+        if let Err(e) = caption_server::apply(&state, caption_settings).await {
+            log::warn!("⚠️ Caption server not started after import: {}", e);
+        }
+    }
+    if let Some(snippet_store) = snippet_store {
+        let mut snippets = state.snippets.lock().await;
+        *snippets = snippet_store;
+        storage::save_json(app, snippets::CONFIG_FILE, &*snippets).map_err(|e| e.to_string())?;
+    }
+    // Whatever the file says, the administrator's policy still holds
+    crate::policy::apply(app).await;
+
+    log::info!("📥 Imported settings (schema version {})", bundle.schema_version);
+    Ok(())
+}
+
+// Version 1 had one flat formatting object, with the profanity mode and word lists inside it. It
+// becomes the default slot's formatting, and its word lists the shared ones.
+fn migrate_v1(bundle: &mut Value) -> Result<(), String> {
+    let Some(formatting) = bundle.get_mut("settings").and_then(|settings| settings.get_mut("formatting")) else {
+        return Ok(());
+    };
+    if formatting.is_null() {
+        return Ok(());
+    }
+    let Some(fields) = formatting.as_object_mut() else {
+        return Err("Not a valid settings file: formatting is not an object".to_string());
+    };
+
+    let mut profanity = fields.remove("profanity").unwrap_or_else(|| serde_json::json!({}));
+    if let Some(mode) = profanity.get_mut("mode").map(Value::take) {
+        fields.insert("profanity".to_string(), mode);
+    }
+    let settings: FormattingSettings =
+        serde_json::from_value(formatting.take()).map_err(|e| format!("Not a valid settings file: formatting: {}", e))?;
+    let profanity_words: ProfanityWords =
+        serde_json::from_value(profanity).map_err(|e| format!("Not a valid settings file: formatting.profanity: {}", e))?;
+
+    let mut profiles = FormattingProfiles { profanity_words, ..Default::default() };
+    profiles.select(model_slots::DEFAULT, settings);
+    *formatting = serde_json::to_value(profiles).map_err(|e| e.to_string())?;
+    Ok(())
+}
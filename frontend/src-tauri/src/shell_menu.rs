@@ -0,0 +1,127 @@
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+use tokio::sync::mpsc::UnboundedSender;
+use windows::core::{HSTRING, PCWSTR};
+use windows::Win32::Foundation::ERROR_FILE_NOT_FOUND;
+use windows::Win32::System::Registry::{
+    RegCloseKey, RegCreateKeyExW, RegDeleteTreeW, RegSetValueExW, HKEY, HKEY_CURRENT_USER, KEY_SET_VALUE, REG_OPTION_NON_VOLATILE, REG_SZ,
+};
+use windows::Win32::UI::Shell::{SHChangeNotify, SHCNE_ASSOCCHANGED, SHCNF_IDLIST};
+
+use crate::file_transcription::{self, Destination};
+use crate::recording_state::{self, RecordingState};
+use crate::AppState;
+
+// "Transcribe with Whisper4Windows" in Explorer's context menu for audio and video files. The entry
+// starts the executable with `--transcribe <file>`; the single-instance plugin hands that to the
+// running app, which queues the file and writes the transcript next to it. It's registered for the
+// current user only (under HKCU\Software\Classes), so no admin rights are needed.
+
+pub const TRANSCRIBE_ARG: &str = "--transcribe";
+
+const VERB: &str = "Whisper4Windows.Transcribe";
+
+// Files waiting to be transcribed, handed to the worker started in setup
+static QUEUE: Mutex<Option<UnboundedSender<PathBuf>>> = Mutex::new(None);
+
+// Add or remove the context menu entry. Also run at startup, so the entry follows the executable
+// if it's moved (portable mode) and its title follows the display language.
+pub fn apply(enabled: bool) {
+    let result = if enabled {
+        std::env::current_exe().map_err(|e| e.to_string()).and_then(|exe| register(&exe).map_err(|e| e.to_string()))
+    } else {
+        unregister().map_err(|e| e.to_string())
+    };
+    // Explorer caches context menus per file type
+    unsafe { SHChangeNotify(SHCNE_ASSOCCHANGED, SHCNF_IDLIST, None, None) };
+    if let Err(e) = result {
+        log::warn!("⚠️ Failed to update the Explorer context menu: {}", e);
+    }
+}
+
+fn verb_key(extension: &str) -> String {
+    format!("Software\\Classes\\SystemFileAssociations\\.{}\\shell\\{}", extension, VERB)
+}
+
+fn register(exe: &Path) -> windows::core::Result<()> {
+    let title = crate::locale::tr("shell.transcribe");
+    let icon = format!("{},0", exe.display());
+    let command = format!("\"{}\" {} \"%1\"", exe.display(), TRANSCRIBE_ARG);
+    for extension in file_transcription::extensions() {
+        let key = verb_key(extension);
+        unsafe {
+            set_string(&key, "MUIVerb", &title)?;
+            set_string(&key, "Icon", &icon)?;
+            set_string(&format!("{}\\command", key), "", &command)?;
+        }
+    }
+    Ok(())
+}
+
+fn unregister() -> windows::core::Result<()> {
+    for extension in file_transcription::extensions() {
+        match unsafe { RegDeleteTreeW(HKEY_CURRENT_USER, &HSTRING::from(verb_key(extension))) } {
+            Ok(()) => {}
+            Err(e) if e.code() == ERROR_FILE_NOT_FOUND.to_hresult() => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(())
+}
+
+// Set a string value ("" = the key's default value), creating the key if needed
+unsafe fn set_string(subkey: &str, name: &str, value: &str) -> windows::core::Result<()> {
+    let mut key = HKEY::default();
+    RegCreateKeyExW(
+        HKEY_CURRENT_USER,
+        &HSTRING::from(subkey),
+        0,
+        PCWSTR::null(),
+        REG_OPTION_NON_VOLATILE,
+        KEY_SET_VALUE,
+        None,
+        &mut key,
+        None,
+    )?;
+    let data: Vec<u16> = value.encode_utf16().chain(std::iter::once(0)).collect();
+    let bytes = std::slice::from_raw_parts(data.as_ptr() as *const u8, std::mem::size_of_val(data.as_slice()));
+    let result = RegSetValueExW(key, &HSTRING::from(name), 0, REG_SZ, Some(bytes));
+    let _ = RegCloseKey(key);
+    result
+}
+
+// The file a `--transcribe <file>` launch asked for
+pub fn file_from_args(args: &[String]) -> Option<PathBuf> {
+    let index = args.iter().position(|arg| arg == TRANSCRIBE_ARG)?;
+    args.get(index + 1).map(PathBuf::from)
+}
+
+// Queue the file from a context menu launch (a second instance's arguments, or our own at startup)
+pub fn run(args: &[String]) -> bool {
+    let Some(path) = file_from_args(args) else { return false };
+    log::info!("📂 Queued from Explorer: {}", path.display());
+    match QUEUE.lock().unwrap().as_ref() {
+        Some(queue) => {
+            let _ = queue.send(path);
+        }
+        None => log::warn!("⚠️ File queue isn't running"),
+    }
+    true
+}
+
+// Transcribe queued files one at a time, each once no dictation is in progress
+pub fn spawn_worker(app: AppHandle) {
+    let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel::<PathBuf>();
+    *QUEUE.lock().unwrap() = Some(sender);
+
+    tauri::async_runtime::spawn(async move {
+        while let Some(path) = receiver.recv().await {
+            let state: tauri::State<AppState> = app.state();
+            while !matches!(recording_state::get(&state).await, RecordingState::Idle | RecordingState::Error) {
+                tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+            }
+            file_transcription::transcribe(&app, &state, &path, Destination::NextToFile).await;
+        }
+    });
+}
@@ -0,0 +1,188 @@
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+
+use crate::regional_format::RegionalFormat;
+
+pub const CONFIG_FILE: &str = "snippets.json";
+
+// Spoken prefix used when a snippet has no explicit phrase ("insert signature")
+const DEFAULT_PREFIX: &str = "insert";
+
+// A block of text inserted when its phrase is spoken
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct Snippet {
+    pub name: String,  // Unique, e.g. "signature"
+    pub phrase: String,  // What to say; empty = "insert <name>"
+    pub template: String,  // May contain {date}, {time}, {datetime}, {clipboard}, or {date:<strftime format>}
+}
+
+impl Snippet {
+    fn trigger_phrase(&self) -> String {
+        if self.phrase.trim().is_empty() {
+            format!("{} {}", DEFAULT_PREFIX, self.name)
+        } else {
+            self.phrase.clone()
+        }
+    }
+}
+
+// Snippets persisted in the config directory
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct SnippetStore {
+    pub snippets: Vec<Snippet>,
+}
+
+impl SnippetStore {
+    // Add a snippet, or replace the one with the same name
+    pub fn upsert(&mut self, snippet: Snippet) -> Result<(), String> {
+        if snippet.name.trim().is_empty() {
+            return Err("Snippet name cannot be empty".to_string());
+        }
+        if snippet.template.is_empty() {
+            return Err("Snippet text cannot be empty".to_string());
+        }
+
+        let phrase = words(&snippet.trigger_phrase());
+        if phrase.is_empty() {
+            return Err("Snippet phrase must contain at least one word".to_string());
+        }
+        if let Some(other) = self
+            .snippets
+            .iter()
+            .find(|s| !s.name.eq_ignore_ascii_case(&snippet.name) && words(&s.trigger_phrase()) == phrase)
+        {
+            return Err(format!("\"{}\" is already the phrase for snippet {}", snippet.trigger_phrase(), other.name));
+        }
+
+        match self.snippets.iter_mut().find(|s| s.name.eq_ignore_ascii_case(&snippet.name)) {
+            Some(existing) => *existing = snippet,
+            None => self.snippets.push(snippet),
+        }
+        Ok(())
+    }
+
+    // Remove a snippet by name; returns false if there was none
+    pub fn remove(&mut self, name: &str) -> bool {
+        let before = self.snippets.len();
+        self.snippets.retain(|s| !s.name.eq_ignore_ascii_case(name));
+        self.snippets.len() != before
+    }
+}
+
+// Lowercase words without surrounding punctuation, for phrase matching
+fn words(text: &str) -> Vec<String> {
+    text.split_whitespace()
+        .map(|w| w.trim_matches(|c: char| !c.is_alphanumeric()).to_lowercase())
+        .filter(|w| !w.is_empty())
+        .collect()
+}
+
+// Fill in a template's placeholders (unknown placeholders are left as typed)
+pub fn render(template: &str, format: &RegionalFormat) -> String {
+    render_at(template, chrono::Local::now(), format, true)
+}
+
+// Fill in the placeholders with dates and times from `now` in `format` (unless the placeholder
+// gives its own), and the clipboard if `clipboard` is set
+pub fn render_at(template: &str, now: chrono::DateTime<chrono::Local>, format: &RegionalFormat, clipboard: bool) -> String {
+    let mut result = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(open) = rest.find('{') {
+        result.push_str(&rest[..open]);
+        let Some(close) = rest[open..].find('}').map(|i| open + i) else {
+            result.push_str(&rest[open..]);
+            return result;
+        };
+        let placeholder = &rest[open + 1..close];
+
+        let value = match placeholder.split_once(':') {
+            Some(("date", format)) | Some(("time", format)) | Some(("datetime", format)) => {
+                // An invalid format string makes Display fail, which `to_string` would turn into a panic
+                let mut formatted = String::new();
+                write!(formatted, "{}", now.format(format)).ok().map(|_| formatted)
+            }
+            Some(_) => None,
+            None => match placeholder {
+                "date" => Some(now.format(format.date).to_string()),
+                "time" => Some(now.format(format.time).to_string()),
+                "datetime" => Some(now.format(&format.datetime()).to_string()),
+                "clipboard" if clipboard => crate::clipboard::get_text().ok().flatten(),
+                _ => None,
+            },
+        };
+
+        match value {
+            Some(value) => result.push_str(&value),
+            None => result.push_str(&rest[open..=close]),
+        }
+        rest = &rest[close + 1..];
+    }
+    result.push_str(rest);
+    result
+}
+
+// Replace spoken snippet phrases in `text` with their rendered templates.
+// When the whole transcription is a phrase, punctuation the model or formatting added around it is dropped.
+pub fn expand(text: &str, snippets: &[Snippet], format: &RegionalFormat) -> String {
+    if snippets.is_empty() {
+        return text.to_string();
+    }
+
+    let trailing_ws = &text[text.trim_end().len()..];
+    let tokens: Vec<&str> = text.split_whitespace().collect();
+    let normalized: Vec<String> = tokens
+        .iter()
+        .map(|t| t.trim_matches(|c: char| !c.is_alphanumeric()).to_lowercase())
+        .collect();
+
+    // Longest phrases first so "insert signature long" wins over "insert signature"
+    let mut phrases: Vec<(Vec<String>, &Snippet)> =
+        snippets.iter().map(|s| (words(&s.trigger_phrase()), s)).filter(|(p, _)| !p.is_empty()).collect();
+    phrases.sort_by_key(|(phrase, _)| std::cmp::Reverse(phrase.len()));
+
+    let mut parts: Vec<String> = Vec::new();
+    let mut expanded = false;
+    let mut i = 0;
+    while i < tokens.len() {
+        let matched = phrases
+            .iter()
+            .find(|(phrase, _)| normalized.len() >= i + phrase.len() && normalized[i..i + phrase.len()] == phrase[..]);
+
+        match matched {
+            Some((phrase, snippet)) => {
+                let whole_text = i == 0 && phrase.len() == tokens.len();
+                let rendered = render(&snippet.template, format);
+                log::info!("🧩 Expanded snippet: {}", snippet.name);
+
+                if whole_text {
+                    parts.push(rendered);
+                } else {
+                    // Keep punctuation glued to the spoken words ("...then, insert signature.")
+                    let first = tokens[i];
+                    let last = tokens[i + phrase.len() - 1];
+                    let prefix = &first[..first.find(char::is_alphanumeric).unwrap_or(0)];
+                    let suffix_start = last
+                        .char_indices()
+                        .rev()
+                        .find(|(_, c)| c.is_alphanumeric())
+                        .map_or(last.len(), |(p, c)| p + c.len_utf8());
+                    parts.push(format!("{}{}{}", prefix, rendered, &last[suffix_start..]));
+                }
+                expanded = true;
+                i += phrase.len();
+            }
+            None => {
+                parts.push(tokens[i].to_string());
+                i += 1;
+            }
+        }
+    }
+
+    if !expanded {
+        return text.to_string();
+    }
+    format!("{}{}", parts.join(" "), trailing_ws)
+}
@@ -0,0 +1,173 @@
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+use windows::core::{HSTRING, PCWSTR};
+use windows::Win32::Media::Audio::{waveOutSetVolume, PlaySoundW, HWAVEOUT, SND_FILENAME, SND_MEMORY, SND_NODEFAULT, SND_SYNC};
+
+use crate::AppState;
+
+// Audio cues for recording events, played through WinMM so they work with the overlay hidden.
+// Each event has a built-in tone that a WAV file can replace.
+
+pub const CONFIG_FILE: &str = "sounds.json";
+
+const SAMPLE_RATE: u32 = 22050;
+// Built-in tones peak at this fraction of full scale, before the volume setting
+const TONE_AMPLITUDE: f64 = 0.3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SoundEvent {
+    Start,
+    Stop,
+    Cancel,
+    Error,
+}
+
+impl SoundEvent {
+    // (frequency in Hz, seconds) segments; 0 Hz is a pause
+    fn tone(self) -> &'static [(f64, f64)] {
+        match self {
+            SoundEvent::Start => &[(800.0, 0.1)],  // Higher pitch, short beep
+            SoundEvent::Stop => &[(400.0, 0.15)],  // Lower pitch, slightly longer beep
+            SoundEvent::Cancel => &[(600.0, 0.08), (0.0, 0.04), (300.0, 0.12)],
+            SoundEvent::Error => &[(250.0, 0.15), (0.0, 0.06), (250.0, 0.15)],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SoundCue {
+    pub enabled: bool,
+    pub file: Option<String>,  // WAV file played instead of the built-in tone
+}
+
+impl Default for SoundCue {
+    fn default() -> Self {
+        Self { enabled: true, file: None }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SoundSettings {
+    pub enabled: bool,
+    pub volume: u8,  // 0-100
+    pub start: SoundCue,
+    pub stop: SoundCue,
+    pub cancel: SoundCue,
+    pub error: SoundCue,
+}
+
+impl Default for SoundSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            volume: 80,
+            start: SoundCue::default(),
+            stop: SoundCue::default(),
+            cancel: SoundCue::default(),
+            error: SoundCue::default(),
+        }
+    }
+}
+
+impl SoundSettings {
+    fn cue(&self, event: SoundEvent) -> &SoundCue {
+        match event {
+            SoundEvent::Start => &self.start,
+            SoundEvent::Stop => &self.stop,
+            SoundEvent::Cancel => &self.cancel,
+            SoundEvent::Error => &self.error,
+        }
+    }
+}
+
+// Play the cue for `event` in the background, if the settings allow it right now
+pub fn play(app: &AppHandle, event: SoundEvent) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let state: tauri::State<AppState> = app.state();
+        let settings = state.sounds.lock().await.clone();
+        let cue = settings.cue(event);
+        if !settings.enabled || !cue.enabled {
+            return;
+        }
+        if crate::focus_assist::is_quiet(&app) {
+            log::info!("🔕 Skipping the {:?} sound (Focus Assist)", event);
+            return;
+        }
+        if let Err(e) = preview(event, cue.file.clone(), settings.volume).await {
+            log::warn!("⚠️ Couldn't play the {:?} sound: {}", event, e);
+        }
+    });
+}
+
+// Play the cue for `event` regardless of the enable settings (also used by the settings window's Test button)
+pub async fn preview(event: SoundEvent, file: Option<String>, volume: u8) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || play_blocking(event, file.as_deref(), volume))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+fn play_blocking(event: SoundEvent, file: Option<&str>, volume: u8) -> Result<(), String> {
+    // Sets the volume of our own audio session, which only these cues play through
+    let level = u32::from(volume.min(100)) * 0xFFFF / 100;
+    unsafe { waveOutSetVolume(HWAVEOUT::default(), level | (level << 16)) };
+
+    let played = match file.filter(|f| !f.trim().is_empty()) {
+        Some(file) => {
+            if !std::path::Path::new(file).is_file() {
+                return Err(format!("{} not found", file));
+            }
+            unsafe { PlaySoundW(&HSTRING::from(file), None, SND_FILENAME | SND_NODEFAULT | SND_SYNC) }
+        }
+        None => {
+            let wav = tone_wav(event.tone());
+            unsafe { PlaySoundW(PCWSTR(wav.as_ptr() as *const u16), None, SND_MEMORY | SND_NODEFAULT | SND_SYNC) }
+        }
+    };
+    if played.as_bool() {
+        Ok(())
+    } else {
+        Err("The sound couldn't be played (only WAV files are supported)".to_string())
+    }
+}
+
+// A 16-bit mono WAV of sine segments, each fading out like a struck bell
+fn tone_wav(segments: &[(f64, f64)]) -> Vec<u8> {
+    let mut samples: Vec<i16> = Vec::new();
+    for &(frequency, seconds) in segments {
+        let count = (seconds * f64::from(SAMPLE_RATE)) as usize;
+        for i in 0..count {
+            let t = i as f64 / f64::from(SAMPLE_RATE);
+            let value = if frequency > 0.0 {
+                // Down to 1/30 of the start over the segment
+                let envelope = (-(30f64.ln()) * t / seconds).exp();
+                (2.0 * std::f64::consts::PI * frequency * t).sin() * envelope * TONE_AMPLITUDE
+            } else {
+                0.0
+            };
+            samples.push((value * f64::from(i16::MAX)) as i16);
+        }
+    }
+
+    let data_len = (samples.len() * 2) as u32;
+    let mut wav = Vec::with_capacity(44 + data_len as usize);
+    wav.extend_from_slice(b"RIFF");
+    wav.extend_from_slice(&(36 + data_len).to_le_bytes());
+    wav.extend_from_slice(b"WAVEfmt ");
+    wav.extend_from_slice(&16u32.to_le_bytes());
+    wav.extend_from_slice(&1u16.to_le_bytes());  // PCM
+    wav.extend_from_slice(&1u16.to_le_bytes());  // Mono
+    wav.extend_from_slice(&SAMPLE_RATE.to_le_bytes());
+    wav.extend_from_slice(&(SAMPLE_RATE * 2).to_le_bytes());  // Bytes per second
+    wav.extend_from_slice(&2u16.to_le_bytes());  // Block align
+    wav.extend_from_slice(&16u16.to_le_bytes());  // Bits per sample
+    wav.extend_from_slice(b"data");
+    wav.extend_from_slice(&data_len.to_le_bytes());
+    for sample in samples {
+        wav.extend_from_slice(&sample.to_le_bytes());
+    }
+    wav
+}
@@ -0,0 +1,174 @@
+use Joining::{Break, Leading, Trailing};
+
+// How a spoken command's output joins the surrounding words
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Joining {
+    Trailing,  // Attached to the previous word: "word," / "word."
+    Leading,  // Attached to the next word: "(word" / "\"word"
+    Break,  // Line breaks: no spaces on either side
+}
+
+// (phrase, output, joining) per language; phrases are matched case-insensitively as whole words
+type Commands = &'static [(&'static str, &'static str, Joining)];
+
+const ENGLISH: Commands = &[
+    ("period", ".", Trailing),
+    ("full stop", ".", Trailing),
+    ("comma", ",", Trailing),
+    ("question mark", "?", Trailing),
+    ("exclamation mark", "!", Trailing),
+    ("exclamation point", "!", Trailing),
+    ("colon", ":", Trailing),
+    ("semicolon", ";", Trailing),
+    ("open quote", "\"", Leading),
+    ("close quote", "\"", Trailing),
+    ("open parenthesis", "(", Leading),
+    ("close parenthesis", ")", Trailing),
+    ("new line", "\n", Break),
+    ("new paragraph", "\n\n", Break),
+];
+
+const GERMAN: Commands = &[
+    ("punkt", ".", Trailing),
+    ("komma", ",", Trailing),
+    ("fragezeichen", "?", Trailing),
+    ("ausrufezeichen", "!", Trailing),
+    ("doppelpunkt", ":", Trailing),
+    ("semikolon", ";", Trailing),
+    ("anführungszeichen auf", "„", Leading),
+    ("anführungszeichen zu", "“", Trailing),
+    ("klammer auf", "(", Leading),
+    ("klammer zu", ")", Trailing),
+    ("neue zeile", "\n", Break),
+    ("neuer absatz", "\n\n", Break),
+];
+
+const FRENCH: Commands = &[
+    ("point", ".", Trailing),
+    ("virgule", ",", Trailing),
+    ("point d'interrogation", "?", Trailing),
+    ("point d'exclamation", "!", Trailing),
+    ("deux points", ":", Trailing),
+    ("deux-points", ":", Trailing),
+    ("point-virgule", ";", Trailing),
+    ("point virgule", ";", Trailing),
+    ("ouvrez les guillemets", "«", Leading),
+    ("fermez les guillemets", "»", Trailing),
+    ("ouvrez la parenthèse", "(", Leading),
+    ("fermez la parenthèse", ")", Trailing),
+    ("nouvelle ligne", "\n", Break),
+    ("à la ligne", "\n", Break),
+    ("nouveau paragraphe", "\n\n", Break),
+];
+
+const SPANISH: Commands = &[
+    ("punto", ".", Trailing),
+    ("coma", ",", Trailing),
+    ("signo de interrogación", "?", Trailing),
+    ("signo de exclamación", "!", Trailing),
+    ("dos puntos", ":", Trailing),
+    ("punto y coma", ";", Trailing),
+    ("abrir comillas", "\"", Leading),
+    ("cerrar comillas", "\"", Trailing),
+    ("abrir paréntesis", "(", Leading),
+    ("cerrar paréntesis", ")", Trailing),
+    ("nueva línea", "\n", Break),
+    ("nuevo párrafo", "\n\n", Break),
+];
+
+// Punctuation the model inserts on its own, removed before spoken commands are applied
+const MODEL_PUNCTUATION: &[char] = &['.', ',', '!', '?', ';', ':', '¿', '¡', '。', '、', '！', '？'];
+
+// Command dictionary for `language` ("de", "fr-FR"...); English when unknown
+fn commands(language: Option<&str>) -> Commands {
+    let code = language.map(|l| l.split(['-', '_']).next().unwrap_or(l).to_lowercase());
+    match code.as_deref() {
+        Some("de") => GERMAN,
+        Some("fr") => FRENCH,
+        Some("es") => SPANISH,
+        _ => ENGLISH,
+    }
+}
+
+// Drop model punctuation, keeping it inside numbers ("3.5", "10:30", "1,000")
+pub fn strip_model_punctuation(text: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    chars
+        .iter()
+        .enumerate()
+        .filter(|&(i, c)| {
+            if !MODEL_PUNCTUATION.contains(c) {
+                return true;
+            }
+            let digit_before = i > 0 && chars[i - 1].is_ascii_digit();
+            let digit_after = chars.get(i + 1).is_some_and(|next| next.is_ascii_digit());
+            digit_before && digit_after
+        })
+        .map(|(_, c)| *c)
+        .collect()
+}
+
+// Words compared against command phrases: lowercase, without quotes or other stray symbols
+fn normalize(word: &str) -> String {
+    word.trim_matches(|c: char| !c.is_alphanumeric()).to_lowercase()
+}
+
+// Replace model punctuation with the punctuation the user dictated ("comma", "new line", ...)
+pub fn apply(text: &str, language: Option<&str>) -> String {
+    let stripped = strip_model_punctuation(text);
+    let words: Vec<&str> = stripped.split_whitespace().collect();
+    let normalized: Vec<String> = words.iter().map(|w| normalize(w)).collect();
+
+    // Longest phrases first so "punto y coma" wins over "punto"
+    let mut phrases: Vec<(Vec<&str>, &str, Joining)> = commands(language)
+        .iter()
+        .map(|(phrase, output, joining)| (phrase.split(' ').collect(), *output, *joining))
+        .collect();
+    phrases.sort_by_key(|(phrase_words, _, _)| std::cmp::Reverse(phrase_words.len()));
+
+    let mut result = String::new();
+    let mut glue_next = true;  // No space before the next word (start of text, after "(" or a line break)
+    let mut i = 0;
+
+    while i < words.len() {
+        let command = phrases.iter().find(|(phrase_words, _, _)| {
+            normalized.len() >= i + phrase_words.len()
+                && phrase_words.iter().zip(&normalized[i..]).all(|(p, w)| p == w)
+        });
+
+        match command {
+            Some((phrase_words, output, joining)) => {
+                match joining {
+                    Trailing => {
+                        result.push_str(output);
+                        glue_next = false;
+                    }
+                    Leading => {
+                        if !glue_next {
+                            result.push(' ');
+                        }
+                        result.push_str(output);
+                        glue_next = true;
+                    }
+                    Break => {
+                        let trimmed_len = result.trim_end_matches(' ').len();
+                        result.truncate(trimmed_len);
+                        result.push_str(output);
+                        glue_next = true;
+                    }
+                }
+                i += phrase_words.len();
+            }
+            None => {
+                if !glue_next {
+                    result.push(' ');
+                }
+                result.push_str(words[i]);
+                glue_next = false;
+                i += 1;
+            }
+        }
+    }
+
+    result
+}
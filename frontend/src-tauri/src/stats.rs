@@ -0,0 +1,211 @@
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use crate::window_info::WindowTarget;
+use crate::AppState;
+
+pub const DB_FILE: &str = "stats.db";
+pub const CONFIG_FILE: &str = "stats.json";
+
+// Usage statistics collection (nothing is recorded while disabled)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StatsSettings {
+    pub enabled: bool,
+    pub typing_wpm: u32,  // Typing speed the "time saved" estimate compares against
+}
+
+impl Default for StatsSettings {
+    fn default() -> Self {
+        Self { enabled: true, typing_wpm: 40 }
+    }
+}
+
+// Aggregated usage for one day ("2025-01-31") or week ("2025-W05")
+#[derive(Debug, Clone, Serialize)]
+pub struct StatsPeriod {
+    pub period: String,
+    pub recordings: i64,
+    pub words: i64,
+    pub audio_secs: f64,
+    pub avg_latency_secs: Option<f64>,  // Average time from stop to transcript
+    pub avg_confidence: Option<f64>,  // Mean word probability, a rough accuracy proxy
+    pub time_saved_secs: f64,  // Typing time for these words minus the time spent speaking
+    pub models: Vec<String>,  // Models used in this period, most used first
+}
+
+// Aggregated usage for one app (executable name) the text was delivered to
+#[derive(Debug, Clone, Serialize)]
+pub struct AppUsage {
+    pub app: String,
+    pub recordings: i64,
+    pub words: i64,
+    pub audio_secs: f64,
+}
+
+#[derive(Debug)]
+pub struct StatsStore {
+    conn: Connection,
+}
+
+impl StatsStore {
+    pub fn open(path: &Path) -> rusqlite::Result<Self> {
+        let conn = Connection::open(path)?;
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS daily (
+                day TEXT NOT NULL,
+                model TEXT NOT NULL,
+                recordings INTEGER NOT NULL DEFAULT 0,
+                words INTEGER NOT NULL DEFAULT 0,
+                audio_secs REAL NOT NULL DEFAULT 0,
+                latency_secs REAL NOT NULL DEFAULT 0,
+                latency_count INTEGER NOT NULL DEFAULT 0,
+                confidence_sum REAL NOT NULL DEFAULT 0,
+                confidence_words INTEGER NOT NULL DEFAULT 0,
+                PRIMARY KEY (day, model)
+            );
+            CREATE TABLE IF NOT EXISTS app_daily (
+                day TEXT NOT NULL,
+                app TEXT NOT NULL,
+                recordings INTEGER NOT NULL DEFAULT 0,
+                words INTEGER NOT NULL DEFAULT 0,
+                audio_secs REAL NOT NULL DEFAULT 0,
+                PRIMARY KEY (day, app)
+            );",
+        )?;
+        Ok(Self { conn })
+    }
+
+    // Count one finished recording from its backend result (`data`), delivered text, and target window
+    pub fn add(&self, text: &str, data: &serde_json::Value, target: &WindowTarget) -> rusqlite::Result<()> {
+        let day = chrono::Local::now().format("%Y-%m-%d").to_string();
+        let words = text.split_whitespace().count() as i64;
+        let audio_secs = data.get("duration").and_then(|d| d.as_f64()).unwrap_or(0.0);
+        let model = data.get("model").and_then(|m| m.as_str()).unwrap_or("unknown");
+        let latency = data.get("transcription_time").and_then(|t| t.as_f64());
+        let probabilities: Vec<f64> = data
+            .get("words")
+            .and_then(|w| w.as_array())
+            .map(|words| words.iter().filter_map(|w| w.get("probability")?.as_f64()).collect())
+            .unwrap_or_default();
+
+        self.conn.execute(
+            "INSERT INTO daily (day, model, recordings, words, audio_secs, latency_secs, latency_count, confidence_sum, confidence_words)
+             VALUES (?1, ?2, 1, ?3, ?4, ?5, ?6, ?7, ?8)
+             ON CONFLICT (day, model) DO UPDATE SET
+                recordings = recordings + 1,
+                words = words + excluded.words,
+                audio_secs = audio_secs + excluded.audio_secs,
+                latency_secs = latency_secs + excluded.latency_secs,
+                latency_count = latency_count + excluded.latency_count,
+                confidence_sum = confidence_sum + excluded.confidence_sum,
+                confidence_words = confidence_words + excluded.confidence_words",
+            params![
+                day,
+                model,
+                words,
+                audio_secs,
+                latency.unwrap_or(0.0),
+                latency.is_some() as i64,
+                probabilities.iter().sum::<f64>(),
+                probabilities.len() as i64,
+            ],
+        )?;
+
+        // Executable names differ in case between launches, so one row per lowercase name
+        let app = target.process_name.as_deref().unwrap_or("unknown").to_lowercase();
+        self.conn.execute(
+            "INSERT INTO app_daily (day, app, recordings, words, audio_secs) VALUES (?1, ?2, 1, ?3, ?4)
+             ON CONFLICT (day, app) DO UPDATE SET
+                recordings = recordings + 1,
+                words = words + excluded.words,
+                audio_secs = audio_secs + excluded.audio_secs",
+            params![day, app, words, audio_secs],
+        )?;
+        Ok(())
+    }
+
+    // Per-app totals for the last `days` days, most used first
+    pub fn by_app(&self, days: u32) -> rusqlite::Result<Vec<AppUsage>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT app, SUM(recordings), SUM(words), SUM(audio_secs) FROM app_daily
+             WHERE day >= date('now', 'localtime', ?1)
+             GROUP BY app ORDER BY SUM(recordings) DESC, app",
+        )?;
+        let apps = stmt
+            .query_map(params![format!("-{} days", days.saturating_sub(1))], |row| {
+                Ok(AppUsage { app: row.get(0)?, recordings: row.get(1)?, words: row.get(2)?, audio_secs: row.get(3)? })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(apps)
+    }
+
+    // Per-day totals for the last `days` days (days without recordings are omitted)
+    pub fn daily(&self, days: u32, typing_wpm: u32) -> rusqlite::Result<Vec<StatsPeriod>> {
+        self.aggregate("day", &format!("-{} days", days.saturating_sub(1)), typing_wpm)
+    }
+
+    // Per-week totals (weeks start on Monday) for the last `weeks` weeks
+    pub fn weekly(&self, weeks: u32, typing_wpm: u32) -> rusqlite::Result<Vec<StatsPeriod>> {
+        self.aggregate("strftime('%Y-W%W', day)", &format!("-{} days", weeks.max(1) * 7 - 1), typing_wpm)
+    }
+
+    fn aggregate(&self, period_expr: &str, since_modifier: &str, typing_wpm: u32) -> rusqlite::Result<Vec<StatsPeriod>> {
+        // Inner query totals each model per period (most used first), the outer one folds models together
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT period, SUM(recordings), SUM(words), SUM(audio_secs),
+                    SUM(latency_secs) / NULLIF(SUM(latency_count), 0),
+                    SUM(confidence_sum) / NULLIF(SUM(confidence_words), 0),
+                    GROUP_CONCAT(model, char(31))
+             FROM (
+                SELECT {period} AS period, model, SUM(recordings) AS recordings, SUM(words) AS words,
+                       SUM(audio_secs) AS audio_secs, SUM(latency_secs) AS latency_secs,
+                       SUM(latency_count) AS latency_count, SUM(confidence_sum) AS confidence_sum,
+                       SUM(confidence_words) AS confidence_words
+                FROM daily WHERE day >= date('now', 'localtime', ?1)
+                GROUP BY period, model ORDER BY period, recordings DESC
+             )
+             GROUP BY period ORDER BY period DESC",
+            period = period_expr
+        ))?;
+
+        let periods = stmt
+            .query_map(params![since_modifier], |row| {
+                let words: i64 = row.get(2)?;
+                let audio_secs: f64 = row.get(3)?;
+                let typing_secs = words as f64 * 60.0 / typing_wpm.max(1) as f64;
+                let models: String = row.get(6)?;
+                Ok(StatsPeriod {
+                    period: row.get(0)?,
+                    recordings: row.get(1)?,
+                    words,
+                    audio_secs,
+                    avg_latency_secs: row.get(4)?,
+                    avg_confidence: row.get(5)?,
+                    time_saved_secs: (typing_secs - audio_secs).max(0.0),
+                    models: models.split('\u{1f}').map(str::to_string).collect(),
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(periods)
+    }
+
+    pub fn clear(&self) -> rusqlite::Result<()> {
+        self.conn.execute("DELETE FROM daily", [])?;
+        self.conn.execute("DELETE FROM app_daily", [])?;
+        Ok(())
+    }
+}
+
+// Count a finished recording, unless collection is disabled or privacy mode is on (failures are only logged)
+pub async fn record(state: &AppState, text: &str, data: &serde_json::Value, target: &WindowTarget) {
+    if text.is_empty() || !state.stats_settings.lock().await.enabled || crate::privacy::is_enabled(state).await {
+        return;
+    }
+    if let Some(store) = state.stats.lock().await.as_ref() {
+        if let Err(e) = store.add(text, data, target) {
+            log::error!("❌ Failed to update statistics: {}", e);
+        }
+    }
+}
@@ -0,0 +1,118 @@
+use anyhow::Result;
+use serde::{de::DeserializeOwned, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use tauri::{AppHandle, Manager};
+
+// Previous versions kept of each JSON file (name.json.bak1 is the newest)
+const BACKUP_COUNT: u32 = 3;
+
+// A file with this name next to the executable (or the --portable argument) enables portable mode
+const PORTABLE_FLAG: &str = "portable.flag";
+const PORTABLE_ARG: &str = "--portable";
+// Folder next to the executable that holds everything in portable mode
+const PORTABLE_DATA_DIR: &str = "data";
+
+static PORTABLE_BASE: OnceLock<Option<PathBuf>> = OnceLock::new();
+
+// Base directory for all app data in portable mode (None = installed mode, data lives in AppData)
+pub fn portable_base() -> Option<&'static Path> {
+    PORTABLE_BASE
+        .get_or_init(|| {
+            let exe_dir = std::env::current_exe().ok()?.parent()?.to_path_buf();
+            let requested = std::env::args().any(|arg| arg == PORTABLE_ARG) || exe_dir.join(PORTABLE_FLAG).exists();
+            requested.then(|| exe_dir.join(PORTABLE_DATA_DIR))
+        })
+        .as_deref()
+}
+
+// Directory holding the app's JSON config files and databases (created on demand)
+pub fn config_dir(app: &AppHandle) -> Result<PathBuf> {
+    let dir = match portable_base() {
+        Some(base) => base.join("config"),
+        None => app
+            .path()
+            .app_config_dir()
+            .map_err(|e| anyhow::anyhow!("Failed to resolve config directory: {}", e))?,
+    };
+    std::fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+// Directory for log files in portable mode (installed mode uses the log plugin's default)
+pub fn portable_log_dir() -> Option<PathBuf> {
+    portable_base().map(|base| base.join("logs"))
+}
+
+// Directory holding the app's log files
+pub fn log_dir(app: &AppHandle) -> Result<PathBuf> {
+    match portable_log_dir() {
+        Some(dir) => Ok(dir),
+        None => app.path().app_log_dir().map_err(|e| anyhow::anyhow!("Failed to resolve log directory: {}", e)),
+    }
+}
+
+// Load a JSON file from the config directory, falling back to defaults if missing or corrupt
+pub fn load_json<T: DeserializeOwned + Default>(app: &AppHandle, file_name: &str) -> T {
+    let path = match config_dir(app) {
+        Ok(dir) => dir.join(file_name),
+        Err(e) => {
+            log::warn!("⚠️ {}", e);
+            return T::default();
+        }
+    };
+
+    match std::fs::read_to_string(&path) {
+        Ok(contents) => serde_json::from_str(&contents).unwrap_or_else(|e| {
+            log::warn!("⚠️ Ignoring invalid {}: {}", path.display(), e);
+            T::default()
+        }),
+        Err(_) => T::default(),
+    }
+}
+
+// Write a JSON file to the config directory (via a temp file so a crash can't truncate it),
+// keeping the previous contents as a rolling backup
+pub fn save_json<T: Serialize>(app: &AppHandle, file_name: &str, value: &T) -> Result<()> {
+    let path = config_dir(app)?.join(file_name);
+    let tmp_path = path.with_extension("json.tmp");
+
+    std::fs::write(&tmp_path, serde_json::to_string_pretty(value)?)?;
+    if let Err(e) = rotate_backups(&path) {
+        log::warn!("⚠️ Failed to back up {}: {}", path.display(), e);
+    }
+    std::fs::rename(&tmp_path, &path)?;
+    Ok(())
+}
+
+// Delete a JSON file from the config directory along with its backups
+pub fn remove_json(app: &AppHandle, file_name: &str) -> Result<()> {
+    let path = config_dir(app)?.join(file_name);
+    let backups = (1..=BACKUP_COUNT).map(|n| backup_path(&path, n));
+    for path in std::iter::once(path.clone()).chain(backups) {
+        match std::fs::remove_file(&path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e.into()),
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+fn backup_path(path: &Path, n: u32) -> PathBuf {
+    path.with_extension(format!("json.bak{}", n))
+}
+
+// Shift name.json.bak1 -> bak2 -> ... (dropping the oldest) and copy the current file to bak1
+fn rotate_backups(path: &Path) -> Result<()> {
+    if !path.exists() {
+        return Ok(());
+    }
+    for n in (1..BACKUP_COUNT).rev() {
+        let from = backup_path(path, n);
+        if from.exists() {
+            std::fs::rename(&from, backup_path(path, n + 1))?;
+        }
+    }
+    std::fs::copy(path, backup_path(path, 1))?;
+    Ok(())
+}
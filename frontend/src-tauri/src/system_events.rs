@@ -0,0 +1,147 @@
+use std::sync::OnceLock;
+use tauri::{AppHandle, Manager};
+use windows::core::{w, PCWSTR};
+use windows::Win32::{
+    Foundation::{HINSTANCE, HWND, LPARAM, LRESULT, WPARAM},
+    System::RemoteDesktop::{WTSRegisterSessionNotification, NOTIFY_FOR_THIS_SESSION},
+    UI::WindowsAndMessaging::{
+        CreateWindowExW, DefWindowProcW, DispatchMessageW, GetMessageW, RegisterClassW, TranslateMessage, MSG,
+        PBT_APMRESUMEAUTOMATIC, PBT_APMSUSPEND, WINDOW_EX_STYLE, WM_POWERBROADCAST, WM_SETTINGCHANGE, WM_WTSSESSION_CHANGE,
+        WNDCLASSW, WS_OVERLAPPED, WTS_SESSION_LOCK, WTS_SESSION_UNLOCK,
+    },
+};
+
+use crate::AppState;
+
+// How long to wait for the backend to answer after a resume before restarting it
+const BACKEND_CHECK_ATTEMPTS: u32 = 5;
+const BACKEND_CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SystemEvent {
+    Suspend,
+    Resume,
+    Lock,
+    Unlock,
+    ThemeChanged,
+}
+
+static APP: OnceLock<AppHandle> = OnceLock::new();
+
+// Listen for sleep/resume, lock/unlock, and theme changes on a hidden window with its own message loop.
+// A message-only window would miss WM_POWERBROADCAST, so this is a regular top-level window that is never shown.
+pub fn spawn_listener(app: AppHandle) {
+    if APP.set(app).is_err() {
+        return;
+    }
+
+    std::thread::spawn(|| unsafe {
+        let class = WNDCLASSW {
+            lpfnWndProc: Some(window_proc),
+            lpszClassName: w!("Whisper4WindowsSystemEvents"),
+            ..Default::default()
+        };
+        if RegisterClassW(&class) == 0 {
+            log::error!("❌ Failed to register system event window class");
+            return;
+        }
+
+        let hwnd = CreateWindowExW(
+            WINDOW_EX_STYLE::default(),
+            w!("Whisper4WindowsSystemEvents"),
+            w!("Whisper4Windows system events"),
+            WS_OVERLAPPED,
+            0,
+            0,
+            0,
+            0,
+            HWND::default(),
+            None,
+            HINSTANCE::default(),
+            None,
+        );
+        if hwnd.0 == 0 {
+            log::error!("❌ Failed to create system event window");
+            return;
+        }
+        if let Err(e) = WTSRegisterSessionNotification(hwnd, NOTIFY_FOR_THIS_SESSION) {
+            log::warn!("⚠️ Lock/unlock notifications unavailable: {}", e);
+        }
+
+        let mut msg = MSG::default();
+        while GetMessageW(&mut msg, HWND::default(), 0, 0).as_bool() {
+            let _ = TranslateMessage(&msg);
+            DispatchMessageW(&msg);
+        }
+    });
+}
+
+unsafe extern "system" fn window_proc(hwnd: HWND, msg: u32, wparam: WPARAM, lparam: LPARAM) -> LRESULT {
+    let event = match (msg, wparam.0 as u32) {
+        (WM_POWERBROADCAST, PBT_APMSUSPEND) => Some(SystemEvent::Suspend),
+        (WM_POWERBROADCAST, PBT_APMRESUMEAUTOMATIC) => Some(SystemEvent::Resume),
+        (WM_WTSSESSION_CHANGE, WTS_SESSION_LOCK) => Some(SystemEvent::Lock),
+        (WM_WTSSESSION_CHANGE, WTS_SESSION_UNLOCK) => Some(SystemEvent::Unlock),
+        // Sent with "ImmersiveColorSet" when the light/dark theme changes
+        (WM_SETTINGCHANGE, _) if lparam.0 != 0 && PCWSTR(lparam.0 as *const u16).to_string().is_ok_and(|s| s == "ImmersiveColorSet") => {
+            Some(SystemEvent::ThemeChanged)
+        }
+        _ => None,
+    };
+
+    if let (Some(event), Some(app)) = (event, APP.get()) {
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move { handle(&app, event).await });
+    }
+    DefWindowProcW(hwnd, msg, wparam, lparam)
+}
+
+async fn handle(app: &AppHandle, event: SystemEvent) {
+    log::info!("💻 System event: {:?}", event);
+    let state: tauri::State<AppState> = app.state();
+
+    match event {
+        SystemEvent::Suspend | SystemEvent::Lock => {
+            // Nothing sensible can receive the text while locked or asleep, and the audio stream
+            // rarely survives a suspend, so drop the recording instead of leaving it stuck
+            if crate::recording_state::is_recording(&state).await {
+                log::warn!("⚠️ Cancelling active recording ({:?})", event);
+                let _ = crate::cmd_cancel_recording(app.clone()).await;
+                let reason = if event == SystemEvent::Lock { "toast.cancelled_locked" } else { "toast.cancelled_sleep" };
+                crate::show_toast(app, &crate::locale::tr("toast.recording_cancelled"), &crate::locale::tr(reason));
+            }
+        }
+        SystemEvent::ThemeChanged => {
+            // The tray icon follows the taskbar theme
+            crate::refresh_tray(app).await;
+        }
+        SystemEvent::Resume | SystemEvent::Unlock => {
+            // Registrations can silently go stale across sleep and lock; a blocklisted app keeps them suspended
+            if !*state.hotkeys_suspended.lock().await {
+                crate::set_global_shortcuts_active(app, false).await;
+                crate::set_global_shortcuts_active(app, true).await;
+                log::info!("⌨️ Global shortcuts re-registered");
+            }
+            if event == SystemEvent::Resume {
+                ensure_backend(app).await;
+            }
+        }
+    }
+}
+
+// Wait for the backend to answer again, restarting it if it didn't survive the suspend
+async fn ensure_backend(app: &AppHandle) {
+    let client = crate::backend::client();
+    for _ in 0..BACKEND_CHECK_ATTEMPTS {
+        if let Ok(resp) = client.get(crate::backend::url("health")).send().await {
+            if resp.status().is_success() {
+                log::info!("✅ Backend reachable after resume");
+                return;
+            }
+        }
+        tokio::time::sleep(BACKEND_CHECK_INTERVAL).await;
+    }
+
+    log::warn!("⚠️ Backend not responding after resume, restarting it");
+    crate::backend::restart(app).await;
+}
@@ -0,0 +1,232 @@
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use tauri::image::Image;
+use windows::core::w;
+use windows::Win32::System::Registry::{RegGetValueW, HKEY_CURRENT_USER, RRF_RT_REG_DWORD};
+
+// Tray icon drawn to suit the taskbar: a microphone glyph in the taskbar's contrast colour, with a
+// coloured badge for the recording state. An icon pack (a folder of PNGs) can replace the glyph.
+
+pub const CONFIG_FILE: &str = "tray_icon.json";
+
+// Size the built-in icons are drawn at; Windows scales them to the tray
+const SIZE: u32 = 32;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum TrayTheme {
+    #[default]
+    Auto,  // Follow the Windows taskbar
+    Light,  // Light taskbar: dark glyph
+    Dark,  // Dark taskbar: white glyph
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct TrayIconSettings {
+    pub theme: TrayTheme,
+    // Folder with <state>.png or <state>-light.png / <state>-dark.png, state being idle, recording,
+    // paused, processing or error. A missing state icon is drawn from the pack's idle icon.
+    pub icon_pack: Option<String>,
+}
+
+// What the icon shows, from the recording state
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IconState {
+    Idle,
+    Recording,
+    Paused,
+    Processing,
+    Error,
+}
+
+impl IconState {
+    fn file_stem(self) -> &'static str {
+        match self {
+            IconState::Idle => "idle",
+            IconState::Recording => "recording",
+            IconState::Paused => "paused",
+            IconState::Processing => "processing",
+            IconState::Error => "error",
+        }
+    }
+
+    // Badge colour, and whether it's drawn as a ring; idle has none
+    fn badge(self) -> Option<([u8; 3], bool)> {
+        match self {
+            IconState::Idle => None,
+            IconState::Recording => Some(([0xE5, 0x39, 0x35], false)),
+            IconState::Paused => Some(([0x9E, 0x9E, 0x9E], false)),
+            IconState::Processing => Some(([0xF5, 0xA6, 0x23], false)),
+            IconState::Error => Some(([0xE5, 0x39, 0x35], true)),
+        }
+    }
+}
+
+// Whether the taskbar is light; Windows keeps this separate from the app theme
+fn taskbar_is_light() -> bool {
+    let mut value = 0u32;
+    let mut size = std::mem::size_of::<u32>() as u32;
+    let result = unsafe {
+        RegGetValueW(
+            HKEY_CURRENT_USER,
+            w!("Software\\Microsoft\\Windows\\CurrentVersion\\Themes\\Personalize"),
+            w!("SystemUsesLightTheme"),
+            RRF_RT_REG_DWORD,
+            None,
+            Some(&mut value as *mut u32 as *mut std::ffi::c_void),
+            Some(&mut size),
+        )
+    };
+    // Missing on Windows versions before the light taskbar, which were always dark
+    result.is_ok() && value != 0
+}
+
+fn is_light(theme: TrayTheme) -> bool {
+    match theme {
+        TrayTheme::Auto => taskbar_is_light(),
+        TrayTheme::Light => true,
+        TrayTheme::Dark => false,
+    }
+}
+
+// The icon for `state` with these settings
+pub fn icon(settings: &TrayIconSettings, state: IconState) -> Image<'static> {
+    let light = is_light(settings.theme);
+
+    if let Some(pack) = settings.icon_pack.as_deref().filter(|p| !p.trim().is_empty()) {
+        let pack = Path::new(pack);
+        if let Some(image) = load_pack_icon(pack, state.file_stem(), light) {
+            return image.into_image();
+        }
+        if let Some(mut image) = load_pack_icon(pack, IconState::Idle.file_stem(), light) {
+            draw_badge(&mut image, state);
+            return image.into_image();
+        }
+        log::warn!("⚠️ No idle icon in icon pack {}, using the built-in icons", pack.display());
+    }
+
+    let mut canvas = Canvas::new(SIZE, SIZE);
+    draw_microphone(&mut canvas, if light { [0x1F, 0x1F, 0x1F] } else { [0xFF, 0xFF, 0xFF] });
+    draw_badge(&mut canvas, state);
+    canvas.into_image()
+}
+
+// <stem>-light.png / <stem>-dark.png for the taskbar theme, else <stem>.png
+fn load_pack_icon(pack: &Path, stem: &str, light: bool) -> Option<Canvas> {
+    let themed = pack.join(format!("{}-{}.png", stem, if light { "light" } else { "dark" }));
+    [themed, pack.join(format!("{}.png", stem))]
+        .iter()
+        .filter(|path| path.is_file())
+        .find_map(|path| match decode_png(path) {
+            Ok(canvas) => Some(canvas),
+            Err(e) => {
+                log::warn!("⚠️ Couldn't read tray icon {}: {}", path.display(), e);
+                None
+            }
+        })
+}
+
+fn decode_png(path: &Path) -> Result<Canvas, String> {
+    let file = std::fs::File::open(path).map_err(|e| e.to_string())?;
+    let mut decoder = png::Decoder::new(file);
+    decoder.set_transformations(png::Transformations::normalize_to_color8());
+    let mut reader = decoder.read_info().map_err(|e| e.to_string())?;
+    let mut buffer = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut buffer).map_err(|e| e.to_string())?;
+    let pixels = &buffer[..info.buffer_size()];
+
+    let rgba = match info.color_type {
+        png::ColorType::Rgba => pixels.to_vec(),
+        png::ColorType::Rgb => pixels.chunks_exact(3).flat_map(|p| [p[0], p[1], p[2], 0xFF]).collect(),
+        png::ColorType::GrayscaleAlpha => pixels.chunks_exact(2).flat_map(|p| [p[0], p[0], p[0], p[1]]).collect(),
+        png::ColorType::Grayscale => pixels.iter().flat_map(|&g| [g, g, g, 0xFF]).collect(),
+        png::ColorType::Indexed => return Err("Unsupported PNG colour type".to_string()),
+    };
+    Ok(Canvas { rgba, width: info.width, height: info.height })
+}
+
+struct Canvas {
+    rgba: Vec<u8>,
+    width: u32,
+    height: u32,
+}
+
+impl Canvas {
+    fn new(width: u32, height: u32) -> Self {
+        Self { rgba: vec![0; (width * height * 4) as usize], width, height }
+    }
+
+    fn into_image(self) -> Image<'static> {
+        Image::new_owned(self.rgba, self.width, self.height)
+    }
+
+    // Paint every pixel with `color` at the coverage `shape` gives its centre, in 32x32 icon units
+    fn fill(&mut self, color: [u8; 3], shape: impl Fn(f32, f32) -> f32) {
+        let scale = SIZE as f32 / self.width.min(self.height) as f32;
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let coverage = shape((x as f32 + 0.5) * scale, (y as f32 + 0.5) * scale).clamp(0.0, 1.0);
+                if coverage <= 0.0 {
+                    continue;
+                }
+                let i = ((y * self.width + x) * 4) as usize;
+                let pixel = &mut self.rgba[i..i + 4];
+                let alpha = pixel[3] as f32 / 255.0;
+                let out_alpha = coverage + alpha * (1.0 - coverage);
+                for c in 0..3 {
+                    let blended = color[c] as f32 * coverage + pixel[c] as f32 * alpha * (1.0 - coverage);
+                    pixel[c] = (blended / out_alpha).round() as u8;
+                }
+                pixel[3] = (out_alpha * 255.0).round() as u8;
+            }
+        }
+    }
+
+    // Make pixels transparent by the coverage `shape` gives them
+    fn erase(&mut self, shape: impl Fn(f32, f32) -> f32) {
+        let scale = SIZE as f32 / self.width.min(self.height) as f32;
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let coverage = shape((x as f32 + 0.5) * scale, (y as f32 + 0.5) * scale).clamp(0.0, 1.0);
+                let i = ((y * self.width + x) * 4 + 3) as usize;
+                self.rgba[i] = (self.rgba[i] as f32 * (1.0 - coverage)).round() as u8;
+            }
+        }
+    }
+}
+
+// Coverage of a pixel by a shape at signed distance `d` (negative inside)
+fn coverage(d: f32) -> f32 {
+    0.5 - d
+}
+
+// Distance from (x, y) to the segment a-b
+fn segment_distance(x: f32, y: f32, a: (f32, f32), b: (f32, f32)) -> f32 {
+    let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+    let t = (((x - a.0) * dx + (y - a.1) * dy) / (dx * dx + dy * dy)).clamp(0.0, 1.0);
+    ((x - a.0 - t * dx).powi(2) + (y - a.1 - t * dy).powi(2)).sqrt()
+}
+
+fn draw_microphone(canvas: &mut Canvas, color: [u8; 3]) {
+    canvas.fill(color, |x, y| {
+        let capsule = segment_distance(x, y, (16.0, 8.0), (16.0, 14.0)) - 5.0;
+        // Lower half of a ring around the capsule
+        let ring = (((x - 16.0).powi(2) + (y - 14.0).powi(2)).sqrt() - 9.0).abs() - 1.25;
+        let cradle = if y >= 14.0 { ring } else { f32::MAX };
+        let stem = segment_distance(x, y, (16.0, 23.0), (16.0, 28.0)) - 1.25;
+        let base = segment_distance(x, y, (11.0, 28.0), (21.0, 28.0)) - 1.25;
+        coverage(capsule.min(cradle).min(stem).min(base))
+    });
+}
+
+// State badge in the bottom-right corner, cut out of the icon so it stands apart on any taskbar
+fn draw_badge(canvas: &mut Canvas, state: IconState) {
+    let Some((color, ring)) = state.badge() else { return };
+    let distance = |x: f32, y: f32| ((x - 25.0).powi(2) + (y - 25.0).powi(2)).sqrt();
+    canvas.erase(|x, y| coverage(distance(x, y) - 7.5));
+    canvas.fill(color, |x, y| {
+        let d = distance(x, y) - 6.0;
+        coverage(if ring { d.abs() - 1.0 } else { d })
+    });
+}
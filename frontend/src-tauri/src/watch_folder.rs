@@ -0,0 +1,204 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, UNIX_EPOCH};
+use tauri::{AppHandle, Manager};
+
+use crate::file_transcription::AudioFile;
+use crate::recording_state::{self, RecordingState};
+use crate::{storage, AppState};
+
+// Watch folder: audio files dropped into a folder are transcribed one at a time between dictations,
+// each getting a .txt and/or .srt next to it. A ledger of the files handled (with their size and
+// modification time) keeps restarts from transcribing them again; a file that changes is redone.
+
+pub const CONFIG_FILE: &str = "watch_folder.json";
+const LEDGER_FILE: &str = "watch_folder_ledger.json";
+
+// How often the folder is scanned; a file must look the same on two scans in a row, so one still
+// being copied in isn't picked up half-written
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WatchFolderSettings {
+    pub enabled: bool,
+    pub folder: String,
+    pub write_txt: bool,  // <name>.txt with the transcript
+    pub write_srt: bool,  // <name>.srt with timed subtitles
+}
+
+impl Default for WatchFolderSettings {
+    fn default() -> Self {
+        Self { enabled: false, folder: String::new(), write_txt: true, write_srt: false }
+    }
+}
+
+// A file as it was on disk
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+struct Fingerprint {
+    size: u64,
+    modified_ms: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct LedgerEntry {
+    #[serde(flatten)]
+    fingerprint: Fingerprint,
+    processed_at: i64,  // Unix milliseconds
+    error: Option<String>,  // Failed files aren't retried until they change
+}
+
+// Files handled so far, by full path
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct Ledger {
+    files: HashMap<String, LedgerEntry>,
+}
+
+// Files waiting in the folder or being transcribed, for the tray
+static QUEUED: AtomicUsize = AtomicUsize::new(0);
+
+pub fn queued() -> usize {
+    QUEUED.load(Ordering::SeqCst)
+}
+
+async fn set_queued(app: &AppHandle, count: usize) {
+    if QUEUED.swap(count, Ordering::SeqCst) != count {
+        crate::refresh_tray(app).await;
+    }
+}
+
+// Reject settings the watcher couldn't work with
+pub fn validate(settings: &WatchFolderSettings) -> Result<(), String> {
+    if !settings.enabled {
+        return Ok(());
+    }
+    let folder = Path::new(settings.folder.trim());
+    if settings.folder.trim().is_empty() {
+        return Err("No watch folder set".to_string());
+    }
+    if !folder.is_dir() {
+        return Err(format!("{} is not a folder", folder.display()));
+    }
+    if !settings.write_txt && !settings.write_srt {
+        return Err("Pick a .txt or .srt output".to_string());
+    }
+    Ok(())
+}
+
+// Scan the watch folder and transcribe new or changed files while the app is otherwise idle
+pub fn spawn_watcher(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut ledger: Ledger = storage::load_json(&app, LEDGER_FILE);
+        // Files not in the ledger as they looked on the previous scan
+        let mut seen: HashMap<PathBuf, Fingerprint> = HashMap::new();
+        let mut last_error: Option<String> = None;
+
+        loop {
+            tokio::time::sleep(POLL_INTERVAL).await;
+
+            let state: tauri::State<AppState> = app.state();
+            let settings = state.watch_folder.lock().await.clone();
+            if !settings.enabled || settings.folder.trim().is_empty() {
+                seen.clear();
+                set_queued(&app, 0).await;
+                continue;
+            }
+
+            let pending = match scan(Path::new(settings.folder.trim()), &ledger) {
+                Ok(pending) => pending,
+                Err(e) => {
+                    // Logged once, not every scan, while the folder stays unreadable
+                    let error = format!("Can't read the watch folder {}: {}", settings.folder.trim(), e);
+                    if last_error.as_ref() != Some(&error) {
+                        log::warn!("⚠️ {}", error);
+                        last_error = Some(error);
+                    }
+                    seen.clear();
+                    set_queued(&app, 0).await;
+                    continue;
+                }
+            };
+            last_error = None;
+
+            let ready: Vec<(PathBuf, Fingerprint)> =
+                pending.iter().filter(|(path, fingerprint)| seen.get(*path) == Some(*fingerprint)).map(|(p, f)| (p.clone(), *f)).collect();
+            seen = pending;
+            set_queued(&app, seen.len()).await;
+
+            for (path, fingerprint) in ready {
+                // Dictation comes first; the rest waits for the next scan
+                if !matches!(recording_state::get(&state).await, RecordingState::Idle | RecordingState::Error) {
+                    break;
+                }
+
+                let error = transcribe(&app, &state, &path, &settings).await.err();
+                if let Some(e) = &error {
+                    log::error!("❌ Watch folder: {} failed: {}", path.display(), e);
+                }
+                let processed_at = chrono::Utc::now().timestamp_millis();
+                ledger.files.insert(path.to_string_lossy().to_string(), LedgerEntry { fingerprint, processed_at, error });
+                if let Err(e) = storage::save_json(&app, LEDGER_FILE, &ledger) {
+                    log::warn!("⚠️ Failed to save the watch folder ledger: {}", e);
+                }
+
+                seen.remove(&path);
+                set_queued(&app, seen.len()).await;
+            }
+        }
+    });
+}
+
+// Audio files in `folder` (not its subfolders) that the ledger doesn't have as they are now
+fn scan(folder: &Path, ledger: &Ledger) -> std::io::Result<HashMap<PathBuf, Fingerprint>> {
+    let mut pending = HashMap::new();
+    for entry in std::fs::read_dir(folder)? {
+        let Ok(entry) = entry else { continue };
+        let path = entry.path();
+        let is_audio = crate::file_transcription::is_media(&path);
+        let Ok(metadata) = entry.metadata() else { continue };
+        if !is_audio || !metadata.is_file() {
+            continue;
+        }
+
+        let modified_ms = metadata
+            .modified()
+            .ok()
+            .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+            .map(|since| since.as_millis() as u64)
+            .unwrap_or_default();
+        let fingerprint = Fingerprint { size: metadata.len(), modified_ms };
+        let done = ledger.files.get(path.to_string_lossy().as_ref()).is_some_and(|entry| entry.fingerprint == fingerprint);
+        if !done {
+            pending.insert(path, fingerprint);
+        }
+    }
+    Ok(pending)
+}
+
+// Transcribe one file and write its transcript files next to it
+async fn transcribe(app: &AppHandle, state: &AppState, path: &Path, settings: &WatchFolderSettings) -> Result<(), String> {
+    log::info!("👀 Watch folder: transcribing {}", path.display());
+    // Videos with several audio tracks use the first
+    let file = AudioFile { path: path.to_string_lossy().to_string(), track: 0 };
+    let Some((text, data)) = crate::transcribe_without_recording(app, state, Some(&file)).await? else {
+        log::info!("👀 Nothing transcribed from {}", path.display());
+        return Ok(());
+    };
+
+    if settings.write_txt {
+        write(&path.with_extension("txt"), &text)?;
+    }
+    if settings.write_srt {
+        write(&path.with_extension("srt"), &crate::file_transcription::srt(&data, &text))?;
+    }
+    Ok(())
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), String> {
+    std::fs::write(path, contents).map_err(|e| format!("Can't write {}: {}", path.display(), e))?;
+    log::info!("👀 Wrote {}", path.display());
+    Ok(())
+}
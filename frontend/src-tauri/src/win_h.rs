@@ -0,0 +1,124 @@
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::Mutex;
+use tauri::AppHandle;
+use windows::Win32::{
+    Foundation::{LPARAM, LRESULT, WPARAM},
+    UI::Input::KeyboardAndMouse::{
+        GetAsyncKeyState, SendInput, INPUT, KEYBD_EVENT_FLAGS, KEYEVENTF_KEYUP, VIRTUAL_KEY, VK_CONTROL, VK_H, VK_LWIN,
+        VK_MENU, VK_RWIN, VK_SHIFT,
+    },
+    UI::WindowsAndMessaging::{
+        CallNextHookEx, HC_ACTION, HHOOK, KBDLLHOOKSTRUCT, LLKHF_INJECTED, WH_KEYBOARD_LL, WM_KEYDOWN, WM_KEYUP,
+        WM_SYSKEYDOWN, WM_SYSKEYUP,
+    },
+};
+
+// Taking Win+H over from Windows voice typing: a keyboard hook (only installed while enabled)
+// swallows the combo and toggles recording instead. While a transcription is being processed or
+// delivered, or hotkeys are released for a blocklisted app, Win+H goes through to Windows.
+
+pub const CONFIG_FILE: &str = "win_h.json";
+
+// Unassigned key sent before Win is released, so the Start menu doesn't open for a lone Win tap
+const MASK_KEY: VIRTUAL_KEY = VIRTUAL_KEY(0xE8);
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct WinHSettings {
+    pub enabled: bool,
+}
+
+static APP: Mutex<Option<AppHandle>> = Mutex::new(None);
+// Thread running the hook's message loop (0 = hook not installed)
+static HOOK_THREAD: AtomicU32 = AtomicU32::new(0);
+// Set while global hotkeys are released for a blocklisted app
+static SUSPENDED: AtomicBool = AtomicBool::new(false);
+// Set while a transcription is processed or delivered and a new recording can't start
+static BUSY: AtomicBool = AtomicBool::new(false);
+// The H press we swallowed, so its auto-repeats and release are swallowed too
+static SWALLOWING: AtomicBool = AtomicBool::new(false);
+
+// Install or remove the keyboard hook to match `settings`
+pub fn apply(app: &AppHandle, settings: &WinHSettings) -> Result<(), String> {
+    *APP.lock().unwrap() = Some(app.clone());
+
+    let running = HOOK_THREAD.load(Ordering::SeqCst);
+    if settings.enabled && running == 0 {
+        let thread_id = crate::ll_hook::install(WH_KEYBOARD_LL, Some(keyboard_proc))?;
+        HOOK_THREAD.store(thread_id, Ordering::SeqCst);
+        log::info!("⌨️ Win+H hook installed");
+    } else if !settings.enabled && running != 0 {
+        HOOK_THREAD.store(0, Ordering::SeqCst);
+        SWALLOWING.store(false, Ordering::SeqCst);
+        crate::ll_hook::uninstall(running);
+        log::info!("⌨️ Win+H hook removed");
+    }
+    Ok(())
+}
+
+// Let Win+H through without touching the hook (used by the per-app blocklist)
+pub fn set_suspended(suspended: bool) {
+    SUSPENDED.store(suspended, Ordering::SeqCst);
+}
+
+// Let Win+H through while the app can't start a recording (kept in step by the recording state)
+pub fn set_busy(busy: bool) {
+    BUSY.store(busy, Ordering::SeqCst);
+}
+
+fn held(vk: VIRTUAL_KEY) -> bool {
+    unsafe { GetAsyncKeyState(vk.0 as i32) < 0 }
+}
+
+// Feed one key event in; returns true if it's ours and must not reach Windows
+fn on_key(vk: u16, pressed: bool) -> bool {
+    if vk != VK_H.0 {
+        return false;
+    }
+    if !pressed {
+        return SWALLOWING.swap(false, Ordering::SeqCst);
+    }
+    if SWALLOWING.load(Ordering::SeqCst) {
+        return true;
+    }
+
+    // Exactly Win+H; Win+Shift+H and the like stay Windows' (or other apps')
+    let win = held(VK_LWIN) || held(VK_RWIN);
+    if !win || held(VK_CONTROL) || held(VK_MENU) || held(VK_SHIFT) {
+        return false;
+    }
+    if SUSPENDED.load(Ordering::SeqCst) || BUSY.load(Ordering::SeqCst) {
+        log::info!("⌨️ Win+H passed to Windows (busy)");
+        return false;
+    }
+
+    SWALLOWING.store(true, Ordering::SeqCst);
+    let mask = [crate::key_input(MASK_KEY, KEYBD_EVENT_FLAGS(0)), crate::key_input(MASK_KEY, KEYEVENTF_KEYUP)];
+    unsafe { SendInput(&mask, std::mem::size_of::<INPUT>() as i32) };
+    if let Some(app) = APP.lock().unwrap().clone() {
+        log::info!("⌨️ Win+H pressed");
+        crate::run_hotkey_action(app, "toggle".to_string());
+    }
+    true
+}
+
+unsafe extern "system" fn keyboard_proc(code: i32, wparam: WPARAM, lparam: LPARAM) -> LRESULT {
+    if code == HC_ACTION as i32 {
+        let info = &*(lparam.0 as *const KBDLLHOOKSTRUCT);
+        let pressed = match wparam.0 as u32 {
+            WM_KEYDOWN | WM_SYSKEYDOWN => Some(true),
+            WM_KEYUP | WM_SYSKEYUP => Some(false),
+            _ => None,
+        };
+
+        // Injected keys include our own mask key and typed text
+        if let Some(pressed) = pressed.filter(|_| info.flags.0 & LLKHF_INJECTED.0 == 0) {
+            if on_key(info.vkCode as u16, pressed) {
+                return LRESULT(1);
+            }
+        }
+    }
+
+    CallNextHookEx(HHOOK::default(), code, wparam, lparam)
+}
@@ -0,0 +1,119 @@
+use windows::core::PWSTR;
+use windows::Win32::{
+    Foundation::{CloseHandle, HWND, POINT, RECT},
+    Graphics::Gdi::{GetMonitorInfoW, MonitorFromWindow, MONITORINFO, MONITOR_DEFAULTTONEAREST},
+    System::Threading::{OpenProcess, QueryFullProcessImageNameW, PROCESS_NAME_WIN32, PROCESS_QUERY_LIMITED_INFORMATION},
+    UI::Shell::{SHQueryUserNotificationState, QUNS_BUSY, QUNS_PRESENTATION_MODE, QUNS_RUNNING_D3D_FULL_SCREEN},
+    UI::WindowsAndMessaging::{
+        GetCursorPos, GetDesktopWindow, GetForegroundWindow, GetShellWindow, GetWindowRect, GetWindowTextW,
+        GetWindowThreadProcessId,
+    },
+};
+
+// Executable file name (e.g. "notepad.exe") of the process that owns `hwnd`
+pub fn process_name_for_window(hwnd: HWND) -> Option<String> {
+    if hwnd.0 == 0 {
+        return None;
+    }
+
+    let mut pid = 0u32;
+    unsafe { GetWindowThreadProcessId(hwnd, Some(&mut pid)) };
+    process_name(pid)
+}
+
+// Executable file name of the process with ID `pid`
+pub fn process_name(pid: u32) -> Option<String> {
+    if pid == 0 {
+        return None;
+    }
+
+    unsafe {
+        let process = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid).ok()?;
+
+        let mut buffer = [0u16; 1024];
+        let mut size = buffer.len() as u32;
+        let result = QueryFullProcessImageNameW(process, PROCESS_NAME_WIN32, PWSTR(buffer.as_mut_ptr()), &mut size);
+        let _ = CloseHandle(process);
+        result.ok()?;
+
+        let path = String::from_utf16_lossy(&buffer[..size as usize]);
+        path.rsplit(['\\', '/']).next().map(|name| name.to_string())
+    }
+}
+
+// Center point of the current foreground window in screen coordinates
+pub fn foreground_window_center() -> Option<(i32, i32)> {
+    unsafe {
+        let hwnd = GetForegroundWindow();
+        if hwnd.0 == 0 {
+            return None;
+        }
+
+        let mut rect = RECT::default();
+        GetWindowRect(hwnd, &mut rect).ok()?;
+        Some(((rect.left + rect.right) / 2, (rect.top + rect.bottom) / 2))
+    }
+}
+
+// Mouse cursor position in screen coordinates
+pub fn cursor_position() -> Option<(i32, i32)> {
+    unsafe {
+        let mut point = POINT::default();
+        GetCursorPos(&mut point).ok()?;
+        Some((point.x, point.y))
+    }
+}
+
+// Executable name of the foreground window's process
+pub fn foreground_process_name() -> Option<String> {
+    process_name_for_window(unsafe { GetForegroundWindow() })
+}
+
+// The window that received (or would receive) an injection
+#[derive(Debug, Clone, Default)]
+pub struct WindowTarget {
+    pub process_name: Option<String>,
+    pub title: Option<String>,
+}
+
+pub fn foreground_target() -> WindowTarget {
+    let hwnd = unsafe { GetForegroundWindow() };
+    let mut buffer = [0u16; 512];
+    let len = unsafe { GetWindowTextW(hwnd, &mut buffer) };
+    let title = (len > 0).then(|| String::from_utf16_lossy(&buffer[..len as usize]));
+    WindowTarget { process_name: process_name_for_window(hwnd), title }
+}
+
+// Whether the foreground app is fullscreen: exclusive D3D / presentation mode as reported by the
+// shell, or a borderless window covering its whole monitor (the desktop itself doesn't count)
+pub fn is_foreground_fullscreen() -> bool {
+    unsafe {
+        if let Ok(state) = SHQueryUserNotificationState() {
+            if state == QUNS_RUNNING_D3D_FULL_SCREEN || state == QUNS_BUSY || state == QUNS_PRESENTATION_MODE {
+                return true;
+            }
+        }
+
+        let hwnd = GetForegroundWindow();
+        if hwnd.0 == 0 || hwnd == GetDesktopWindow() || hwnd == GetShellWindow() {
+            return false;
+        }
+        if process_name_for_window(hwnd).is_some_and(|name| name.eq_ignore_ascii_case("explorer.exe")) {
+            return false;
+        }
+
+        let mut rect = RECT::default();
+        if GetWindowRect(hwnd, &mut rect).is_err() {
+            return false;
+        }
+
+        let monitor = MonitorFromWindow(hwnd, MONITOR_DEFAULTTONEAREST);
+        let mut info = MONITORINFO { cbSize: std::mem::size_of::<MONITORINFO>() as u32, ..Default::default() };
+        if !GetMonitorInfoW(monitor, &mut info).as_bool() {
+            return false;
+        }
+
+        let screen = info.rcMonitor;
+        rect.left <= screen.left && rect.top <= screen.top && rect.right >= screen.right && rect.bottom >= screen.bottom
+    }
+}
@@ -0,0 +1,65 @@
+// Just enough of the ZIP format for a DOCX or a diagnostics bundle: uncompressed entries, no timestamps
+#[derive(Default)]
+pub struct ZipWriter {
+    data: Vec<u8>,
+    directory: Vec<u8>,
+    count: u16,
+}
+
+impl ZipWriter {
+    const VERSION: u16 = 20;
+    const UTF8_NAMES: u16 = 1 << 11;
+    const DOS_DATE_1980: u16 = 0x21;
+
+    pub fn add(&mut self, name: &str, contents: &[u8]) {
+        let offset = self.data.len() as u32;
+        let crc = crc32fast::hash(contents);
+        let size = contents.len() as u32;
+
+        // Local file header, then the data
+        self.data.extend_from_slice(&0x04034b50u32.to_le_bytes());
+        self.data.extend_from_slice(&Self::VERSION.to_le_bytes());
+        self.common_fields(false, crc, size, name);
+        self.data.extend_from_slice(name.as_bytes());
+        self.data.extend_from_slice(contents);
+
+        // Central directory record
+        self.directory.extend_from_slice(&0x02014b50u32.to_le_bytes());
+        self.directory.extend_from_slice(&Self::VERSION.to_le_bytes());  // Made by
+        self.directory.extend_from_slice(&Self::VERSION.to_le_bytes());  // Needed to extract
+        self.common_fields(true, crc, size, name);
+        self.directory.extend_from_slice(&[0; 6]);  // Comment length, disk, internal attributes
+        self.directory.extend_from_slice(&[0; 4]);  // External attributes
+        self.directory.extend_from_slice(&offset.to_le_bytes());
+        self.directory.extend_from_slice(name.as_bytes());
+        self.count += 1;
+    }
+
+    // Flags through extra field length, shared by both headers
+    fn common_fields(&mut self, directory: bool, crc: u32, size: u32, name: &str) {
+        let out = if directory { &mut self.directory } else { &mut self.data };
+        out.extend_from_slice(&Self::UTF8_NAMES.to_le_bytes());
+        out.extend_from_slice(&0u16.to_le_bytes());  // Stored
+        out.extend_from_slice(&0u16.to_le_bytes());  // Time
+        out.extend_from_slice(&Self::DOS_DATE_1980.to_le_bytes());
+        out.extend_from_slice(&crc.to_le_bytes());
+        out.extend_from_slice(&size.to_le_bytes());  // Compressed
+        out.extend_from_slice(&size.to_le_bytes());
+        out.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        out.extend_from_slice(&0u16.to_le_bytes());  // Extra field length
+    }
+
+    pub fn finish(mut self) -> Vec<u8> {
+        let directory_offset = self.data.len() as u32;
+        let directory_size = self.directory.len() as u32;
+        self.data.append(&mut self.directory);
+        self.data.extend_from_slice(&0x06054b50u32.to_le_bytes());
+        self.data.extend_from_slice(&[0; 4]);  // Disk numbers
+        self.data.extend_from_slice(&self.count.to_le_bytes());
+        self.data.extend_from_slice(&self.count.to_le_bytes());
+        self.data.extend_from_slice(&directory_size.to_le_bytes());
+        self.data.extend_from_slice(&directory_offset.to_le_bytes());
+        self.data.extend_from_slice(&0u16.to_le_bytes());  // Comment length
+        self.data
+    }
+}